@@ -0,0 +1,53 @@
+//! times adding lines one by one against adding them with `add_lines`.
+//! the bulk add shouldn't be any slower, it does the bookkeeping of
+//! every line in one go instead of once per line.
+//!
+//! run with `cargo run --release -p program_core --example bulk_add`
+
+use program_core::{Canvas, Point, Style};
+use std::time::{Duration, Instant};
+
+/// the runs of each way, taking turns so a busy moment slows both.
+const RUNS: usize = 10;
+
+fn timed(run: impl FnOnce() -> Canvas) -> (Duration, Canvas) {
+    let start = Instant::now();
+    let canvas = run();
+
+    (start.elapsed(), canvas)
+}
+
+fn main() {
+    for count in [1_000, 10_000, 100_000] {
+        let lines: Vec<(Point, Point)> = (0..count)
+            .map(|i| {
+                let (x, y) = ((i % 1000) as f64, (i / 1000) as f64 * 10.0);
+                (Point::new(x, y), Point::new(x + 5.0, y + 5.0))
+            })
+            .collect();
+
+        let (mut individually, mut bulk) = (Duration::MAX, Duration::MAX);
+        for _ in 0..RUNS {
+            let (elapsed, one_by_one) = timed(|| {
+                let mut canvas = Canvas::new(1000.0, 1000.0);
+                for (start, end) in &lines {
+                    canvas.add_line(start, end).unwrap();
+                }
+                canvas
+            });
+            individually = individually.min(elapsed);
+
+            let (elapsed, at_once) = timed(|| {
+                let mut canvas = Canvas::new(1000.0, 1000.0);
+                canvas.add_lines(lines.iter().cloned(), &Style::default()).unwrap();
+                canvas
+            });
+            bulk = bulk.min(elapsed);
+
+            assert!(one_by_one.drawables().eq(at_once.drawables()));
+        }
+
+        println!("{:>7} lines: {:>10.2?} one by one, {:>10.2?} in bulk", count, individually, bulk);
+        assert!(bulk <= individually, "adding {} lines in bulk took {:?}, one by one {:?}", count, bulk, individually);
+    }
+}
@@ -0,0 +1,79 @@
+//! times point queries against canvases of growing size.
+//! with the spatial index the time per query should stay roughly
+//! flat instead of growing with the number of drawables, like a
+//! scan through every drawable does.
+//!
+//! run with `cargo run --release -p program_core --example hit_testing`
+
+use program_core::{Canvas, DrawableId, Point};
+use std::time::{Duration, Instant};
+
+const QUERIES: usize = 10_000;
+/// scanning is slow enough on a large canvas that fewer queries do.
+const SCANS: usize = 1_000;
+/// how much slower a query may get on a canvas ten times larger.
+/// a scan through every drawable gets about ten times slower.
+const MAX_GROWTH: f64 = 3.0;
+
+/// a canvas with `count` circles in a grid, and its side length.
+fn grid(count: usize) -> (Canvas, usize) {
+    let side = (count as f64).sqrt().ceil() as usize;
+    let mut canvas = Canvas::new((side * 20) as f64, (side * 20) as f64);
+
+    for i in 0..count {
+        let center = Point::new(((i % side) * 20) as f64, ((i / side) * 20) as f64);
+        canvas.add_circle(&center, 8.0).unwrap();
+    }
+
+    (canvas, side)
+}
+
+/// the `i`th of the points queried, spread out over the grid.
+fn query_point(i: usize, side: usize) -> Point {
+    Point::new((i * 7 % (side * 20)) as f64, (i * 13 % (side * 20)) as f64)
+}
+
+/// runs `query` at the first `queries` points, returning the time per query and the hits.
+fn time_queries(side: usize, queries: usize, query: impl Fn(&Point) -> Vec<DrawableId>) -> (Duration, usize) {
+    let start = Instant::now();
+    let mut hits = 0;
+    for i in 0..queries {
+        hits += query(&query_point(i, side)).len();
+    }
+
+    (start.elapsed() / queries as u32, hits)
+}
+
+/// what `drawables_at` finds, without the index.
+fn scan(canvas: &Canvas, point: &Point) -> Vec<DrawableId> {
+    canvas
+        .drawables()
+        .filter(|(id, drawable)| !canvas.is_hidden(*id) && drawable.contains(point))
+        .map(|(id, _)| id)
+        .collect()
+}
+
+fn main() {
+    let mut per_query = Vec::new();
+
+    for count in [1_000, 10_000, 100_000] {
+        let (canvas, side) = grid(count);
+        let (indexed, hits) = time_queries(side, QUERIES, |point| canvas.drawables_at(point));
+        let (scanned, _) = time_queries(side, SCANS, |point| scan(&canvas, point));
+        for point in (0..SCANS).map(|i| query_point(i, side)) {
+            assert_eq!(scan(&canvas, &point), canvas.drawables_at(&point));
+        }
+
+        println!(
+            "{:>7} drawables: {:>8.2?} per query, {:>8.2?} scanning every drawable ({} hits)",
+            count, indexed, scanned, hits
+        );
+        per_query.push(indexed);
+    }
+
+    for (count, pair) in [1_000, 10_000].iter().zip(per_query.windows(2)) {
+        let growth = pair[1].as_secs_f64() / pair[0].as_secs_f64();
+        println!("{:>7} to {:>7} drawables: queries {:.2}x slower", count, count * 10, growth);
+        assert!(growth < MAX_GROWTH, "queries got {:.2}x slower on a canvas ten times larger", growth);
+    }
+}
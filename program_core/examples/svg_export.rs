@@ -0,0 +1,47 @@
+//! times serializing the tags of 10k mixed drawables, the old way through
+//! a HashMap of attributes and the new way writing them straight out.
+//!
+//! run with `cargo run --release -p program_core --features legacy-svg --example svg_export`
+
+use program_core::{Circle, Drawable, Line, Point, Text};
+use std::time::{Duration, Instant};
+
+const DRAWABLES: usize = 10_000;
+const ROUNDS: u32 = 20;
+
+/// the time a round of `to_tag` over every drawable takes, and the bytes it gives.
+fn time(drawables: &[Drawable], to_tag: fn(&Drawable) -> String) -> (Duration, usize) {
+    let start = Instant::now();
+    let mut bytes = 0;
+    for _ in 0..ROUNDS {
+        bytes += drawables.iter().map(|drawable| to_tag(drawable).len()).sum::<usize>();
+    }
+
+    (start.elapsed() / ROUNDS, bytes / ROUNDS as usize)
+}
+
+fn main() {
+    let drawables: Vec<Drawable> = (0..DRAWABLES)
+        .map(|i| {
+            let point = Point::new(i as f64 * 0.37, i as f64 * 1.13);
+            match i % 3 {
+                0 => Drawable::Line(Line::new(&point, &Point::new(point.x + 10.5, point.y - 3.25))),
+                1 => Drawable::Circle(Circle::new(&point, 4.75)),
+                _ => Drawable::Text(Text::new(format!("label {}", i), point)),
+            }
+        })
+        .collect();
+
+    assert!(
+        drawables.iter().all(|drawable| drawable.to_svg_tag() == drawable.to_svg_tag_legacy()),
+        "both ways should give the same tags"
+    );
+
+    let (old, old_bytes) = time(&drawables, Drawable::to_svg_tag_legacy);
+    let (new, new_bytes) = time(&drawables, Drawable::to_svg_tag);
+
+    println!("{} drawables, per round:", DRAWABLES);
+    println!("  old, through a HashMap: {:.2?} ({} bytes)", old, old_bytes);
+    println!("  new, written directly:  {:.2?} ({} bytes)", new, new_bytes);
+    println!("  {:.1}x faster", old.as_secs_f64() / new.as_secs_f64());
+}
@@ -0,0 +1,38 @@
+//! times 1M translations of a line, both on the drawable itself and
+//! through the canvas, where each move also refreshes the cached tag
+//! and the spatial index.
+//!
+//! run with `cargo run --release -p program_core --example translate`
+
+use program_core::{Canvas, Drawable, Line, Point};
+use std::hint::black_box;
+use std::time::Instant;
+
+const MOVES: u32 = 1_000_000;
+
+fn main() {
+    let mut line = Drawable::Line(Line::new(&Point::new(0.0, 0.0), &Point::new(30.0, 40.0)));
+    let offset = Point::new(0.5, -0.25);
+
+    let start = Instant::now();
+    for _ in 0..MOVES {
+        black_box(&mut line).translate(black_box(&offset));
+    }
+    println!("drawable: {:.2?} per translate", start.elapsed() / MOVES);
+
+    let mut canvas = Canvas::new(1000.0, 1000.0);
+    canvas.add_line(&Point::new(0.0, 0.0), &Point::new(30.0, 40.0)).unwrap();
+    canvas.select_drawable_at(&Point::new(0.0, 0.0));
+
+    let start = Instant::now();
+    for _ in 0..MOVES {
+        canvas.translate_selected_drawable(&offset);
+    }
+    println!("canvas:   {:.2?} per translate", start.elapsed() / MOVES);
+
+    let start = Instant::now();
+    for _ in 0..MOVES {
+        canvas.translate_selected_drawable(&Point::new(0.0, 0.0));
+    }
+    println!("identity: {:.2?} per translate", start.elapsed() / MOVES);
+}
@@ -0,0 +1,3754 @@
+//! # canvas
+//! this module contains the canvas, the document that holds
+//! every drawable and turns them into an SVG document.
+
+use super::drawable::{escape, HIT_TOLERANCE};
+use super::export::{self, ExportError, ExportFileOptions};
+use super::spatial_index::SpatialIndex;
+use super::props::{ApplyPropsError, DrawableProps, PivotSpec, Props};
+use super::guides::{ConstructionGuide, Guide, GuideAxis, GuideId, GuideLine, GUIDE_COLOR};
+use super::handles::{Handle, HandleKind};
+use super::snap::{self, Snap, SnapKind, SnapKinds};
+use super::storage::{DrawableId, Storage};
+use super::svg_import::SvgError;
+use super::units::Unit;
+use super::viewport::Viewport;
+use super::{Aabb, Angle, Circle, Drawable, Line, LineCap, NotFiniteError, Paint, Point, Rect, StrokeStyle, Style, StyleBuilder, Text};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+/// # Canvas
+/// structure to hold a drawing.
+/// it stores the drawables in stacking order, the size of the drawing,
+/// and the document information embedded in exports.
+/// drawables are shared between clones and only copied when modified,
+/// so cloning a canvas is cheap.
+///
+/// # Examples
+/// ```
+/// use program_core::{Canvas, Point};
+///
+/// let mut canvas = Canvas::new(500.0, 500.0);
+/// let id = canvas.add_line(&Point::new(0.0, 0.0), &Point::new(10.0, 10.0)).unwrap();
+///
+/// assert_eq!(1, canvas.len());
+/// assert_eq!(vec![id], canvas.ids());
+/// assert!(canvas.to_svg().contains("<line x1=\"0\" x2=\"10\" y1=\"0\" y2=\"10\" />"));
+///
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Canvas {
+    width: f64,
+    height: f64,
+    storage: Storage,
+    serialized_tags: usize,
+    cloned_drawables: usize,
+    dirty: BTreeSet<DrawableId>,
+    /// the selected drawables, in the order they were selected.
+    selection: Vec<DrawableId>,
+    locked: BTreeSet<DrawableId>,
+    /// the names given to drawables, the others go by their kind.
+    names: BTreeMap<DrawableId, String>,
+    /// the drawables left out of the drawing, its exports and hit testing.
+    hidden: BTreeSet<DrawableId>,
+    /// counts the changes to the drawables, see `revision`.
+    revision: u64,
+    spatial_index: Arc<SpatialIndex>,
+    title: Option<String>,
+    description: Option<String>,
+    metadata: BTreeMap<String, String>,
+    viewport: Viewport,
+    grid_spacing: Option<f64>,
+    export_grid: bool,
+    /// the decimals exported numbers are written with, see `set_svg_precision`.
+    svg_precision: Option<u32>,
+    /// the ruler guides, in the order they were added.
+    guides: Vec<Guide>,
+    /// the rays and endless lines, in the order they were added.
+    construction_guides: Vec<ConstructionGuide>,
+    next_guide: u32,
+    render_guides: bool,
+    /// the angle, in radians, shapes are drawn at multiples of.
+    ortho_mode: Option<f64>,
+    /// whether moving the selection keeps the drawables' centers on the canvas.
+    clamp_to_bounds: bool,
+    /// the color painted behind the drawing, `None` for a transparent one.
+    background: Option<String>,
+    unit: Unit,
+    /// whether the drawing changed since it was last saved.
+    unsaved: bool,
+}
+
+/// what happens to the drawables when the canvas is resized.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub enum ResizeMode {
+    /// they stay where they are, the canvas grows or shrinks at its
+    /// right and bottom edges.
+    #[default]
+    Anchor,
+    /// they move so they stay centered on the canvas.
+    Center,
+    /// they're scaled so the drawing fills the canvas as before,
+    /// by the smaller of the horizontal and vertical ratios.
+    Scale,
+}
+
+/// one of the axes of the canvas.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+impl Canvas {
+    pub fn new(width: f64, height: f64) -> Canvas {
+        Canvas {
+            width,
+            height,
+            storage: Storage::new(),
+            serialized_tags: 0,
+            cloned_drawables: 0,
+            dirty: BTreeSet::new(),
+            selection: Vec::new(),
+            locked: BTreeSet::new(),
+            names: BTreeMap::new(),
+            hidden: BTreeSet::new(),
+            revision: 0,
+            spatial_index: Arc::new(SpatialIndex::new()),
+            title: None,
+            description: None,
+            metadata: BTreeMap::new(),
+            viewport: Viewport::default(),
+            grid_spacing: None,
+            ortho_mode: None,
+            clamp_to_bounds: false,
+            export_grid: false,
+            svg_precision: None,
+            guides: Vec::new(),
+            construction_guides: Vec::new(),
+            next_guide: 0,
+            render_guides: false,
+            background: None,
+            unit: Unit::default(),
+            unsaved: false,
+        }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// changes the size of the drawing, moving or scaling the
+    /// drawables according to `mode`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point, ResizeMode};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    ///
+    /// canvas.resize(200.0, 100.0, ResizeMode::Anchor);
+    /// assert_eq!((200.0, 100.0), (canvas.width(), canvas.height()));
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" r=\"10\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// canvas.resize(300.0, 200.0, ResizeMode::Center);
+    /// assert_eq!(Some("<circle cx=\"100\" cy=\"100\" r=\"10\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// canvas.resize(600.0, 800.0, ResizeMode::Scale);
+    /// assert_eq!(Some("<circle cx=\"200\" cy=\"200\" r=\"20\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// ```
+    pub fn resize(&mut self, width: f64, height: f64, mode: ResizeMode) {
+        let ids: Vec<DrawableId> = self.storage.order().collect();
+
+        match mode {
+            ResizeMode::Anchor => {}
+            ResizeMode::Center => {
+                let offset = Point::new((width - self.width) / 2f64, (height - self.height) / 2f64);
+                if offset.x != 0f64 || offset.y != 0f64 {
+                    for id in ids {
+                        self.modify(id, |drawable| drawable.translate(&offset));
+                    }
+                }
+            }
+            ResizeMode::Scale if self.width > 0f64 && self.height > 0f64 => {
+                let factor = (width / self.width).min(height / self.height);
+                if factor != 1f64 {
+                    for id in ids {
+                        self.modify(id, |drawable| drawable.scale(factor, &Point::new(0f64, 0f64)));
+                    }
+                }
+            }
+            ResizeMode::Scale => {}
+        }
+
+        self.width = width;
+        self.height = height;
+        self.unsaved = true;
+    }
+
+    /// whether the drawing changed since it was created or last marked
+    /// saved. moving the view or selecting isn't a change, undoing is.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// assert!(!canvas.has_unsaved_changes());
+    ///
+    /// let snapshot = canvas.snapshot();
+    /// canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    /// assert!(canvas.has_unsaved_changes());
+    ///
+    /// canvas.mark_saved();
+    /// canvas.select_drawable_at(&Point::new(50.0, 50.0));
+    /// canvas.zoom_at(&Point::new(0.0, 0.0), 2.0);
+    /// assert!(!canvas.has_unsaved_changes());
+    ///
+    /// canvas.restore(snapshot);
+    /// assert!(canvas.has_unsaved_changes());
+    ///
+    /// ```
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.unsaved
+    }
+
+    /// records that the drawing as it is now has been saved.
+    pub fn mark_saved(&mut self) {
+        self.unsaved = false;
+    }
+
+    /// the color painted behind the drawing.
+    pub fn background(&self) -> Option<&str> {
+        self.background.as_deref()
+    }
+
+    /// paints the drawing on `background`, any CSS color, or leaves it transparent with `None`.
+    /// it's exported as a rect covering the whole document.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// canvas.set_background(Some(String::from("#fff8e0")));
+    ///
+    /// assert_eq!(Some("#fff8e0"), canvas.background());
+    /// assert!(canvas.to_svg().contains("<rect width=\"100%\" height=\"100%\" fill=\"#fff8e0\" />"));
+    ///
+    /// ```
+    pub fn set_background(&mut self, background: Option<String>) {
+        self.unsaved |= self.background != background;
+        self.background = background;
+    }
+
+    /// the unit lengths are shown in.
+    pub fn unit(&self) -> Unit {
+        self.unit
+    }
+
+    pub fn set_unit(&mut self, unit: Unit) {
+        self.unsaved |= self.unit != unit;
+        self.unit = unit;
+    }
+
+    /// the number of drawables on the canvas.
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.storage.len() == 0
+    }
+
+    /// the ids of every drawable, from the bottom of the stack to the top.
+    pub fn ids(&self) -> Vec<DrawableId> {
+        self.storage.order().collect()
+    }
+
+    /// the drawable right above `id`, hidden or not, `None` when `id` is on
+    /// top or deleted. it lets a renderer put a new element in its place.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let a = canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    /// let b = canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    /// let c = canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    ///
+    /// assert_eq!((Some(b), Some(c), None), (canvas.stacked_above(a), canvas.stacked_above(b), canvas.stacked_above(c)));
+    ///
+    /// canvas.send_to_back(c);
+    /// assert_eq!((Some(a), None), (canvas.stacked_above(c), canvas.stacked_above(b)));
+    ///
+    /// canvas.delete(a);
+    /// assert_eq!((Some(b), None), (canvas.stacked_above(c), canvas.stacked_above(a)));
+    ///
+    /// ```
+    pub fn stacked_above(&self, id: DrawableId) -> Option<DrawableId> {
+        self.storage.above(id)
+    }
+
+    /// returns the drawable with the given id,
+    /// `None` if it has been deleted.
+    pub fn get(&self, id: DrawableId) -> Option<&Arc<Drawable>> {
+        self.storage.get(id)
+    }
+
+    /// every drawable with its id, from the bottom of the stack to the top.
+    pub fn drawables(&self) -> impl ExactSizeIterator<Item = (DrawableId, &Arc<Drawable>)> + '_ {
+        self.storage.iter().map(|(id, drawable, _)| (id, drawable))
+    }
+
+    /// adds a line, which must have a length and finite endpoints.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, CanvasError, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let a = Point::new(10.0, 10.0);
+    ///
+    /// assert_eq!(Err(CanvasError::ZeroLength), canvas.add_line(&a, &a));
+    /// assert_eq!(Err(CanvasError::ZeroLength), canvas.add_line(&a, &Point::new(10.0, 10.0 + f64::EPSILON)));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_line(&a, &Point { x: f64::NAN, y: 0.0 }));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_line(&Point { x: 0.0, y: f64::INFINITY }, &a));
+    /// assert!(canvas.is_empty());
+    ///
+    /// assert!(canvas.add_line(&a, &Point::new(20.0, 10.0)).is_ok());
+    ///
+    /// ```
+    pub fn add_line(&mut self, start: &Point, end: &Point) -> Result<DrawableId, CanvasError> {
+        self.add_line_with_stroke_style(start, end, StrokeStyle::Solid)
+    }
+
+    /// adds a line like `add_line`, with its stroke dashed or dotted.
+    /// the dashes must be finite.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, CanvasError, Point, StrokeStyle};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let (a, b) = (Point::new(10.0, 10.0), Point::new(90.0, 10.0));
+    ///
+    /// let dashed = canvas.add_line_with_stroke_style(&a, &b, StrokeStyle::Dashed(vec![6.0, 3.0])).unwrap();
+    /// assert_eq!(Some("<line stroke-dasharray=\"6 3\" x1=\"10\" x2=\"90\" y1=\"10\" y2=\"10\" />"), canvas.svg_fragment_for(dashed));
+    /// assert!(canvas.to_svg().contains("stroke-dasharray=\"6 3\""));
+    ///
+    /// let dotted = canvas.add_line_with_stroke_style(&a, &b, StrokeStyle::Dotted).unwrap();
+    /// assert_eq!(Some("<line stroke-dasharray=\"1 2\" x1=\"10\" x2=\"90\" y1=\"10\" y2=\"10\" />"), canvas.svg_fragment_for(dotted));
+    ///
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_line_with_stroke_style(&a, &b, StrokeStyle::Dashed(vec![f64::NAN])));
+    ///
+    /// ```
+    pub fn add_line_with_stroke_style(&mut self, start: &Point, end: &Point, stroke_style: StrokeStyle) -> Result<DrawableId, CanvasError> {
+        self.add(Line::builder(start, end).stroke_style(stroke_style).build())
+    }
+
+    /// adds a circle, which must have a positive radius and a finite center.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, CanvasError, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let center = Point::new(50.0, 50.0);
+    ///
+    /// assert_eq!(Err(CanvasError::NonPositiveRadius(0.0)), canvas.add_circle(&center, 0.0));
+    /// assert_eq!(Err(CanvasError::NonPositiveRadius(-5.0)), canvas.add_circle(&center, -5.0));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_circle(&center, f64::NAN));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_circle(&center, f64::INFINITY));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_circle(&Point { x: f64::NAN, y: 0.0 }, 5.0));
+    /// assert!(canvas.is_empty());
+    ///
+    /// assert!(canvas.add_circle(&center, 5.0).is_ok());
+    ///
+    /// ```
+    pub fn add_circle(&mut self, center: &Point, radius: f64) -> Result<DrawableId, CanvasError> {
+        self.add_circle_with_fill(center, radius, true)
+    }
+
+    /// adds a circle like `add_circle`, filled or only outlined. an outlined
+    /// circle is left unfilled in the SVG too, and is only clicked on its rim.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let center = Point::new(50.0, 50.0);
+    ///
+    /// let ring = canvas.add_circle_with_fill(&center, 20.0, false).unwrap();
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" fill=\"none\" r=\"20\" />"), canvas.svg_fragment_for(ring));
+    /// assert_eq!(None, canvas.topmost_at(&center));
+    /// assert_eq!(Some(ring), canvas.topmost_at(&Point::new(70.0, 50.0)));
+    ///
+    /// let disc = canvas.add_circle_with_fill(&center, 20.0, true).unwrap();
+    /// assert_eq!(Some(disc), canvas.topmost_at(&center));
+    ///
+    /// ```
+    pub fn add_circle_with_fill(&mut self, center: &Point, radius: f64, filled: bool) -> Result<DrawableId, CanvasError> {
+        let circle = Circle::builder(center, radius);
+
+        self.add(if filled { circle.build() } else { circle.fill_none().build() })
+    }
+
+    /// adds the circle running through `a`, `b` and `c`, see `Circle::from_three_points`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, CanvasError, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let (a, b, c) = (Point::new(30.0, 50.0), Point::new(50.0, 30.0), Point::new(70.0, 50.0));
+    ///
+    /// let id = canvas.add_circle_from_points(&a, &b, &c).unwrap();
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" r=\"20\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// assert_eq!(Err(CanvasError::CollinearPoints), canvas.add_circle_from_points(&a, &Point::new(50.0, 50.0), &c));
+    /// assert_eq!(Err(CanvasError::CollinearPoints), canvas.add_circle_from_points(&a, &a, &c));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_circle_from_points(&a, &b, &Point { x: f64::NAN, y: 0.0 }));
+    /// assert_eq!(1, canvas.len());
+    ///
+    /// ```
+    pub fn add_circle_from_points(&mut self, a: &Point, b: &Point, c: &Point) -> Result<DrawableId, CanvasError> {
+        if ![a.x, a.y, b.x, b.y, c.x, c.y].iter().all(|value| value.is_finite()) {
+            return Err(CanvasError::NotFinite);
+        }
+
+        self.add(Circle::from_three_points(a, b, c).ok_or(CanvasError::CollinearPoints)?)
+    }
+
+    /// adds an arc running clockwise from `start_angle` to `end_angle`, see `Arc`.
+    /// like a circle it must have a positive radius, and it must turn some way
+    /// from its start, so its angles can't be a whole number of turns apart.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Angle, Canvas, CanvasError, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let center = Point::new(50.0, 50.0);
+    /// let (start, end) = (Angle::degrees(300.0), Angle::degrees(60.0));
+    ///
+    /// assert_eq!(Err(CanvasError::NonPositiveRadius(0.0)), canvas.add_arc(&center, 0.0, start, end));
+    /// assert_eq!(Err(CanvasError::ZeroSweep), canvas.add_arc(&center, 10.0, start, start));
+    /// assert_eq!(Err(CanvasError::ZeroSweep), canvas.add_arc(&center, 10.0, Angle::degrees(0.0), Angle::degrees(360.0)));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_arc(&center, 10.0, start, Angle::degrees(f64::NAN)));
+    /// assert!(canvas.is_empty());
+    ///
+    /// // across 0°, the arc is hit on the right of its circle but not on the left
+    /// let id = canvas.add_arc(&center, 10.0, start, end).unwrap();
+    /// assert_eq!(Some(id), canvas.topmost_at(&Point::new(60.0, 50.0)));
+    /// assert_eq!(None, canvas.topmost_at(&Point::new(40.0, 50.0)));
+    ///
+    /// ```
+    pub fn add_arc(&mut self, center: &Point, radius: f64, start_angle: Angle, end_angle: Angle) -> Result<DrawableId, CanvasError> {
+        self.add(super::Arc::new(center, radius, start_angle, end_angle))
+    }
+
+    /// adds a rect from 2 opposite corners, which must be finite and not share an x or a y.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, CanvasError, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let a = Point::new(10.0, 10.0);
+    ///
+    /// assert_eq!(Err(CanvasError::ZeroArea), canvas.add_rect(&a, &a));
+    /// assert_eq!(Err(CanvasError::ZeroArea), canvas.add_rect(&a, &Point::new(30.0, 10.0)));
+    /// assert_eq!(Err(CanvasError::ZeroArea), canvas.add_rect(&a, &Point::new(10.0, 30.0)));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_rect(&a, &Point { x: f64::NEG_INFINITY, y: 30.0 }));
+    /// assert!(canvas.is_empty());
+    ///
+    /// assert!(canvas.add_rect(&a, &Point::new(30.0, 30.0)).is_ok());
+    ///
+    /// ```
+    pub fn add_rect(&mut self, a: &Point, b: &Point) -> Result<DrawableId, CanvasError> {
+        self.add(Rect::new(a, b))
+    }
+
+    /// adds a text at a finite position.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, CanvasError, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    ///
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_text(String::from("hi"), Point { x: f64::NAN, y: 0.0 }));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_text_with_font_size(String::from("hi"), Point { x: 0.0, y: f64::INFINITY }, 20.0));
+    /// assert!(canvas.is_empty());
+    ///
+    /// assert!(canvas.add_text(String::from("hi"), Point::new(0.0, 0.0)).is_ok());
+    ///
+    /// ```
+    pub fn add_text(&mut self, text: String, pos: Point) -> Result<DrawableId, CanvasError> {
+        self.add(Text::new(text, pos))
+    }
+
+    pub fn add_text_with_font_size(&mut self, text: String, pos: Point, font_size: f64) -> Result<DrawableId, CanvasError> {
+        self.add(Text::with_font_size(text, pos, font_size))
+    }
+
+    /// adds any drawable, like one made with a builder, returning its id.
+    /// it's checked like the drawables of the other `add_` methods.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, CanvasError, Color, Line, Point, StyleBuilder};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add(Line::builder(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0)).stroke(Color::RED).build()).unwrap();
+    /// canvas.add(Point::new(5.0, 5.0)).unwrap();
+    ///
+    /// assert_eq!(&[id], &canvas.ids()[..1]);
+    /// assert!(canvas.to_svg().contains("<line stroke=\"#ff0000\" x1=\"0\" x2=\"10\" y1=\"0\" y2=\"0\" />"));
+    ///
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add(Point { x: f64::NAN, y: 5.0 }));
+    ///
+    /// ```
+    pub fn add(&mut self, drawable: impl Into<Drawable>) -> Result<DrawableId, CanvasError> {
+        let drawable = drawable.into();
+        validate(&drawable)?;
+
+        Ok(self.push(drawable))
+    }
+
+    /// adds many lines at once, all painted with `style`, returning the ids
+    /// they were given. it counts as a single change, so the revision only
+    /// goes up by one however many lines there are.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point, Style};
+    ///
+    /// let lines: Vec<(Point, Point)> = (0..10000)
+    ///     .map(|i| (Point::new(i as f64, 0.0), Point::new(i as f64, 50.0)))
+    ///     .collect();
+    ///
+    /// let mut individually = Canvas::new(10000.0, 100.0);
+    /// for (start, end) in &lines {
+    ///     individually.add_line(start, end).unwrap();
+    /// }
+    ///
+    /// let mut bulk = Canvas::new(10000.0, 100.0);
+    /// assert_eq!(individually.ids(), &bulk.add_lines(lines, &Style::default()).unwrap()[..]);
+    ///
+    /// assert!(individually.drawables().eq(bulk.drawables()));
+    /// assert_eq!(individually.to_svg(), bulk.to_svg());
+    /// assert_eq!(individually.drawables_at(&Point::new(20.0, 25.0)), bulk.drawables_at(&Point::new(20.0, 25.0)));
+    /// assert_eq!(1, bulk.revision());
+    ///
+    /// ```
+    ///
+    /// ```
+    /// use program_core::{Canvas, Color, Point, StrokeStyle, Style};
+    ///
+    /// let style = Style { stroke: Some(Color::RED.into()), stroke_style: StrokeStyle::Dashed(vec![4.0, 2.0]), ..Style::default() };
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let ids = canvas.add_lines([(Point::new(0.0, 10.0), Point::new(50.0, 10.0)), (Point::new(0.0, 20.0), Point::new(50.0, 20.0))], &style).unwrap();
+    ///
+    /// for id in ids {
+    ///     assert_eq!(&style, canvas.get(id).unwrap().style().unwrap());
+    /// }
+    ///
+    /// ```
+    pub fn add_lines(&mut self, lines: impl IntoIterator<Item = (Point, Point)>, style: &Style) -> Result<Vec<DrawableId>, CanvasError> {
+        let lines: Vec<Drawable> = lines
+            .into_iter()
+            .map(|(start, end)| Drawable::Line(Line::new(&start, &end).with_style(style.clone())))
+            .collect();
+        for line in &lines {
+            validate(line)?;
+        }
+
+        Ok(self.extend(lines))
+    }
+
+    /// adds many drawables at once, returning the ids they were given.
+    /// with the `parallel` feature their tags are serialized on all cores,
+    /// producing exactly the same document as adding them one by one.
+    /// they're checked like the drawables of the `add_` methods, and
+    /// nothing is added when any of them can't be. like `add_lines`,
+    /// it counts as a single change.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Circle, Drawable, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// canvas.add_circle(&Point::new(10.0, 10.0), 5.0).unwrap();
+    ///
+    /// let circles = vec![
+    ///     Drawable::Circle(Circle::new(&Point::new(20.0, 20.0), 5.0)),
+    ///     Drawable::Circle(Circle::new(&Point::new(30.0, 30.0), 5.0)),
+    /// ];
+    ///
+    /// let ids = canvas.add_drawables(circles).unwrap();
+    /// assert_eq!(&canvas.ids()[1..], &ids[..]);
+    /// assert_eq!(2, canvas.revision());
+    /// assert_eq!(Some(ids[1]), canvas.select_drawable_at(&Point::new(30.0, 30.0)));
+    ///
+    /// ```
+    ///
+    /// ```
+    /// use program_core::{Canvas, CanvasError, Circle, Drawable, Line, Point, Rect};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let fine = Drawable::Circle(Circle::new(&Point::new(20.0, 20.0), 5.0));
+    /// let a = Point::new(10.0, 10.0);
+    ///
+    /// let bad = [
+    ///     (Drawable::Circle(Circle::new(&a, 0.0)), CanvasError::NonPositiveRadius(0.0)),
+    ///     (Drawable::Line(Line::new(&a, &a)), CanvasError::ZeroLength),
+    ///     (Drawable::Rect(Rect::new(&a, &Point::new(30.0, 10.0))), CanvasError::ZeroArea),
+    ///     (Drawable::Circle(Circle::new(&Point { x: f64::NAN, y: 0.0 }, 5.0)), CanvasError::NotFinite),
+    /// ];
+    /// for (drawable, error) in bad {
+    ///     assert_eq!(Err(error), canvas.add_drawables(vec![fine.clone(), drawable]));
+    /// }
+    /// assert!(canvas.is_empty());
+    ///
+    /// ```
+    ///
+    /// ```
+    /// use program_core::{Canvas, Circle, Drawable, Line, Point, Text};
+    ///
+    /// let mut seed: u64 = 0x2545f4914f6cdd1d;
+    /// let mut random = move || {
+    ///     seed ^= seed << 13;
+    ///     seed ^= seed >> 7;
+    ///     seed ^= seed << 17;
+    ///     (seed % 100000) as f64 / 100.0
+    /// };
+    ///
+    /// let drawables: Vec<Drawable> = (0..5000)
+    ///     .map(|i| {
+    ///         let point = Point::new(random(), random());
+    ///         match i % 3 {
+    ///             0 => Drawable::Line(Line::new(&point, &Point::new(random(), random()))),
+    ///             1 => Drawable::Circle(Circle::new(&point, random())),
+    ///             _ => Drawable::Text(Text::new(format!("{} & {}", random(), random()), point)),
+    ///         }
+    ///     })
+    ///     .collect();
+    ///
+    /// let mut sequential = Canvas::new(1000.0, 1000.0);
+    /// for drawable in &drawables {
+    ///     match drawable {
+    ///         Drawable::Line(line) => sequential.add_line(&line.start(), &line.end()).unwrap(),
+    ///         Drawable::Circle(circle) => sequential.add_circle(&circle.center(), circle.radius()).unwrap(),
+    ///         Drawable::Text(text) => sequential.add_text(text.text().clone(), text.pos()).unwrap(),
+    ///         Drawable::Rect(_) | Drawable::Point(_) | Drawable::Arc(_) => unreachable!(),
+    ///     };
+    /// }
+    ///
+    /// let mut bulk = Canvas::new(1000.0, 1000.0);
+    /// bulk.add_drawables(drawables).unwrap();
+    ///
+    /// assert_eq!(sequential.to_svg(), bulk.to_svg());
+    ///
+    /// ```
+    pub fn add_drawables(&mut self, drawables: Vec<Drawable>) -> Result<Vec<DrawableId>, CanvasError> {
+        for drawable in &drawables {
+            validate(drawable)?;
+        }
+
+        Ok(self.extend(drawables))
+    }
+
+    /// adds the drawables as one change: their tags are serialized, their
+    /// bounds computed and the index updated in one go each.
+    fn extend(&mut self, drawables: impl IntoIterator<Item = Drawable>) -> Vec<DrawableId> {
+        let drawables: Vec<Drawable> = drawables.into_iter().collect();
+        let tags = serialize_tags(&drawables);
+        let bounds: Vec<Aabb> = drawables.iter().map(hit_bounds).collect();
+
+        self.storage.reserve(drawables.len());
+        let ids = self.storage.extend(drawables.into_iter().map(Arc::new).zip(tags));
+
+        Arc::make_mut(&mut self.spatial_index).extend(ids.iter().copied().zip(bounds));
+        self.serialized_tags += ids.len();
+        self.changed_all(&ids);
+
+        ids
+    }
+
+    fn push(&mut self, drawable: Drawable) -> DrawableId {
+        let (bounds, tag) = (hit_bounds(&drawable), drawable.to_svg_tag().into());
+        let id = self.storage.insert(Arc::new(drawable), tag);
+
+        Arc::make_mut(&mut self.spatial_index).insert(id, &bounds);
+        self.changed(id);
+        self.serialized_tags += 1;
+
+        id
+    }
+
+    /// removes a drawable from the canvas, returning whether it existed.
+    /// its id, and any copy of it, will never refer to a drawable again.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let mut ids: Vec<_> = (0..5).map(|i| canvas.add_circle(&Point::new((i * 20) as f64, 50.0), 5.0).unwrap()).collect();
+    ///
+    /// for round in 0..10 {
+    ///     let deleted = ids.remove(round % ids.len());
+    ///     assert!(canvas.delete(deleted));
+    ///     assert!(!canvas.delete(deleted));
+    ///     assert!(canvas.get(deleted).is_none());
+    ///
+    ///     let added = canvas.add_circle(&Point::new(50.0, 90.0), 5.0).unwrap();
+    ///     assert_ne!(deleted, added);
+    ///     assert!(canvas.get(deleted).is_none());
+    ///     ids.push(added);
+    ///
+    ///     assert_eq!(&ids[..], canvas.ids());
+    /// }
+    ///
+    /// let from_order: String = canvas.ids().iter().map(|&id| canvas.svg_fragment_for(id).unwrap()).collect();
+    /// assert!(canvas.to_svg().contains(&from_order));
+    /// assert_eq!(Some(ids[4]), canvas.select_drawable_at(&Point::new(50.0, 90.0)));
+    ///
+    /// ```
+    pub fn delete(&mut self, id: DrawableId) -> bool {
+        match self.storage.remove(id) {
+            Some(drawable) => {
+                Arc::make_mut(&mut self.spatial_index).remove(id, &hit_bounds(&drawable));
+                self.changed(id);
+                self.locked.remove(&id);
+                self.names.remove(&id);
+                self.hidden.remove(&id);
+                self.selection.retain(|&selected| selected != id);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// removes the selected drawables, returning whether any was removed.
+    /// locked drawables stay, and stay selected.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let kept = canvas.add_circle(&Point::new(20.0, 20.0), 5.0).unwrap();
+    /// canvas.add_circle(&Point::new(60.0, 60.0), 5.0).unwrap();
+    ///
+    /// assert!(!canvas.delete_selected());
+    ///
+    /// canvas.select_drawable_at(&Point::new(60.0, 60.0));
+    /// assert!(canvas.delete_selected());
+    /// assert_eq!(None, canvas.selected_drawable());
+    /// assert_eq!(vec![kept], canvas.ids());
+    ///
+    /// ```
+    pub fn delete_selected(&mut self) -> bool {
+        let unlocked: Vec<DrawableId> = self.selection.iter().copied().filter(|&id| !self.is_locked(id)).collect();
+
+        let mut deleted = false;
+        for id in unlocked {
+            deleted |= self.delete(id);
+        }
+        deleted
+    }
+
+    /// puts a drawable on top of every other one, returning whether it exists.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let a = canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    /// let b = canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    /// let c = canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    ///
+    /// assert!(canvas.bring_to_front(a));
+    /// assert_eq!(vec![b, c, a], canvas.ids());
+    /// assert_eq!(Some(a), canvas.topmost_at(&Point::new(50.0, 50.0)));
+    ///
+    /// assert!(canvas.send_to_back(c));
+    /// assert_eq!(vec![c, b, a], canvas.ids());
+    /// assert_eq!(vec![c, b, a], canvas.drawables_at(&Point::new(50.0, 50.0)));
+    ///
+    /// canvas.delete(b);
+    /// assert!(!canvas.bring_to_front(b));
+    /// assert!(canvas.to_svg().ends_with("<circle cx=\"50\" cy=\"50\" r=\"10\" /><circle cx=\"50\" cy=\"50\" r=\"10\" /></svg>"));
+    ///
+    /// ```
+    pub fn bring_to_front(&mut self, id: DrawableId) -> bool {
+        let raised = self.storage.raise(id);
+        if raised {
+            self.changed(id);
+        }
+
+        raised
+    }
+
+    /// puts a drawable below every other one, returning whether it exists.
+    pub fn send_to_back(&mut self, id: DrawableId) -> bool {
+        let lowered = self.storage.lower(id);
+        if lowered {
+            self.changed(id);
+        }
+
+        lowered
+    }
+
+    /// whether a drawable is locked against edits made through the selection.
+    pub fn is_locked(&self, id: DrawableId) -> bool {
+        self.locked.contains(&id)
+    }
+
+    /// locks or unlocks a drawable, returning whether it exists.
+    /// a locked drawable can still be selected, but moving, editing
+    /// or deleting the selection leaves it as it is.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// canvas.select_drawable_at(&Point::new(50.0, 50.0));
+    ///
+    /// assert!(canvas.set_locked(id, true));
+    /// assert!(!canvas.nudge_selected(10.0, 0.0));
+    /// assert!(!canvas.delete_selected());
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" r=\"5\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// assert!(canvas.set_locked(id, false));
+    /// assert!(canvas.nudge_selected(10.0, 0.0));
+    /// assert!(canvas.delete_selected());
+    /// assert!(!canvas.set_locked(id, true));
+    ///
+    /// ```
+    pub fn set_locked(&mut self, id: DrawableId, locked: bool) -> bool {
+        if self.storage.get(id).is_none() {
+            return false;
+        }
+
+        let changed = if locked { self.locked.insert(id) } else { self.locked.remove(&id) };
+        // locks aren't saved, only views of the drawables care
+        if changed {
+            self.revision += 1;
+        }
+
+        true
+    }
+
+    /// a number that grows whenever a drawable is added, changed, deleted,
+    /// reordered, renamed, locked or hidden, and when a snapshot is restored.
+    /// views listing the drawables compare it to tell when to refresh.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let start = canvas.revision();
+    ///
+    /// let id = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// let added = canvas.revision();
+    /// assert!(added > start);
+    ///
+    /// canvas.zoom_at(&Point::new(0.0, 0.0), 2.0);
+    /// canvas.set_selected_drawable(Some(id));
+    /// assert_eq!(added, canvas.revision());
+    ///
+    /// canvas.set_locked(id, true);
+    /// assert!(canvas.revision() > added);
+    ///
+    /// ```
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// the name given to a drawable, if any.
+    pub fn name(&self, id: DrawableId) -> Option<&str> {
+        self.names.get(&id).map(String::as_str)
+    }
+
+    /// names a drawable, or with `None` or a blank name takes its name away.
+    /// returns whether the drawable exists.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    ///
+    /// assert!(canvas.set_name(id, Some(String::from(" wheel "))));
+    /// assert_eq!(Some("wheel"), canvas.name(id));
+    ///
+    /// canvas.set_name(id, Some(String::new()));
+    /// assert_eq!(None, canvas.name(id));
+    ///
+    /// canvas.delete(id);
+    /// assert!(!canvas.set_name(id, Some(String::from("gone"))));
+    ///
+    /// ```
+    pub fn set_name(&mut self, id: DrawableId, name: Option<String>) -> bool {
+        if self.storage.get(id).is_none() {
+            return false;
+        }
+
+        let name = name.map(|name| name.trim().to_string()).filter(|name| !name.is_empty());
+        if self.names.get(&id) != name.as_ref() {
+            match name {
+                Some(name) => self.names.insert(id, name),
+                None => self.names.remove(&id),
+            };
+            self.changed(id);
+        }
+
+        true
+    }
+
+    pub fn is_hidden(&self, id: DrawableId) -> bool {
+        self.hidden.contains(&id)
+    }
+
+    /// hides or shows a drawable, returning whether it exists. a hidden
+    /// drawable isn't drawn, exported or hit by clicks, and hiding it
+    /// takes it out of the selection.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// canvas.select_drawable_at(&Point::new(50.0, 50.0));
+    ///
+    /// assert!(canvas.set_hidden(id, true));
+    /// assert!(canvas.selection().is_empty());
+    /// assert_eq!(None, canvas.topmost_at(&Point::new(50.0, 50.0)));
+    /// assert!(!canvas.to_svg().contains("<circle"));
+    ///
+    /// assert!(canvas.set_hidden(id, false));
+    /// assert_eq!(Some(id), canvas.topmost_at(&Point::new(50.0, 50.0)));
+    ///
+    /// ```
+    pub fn set_hidden(&mut self, id: DrawableId, hidden: bool) -> bool {
+        if self.storage.get(id).is_none() {
+            return false;
+        }
+
+        let changed = if hidden { self.hidden.insert(id) } else { self.hidden.remove(&id) };
+        if changed {
+            self.selection.retain(|&selected| selected != id);
+            self.changed(id);
+        }
+
+        true
+    }
+
+    /// applies `change` to the drawable with the given id, copying it first
+    /// if a snapshot still shares it, then refreshes its cached SVG tag
+    /// and its place in the spatial index.
+    fn modify(&mut self, id: DrawableId, change: impl FnOnce(&mut Drawable)) {
+        let drawable = match self.storage.get_mut(id) {
+            Some(drawable) => drawable,
+            None => return,
+        };
+        let old_bounds = hit_bounds(drawable);
+
+        if Arc::strong_count(drawable) > 1 {
+            self.cloned_drawables += 1;
+        }
+        change(Arc::make_mut(drawable));
+
+        let (new_bounds, tag) = (hit_bounds(drawable), drawable.to_svg_tag().into());
+        let spatial_index = Arc::make_mut(&mut self.spatial_index);
+        spatial_index.remove(id, &old_bounds);
+        spatial_index.insert(id, &new_bounds);
+        self.storage.set_tag(id, tag);
+        self.serialized_tags += 1;
+        self.changed(id);
+    }
+
+    /// records that a drawable was added, changed, or deleted.
+    fn changed(&mut self, id: DrawableId) {
+        self.dirty.insert(id);
+        self.unsaved = true;
+        self.revision += 1;
+    }
+
+    /// records that many drawables were, as a single change.
+    fn changed_all(&mut self, ids: &[DrawableId]) {
+        if ids.is_empty() {
+            return;
+        }
+
+        // like the stacking order, many sorted ids are quicker merged than inserted
+        let mut ids: BTreeSet<DrawableId> = ids.iter().copied().collect();
+        if ids.len() >= self.dirty.len() {
+            self.dirty.append(&mut ids);
+        } else {
+            self.dirty.extend(ids);
+        }
+        self.unsaved = true;
+        self.revision += 1;
+    }
+
+    /// returns the ids of the drawables added, changed, or deleted since
+    /// the last call, sorted by id. deleted ones are those `get` returns `None` for.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let first = canvas.add_circle(&Point::new(10.0, 10.0), 5.0).unwrap();
+    /// let second = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// assert_eq!(vec![first, second], canvas.take_dirty_ids());
+    /// assert!(canvas.take_dirty_ids().is_empty());
+    ///
+    /// let snapshot = canvas.snapshot();
+    /// canvas.select_drawable_at(&Point::new(50.0, 50.0));
+    /// canvas.translate_selected_drawable(&Point::new(5.0, 0.0));
+    /// canvas.delete(first);
+    /// assert_eq!(vec![first, second], canvas.take_dirty_ids());
+    /// assert!(canvas.get(first).is_none());
+    ///
+    /// let third = canvas.add_circle(&Point::new(90.0, 90.0), 5.0).unwrap();
+    /// canvas.restore(snapshot);
+    /// let mut restored = vec![first, second, third];
+    /// restored.sort();
+    /// assert_eq!(restored, canvas.take_dirty_ids());
+    ///
+    /// ```
+    pub fn take_dirty_ids(&mut self) -> Vec<DrawableId> {
+        std::mem::take(&mut self.dirty).into_iter().collect()
+    }
+
+    /// the ids `take_dirty_ids` would return, left in place. a renderer that
+    /// can't change the canvas it draws reads them, and whoever changes it
+    /// next takes them.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add_circle(&Point::new(10.0, 10.0), 5.0).unwrap();
+    ///
+    /// assert_eq!(vec![id], canvas.dirty_ids().collect::<Vec<_>>());
+    /// assert_eq!(vec![id], canvas.take_dirty_ids());
+    /// assert_eq!(0, canvas.dirty_ids().len());
+    ///
+    /// ```
+    pub fn dirty_ids(&self) -> impl ExactSizeIterator<Item = DrawableId> + '_ {
+        self.dirty.iter().copied()
+    }
+
+    /// saves the current state of the canvas without copying any drawable.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point, Style};
+    ///
+    /// let mut canvas = Canvas::new(100000.0, 100.0);
+    /// let ids = canvas.add_lines((0..10000).map(|i| (Point::new((i * 10) as f64, 0.0), Point::new((i * 10) as f64, 50.0))), &Style::default()).unwrap();
+    ///
+    /// let snapshot = canvas.snapshot();
+    /// assert_eq!(0, canvas.cloned_drawables());
+    ///
+    /// canvas.select_drawable_at(&Point::new(500.0, 25.0));
+    /// canvas.translate_selected_drawable(&Point::new(2.0, 0.0));
+    /// canvas.translate_selected_drawable(&Point::new(2.0, 0.0));
+    /// assert_eq!(1, canvas.cloned_drawables());
+    ///
+    /// canvas.restore(snapshot);
+    /// assert_eq!(None, canvas.select_drawable_at(&Point::new(504.0, 25.0)));
+    /// assert_eq!(Some(ids[50]), canvas.select_drawable_at(&Point::new(500.0, 25.0)));
+    ///
+    /// ```
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.clone())
+    }
+
+    /// returns the canvas to the state saved in `snapshot`. the view, the grid, the guides,
+    /// ortho mode, `clamp_to_bounds` and `svg_precision` aren't part of the drawing, so they stay as they are.
+    /// the ids of drawables the snapshot doesn't have stay stale, and aren't handed out again.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let kept = canvas.add_circle(&Point::new(20.0, 20.0), 5.0).unwrap();
+    /// let snapshot = canvas.snapshot();
+    ///
+    /// let undone = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// canvas.restore(snapshot.clone());
+    /// let added = canvas.add_rect(&Point::new(0.0, 0.0), &Point::new(10.0, 10.0)).unwrap();
+    ///
+    /// assert_ne!(undone, added);
+    /// assert!(canvas.get(undone).is_none());
+    /// assert!(canvas.get(kept).is_some());
+    ///
+    /// // deleting, then going back to before the delete, keeps the id it had
+    /// canvas.delete(kept);
+    /// let reused = canvas.add_circle(&Point::new(80.0, 80.0), 5.0).unwrap();
+    /// canvas.restore(snapshot);
+    /// assert!(canvas.get(kept).is_some() && canvas.get(reused).is_none() && canvas.get(added).is_none());
+    /// let again = canvas.add_circle(&Point::new(80.0, 80.0), 5.0).unwrap();
+    /// assert!(![kept, undone, added, reused].contains(&again));
+    ///
+    /// ```
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        let (serialized_tags, cloned_drawables) = (self.serialized_tags, self.cloned_drawables);
+        let mut dirty: BTreeSet<DrawableId> = self.storage.order().collect();
+        let viewport = std::mem::take(&mut self.viewport);
+        let (grid_spacing, export_grid, svg_precision) = (self.grid_spacing, self.export_grid, self.svg_precision);
+        let (ortho_mode, clamp_to_bounds) = (self.ortho_mode, self.clamp_to_bounds);
+        let (guides, next_guide, render_guides) = (std::mem::take(&mut self.guides), self.next_guide, self.render_guides);
+        let construction_guides = std::mem::take(&mut self.construction_guides);
+        let revision = self.revision;
+        let storage = std::mem::take(&mut self.storage);
+
+        *self = snapshot.0;
+        self.storage.keep_generations_of(&storage);
+        dirty.extend(self.storage.order());
+        self.viewport = viewport;
+        self.grid_spacing = grid_spacing;
+        self.export_grid = export_grid;
+        self.svg_precision = svg_precision;
+        self.guides = guides;
+        self.construction_guides = construction_guides;
+        self.next_guide = next_guide;
+        self.render_guides = render_guides;
+        self.ortho_mode = ortho_mode;
+        self.clamp_to_bounds = clamp_to_bounds;
+        self.serialized_tags = serialized_tags;
+        self.cloned_drawables = cloned_drawables;
+        self.dirty = dirty;
+        self.unsaved = true;
+        self.revision = revision + 1;
+    }
+
+    /// the number of drawables copied because a snapshot still shared them.
+    pub fn cloned_drawables(&self) -> usize {
+        self.cloned_drawables
+    }
+
+    /// returns the cached SVG tag of a drawable,
+    /// so a single element can be patched without rebuilding the document.
+    pub fn svg_fragment_for(&self, id: DrawableId) -> Option<&str> {
+        self.storage.tag(id).map(|tag| &**tag)
+    }
+
+    /// the number of drawable tags serialized since the canvas was created.
+    /// tags are only serialized when a drawable is added or modified.
+    pub fn serialized_tags(&self) -> usize {
+        self.serialized_tags
+    }
+
+    /// the part of the drawing shown on screen.
+    /// the screen is as large as the canvas.
+    pub fn viewport(&self) -> &Viewport {
+        &self.viewport
+    }
+
+    /// converts a point on screen, like a click, to drawing coordinates.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(500.0, 500.0);
+    /// let id = canvas.add_circle(&Point::new(300.0, 300.0), 10.0).unwrap();
+    ///
+    /// canvas.zoom_at(&Point::new(0.0, 0.0), 2.0);
+    /// canvas.pan_by(&Point::new(-100.0, -50.0));
+    ///
+    /// let on_screen = canvas.viewport().world_to_screen(&Point::new(300.0, 300.0));
+    /// assert_eq!(Point::new(500.0, 550.0), on_screen);
+    /// assert_eq!(None, canvas.select_drawable_at(&on_screen));
+    /// assert_eq!(Some(id), canvas.select_drawable_at(&canvas.screen_to_world(&on_screen)));
+    /// assert_eq!(Some(id), canvas.select_drawable_at(&canvas.screen_to_world(&Point::new(515.0, 550.0))));
+    ///
+    /// ```
+    pub fn screen_to_world(&self, point: &Point) -> Point {
+        self.viewport.screen_to_world(point)
+    }
+
+    /// zooms the view by `factor`, keeping the drawing under
+    /// the screen point `anchor` in place.
+    pub fn zoom_at(&mut self, anchor: &Point, factor: f64) {
+        self.viewport.zoom_at(anchor, factor);
+    }
+
+    /// moves the view by `offset` screen pixels.
+    pub fn pan_by(&mut self, offset: &Point) {
+        self.viewport.pan_by(offset);
+    }
+
+    /// the spacing of the grid drawn points snap to, `None` when there's no grid.
+    pub fn grid_spacing(&self) -> Option<f64> {
+        self.grid_spacing
+    }
+
+    /// turns the grid on with the given spacing, or off with `None`.
+    /// spacings that aren't positive turn it off.
+    pub fn set_grid_spacing(&mut self, spacing: Option<f64>) {
+        self.grid_spacing = spacing.filter(|spacing| *spacing > 0f64 && spacing.is_finite());
+    }
+
+    /// the angular step, in radians, lines and rects are drawn at multiples of,
+    /// `None` when they're drawn at any angle.
+    pub fn ortho_mode(&self) -> Option<f64> {
+        self.ortho_mode
+    }
+
+    /// constrains drawing to multiples of `step` radians, `FRAC_PI_2` for
+    /// horizontal and vertical lines only, or lifts it with `None`.
+    /// steps that aren't positive lift it too. see `Point::snapped_to_angle`.
+    pub fn set_ortho_mode(&mut self, step: Option<f64>) {
+        self.ortho_mode = step.filter(|step| *step > 0f64 && step.is_finite());
+    }
+
+    /// whether `translate_selected_drawable` keeps the drawables on the canvas.
+    pub fn clamp_to_bounds(&self) -> bool {
+        self.clamp_to_bounds
+    }
+
+    /// keeps the selection from being moved off the canvas, where it can't be
+    /// reached again: moving it stops once the center of a drawable reaches an edge.
+    pub fn set_clamp_to_bounds(&mut self, clamp_to_bounds: bool) {
+        self.clamp_to_bounds = clamp_to_bounds;
+    }
+
+    /// snaps `point` to the closest of the `kinds` of points within `tolerance` of it:
+    /// the ends, middles and centers of the visible drawables and where their edges
+    /// cross, or else the guides, or else the grid. drawables in `ignore`, like the
+    /// ones being dragged, aren't snapped to. the point stays as it is when nothing is close enough.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point, SnapKind, SnapKinds};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let all = SnapKinds::ALL;
+    /// assert_eq!(None, canvas.snap(&Point::new(12.0, 17.0), all, 4.0, &[]).kind);
+    ///
+    /// canvas.set_grid_spacing(Some(5.0));
+    /// let snapped = canvas.snap(&Point::new(12.0, 17.0), all, 4.0, &[]);
+    /// assert_eq!((Point::new(10.0, 15.0), Some(SnapKind::Grid)), (snapped.point, snapped.kind));
+    ///
+    /// let line = canvas.add_line(&Point::new(0.0, 0.0), &Point::new(40.0, 40.0)).unwrap();
+    /// canvas.add_line(&Point::new(0.0, 40.0), &Point::new(60.0, 0.0)).unwrap();
+    /// canvas.add_circle(&Point::new(70.0, 70.0), 10.0).unwrap();
+    ///
+    /// let snap = |x, y, kinds| canvas.snap(&Point::new(x, y), kinds, 4.0, &[]);
+    /// assert_eq!(Some(SnapKind::Endpoint), snap(38.0, 41.0, all).kind);
+    /// let crossing = snap(25.0, 23.0, all);
+    /// assert_eq!((Point::new(24.0, 24.0), Some(SnapKind::Intersection)), (crossing.point, crossing.kind));
+    /// assert_eq!(Some(SnapKind::Center), snap(71.0, 68.0, all).kind);
+    ///
+    /// let mut kinds = all;
+    /// kinds.set(SnapKind::Intersection, false);
+    /// kinds.set(SnapKind::Endpoint, false);
+    /// assert_eq!(Some(SnapKind::Midpoint), snap(21.0, 19.0, kinds).kind);
+    /// kinds.set(SnapKind::Midpoint, false);
+    /// assert_eq!(Some(SnapKind::Grid), snap(21.0, 19.0, kinds).kind);
+    /// assert_eq!(None, snap(21.0, 19.0, SnapKinds::NONE).kind);
+    ///
+    /// // the line being dragged isn't snapped to
+    /// assert_eq!(Some(SnapKind::Grid), canvas.snap(&Point::new(1.0, 1.0), all, 4.0, &[line]).kind);
+    ///
+    /// // a guide comes before the grid, and two of them snap to where they cross
+    /// let near = Point::new(21.0, 19.0);
+    /// canvas.add_v_guide(22.5).unwrap();
+    /// let guided = canvas.snap(&near, kinds, 4.0, &[]);
+    /// assert_eq!((Point::new(22.5, 19.0), Some(SnapKind::Guide)), (guided.point, guided.kind));
+    /// canvas.add_h_guide(17.0).unwrap();
+    /// assert_eq!(Point::new(22.5, 17.0), canvas.snap(&near, kinds, 4.0, &[]).point);
+    /// kinds.set(SnapKind::Guide, false);
+    /// assert_eq!(Some(SnapKind::Grid), canvas.snap(&near, kinds, 4.0, &[]).kind);
+    ///
+    /// canvas.set_grid_spacing(Some(-5.0));
+    /// assert_eq!(None, canvas.grid_spacing());
+    ///
+    /// ```
+    pub fn snap(&self, point: &Point, kinds: SnapKinds, tolerance: f64, ignore: &[DrawableId]) -> Snap {
+        let near = Aabb::new(&Point::new(point.x - tolerance, point.y - tolerance), &Point::new(point.x + tolerance, point.y + tolerance));
+        let candidates: Vec<&Drawable> = self
+            .drawables_in_rect(&near)
+            .into_iter()
+            .filter(|id| !ignore.contains(id))
+            .filter_map(|id| self.storage.get(id).map(|drawable| drawable.as_ref()))
+            .collect();
+
+        let mut found: Vec<(SnapKind, Point)> = Vec::new();
+        for drawable in &candidates {
+            found.extend(snap::snap_points(drawable).into_iter().filter(|(kind, _)| kinds.contains(*kind)));
+        }
+        if kinds.contains(SnapKind::Intersection) {
+            let edges: Vec<Vec<Line>> = candidates.iter().map(|drawable| snap::segments(drawable)).collect();
+            for (i, a) in edges.iter().enumerate() {
+                for (first, second) in edges[i + 1..].iter().flatten().flat_map(|second| a.iter().map(move |first| (first, second))) {
+                    if let Some(crossing) = first.intersect(second) {
+                        found.push((SnapKind::Intersection, crossing));
+                    }
+                }
+            }
+        }
+
+        let distance = |other: &Point| (other.x - point.x).hypot(other.y - point.y);
+        let mut closest: Option<(f64, SnapKind, Point)> = None;
+        for (kind, found) in found {
+            let away = distance(&found);
+            if away <= tolerance && closest.as_ref().is_none_or(|(best, ..)| away < *best) {
+                closest = Some((away, kind, found));
+            }
+        }
+
+        let guided = if kinds.contains(SnapKind::Guide) { self.snap_to_guides(point, tolerance) } else { None };
+
+        match (closest, guided, self.grid_spacing) {
+            (Some((_, kind, point)), _, _) => Snap { point, kind: Some(kind) },
+            (None, Some(point), _) => Snap { point, kind: Some(SnapKind::Guide) },
+            (None, None, Some(spacing)) if kinds.contains(SnapKind::Grid) => {
+                Snap { point: point.snapped_to_grid(spacing), kind: Some(SnapKind::Grid) }
+            }
+            _ => Snap { point: point.clone(), kind: None },
+        }
+    }
+
+    /// `point` moved onto the closest vertical and the closest horizontal
+    /// guide within `tolerance` of it, or else onto the closest construction
+    /// guide, `None` when there are none.
+    fn snap_to_guides(&self, point: &Point, tolerance: f64) -> Option<Point> {
+        let closest = |axis: GuideAxis| {
+            self.guides
+                .iter()
+                .filter(|guide| guide.axis == axis && guide.distance_to(point) <= tolerance)
+                .min_by(|a, b| a.distance_to(point).total_cmp(&b.distance_to(point)))
+                .map(|guide| guide.position)
+        };
+
+        match (closest(GuideAxis::Vertical), closest(GuideAxis::Horizontal)) {
+            (None, None) => self
+                .construction_guides
+                .iter()
+                .map(|guide| guide.line.closest_point(point))
+                .map(|closest| ((closest.clone() - point.clone()).len(), closest))
+                .filter(|(away, _)| *away <= tolerance)
+                .min_by(|(a, _), (b, _)| a.total_cmp(b))
+                .map(|(_, closest)| closest),
+            (x, y) => Some(Point::new(x.unwrap_or(point.x), y.unwrap_or(point.y))),
+        }
+    }
+
+    pub fn export_grid(&self) -> bool {
+        self.export_grid
+    }
+
+    /// whether exports draw the grid behind the drawing.
+    /// the grid is never exported while it's off.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// canvas.set_grid_spacing(Some(10.0));
+    /// assert!(!canvas.to_svg().contains("<pattern"));
+    ///
+    /// canvas.set_export_grid(true);
+    /// assert!(canvas.to_svg().contains("<pattern id=\"grid\" width=\"10\" height=\"10\" patternUnits=\"userSpaceOnUse\">"));
+    ///
+    /// canvas.set_grid_spacing(None);
+    /// assert!(!canvas.to_svg().contains("<pattern"));
+    ///
+    /// ```
+    pub fn set_export_grid(&mut self, export_grid: bool) {
+        self.export_grid = export_grid;
+    }
+
+    /// the decimals numbers are written with in exported SVG documents,
+    /// `None` when they're written in full.
+    pub fn svg_precision(&self) -> Option<u32> {
+        self.svg_precision
+    }
+
+    /// rounds the numbers of exported SVG documents to `decimals` decimals, or
+    /// writes them in full with `None`. the drawables keep their numbers, and
+    /// `ExportOptions::precision` overrides it for a single export.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, ExportOptions, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add_line(&Point::new(1.0 / 3.0, 2.0 / 3.0), &Point::new(1e-7, 50.0)).unwrap();
+    /// // small numbers are written out in full, not in scientific notation
+    /// assert!(canvas.to_svg().contains("<line x1=\"0.3333333333333333\" x2=\"0.0000001\" y1=\"0.6666666666666666\" y2=\"50\" />"));
+    ///
+    /// canvas.set_svg_precision(Some(3));
+    /// let svg = canvas.to_svg();
+    /// assert!(svg.contains("<line x1=\"0.333\" x2=\"0\" y1=\"0.667\" y2=\"50\" />"));
+    /// assert_eq!(Some("<line x1=\"0.3333333333333333\" x2=\"0.0000001\" y1=\"0.6666666666666666\" y2=\"50\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// let svg = canvas.to_svg_with(&ExportOptions { precision: Some(1), ..ExportOptions::default() });
+    /// assert!(svg.contains("<line x1=\"0.3\" x2=\"0\" y1=\"0.7\" y2=\"50\" />"));
+    ///
+    /// ```
+    pub fn set_svg_precision(&mut self, decimals: Option<u32>) {
+        self.svg_precision = decimals;
+    }
+
+    /// adds a horizontal guide at `y`, returning its id. guides aren't
+    /// drawables: they aren't saved, undone, selected or exported by default.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, CanvasError, GuideAxis, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let top = canvas.add_h_guide(10.0).unwrap();
+    /// let side = canvas.add_v_guide(25.0).unwrap();
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_v_guide(f64::NAN));
+    ///
+    /// assert_eq!(0, canvas.len());
+    /// assert_eq!(vec![(top, GuideAxis::Horizontal, 10.0), (side, GuideAxis::Vertical, 25.0)],
+    ///     canvas.guides().iter().map(|guide| (guide.id, guide.axis, guide.position)).collect::<Vec<_>>());
+    /// assert_eq!(None, canvas.select_drawable_at(&Point::new(25.0, 10.0)));
+    ///
+    /// assert_eq!(Some(side), canvas.guide_at(&Point::new(27.0, 50.0), 4.0));
+    /// assert!(canvas.move_guide(side, 60.0));
+    /// assert_eq!(None, canvas.guide_at(&Point::new(27.0, 50.0), 4.0));
+    ///
+    /// assert!(canvas.remove_guide(top));
+    /// assert!(!canvas.remove_guide(top));
+    /// assert!(!canvas.move_guide(top, 5.0));
+    /// assert_ne!(top, canvas.add_h_guide(10.0).unwrap());
+    ///
+    /// ```
+    pub fn add_h_guide(&mut self, y: f64) -> Result<GuideId, CanvasError> {
+        self.add_axis_guide(GuideAxis::Horizontal, y)
+    }
+
+    /// adds a vertical guide at `x`, returning its id. see `add_h_guide`.
+    pub fn add_v_guide(&mut self, x: f64) -> Result<GuideId, CanvasError> {
+        self.add_axis_guide(GuideAxis::Vertical, x)
+    }
+
+    fn add_axis_guide(&mut self, axis: GuideAxis, position: f64) -> Result<GuideId, CanvasError> {
+        if !position.is_finite() {
+            return Err(CanvasError::NotFinite);
+        }
+
+        let id = GuideId(self.next_guide);
+        self.next_guide += 1;
+        self.guides.push(Guide { id, axis, position });
+
+        Ok(id)
+    }
+
+    /// adds a construction guide, a ray or an endless line running any way,
+    /// returning its id. like the other guides it isn't a drawable, the cursor
+    /// snaps onto it, and it's only exported when `render_guides` is set,
+    /// cut to the drawing and dashed. it fails when it isn't finite or has
+    /// no direction.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, CanvasError, GuideLine, InfiniteLine, Line, Point, Ray, SnapKind, SnapKinds, GUIDE_COLOR};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 50.0);
+    /// let diagonal = canvas.add_guide(InfiniteLine::through(&Point::new(0.0, 0.0), &Point::new(2.0, 1.0))).unwrap();
+    /// let ray = canvas.add_guide(Ray::new(&Point::new(90.0, 40.0), &Point::new(0.0, 1.0))).unwrap();
+    /// assert_eq!(Err(CanvasError::ZeroLength), canvas.add_guide(Ray::new(&Point::new(1.0, 1.0), &Point::new(0.0, 0.0))));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_guide(Ray::new(&Point { x: f64::NAN, y: 1.0 }, &Point::new(1.0, 0.0))));
+    /// assert_eq!(0, canvas.len());
+    /// assert_eq!(vec![diagonal, ray], canvas.construction_guides().iter().map(|guide| guide.id).collect::<Vec<_>>());
+    ///
+    /// // it's hit and snapped onto anywhere along it, and crosses lines
+    /// assert_eq!(Some(diagonal), canvas.guide_at(&Point::new(80.0, 41.0), 2.0));
+    /// assert_eq!(Some(ray), canvas.guide_at(&Point::new(90.0, 49.0), 2.0));
+    /// assert_eq!(None, canvas.guide_at(&Point::new(90.0, 30.0), 2.0));
+    /// let snapped = canvas.snap(&Point::new(60.0, 31.0), SnapKinds::default(), 2.0, &[]);
+    /// assert_eq!((Point::new(60.4, 30.2), Some(SnapKind::Guide)), (snapped.point, snapped.kind));
+    /// let line = Line::new(&Point::new(0.0, 50.0), &Point::new(100.0, 0.0));
+    /// assert_eq!(Some(Point::new(50.0, 25.0)), canvas.construction_guides()[0].line.intersect(&line));
+    ///
+    /// // exported across the whole drawing only when guides are rendered
+    /// assert!(!canvas.to_svg().contains("<line"));
+    /// canvas.set_render_guides(true);
+    /// let drawn = format!("<line x1=\"0\" x2=\"100\" y1=\"0\" y2=\"50\" stroke=\"{}\" stroke-dasharray=\"4 2\" stroke-width=\"0.5\" />", GUIDE_COLOR);
+    /// assert!(canvas.to_svg().contains(&drawn));
+    /// assert!(canvas.to_svg().contains("<line x1=\"90\" x2=\"90\" y1=\"40\" y2=\"50\""));
+    ///
+    /// assert!(canvas.remove_guide(diagonal));
+    /// assert_eq!(1, canvas.construction_guides().len());
+    ///
+    /// ```
+    pub fn add_guide(&mut self, line: impl Into<GuideLine>) -> Result<GuideId, CanvasError> {
+        let line = line.into();
+        let (origin, direction) = (line.origin(), line.direction());
+        if ![origin.x, origin.y, direction.x, direction.y].iter().all(|value| value.is_finite()) {
+            return Err(CanvasError::NotFinite);
+        }
+        if direction.len() == 0f64 {
+            return Err(CanvasError::ZeroLength);
+        }
+
+        let id = GuideId(self.next_guide);
+        self.next_guide += 1;
+        self.construction_guides.push(ConstructionGuide { id, line });
+
+        Ok(id)
+    }
+
+    /// the guides, in the order they were added.
+    pub fn guides(&self) -> &[Guide] {
+        &self.guides
+    }
+
+    /// the construction guides, in the order they were added.
+    pub fn construction_guides(&self) -> &[ConstructionGuide] {
+        &self.construction_guides
+    }
+
+    pub fn guide(&self, id: GuideId) -> Option<&Guide> {
+        self.guides.iter().find(|guide| guide.id == id)
+    }
+
+    /// the guide closest to `point` within `tolerance` of it, construction guides included.
+    pub fn guide_at(&self, point: &Point, tolerance: f64) -> Option<GuideId> {
+        let rulers = self.guides.iter().map(|guide| (guide.id, guide.distance_to(point)));
+        let construction = self.construction_guides.iter().map(|guide| (guide.id, guide.line.distance_to(point)));
+
+        rulers
+            .chain(construction)
+            .filter(|(_, away)| *away <= tolerance)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(id, _)| id)
+    }
+
+    /// puts a guide at another y or x, returning false when
+    /// it doesn't exist or `position` isn't finite.
+    pub fn move_guide(&mut self, id: GuideId, position: f64) -> bool {
+        match self.guides.iter_mut().find(|guide| guide.id == id) {
+            Some(guide) if position.is_finite() => {
+                guide.position = position;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// removes a guide or a construction guide, returning whether it existed.
+    pub fn remove_guide(&mut self, id: GuideId) -> bool {
+        let count = self.guides.len() + self.construction_guides.len();
+        self.guides.retain(|guide| guide.id != id);
+        self.construction_guides.retain(|guide| guide.id != id);
+
+        self.guides.len() + self.construction_guides.len() < count
+    }
+
+    pub fn render_guides(&self) -> bool {
+        self.render_guides
+    }
+
+    /// whether exports draw the guides as thin lines across the document.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, GUIDE_COLOR};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 50.0);
+    /// canvas.add_v_guide(20.0).unwrap();
+    /// assert_eq!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"50\"></svg>", canvas.to_svg());
+    ///
+    /// canvas.set_render_guides(true);
+    /// let guide = format!("<line x1=\"20\" x2=\"20\" y1=\"0\" y2=\"50\" stroke=\"{}\" stroke-width=\"0.5\" />", GUIDE_COLOR);
+    /// assert!(canvas.to_svg().ends_with(&format!("{}</svg>", guide)));
+    ///
+    /// ```
+    pub fn set_render_guides(&mut self, render_guides: bool) {
+        self.render_guides = render_guides;
+    }
+
+    /// the smallest box containing every visible drawable, `None` when there's none.
+    pub fn content_bounds(&self) -> Option<Aabb> {
+        self.storage
+            .iter()
+            .filter(|(id, _, _)| !self.is_hidden(*id))
+            .map(|(_, drawable, _)| drawable.bounding_box())
+            .reduce(|bounds, other| bounds.union(&other))
+    }
+
+    /// zooms and pans so every drawable is visible,
+    /// going back to the default view for an empty canvas.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(400.0, 200.0);
+    /// canvas.fit_to_content();
+    /// assert_eq!(1.0, canvas.viewport().zoom());
+    ///
+    /// canvas.add_line(&Point::new(100.0, 100.0), &Point::new(200.0, 125.0)).unwrap();
+    /// canvas.fit_to_content();
+    ///
+    /// assert_eq!(4.0, canvas.viewport().zoom());
+    /// assert_eq!(Point::new(0.0, 50.0), canvas.viewport().world_to_screen(&Point::new(100.0, 100.0)));
+    /// assert_eq!(Point::new(400.0, 150.0), canvas.viewport().world_to_screen(&Point::new(200.0, 125.0)));
+    ///
+    /// ```
+    pub fn fit_to_content(&mut self) {
+        match self.content_bounds() {
+            Some(bounds) => self.viewport.fit(&bounds, self.width, self.height),
+            None => self.viewport = Viewport::default(),
+        }
+    }
+
+    /// the selected drawable when exactly one is selected.
+    pub fn selected_drawable(&self) -> Option<DrawableId> {
+        match self.selection[..] {
+            [id] => Some(id),
+            _ => None,
+        }
+    }
+
+    /// every selected drawable, in the order they were selected.
+    /// the selection holds ids rather than positions, so reordering keeps
+    /// it on the same drawables, and deleting or hiding one drops it.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let a = canvas.add_circle(&Point::new(10.0, 10.0), 5.0).unwrap();
+    /// let b = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// let c = canvas.add_circle(&Point::new(90.0, 90.0), 5.0).unwrap();
+    /// canvas.set_selected_drawable(Some(b));
+    /// let before = canvas.snapshot();
+    ///
+    /// canvas.send_to_back(b);
+    /// canvas.delete(a);
+    /// assert_eq!(&[b], canvas.selection());
+    /// assert_eq!(Some(b), canvas.selected_props().map(|props| props.id));
+    ///
+    /// canvas.translate_selected_drawable(&Point::new(5.0, 0.0));
+    /// assert_eq!(Some("<circle cx=\"55\" cy=\"50\" r=\"5\" />"), canvas.svg_fragment_for(b));
+    /// assert_eq!(Some("<circle cx=\"90\" cy=\"90\" r=\"5\" />"), canvas.svg_fragment_for(c));
+    ///
+    /// canvas.delete(b);
+    /// assert!(canvas.selection().is_empty());
+    /// assert_eq!(None, canvas.selected_props());
+    /// canvas.translate_selected_drawable(&Point::new(5.0, 0.0));
+    /// assert_eq!(None, canvas.selection_to_svg());
+    /// assert!(canvas.to_svg().contains("<circle cx=\"90\" cy=\"90\" r=\"5\" />"));
+    ///
+    /// // undoing brings the drawables back with the selection they had
+    /// canvas.restore(before);
+    /// assert_eq!(&[b], canvas.selection());
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" r=\"5\" />"), canvas.svg_fragment_for(b));
+    ///
+    /// ```
+    pub fn selection(&self) -> &[DrawableId] {
+        &self.selection
+    }
+
+    pub fn is_selected(&self, id: DrawableId) -> bool {
+        self.selection.contains(&id)
+    }
+
+    /// selects only the drawable with the given id, or clears the selection
+    /// with `None`. an id that no longer refers to a drawable, or refers to
+    /// a hidden one, clears it too.
+    pub fn set_selected_drawable(&mut self, id: Option<DrawableId>) {
+        self.selection = id.filter(|&id| self.storage.get(id).is_some() && !self.is_hidden(id)).into_iter().collect();
+    }
+
+    /// adds a drawable to the selection, or takes it out if it's already in,
+    /// returning whether it's selected now.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let a = canvas.add_circle(&Point::new(20.0, 20.0), 5.0).unwrap();
+    /// let b = canvas.add_circle(&Point::new(60.0, 60.0), 5.0).unwrap();
+    ///
+    /// assert!(canvas.toggle_selected(a));
+    /// assert!(canvas.toggle_selected(b));
+    /// assert_eq!(&[a, b], canvas.selection());
+    /// assert_eq!(None, canvas.selected_drawable());
+    ///
+    /// assert!(canvas.nudge_selected(5.0, 0.0));
+    /// assert_eq!(Some("<circle cx=\"65\" cy=\"60\" r=\"5\" />"), canvas.svg_fragment_for(b));
+    ///
+    /// assert!(!canvas.toggle_selected(a));
+    /// assert_eq!(Some(b), canvas.selected_drawable());
+    ///
+    /// ```
+    pub fn toggle_selected(&mut self, id: DrawableId) -> bool {
+        if self.is_selected(id) {
+            self.selection.retain(|&selected| selected != id);
+            return false;
+        }
+
+        if self.storage.get(id).is_none() || self.is_hidden(id) {
+            return false;
+        }
+        self.selection.push(id);
+
+        true
+    }
+
+    /// selects the drawables whose bounding box lies within `rect`, or with
+    /// `contained` unset every one that overlaps it. with `add` they join the
+    /// selection instead of replacing it. returns how many are selected.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Aabb, Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let inside = canvas.add_circle(&Point::new(20.0, 20.0), 5.0).unwrap();
+    /// let across = canvas.add_line(&Point::new(10.0, 40.0), &Point::new(90.0, 40.0)).unwrap();
+    /// let outside = canvas.add_circle(&Point::new(80.0, 80.0), 5.0).unwrap();
+    /// let rect = Aabb::new(&Point::new(0.0, 0.0), &Point::new(50.0, 50.0));
+    ///
+    /// assert_eq!(2, canvas.select_drawables_in_rect(&rect, false, false));
+    /// assert_eq!(&[inside, across], canvas.selection());
+    ///
+    /// assert_eq!(1, canvas.select_drawables_in_rect(&rect, true, false));
+    /// assert_eq!(&[inside], canvas.selection());
+    ///
+    /// let corner = Aabb::new(&Point::new(70.0, 70.0), &Point::new(100.0, 100.0));
+    /// assert_eq!(2, canvas.select_drawables_in_rect(&corner, true, true));
+    /// assert_eq!(&[inside, outside], canvas.selection());
+    ///
+    /// ```
+    pub fn select_drawables_in_rect(&mut self, rect: &Aabb, contained: bool, add: bool) -> usize {
+        let hits: Vec<DrawableId> = self
+            .drawables_in_rect(rect)
+            .into_iter()
+            .filter(|&id| {
+                !contained
+                    || self.storage.get(id).is_some_and(|drawable| {
+                        let bounds = drawable.bounding_box();
+                        rect.contains(&bounds.min()) && rect.contains(&bounds.max())
+                    })
+            })
+            .collect();
+
+        if !add {
+            self.selection.clear();
+        }
+        for id in hits {
+            if !self.is_selected(id) {
+                self.selection.push(id);
+            }
+        }
+
+        self.selection.len()
+    }
+
+    /// returns the ids of every drawable hit by a click at `point`,
+    /// in stacking order.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, DrawableId, Point};
+    ///
+    /// let mut canvas = Canvas::new(1000.0, 1000.0);
+    /// for i in 0..100 {
+    ///     let offset = (i * 10) as f64;
+    ///     canvas.add_circle(&Point::new(offset, offset), 15.0).unwrap();
+    ///     canvas.add_line(&Point::new(offset, 0.0), &Point::new(offset, 1000.0)).unwrap();
+    /// }
+    ///
+    /// for (x, y) in [(0.0, 0.0), (12.0, 20.0), (500.0, 3.0), (999.0, 1.0), (-50.0, 5.0)] {
+    ///     let point = Point::new(x, y);
+    ///     let linear: Vec<DrawableId> = canvas.drawables()
+    ///         .filter(|(_, drawable)| drawable.contains(&point))
+    ///         .map(|(id, _)| id)
+    ///         .collect();
+    ///
+    ///     debug_assert_eq!(linear, canvas.drawables_at(&point));
+    /// }
+    ///
+    /// assert_eq!(&canvas.ids()[..3], &canvas.drawables_at(&Point::new(1.0, 1.0))[..]);
+    ///
+    /// ```
+    pub fn drawables_at(&self, point: &Point) -> Vec<DrawableId> {
+        let mut ids: Vec<DrawableId> = self
+            .spatial_index
+            .query_point(point)
+            .into_iter()
+            .filter(|&id| !self.is_hidden(id) && self.storage.get(id).is_some_and(|drawable| drawable.contains(point)))
+            .collect();
+
+        self.storage.sort_by_stacking(&mut ids);
+
+        ids
+    }
+
+    /// selects the topmost drawable hit by a click at `point`,
+    /// clearing the selection when nothing is hit.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let bottom = canvas.add_circle(&Point::new(50.0, 50.0), 20.0).unwrap();
+    /// let top = canvas.add_circle(&Point::new(60.0, 50.0), 20.0).unwrap();
+    ///
+    /// assert_eq!(Some(top), canvas.select_drawable_at(&Point::new(55.0, 50.0)));
+    /// assert_eq!(Some(bottom), canvas.select_drawable_at(&Point::new(35.0, 50.0)));
+    /// assert_eq!(None, canvas.select_drawable_at(&Point::new(0.0, 0.0)));
+    /// assert_eq!(None, canvas.selected_drawable());
+    ///
+    /// ```
+    pub fn select_drawable_at(&mut self, point: &Point) -> Option<DrawableId> {
+        let hit = self.topmost_at(point);
+        self.set_selected_drawable(hit);
+
+        hit
+    }
+
+    /// the topmost drawable hit by a click at `point`.
+    pub fn topmost_at(&self, point: &Point) -> Option<DrawableId> {
+        self.drawables_at(point).last().copied()
+    }
+
+    /// moves the selected drawables by `offset`, leaving the locked ones
+    /// where they are. does nothing when the offset is zero.
+    ///
+    /// with `clamp_to_bounds` on, the selection moves together only as far
+    /// as it can before the center of one of its drawables leaves the canvas.
+    /// drawables already off it aren't pulled back, they just can't go further.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(10000.0, 1000.0);
+    /// for i in 0..1000 {
+    ///     canvas.add_circle(&Point::new((i * 10) as f64, 500.0), 1.0).unwrap();
+    /// }
+    /// let id = canvas.select_drawable_at(&Point::new(7000.0, 500.0)).unwrap();
+    ///
+    /// let serialized = canvas.serialized_tags();
+    /// canvas.translate_selected_drawable(&Point::new(0.0, 100.0));
+    ///
+    /// assert_eq!(serialized + 1, canvas.serialized_tags());
+    ///
+    /// canvas.translate_selected_drawable(&Point::new(0.0, 0.0));
+    /// assert_eq!(serialized + 1, canvas.serialized_tags());
+    /// assert_eq!(Some("<circle cx=\"7000\" cy=\"600\" r=\"1\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// let from_scratch: String = canvas.drawables().map(|(_, d)| d.to_svg_tag()).collect();
+    /// assert!(canvas.to_svg().contains(&from_scratch));
+    /// assert_eq!(Some(id), canvas.select_drawable_at(&Point::new(7000.0, 600.0)));
+    ///
+    /// ```
+    ///
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// canvas.set_clamp_to_bounds(true);
+    /// let circle = canvas.add_circle(&Point::new(90.0, 50.0), 5.0).unwrap();
+    /// let dot = canvas.add_circle(&Point::new(60.0, 20.0), 1.0).unwrap();
+    /// canvas.set_selected_drawable(Some(circle));
+    /// canvas.toggle_selected(dot);
+    ///
+    /// // dragged past the right edge, the circle stops on it and the dot keeps its distance
+    /// canvas.translate_selected_drawable(&Point::new(25.0, -30.0));
+    /// assert_eq!(Some("<circle cx=\"100\" cy=\"30\" r=\"5\" />"), canvas.svg_fragment_for(circle));
+    /// assert_eq!(Some("<circle cx=\"70\" cy=\"0\" r=\"1\" />"), canvas.svg_fragment_for(dot));
+    ///
+    /// canvas.translate_selected_drawable(&Point::new(-10.0, 0.0));
+    /// assert_eq!(Some("<circle cx=\"90\" cy=\"30\" r=\"5\" />"), canvas.svg_fragment_for(circle));
+    ///
+    /// canvas.set_clamp_to_bounds(false);
+    /// canvas.translate_selected_drawable(&Point::new(500.0, 0.0));
+    /// assert_eq!(Some("<circle cx=\"590\" cy=\"30\" r=\"5\" />"), canvas.svg_fragment_for(circle));
+    ///
+    /// // an offset that isn't finite would leave them nowhere
+    /// canvas.translate_selected_drawable(&Point { x: f64::NAN, y: 0.0 });
+    /// assert_eq!(Some("<circle cx=\"590\" cy=\"30\" r=\"5\" />"), canvas.svg_fragment_for(circle));
+    ///
+    /// ```
+    pub fn translate_selected_drawable(&mut self, offset: &Point) {
+        if (offset.x == 0f64 && offset.y == 0f64) || !offset.x.is_finite() || !offset.y.is_finite() {
+            return;
+        }
+
+        let unlocked: Vec<DrawableId> = self.selection.iter().copied().filter(|&id| !self.is_locked(id)).collect();
+        let offset = if self.clamp_to_bounds { self.clamped_offset(&unlocked, offset) } else { offset.clone() };
+        if offset.x == 0f64 && offset.y == 0f64 {
+            return;
+        }
+
+        for id in unlocked {
+            self.modify(id, |drawable| drawable.translate(&offset));
+        }
+    }
+
+    /// moves the selected drawables by the part of `offset` along `axis`,
+    /// like dragging them with the other axis locked.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Axis, Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add_circle(&Point::new(20.0, 20.0), 5.0).unwrap();
+    /// canvas.set_selected_drawable(Some(id));
+    ///
+    /// canvas.translate_selected_axis_locked(&Point::new(30.0, 40.0), Axis::X);
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"20\" r=\"5\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// canvas.translate_selected_axis_locked(&Point::new(30.0, 40.0), Axis::Y);
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"60\" r=\"5\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// ```
+    pub fn translate_selected_axis_locked(&mut self, offset: &Point, axis: Axis) {
+        let offset = match axis {
+            Axis::X => offset.only_x(),
+            Axis::Y => offset.only_y(),
+        };
+
+        self.translate_selected_drawable(&offset);
+    }
+
+    /// the part of `offset` that moves the centers of `ids` no further off
+    /// the canvas than they are.
+    fn clamped_offset(&self, ids: &[DrawableId], offset: &Point) -> Point {
+        let (min, max) = (Point::new(0f64, 0f64), Point::new(self.width, self.height));
+        let allowed: Vec<Point> = ids
+            .iter()
+            .filter_map(|&id| self.storage.get(id))
+            .map(|drawable| {
+                let center = drawable.center();
+                (center.clone() + offset.clone()).clamp(&min, &max) - center
+            })
+            .collect();
+
+        // each axis moves as far as the drawable that can move the least that way
+        let limit = |wanted: f64, allowed: &mut dyn Iterator<Item = f64>| {
+            if wanted > 0f64 {
+                allowed.fold(wanted, f64::min).max(0f64)
+            } else {
+                allowed.fold(wanted, f64::max).min(0f64)
+            }
+        };
+
+        Point::new(limit(offset.x, &mut allowed.iter().map(|a| a.x)), limit(offset.y, &mut allowed.iter().map(|a| a.y)))
+    }
+
+    /// the box to outline around the selected drawables,
+    /// `None` when nothing is selected.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Aabb, Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    /// assert_eq!(None, canvas.selection_bounds());
+    ///
+    /// canvas.select_drawable_at(&Point::new(50.0, 50.0));
+    /// canvas.translate_selected_drawable(&Point::new(5.0, 0.0));
+    /// assert_eq!(Some(Aabb::new(&Point::new(45.0, 40.0), &Point::new(65.0, 60.0))), canvas.selection_bounds());
+    ///
+    /// canvas.select_drawable_at(&Point::new(0.0, 0.0));
+    /// assert_eq!(None, canvas.selection_bounds());
+    ///
+    /// ```
+    pub fn selection_bounds(&self) -> Option<Aabb> {
+        self.selection
+            .iter()
+            .filter_map(|&id| self.storage.get(id))
+            .map(|drawable| drawable.bounding_box())
+            .reduce(|bounds, other| bounds.union(&other))
+    }
+
+    /// copies of the selected drawables, bottom to top.
+    pub fn selected_drawables(&self) -> Vec<Drawable> {
+        let mut ids = self.selection.clone();
+        self.storage.sort_by_stacking(&mut ids);
+
+        ids.into_iter().filter_map(|id| self.storage.get(id)).map(|drawable| Drawable::clone(drawable)).collect()
+    }
+
+    /// an SVG document of the selected drawables alone, cropped to them,
+    /// `None` when nothing is selected.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(400.0, 300.0);
+    /// let circle = canvas.add_circle(&Point::new(100.0, 100.0), 10.0).unwrap();
+    /// canvas.add_line(&Point::new(0.0, 0.0), &Point::new(400.0, 300.0)).unwrap();
+    /// assert_eq!(None, canvas.selection_to_svg());
+    ///
+    /// canvas.set_selected_drawable(Some(circle));
+    /// let svg = canvas.selection_to_svg().unwrap();
+    /// assert!(svg.contains(" viewBox=\"88 88 24 24\""));
+    /// assert!(svg.contains("<circle cx=\"100\" cy=\"100\" r=\"10\" />") && !svg.contains("<line"));
+    ///
+    /// ```
+    pub fn selection_to_svg(&self) -> Option<String> {
+        if self.selection.is_empty() {
+            return None;
+        }
+
+        let mut selected = Canvas::new(self.width, self.height);
+        selected.extend(self.selected_drawables());
+
+        Some(selected.to_svg_with(&ExportOptions { fit_to_content: true, margin: 2f64, size_mm: None, precision: None }))
+    }
+
+    /// adds copies of `drawables` on top of the others, moved together so the
+    /// middle of their bounding box is at `center`, and selects them. the copies
+    /// are checked like `add_drawables` does, leaving the canvas as it was
+    /// when any of them can't be added.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Drawable, Point};
+    ///
+    /// let mut canvas = Canvas::new(400.0, 300.0);
+    /// canvas.add_line(&Point::new(0.0, 0.0), &Point::new(20.0, 0.0)).unwrap();
+    /// canvas.add_circle(&Point::new(20.0, 10.0), 10.0).unwrap();
+    /// let stamp: Vec<Drawable> = canvas.drawables().map(|(_, drawable)| Drawable::clone(drawable)).collect();
+    ///
+    /// let ids = canvas.paste(&stamp, &Point::new(200.0, 100.0)).unwrap();
+    /// assert_eq!(4, canvas.len());
+    /// assert_eq!(&ids[..], canvas.selection());
+    /// assert_eq!(Some("<line x1=\"185\" x2=\"205\" y1=\"90\" y2=\"90\" />"), canvas.svg_fragment_for(ids[0]));
+    /// assert_eq!(Some("<circle cx=\"205\" cy=\"100\" r=\"10\" />"), canvas.svg_fragment_for(ids[1]));
+    ///
+    /// assert_eq!(Ok(Vec::new()), canvas.paste(&[], &Point::new(0.0, 0.0)));
+    ///
+    /// ```
+    ///
+    /// ```
+    /// use program_core::{Canvas, CanvasError, Circle, Drawable, Line, Point};
+    ///
+    /// let mut canvas = Canvas::new(400.0, 300.0);
+    /// let a = Point::new(10.0, 10.0);
+    /// let circle = Drawable::Circle(Circle::new(&a, 5.0));
+    ///
+    /// assert_eq!(Err(CanvasError::ZeroLength), canvas.paste(&[circle.clone(), Drawable::Line(Line::new(&a, &a))], &Point::new(50.0, 50.0)));
+    /// assert_eq!(Err(CanvasError::NonPositiveRadius(0.0)), canvas.paste(&[Drawable::Circle(Circle::new(&a, 0.0))], &Point::new(50.0, 50.0)));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.paste(&[circle], &Point { x: f64::NAN, y: 50.0 }));
+    /// assert!(canvas.is_empty() && canvas.selection().is_empty());
+    ///
+    /// ```
+    pub fn paste(&mut self, drawables: &[Drawable], center: &Point) -> Result<Vec<DrawableId>, CanvasError> {
+        let bounds = match drawables.iter().map(Drawable::bounding_box).reduce(|bounds, other| bounds.union(&other)) {
+            Some(bounds) => bounds,
+            None => return Ok(Vec::new()),
+        };
+        let (min, max) = (bounds.min(), bounds.max());
+        let offset = Point { x: center.x - (min.x + max.x) / 2f64, y: center.y - (min.y + max.y) / 2f64 };
+        if !(offset.x.is_finite() && offset.y.is_finite()) {
+            return Err(CanvasError::NotFinite);
+        }
+
+        let ids = self.add_drawables(
+            drawables
+                .iter()
+                .map(|drawable| {
+                    let mut copy = drawable.clone();
+                    copy.translate(&offset);
+                    copy
+                })
+                .collect(),
+        )?;
+        self.selection = ids.clone();
+
+        Ok(ids)
+    }
+
+    /// adds the drawables of `other` on top of the others, placed as
+    /// `placement` says, and selects them so they can be moved together.
+    /// they get new ids and keep their names, locks and whether they're
+    /// hidden. the metadata of `other` is added under the keys not already
+    /// used, and its size, title, background and grid are left out. the moved
+    /// drawables are checked like `add_drawables` does, so an offset that
+    /// isn't finite leaves the canvas as it was.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, MergePlacement, Point, MERGE_GAP};
+    ///
+    /// let mut plan = Canvas::new(400.0, 300.0);
+    /// let wall = plan.add_line(&Point::new(0.0, 0.0), &Point::new(100.0, 0.0)).unwrap();
+    /// plan.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    /// plan.set_name(wall, Some(String::from("wall")));
+    /// plan.set_metadata(String::from("author"), String::from("a")).unwrap();
+    ///
+    /// // a drawing of its own, with ids and names the plan uses too
+    /// let mut other = Canvas::new(100.0, 100.0);
+    /// let other_wall = other.add_line(&Point::new(10.0, 20.0), &Point::new(30.0, 20.0)).unwrap();
+    /// let door = other.add_rect(&Point::new(10.0, 30.0), &Point::new(20.0, 50.0)).unwrap();
+    /// other.set_name(other_wall, Some(String::from("wall")));
+    /// other.set_locked(door, true);
+    /// other.set_hidden(door, true);
+    /// other.set_metadata(String::from("author"), String::from("b")).unwrap();
+    /// other.set_metadata(String::from("scale"), String::from("1:50")).unwrap();
+    /// assert_eq!(wall, other_wall);
+    ///
+    /// let merged = |placement| {
+    ///     let mut canvas = plan.clone();
+    ///     let ids = canvas.merge(other.clone(), placement).unwrap();
+    ///     (canvas, ids)
+    /// };
+    ///
+    /// // the hidden door is merged but left out of the selection
+    /// let (canvas, ids) = merged(MergePlacement::AtOrigin);
+    /// assert_eq!(4, canvas.len());
+    /// assert_eq!(&ids[..1], canvas.selection());
+    /// assert!(ids.iter().all(|id| !plan.ids().contains(id)));
+    /// assert_eq!((Some("wall"), Some("wall")), (canvas.name(wall), canvas.name(ids[0])));
+    /// assert!(!canvas.is_locked(wall) && canvas.is_locked(ids[1]) && canvas.is_hidden(ids[1]));
+    /// assert_eq!(Some("<line x1=\"10\" x2=\"30\" y1=\"20\" y2=\"20\" />"), canvas.svg_fragment_for(ids[0]));
+    /// assert_eq!(Some("a"), canvas.metadata().get("author").map(String::as_str));
+    /// assert_eq!(Some("1:50"), canvas.metadata().get("scale").map(String::as_str));
+    /// assert_eq!(400.0, canvas.width());
+    ///
+    /// let (canvas, ids) = merged(MergePlacement::Offset(Point::new(5.0, -5.0)));
+    /// assert_eq!(Some("<line x1=\"15\" x2=\"35\" y1=\"15\" y2=\"15\" />"), canvas.svg_fragment_for(ids[0]));
+    ///
+    /// // the plan's drawables span (0, 0) to (100, 60), and the other's start at (10, 20)
+    /// let (canvas, ids) = merged(MergePlacement::Beside);
+    /// assert_eq!(20.0, MERGE_GAP);
+    /// assert_eq!(Some("<line x1=\"120\" x2=\"140\" y1=\"0\" y2=\"0\" />"), canvas.svg_fragment_for(ids[0]));
+    /// assert_eq!(Some("<rect height=\"20\" width=\"10\" x=\"120\" y=\"10\" />"), canvas.svg_fragment_for(ids[1]));
+    ///
+    /// let mut empty = Canvas::new(100.0, 100.0);
+    /// let ids = empty.merge(other.clone(), MergePlacement::Beside).unwrap();
+    /// assert_eq!(Some("<line x1=\"10\" x2=\"30\" y1=\"20\" y2=\"20\" />"), empty.svg_fragment_for(ids[0]));
+    ///
+    /// assert_eq!(Err(program_core::CanvasError::NotFinite), empty.merge(other.clone(), MergePlacement::Offset(Point { x: f64::INFINITY, y: 0.0 })));
+    /// assert_eq!(2, empty.len());
+    ///
+    /// ```
+    pub fn merge(&mut self, other: Canvas, placement: MergePlacement) -> Result<Vec<DrawableId>, CanvasError> {
+        let incoming = other.storage.iter().map(|(_, drawable, _)| drawable.bounding_box()).reduce(|bounds, other| bounds.union(&other));
+        let offset = match (placement, self.content_bounds(), incoming) {
+            (MergePlacement::Offset(offset), _, _) => offset,
+            (MergePlacement::Beside, Some(existing), Some(incoming)) => Point::new(
+                existing.max().x + MERGE_GAP - incoming.min().x,
+                existing.min().y - incoming.min().y,
+            ),
+            _ => Point::new(0f64, 0f64),
+        };
+        if !(offset.x.is_finite() && offset.y.is_finite()) {
+            return Err(CanvasError::NotFinite);
+        }
+
+        let old_ids: Vec<DrawableId> = other.storage.iter().map(|(id, _, _)| id).collect();
+        let drawables: Vec<Drawable> = other
+            .storage
+            .iter()
+            .map(|(_, drawable, _)| {
+                let mut copy = Drawable::clone(drawable);
+                copy.translate(&offset);
+                copy
+            })
+            .collect();
+        let ids = self.add_drawables(drawables)?;
+
+        for (&old, &id) in old_ids.iter().zip(&ids) {
+            self.set_name(id, other.name(old).map(String::from));
+            self.set_locked(id, other.is_locked(old));
+            self.set_hidden(id, other.is_hidden(old));
+        }
+        for (key, value) in other.metadata {
+            self.metadata.entry(key).or_insert(value);
+        }
+
+        self.selection = ids.iter().copied().filter(|&id| !self.is_hidden(id)).collect();
+
+        Ok(ids)
+    }
+
+    /// the editable properties of the drawable `id`, with its place in the
+    /// stacking order and whether it's locked, `None` if it has been deleted.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let line = canvas.add_line(&Point::new(0.0, 0.0), &Point::new(10.0, 10.0)).unwrap();
+    /// let circle = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// canvas.set_locked(circle, true);
+    /// canvas.send_to_back(circle);
+    ///
+    /// let props = canvas.props_of(circle).unwrap();
+    /// assert_eq!((circle, "circle", 0, true), (props.id, props.kind(), props.z_index, props.locked));
+    /// assert_eq!(1, canvas.props_of(line).unwrap().z_index);
+    ///
+    /// canvas.delete(line);
+    /// assert_eq!(None, canvas.props_of(line));
+    ///
+    /// ```
+    pub fn props_of(&self, id: DrawableId) -> Option<DrawableProps> {
+        let drawable = self.storage.get(id)?;
+
+        Some(DrawableProps {
+            id,
+            z_index: self.storage.order().position(|other| other == id).unwrap_or_default(),
+            locked: self.is_locked(id),
+            props: Props::from(&**drawable),
+        })
+    }
+
+    /// the editable properties of the selected drawable,
+    /// `None` unless exactly one is selected.
+    pub fn selected_props(&self) -> Option<DrawableProps> {
+        self.selected_drawable().and_then(|id| self.props_of(id))
+    }
+
+    /// writes `props` back to the drawable with their id, whether or not
+    /// it's still selected. it fails when the drawable has been deleted or
+    /// locked, when the props are for another kind of drawable, or when they'd
+    /// leave it a drawable `add` would refuse, like a rect without a width.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{ApplyPropsError, Canvas, LineCap, LineJoin, LineProps, Point, Props, StrokeStyle};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let line = canvas.add_line(&Point::new(0.0, 0.0), &Point::new(10.0, 10.0)).unwrap();
+    /// let circle = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    ///
+    /// canvas.set_selected_drawable(Some(line));
+    /// let mut edited = canvas.selected_props().unwrap();
+    /// edited.props = Props::Line(LineProps {
+    ///     start: Point::new(20.0, 20.0),
+    ///     end: Point::new(30.0, 20.0),
+    ///     stroke_style: StrokeStyle::Dashed(vec![2.0, 1.0]),
+    ///     line_cap: LineCap::Butt,
+    ///     line_join: LineJoin::Miter,
+    ///     stroke: None,
+    /// });
+    ///
+    /// // the selection moved on before the edit was applied
+    /// canvas.set_selected_drawable(Some(circle));
+    /// assert_eq!(Ok(()), canvas.apply_props(&edited));
+    /// assert_eq!(Some("<line stroke-dasharray=\"2 1\" x1=\"20\" x2=\"30\" y1=\"20\" y2=\"20\" />"), canvas.svg_fragment_for(line));
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" r=\"5\" />"), canvas.svg_fragment_for(circle));
+    ///
+    /// let wrong_kind = program_core::DrawableProps { id: circle, ..edited.clone() };
+    /// assert_eq!(Err(ApplyPropsError::WrongKind(circle)), canvas.apply_props(&wrong_kind));
+    ///
+    /// canvas.set_locked(line, true);
+    /// assert_eq!(Err(ApplyPropsError::Locked(line)), canvas.apply_props(&edited));
+    ///
+    /// canvas.set_locked(line, false);
+    /// canvas.delete(line);
+    /// assert_eq!(Err(ApplyPropsError::Missing(line)), canvas.apply_props(&edited));
+    ///
+    /// ```
+    ///
+    /// ```
+    /// use program_core::{ApplyPropsError, Canvas, CanvasError, Point, Props};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let line = canvas.add_line(&Point::new(0.0, 0.0), &Point::new(10.0, 10.0)).unwrap();
+    /// let circle = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// let rect = canvas.add_rect(&Point::new(10.0, 10.0), &Point::new(30.0, 20.0)).unwrap();
+    /// let before = canvas.to_svg();
+    ///
+    /// let mut props = canvas.props_of(line).unwrap();
+    /// let Props::Line(line_props) = &mut props.props else { unreachable!() };
+    /// line_props.end = line_props.start.clone();
+    /// assert_eq!(Err(ApplyPropsError::Invalid(CanvasError::ZeroLength)), canvas.apply_props(&props));
+    ///
+    /// let mut props = canvas.props_of(circle).unwrap();
+    /// let Props::Circle(circle_props) = &mut props.props else { unreachable!() };
+    /// circle_props.radius = 0.0;
+    /// assert_eq!(Err(ApplyPropsError::Invalid(CanvasError::NonPositiveRadius(0.0))), canvas.apply_props(&props));
+    ///
+    /// let mut props = canvas.props_of(rect).unwrap();
+    /// let Props::Rect(rect_props) = &mut props.props else { unreachable!() };
+    /// rect_props.width = 0.0;
+    /// assert_eq!(Err(ApplyPropsError::Invalid(CanvasError::ZeroArea)), canvas.apply_props(&props));
+    /// let Props::Rect(rect_props) = &mut props.props else { unreachable!() };
+    /// rect_props.width = f64::NAN;
+    /// assert_eq!(Err(ApplyPropsError::Invalid(CanvasError::NotFinite)), canvas.apply_props(&props));
+    ///
+    /// assert_eq!(before, canvas.to_svg());
+    ///
+    /// ```
+    pub fn apply_props(&mut self, props: &DrawableProps) -> Result<(), ApplyPropsError> {
+        let id = props.id;
+        match self.storage.get(id) {
+            None => return Err(ApplyPropsError::Missing(id)),
+            Some(_) if self.is_locked(id) => return Err(ApplyPropsError::Locked(id)),
+            Some(drawable) if !props.props.applies_to(drawable) => return Err(ApplyPropsError::WrongKind(id)),
+            Some(drawable) => {
+                let mut applied = Drawable::clone(drawable);
+                props.props.apply(&mut applied);
+                validate(&applied).map_err(ApplyPropsError::Invalid)?;
+            }
+        }
+
+        self.modify(id, |drawable| props.props.apply(drawable));
+
+        Ok(())
+    }
+
+    /// moves the ends of the selected line, returning false when the
+    /// selection isn't a single unlocked line. it fails when the ends
+    /// aren't finite or are the same point, like `add_line`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, CanvasError, LineProps, Point, Props};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let line = canvas.add_line(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0)).unwrap();
+    /// assert_eq!(Ok(false), canvas.set_selected_line_endpoints(&Point::new(10.0, 10.0), &Point::new(40.0, 50.0)));
+    ///
+    /// canvas.set_selected_drawable(Some(line));
+    /// assert_eq!(Ok(true), canvas.set_selected_line_endpoints(&Point::new(10.0, 10.0), &Point::new(40.0, 50.0)));
+    /// assert_eq!(Some("<line x1=\"10\" x2=\"40\" y1=\"10\" y2=\"50\" />"), canvas.svg_fragment_for(line));
+    ///
+    /// let Some(Props::Line(LineProps { start, end, .. })) = canvas.selected_props().map(|selected| selected.props) else { unreachable!() };
+    /// assert_eq!((Point::new(10.0, 10.0), Point::new(40.0, 50.0)), (start, end));
+    ///
+    /// let same = Point::new(5.0, 5.0);
+    /// assert_eq!(Err(CanvasError::ZeroLength), canvas.set_selected_line_endpoints(&same, &same));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.set_selected_line_endpoints(&same, &Point { x: f64::NAN, y: 0.0 }));
+    ///
+    /// let circle = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// canvas.set_selected_drawable(Some(circle));
+    /// assert_eq!(Ok(false), canvas.set_selected_line_endpoints(&Point::new(0.0, 0.0), &Point::new(1.0, 1.0)));
+    ///
+    /// ```
+    pub fn set_selected_line_endpoints(&mut self, start: &Point, end: &Point) -> Result<bool, CanvasError> {
+        validate(&Drawable::Line(Line::new(start, end)))?;
+
+        let id = match self.selected_drawable() {
+            Some(id) if !self.is_locked(id) && matches!(self.storage.get(id).map(|drawable| &**drawable), Some(Drawable::Line(_))) => id,
+            _ => return Ok(false),
+        };
+
+        self.modify(id, |drawable| {
+            if let Drawable::Line(line) = drawable {
+                line.set_start(start);
+                line.set_end(end);
+            }
+        });
+
+        Ok(true)
+    }
+
+    /// swaps the ends of the selected line, returning false
+    /// when the selection isn't a single unlocked line.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, LineProps, Point, Props};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let line = canvas.add_line(&Point::new(10.0, 20.0), &Point::new(70.0, 80.0)).unwrap();
+    /// assert!(!canvas.reverse_selected_line());
+    ///
+    /// canvas.set_selected_drawable(Some(line));
+    /// assert!(canvas.reverse_selected_line());
+    /// assert_eq!(Some("<line x1=\"70\" x2=\"10\" y1=\"80\" y2=\"20\" />"), canvas.svg_fragment_for(line));
+    ///
+    /// let Some(Props::Line(LineProps { start, end, .. })) = canvas.selected_props().map(|selected| selected.props) else { unreachable!() };
+    /// assert_eq!((Point::new(70.0, 80.0), Point::new(10.0, 20.0)), (start, end));
+    ///
+    /// canvas.set_locked(line, true);
+    /// assert!(!canvas.reverse_selected_line());
+    ///
+    /// ```
+    pub fn reverse_selected_line(&mut self) -> bool {
+        let id = match self.selected_drawable() {
+            Some(id) if !self.is_locked(id) && matches!(self.storage.get(id).map(|drawable| &**drawable), Some(Drawable::Line(_))) => id,
+            _ => return false,
+        };
+
+        self.modify(id, |drawable| {
+            if let Drawable::Line(line) = drawable {
+                line.reverse();
+            }
+        });
+
+        true
+    }
+
+    /// moves the end of the selected line along it so the line is `len` long,
+    /// a negative `len` flipping it past its start like `Line::set_len`.
+    /// returns false when the selection isn't a single unlocked line,
+    /// and fails when the line would have no length or isn't finite.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, CanvasError, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let line = canvas.add_line(&Point::new(10.0, 10.0), &Point::new(40.0, 50.0)).unwrap();
+    /// assert_eq!(Ok(false), canvas.resize_selected_line(10.0));
+    ///
+    /// canvas.set_selected_drawable(Some(line));
+    /// assert_eq!(Ok(true), canvas.resize_selected_line(10.0));
+    /// assert_eq!(Some("<line x1=\"10\" x2=\"16\" y1=\"10\" y2=\"18\" />"), canvas.svg_fragment_for(line));
+    ///
+    /// assert_eq!(Err(CanvasError::ZeroLength), canvas.resize_selected_line(0.0));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.resize_selected_line(f64::INFINITY));
+    /// assert_eq!(Some("<line x1=\"10\" x2=\"16\" y1=\"10\" y2=\"18\" />"), canvas.svg_fragment_for(line));
+    ///
+    /// ```
+    pub fn resize_selected_line(&mut self, len: f64) -> Result<bool, CanvasError> {
+        let id = match self.selected_drawable() {
+            Some(id) if !self.is_locked(id) => id,
+            _ => return Ok(false),
+        };
+        let resized = match self.storage.get(id).map(|drawable| &**drawable) {
+            Some(Drawable::Line(line)) => Drawable::Line(line.clone().with_len(len)),
+            _ => return Ok(false),
+        };
+        validate(&resized)?;
+
+        self.modify(id, |drawable| *drawable = resized);
+
+        Ok(true)
+    }
+
+    /// paints the ends of every selected unlocked line with `line_cap`,
+    /// returning whether there were any.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Aabb, Canvas, LineCap, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let line = canvas.add_line(&Point::new(10.0, 10.0), &Point::new(90.0, 10.0)).unwrap();
+    /// let circle = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// canvas.set_selected_drawable(Some(circle));
+    /// assert!(!canvas.set_selected_line_cap(LineCap::Round));
+    ///
+    /// canvas.select_drawables_in_rect(&Aabb::new(&Point::new(0.0, 0.0), &Point::new(100.0, 100.0)), true, false);
+    /// assert!(canvas.set_selected_line_cap(LineCap::Round));
+    /// assert_eq!(Some("<line stroke-linecap=\"round\" x1=\"10\" x2=\"90\" y1=\"10\" y2=\"10\" />"), canvas.svg_fragment_for(line));
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" r=\"5\" />"), canvas.svg_fragment_for(circle));
+    ///
+    /// assert!(canvas.set_selected_line_cap(LineCap::Butt));
+    /// assert_eq!(Some("<line x1=\"10\" x2=\"90\" y1=\"10\" y2=\"10\" />"), canvas.svg_fragment_for(line));
+    ///
+    /// ```
+    pub fn set_selected_line_cap(&mut self, line_cap: LineCap) -> bool {
+        let lines: Vec<DrawableId> = self
+            .selection
+            .iter()
+            .copied()
+            .filter(|&id| !self.is_locked(id) && matches!(self.storage.get(id).map(|drawable| &**drawable), Some(Drawable::Line(_))))
+            .collect();
+
+        for &id in &lines {
+            self.modify(id, |drawable| {
+                if let Drawable::Line(line) = drawable {
+                    line.set_line_cap(line_cap);
+                }
+            });
+        }
+
+        !lines.is_empty()
+    }
+
+    /// cuts the selected line in two where it passes closest to `point`.
+    /// the first piece keeps the line's id, place and selection, and the
+    /// second is added on top, returning both ids. it does nothing, returning
+    /// `None`, when the selection isn't a single unlocked line, when `point`
+    /// is off the line, or when the cut is at an end, as a piece without a
+    /// length can't be on the canvas.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let line = canvas.add_line(&Point::new(0.0, 50.0), &Point::new(100.0, 50.0)).unwrap();
+    /// assert_eq!(None, canvas.split_selected_line_at(&Point::new(30.0, 50.0)));
+    ///
+    /// canvas.set_selected_drawable(Some(line));
+    /// assert_eq!(None, canvas.split_selected_line_at(&Point::new(30.0, 90.0)));
+    /// assert_eq!(None, canvas.split_selected_line_at(&Point::new(100.0, 50.0)));
+    ///
+    /// let (first, second) = canvas.split_selected_line_at(&Point::new(30.0, 51.0)).unwrap();
+    /// assert_eq!(line, first);
+    /// assert_eq!(Some(first), canvas.selected_drawable());
+    /// assert_eq!(vec![first, second], canvas.ids());
+    /// assert_eq!(Some("<line x1=\"0\" x2=\"30\" y1=\"50\" y2=\"50\" />"), canvas.svg_fragment_for(first));
+    /// assert_eq!(Some("<line x1=\"30\" x2=\"100\" y1=\"50\" y2=\"50\" />"), canvas.svg_fragment_for(second));
+    ///
+    /// ```
+    pub fn split_selected_line_at(&mut self, point: &Point) -> Option<(DrawableId, DrawableId)> {
+        let id = self.selected_drawable().filter(|&id| !self.is_locked(id))?;
+        let (first, second) = match self.storage.get(id).map(|drawable| &**drawable) {
+            Some(Drawable::Line(line)) => line.split_at_point(point)?,
+            _ => return None,
+        };
+        if first.len() == 0f64 || second.len() == 0f64 {
+            return None;
+        }
+
+        self.modify(id, |drawable| *drawable = Drawable::Line(first));
+        let second = self.push(Drawable::Line(second));
+
+        Some((id, second))
+    }
+
+    /// puts `drawable` in place of the selected drawable, returning
+    /// false when nothing unlocked is selected. it's checked like the
+    /// drawables of the `add_` methods, leaving the selection as it was
+    /// when it can't be on the canvas.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Circle, Drawable, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// let bigger = Drawable::Circle(Circle::new(&Point::new(50.0, 50.0), 20.0));
+    /// assert_eq!(Ok(false), canvas.replace_selected(bigger.clone()));
+    ///
+    /// canvas.select_drawable_at(&Point::new(50.0, 50.0));
+    /// assert_eq!(Ok(true), canvas.replace_selected(bigger));
+    /// assert_eq!(Some(id), canvas.select_drawable_at(&Point::new(65.0, 50.0)));
+    ///
+    /// ```
+    ///
+    /// ```
+    /// use program_core::{Canvas, CanvasError, Circle, Drawable, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// canvas.set_selected_drawable(Some(id));
+    ///
+    /// let nan = Drawable::Circle(Circle::new(&Point { x: f64::NAN, y: 50.0 }, 5.0));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.replace_selected(nan));
+    /// let flat = Drawable::Circle(Circle::new(&Point::new(50.0, 50.0), 0.0));
+    /// assert_eq!(Err(CanvasError::NonPositiveRadius(0.0)), canvas.replace_selected(flat));
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" r=\"5\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// ```
+    pub fn replace_selected(&mut self, drawable: Drawable) -> Result<bool, CanvasError> {
+        let id = match self.selected_drawable() {
+            Some(id) if !self.is_locked(id) => id,
+            _ => return Ok(false),
+        };
+        validate(&drawable)?;
+
+        self.modify(id, |old| *old = drawable);
+
+        Ok(true)
+    }
+
+    /// rounds the coordinates and lengths of every unlocked drawable to
+    /// `decimals` decimals, cleaning up the long tails left by the mouse and
+    /// by repeated transforms. drawables rounding would leave without a length
+    /// or an area are kept as they are. returns how many drawables changed.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let line = canvas.add_line(&Point::new(10.123456, 20.987654), &Point::new(-5.5549, 60.0)).unwrap();
+    /// let circle = canvas.add_circle(&Point::new(50.0, 50.0), 0.004).unwrap();
+    /// let rect = canvas.add_rect(&Point::new(0.3333333, 0.6666667), &Point::new(40.25, 30.125)).unwrap();
+    /// let locked = canvas.add_line(&Point::new(0.1234, 0.0), &Point::new(1.0, 1.0)).unwrap();
+    /// canvas.set_locked(locked, true);
+    ///
+    /// let points: Vec<Point> = (0..50).flat_map(|x| (0..50).map(move |y| Point::new(x as f64 * 2.0, y as f64 * 2.0))).collect();
+    /// let hits: Vec<_> = points.iter().map(|point| canvas.topmost_at(point)).collect();
+    ///
+    /// assert_eq!(2, canvas.round_all_coordinates(2));
+    /// assert_eq!(
+    ///     Some("<line x1=\"10.12\" x2=\"-5.55\" y1=\"20.99\" y2=\"60\" />"),
+    ///     canvas.svg_fragment_for(line),
+    /// );
+    /// assert_eq!(
+    ///     Some("<rect height=\"29.46\" width=\"39.92\" x=\"0.33\" y=\"0.67\" />"),
+    ///     canvas.svg_fragment_for(rect),
+    /// );
+    /// // the circle would have no radius left, and the locked line can't change
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" r=\"0.004\" />"), canvas.svg_fragment_for(circle));
+    /// assert_eq!(Some("<line x1=\"0.1234\" x2=\"1\" y1=\"0\" y2=\"1\" />"), canvas.svg_fragment_for(locked));
+    ///
+    /// // clicks still hit what they hit before
+    /// assert_eq!(hits, points.iter().map(|point| canvas.topmost_at(point)).collect::<Vec<_>>());
+    ///
+    /// // rounding again changes nothing
+    /// assert_eq!(0, canvas.round_all_coordinates(2));
+    ///
+    /// ```
+    pub fn round_all_coordinates(&mut self, decimals: u32) -> usize {
+        let changes: Vec<(DrawableId, Drawable)> = self
+            .storage
+            .iter()
+            .filter(|(id, _, _)| !self.is_locked(*id))
+            .filter_map(|(id, drawable, _)| {
+                let rounded = drawable.rounded(decimals);
+                (rounded != **drawable && validate(&rounded).is_ok()).then_some((id, rounded))
+            })
+            .collect();
+
+        let count = changes.len();
+        for (id, rounded) in changes {
+            self.modify(id, |drawable| *drawable = rounded);
+        }
+
+        count
+    }
+
+    /// the handles of the selected drawable, none while it's locked
+    /// or more than one drawable is selected.
+    /// the rotation handle is `rotate_distance` away from its top.
+    pub fn selected_handles(&self, rotate_distance: f64) -> Vec<Handle> {
+        self.selected_drawable()
+            .filter(|&id| !self.is_locked(id))
+            .and_then(|id| self.storage.get(id))
+            .map(|drawable| drawable.handles(rotate_distance))
+            .unwrap_or_default()
+    }
+
+    /// the handle of the selected drawable within `tolerance` of `point`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, HandleKind, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// canvas.add_line(&Point::new(10.0, 50.0), &Point::new(90.0, 50.0)).unwrap();
+    /// assert_eq!(None, canvas.handle_at(&Point::new(10.0, 50.0), 3.0, 20.0));
+    ///
+    /// canvas.select_drawable_at(&Point::new(50.0, 50.0));
+    /// assert_eq!(Some(HandleKind::Start), canvas.handle_at(&Point::new(11.0, 51.0), 3.0, 20.0));
+    /// assert_eq!(Some(HandleKind::Rotate), canvas.handle_at(&Point::new(50.0, 30.0), 3.0, 20.0));
+    /// assert_eq!(None, canvas.handle_at(&Point::new(50.0, 50.0), 3.0, 20.0));
+    ///
+    /// ```
+    pub fn handle_at(&self, point: &Point, tolerance: f64, rotate_distance: f64) -> Option<HandleKind> {
+        self.selected_handles(rotate_distance)
+            .into_iter()
+            .find(|handle| handle.pos.approx_eq(point, tolerance))
+            .map(|handle| handle.kind)
+    }
+
+    /// replaces the content of the selected text, keeping its position and size.
+    /// returns false when the selection isn't an unlocked text.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add_text_with_font_size(String::from("draft"), Point::new(10.0, 10.0), 20.0).unwrap();
+    /// canvas.add_circle(&Point::new(80.0, 80.0), 5.0).unwrap();
+    /// assert!(!canvas.set_selected_text_content(String::from("final")));
+    ///
+    /// canvas.select_drawable_at(&Point::new(80.0, 80.0));
+    /// assert!(!canvas.set_selected_text_content(String::from("final")));
+    ///
+    /// canvas.select_drawable_at(&Point::new(12.0, 15.0));
+    /// assert!(canvas.set_selected_text_content(String::from("final")));
+    /// assert_eq!(Some("<text font-size=\"20\" x=\"10\" y=\"10\">final</text>"), canvas.svg_fragment_for(id));
+    ///
+    /// ```
+    pub fn set_selected_text_content(&mut self, content: String) -> bool {
+        let id = match self.selected_drawable() {
+            Some(id) if !self.is_locked(id) && matches!(self.storage.get(id).map(|d| &**d), Some(Drawable::Text(_))) => id,
+            _ => return false,
+        };
+
+        self.modify(id, |drawable| {
+            if let Drawable::Text(text) = drawable {
+                *text = Text::with_font_size(content, text.pos(), text.font_size()).with_style(text.style().clone());
+            }
+        });
+
+        true
+    }
+
+    /// moves the selected drawables by `dx` and `dy`,
+    /// returning whether anything unlocked is selected.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// assert!(!canvas.nudge_selected(1.0, 0.0));
+    ///
+    /// canvas.select_drawable_at(&Point::new(50.0, 50.0));
+    /// assert!(canvas.nudge_selected(1.0, 0.0));
+    /// assert!(canvas.nudge_selected(0.0, -10.0));
+    /// assert_eq!(Some("<circle cx=\"51\" cy=\"40\" r=\"5\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// ```
+    pub fn nudge_selected(&mut self, dx: f64, dy: f64) -> bool {
+        self.translate_selected_drawable(&Point::new(dx, dy));
+
+        self.selection.iter().any(|&id| !self.is_locked(id))
+    }
+
+    /// turns each selected drawable by `angle` about its pivot
+    /// from `pivot`, returning whether anything unlocked is selected.
+    /// an angle that isn't finite does nothing, and neither does a zero angle.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Angle, Canvas, Drawable, PivotSpec, Point};
+    ///
+    /// // where the corners of a 10 by 20 rect go after a quarter turn about each pivot
+    /// let corners = |pivot: &PivotSpec| {
+    ///     let mut canvas = Canvas::new(100.0, 100.0);
+    ///     canvas.add_rect(&Point::new(10.0, 10.0), &Point::new(20.0, 30.0)).unwrap();
+    ///     canvas.select_drawable_at(&Point::new(15.0, 20.0));
+    ///     assert!(canvas.rotate_selected_about(pivot, Angle::degrees(90.0)));
+    ///
+    ///     let Some((_, drawable)) = canvas.drawables().next() else { unreachable!() };
+    ///     let Drawable::Rect(rect) = &**drawable else { unreachable!() };
+    ///     rect.corners().map(|corner| (corner.x.round(), corner.y.round()))
+    /// };
+    ///
+    /// assert_eq!([(25.0, 15.0), (25.0, 25.0), (5.0, 25.0), (5.0, 15.0)], corners(&PivotSpec::Center));
+    /// assert_eq!([(10.0, 10.0), (10.0, 20.0), (-10.0, 20.0), (-10.0, 10.0)], corners(&PivotSpec::Start));
+    /// assert_eq!([(0.0, 0.0), (0.0, 10.0), (-20.0, 10.0), (-20.0, 0.0)], corners(&PivotSpec::Point(Point::new(10.0, 0.0))));
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let id = canvas.add_line(&Point::new(10.0, 10.0), &Point::new(20.0, 10.0)).unwrap();
+    /// assert!(!canvas.rotate_selected_about(&PivotSpec::Start, Angle::degrees(90.0)));
+    ///
+    /// canvas.select_drawable_at(&Point::new(15.0, 10.0));
+    /// canvas.set_locked(id, true);
+    /// assert!(!canvas.rotate_selected_about(&PivotSpec::Start, Angle::degrees(90.0)));
+    ///
+    /// canvas.set_locked(id, false);
+    /// assert!(canvas.rotate_selected_about(&PivotSpec::Start, Angle::degrees(90.0)));
+    /// assert_eq!(Some("<line x1=\"10\" x2=\"10\" y1=\"10\" y2=\"20\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// assert!(!canvas.rotate_selected_about(&PivotSpec::Start, Angle::degrees(f64::NAN)));
+    /// assert_eq!(Some("<line x1=\"10\" x2=\"10\" y1=\"10\" y2=\"20\" />"), canvas.svg_fragment_for(id));
+    ///
+    /// canvas.mark_saved();
+    /// let revision = canvas.revision();
+    /// assert!(!canvas.rotate_selected_about(&PivotSpec::Start, Angle::degrees(0.0)));
+    /// assert_eq!(revision, canvas.revision());
+    /// assert!(!canvas.has_unsaved_changes());
+    ///
+    /// ```
+    pub fn rotate_selected_about(&mut self, pivot: &PivotSpec, angle: Angle) -> bool {
+        if !angle.to_radians().is_finite() || angle.to_radians() == 0f64 {
+            return false;
+        }
+
+        let unlocked: Vec<DrawableId> = self.selection.iter().copied().filter(|&id| !self.is_locked(id)).collect();
+        for &id in &unlocked {
+            self.modify(id, |drawable| {
+                let pivot = pivot.pivot_of(drawable);
+                drawable.rotate(angle, &pivot);
+            });
+        }
+
+        !unlocked.is_empty()
+    }
+
+    /// scales each selected drawable by `factor` about its pivot from
+    /// `pivot`, returning whether anything unlocked is selected. about the
+    /// center a shape grows evenly on every side, about its start it grows
+    /// away from that point. a factor that isn't positive and finite does
+    /// nothing, and neither does a factor of 1, which changes nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, PivotSpec, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let rect = canvas.add_rect(&Point::new(10.0, 10.0), &Point::new(30.0, 20.0)).unwrap();
+    /// let line = canvas.add_line(&Point::new(40.0, 40.0), &Point::new(60.0, 40.0)).unwrap();
+    /// canvas.set_selected_drawable(Some(rect));
+    /// canvas.toggle_selected(line);
+    ///
+    /// // the centers stay where they were
+    /// assert!(canvas.scale_selected_about(&PivotSpec::Center, 2.0));
+    /// assert_eq!(Some("<rect height=\"20\" width=\"40\" x=\"0\" y=\"5\" />"), canvas.svg_fragment_for(rect));
+    /// assert_eq!(Some("<line x1=\"30\" x2=\"70\" y1=\"40\" y2=\"40\" />"), canvas.svg_fragment_for(line));
+    ///
+    /// // their starts stay where they were
+    /// assert!(canvas.scale_selected_about(&PivotSpec::Start, 0.5));
+    /// assert_eq!(Some("<rect height=\"10\" width=\"20\" x=\"0\" y=\"5\" />"), canvas.svg_fragment_for(rect));
+    /// assert_eq!(Some("<line x1=\"30\" x2=\"50\" y1=\"40\" y2=\"40\" />"), canvas.svg_fragment_for(line));
+    ///
+    /// assert!(!canvas.scale_selected_about(&PivotSpec::Center, 0.0));
+    /// assert!(!canvas.scale_selected_about(&PivotSpec::Center, -1.0));
+    /// assert_eq!(Some("<rect height=\"10\" width=\"20\" x=\"0\" y=\"5\" />"), canvas.svg_fragment_for(rect));
+    ///
+    /// canvas.mark_saved();
+    /// let revision = canvas.revision();
+    /// assert!(!canvas.scale_selected_about(&PivotSpec::Center, 1.0));
+    /// assert_eq!(revision, canvas.revision());
+    /// assert!(!canvas.has_unsaved_changes());
+    ///
+    /// ```
+    pub fn scale_selected_about(&mut self, pivot: &PivotSpec, factor: f64) -> bool {
+        if !(factor > 0f64 && factor.is_finite()) || factor == 1f64 {
+            return false;
+        }
+
+        let unlocked: Vec<DrawableId> = self.selection.iter().copied().filter(|&id| !self.is_locked(id)).collect();
+        for &id in &unlocked {
+            self.modify(id, |drawable| {
+                let pivot = pivot.pivot_of(drawable);
+                drawable.scale(factor, &pivot);
+            });
+        }
+
+        !unlocked.is_empty()
+    }
+
+    /// adds copies of the selected drawables moved by `offset` on top of
+    /// every other drawable and selects them, returning their ids. an offset
+    /// that isn't finite adds nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let original = canvas.add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// canvas.add_line(&Point::new(0.0, 0.0), &Point::new(100.0, 0.0)).unwrap();
+    /// assert!(canvas.duplicate_selected(&Point::new(10.0, 10.0)).is_empty());
+    ///
+    /// canvas.select_drawable_at(&Point::new(50.0, 50.0));
+    /// let copy = canvas.duplicate_selected(&Point::new(10.0, 10.0))[0];
+    ///
+    /// assert_eq!(Some(copy), canvas.selected_drawable());
+    /// assert_eq!(Some(&copy), canvas.ids().last());
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" r=\"5\" />"), canvas.svg_fragment_for(original));
+    /// assert_eq!(Some("<circle cx=\"60\" cy=\"60\" r=\"5\" />"), canvas.svg_fragment_for(copy));
+    ///
+    /// assert!(canvas.duplicate_selected(&Point { x: f64::INFINITY, y: 0.0 }).is_empty());
+    /// assert_eq!(3, canvas.len());
+    ///
+    /// ```
+    pub fn duplicate_selected(&mut self, offset: &Point) -> Vec<DrawableId> {
+        if !offset.x.is_finite() || !offset.y.is_finite() {
+            return Vec::new();
+        }
+
+        let mut selected = self.selection.clone();
+        self.storage.sort_by_stacking(&mut selected);
+
+        let copies: Vec<Drawable> = selected
+            .into_iter()
+            .filter_map(|id| self.storage.get(id))
+            .map(|drawable| {
+                let mut copy = Drawable::clone(drawable);
+                copy.translate(offset);
+                copy
+            })
+            .collect();
+
+        self.selection = copies.into_iter().map(|copy| self.push(copy)).collect();
+
+        self.selection.clone()
+    }
+
+    /// returns the ids of every drawable whose bounding box
+    /// overlaps `rect`, in stacking order.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Aabb, Canvas, DrawableId, Point};
+    ///
+    /// let mut canvas = Canvas::new(1000.0, 1000.0);
+    /// for i in 0..100 {
+    ///     let offset = (i * 10) as f64;
+    ///     canvas.add_circle(&Point::new(offset, 500.0), 2.0).unwrap();
+    /// }
+    ///
+    /// let rect = Aabb::new(&Point::new(95.0, 0.0), &Point::new(125.0, 1000.0));
+    /// let linear: Vec<DrawableId> = canvas.drawables()
+    ///     .filter(|(_, drawable)| drawable.bounding_box().intersects(&rect))
+    ///     .map(|(id, _)| id)
+    ///     .collect();
+    ///
+    /// debug_assert_eq!(linear, canvas.drawables_in_rect(&rect));
+    /// assert_eq!(&canvas.ids()[10..13], &canvas.drawables_in_rect(&rect)[..]);
+    ///
+    /// ```
+    pub fn drawables_in_rect(&self, rect: &Aabb) -> Vec<DrawableId> {
+        let mut ids: Vec<DrawableId> = self
+            .spatial_index
+            .query_rect(rect)
+            .into_iter()
+            .filter(|&id| !self.is_hidden(id) && self.storage.get(id).is_some_and(|drawable| drawable.bounding_box().intersects(rect)))
+            .collect();
+
+        self.storage.sort_by_stacking(&mut ids);
+
+        ids
+    }
+
+    pub fn title(&self) -> Option<&String> {
+        self.title.as_ref()
+    }
+
+    /// sets the title emitted as the `<title>` of the exported document.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// assert!(!canvas.to_svg().contains("<title>"));
+    ///
+    /// canvas.set_title(String::from("Cats & Dogs"));
+    ///
+    /// assert_eq!(Some(&String::from("Cats & Dogs")), canvas.title());
+    /// assert!(canvas.to_svg().contains("<title>Cats &amp; Dogs</title>"));
+    ///
+    /// ```
+    pub fn set_title(&mut self, title: String) {
+        self.unsaved = true;
+        self.title = Some(title);
+    }
+
+    pub fn description(&self) -> Option<&String> {
+        self.description.as_ref()
+    }
+
+    /// sets the description emitted as the `<desc>` of the exported document.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Canvas;
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// assert!(!canvas.to_svg().contains("<desc>"));
+    ///
+    /// canvas.set_description(String::from("a <small> floor plan"));
+    ///
+    /// assert!(canvas.to_svg().contains("<desc>a &lt;small&gt; floor plan</desc>"));
+    ///
+    /// ```
+    pub fn set_description(&mut self, description: String) {
+        self.unsaved = true;
+        self.description = Some(description);
+    }
+
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// stores an arbitrary key-value pair, emitted as a `data-*`
+    /// attribute on the root element of the exported document. the key
+    /// ends up in the attribute's name, so it can't be empty and may only
+    /// have letters, digits, `-`, `_` and `.` in it.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, MetadataKeyError};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// assert_eq!(Ok(()), canvas.set_metadata(String::from("author"), String::from("\"Sara\"")));
+    /// assert_eq!(Ok(()), canvas.set_metadata(String::from("scale_1.50-الطابق"), String::from("1:50")));
+    ///
+    /// assert_eq!(Some(&String::from("\"Sara\"")), canvas.metadata().get("author"));
+    /// assert!(canvas.to_svg().contains(" data-author=\"&quot;Sara&quot;\""));
+    ///
+    /// for key in ["", "drawn by", "a=b", "say\"hi\"", "x:y", "a<b"] {
+    ///     assert_eq!(Err(MetadataKeyError(String::from(key))), canvas.set_metadata(String::from(key), String::from("value")));
+    /// }
+    /// assert_eq!(2, canvas.metadata().len());
+    ///
+    /// ```
+    pub fn set_metadata(&mut self, key: String, value: String) -> Result<(), MetadataKeyError> {
+        if key.is_empty() || !key.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+            return Err(MetadataKeyError(key));
+        }
+
+        self.unsaved = true;
+        self.metadata.insert(key, value);
+
+        Ok(())
+    }
+
+    /// saves the drawing as JSON, to be loaded back with `from_json`.
+    /// selection, locks, undo state, and ids aren't saved.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point, Unit};
+    ///
+    /// let mut canvas = Canvas::new(300.0, 200.0);
+    /// canvas.set_title(String::from("plan & notes"));
+    /// canvas.set_metadata(String::from("author"), String::from("Sara"));
+    /// canvas.set_background(Some(String::from("white")));
+    /// canvas.set_unit(Unit::Mm);
+    /// canvas.add_line(&Point::new(0.5, 1.0), &Point::new(-3.0, 4.0)).unwrap();
+    /// let circle = canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    /// let rect = canvas.add_rect(&Point::new(10.0, 20.0), &Point::new(5.0, 0.0)).unwrap();
+    /// canvas.add_text(String::from("\"quoted\""), Point::new(1.0, 2.0)).unwrap();
+    /// canvas.set_name(circle, Some(String::from("wheel")));
+    /// canvas.set_hidden(rect, true);
+    ///
+    /// let loaded = Canvas::from_json(&canvas.to_json()).unwrap();
+    ///
+    /// assert_eq!(canvas.to_svg(), loaded.to_svg());
+    /// assert_eq!(canvas.to_json(), loaded.to_json());
+    /// assert_eq!(Unit::Mm, loaded.unit());
+    /// assert_eq!(Some("wheel"), loaded.name(loaded.ids()[1]));
+    /// assert!(loaded.is_hidden(loaded.ids()[2]));
+    ///
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        super::json::to_json(self)
+    }
+
+    /// loads a drawing saved with `to_json`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, JsonError};
+    ///
+    /// let empty = Canvas::from_json(r#"{"version":1,"width":10,"height":20,"drawables":[]}"#).unwrap();
+    /// assert_eq!((10.0, 20.0), (empty.width(), empty.height()));
+    /// assert!(empty.is_empty());
+    ///
+    /// assert!(matches!(Canvas::from_json(r#"{"version":99}"#), Err(JsonError::UnsupportedVersion(99))));
+    /// assert!(matches!(Canvas::from_json("{\"version\":1,"), Err(JsonError::Syntax(_))));
+    /// assert!(matches!(Canvas::from_json(r#"{"version":1,"width":10,"height":20,"drawables":[{"kind":"blob"}]}"#), Err(JsonError::Syntax(_))));
+    ///
+    /// // a line without a length can't be on the canvas
+    /// let flat = r#"{"version":1,"width":10,"height":20,"drawables":[{"kind":"line","start":{"x":1.0,"y":1.0},"end":{"x":1.0,"y":1.0}}]}"#;
+    /// assert!(matches!(Canvas::from_json(flat), Err(JsonError::InvalidDrawable(program_core::CanvasError::ZeroLength))));
+    ///
+    /// // nor a metadata key that would break the exported document
+    /// let spaced = r#"{"version":1,"width":10,"height":20,"metadata":{"drawn by":"Sara"},"drawables":[]}"#;
+    /// assert!(matches!(Canvas::from_json(spaced), Err(JsonError::InvalidMetadataKey(program_core::MetadataKeyError(key))) if key == "drawn by"));
+    ///
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Canvas, super::JsonError> {
+        super::json::from_json(json)
+    }
+
+    /// adds the drawables of an SVG document on top of the others, returning
+    /// their ids. nothing is added when the document can't be read.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point, SvgError};
+    ///
+    /// let mut original = Canvas::new(100.0, 100.0);
+    /// original.add_line(&Point::new(0.0, 0.0), &Point::new(10.0, 5.0)).unwrap();
+    /// original.add_text(String::from("a < b"), Point::new(1.0, 2.0)).unwrap();
+    /// let rect = original.add_rect(&Point::new(10.0, 10.0), &Point::new(30.0, 20.0)).unwrap();
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let ids = canvas.import_svg(&original.to_svg()).unwrap();
+    /// assert_eq!(3, ids.len());
+    /// assert_eq!(original.svg_fragment_for(rect), canvas.svg_fragment_for(ids[2]));
+    /// assert_eq!(Some("<text x=\"1\" y=\"2\">a &lt; b</text>"), canvas.svg_fragment_for(ids[1]));
+    ///
+    /// let rotated = r#"<svg><!-- a comment --><g><rect x="0" y="0" width="4" height="2" transform="rotate(90 2 1)" /></g></svg>"#;
+    /// let ids = canvas.import_svg(rotated).unwrap();
+    /// assert_eq!(Some("<rect height=\"2\" transform=\"rotate(90 2 1)\" width=\"4\" x=\"0\" y=\"0\" />"), canvas.svg_fragment_for(ids[0]));
+    ///
+    /// assert_eq!(Err(SvgError::NotSvg), canvas.import_svg("<html></html>"));
+    /// assert_eq!(Err(SvgError::UnexpectedEnd), canvas.import_svg("<svg><line x1=\"1\""));
+    /// assert!(matches!(canvas.import_svg(r#"<svg><circle r="big" /></svg>"#), Err(SvgError::InvalidNumber { .. })));
+    /// assert!(matches!(canvas.import_svg(r#"<svg><line x2="1" y2="1" transform="scale(2)" /></svg>"#), Err(SvgError::UnsupportedTransform(_))));
+    /// assert_eq!(4, canvas.len());
+    ///
+    /// ```
+    ///
+    /// shapes without the attributes they need, or that a canvas refuses, aren't added.
+    /// ```
+    /// use program_core::{Canvas, CanvasError, SvgError};
+    ///
+    /// let missing = |element: &str, attribute: &str| Err(SvgError::MissingAttribute { element: element.to_string(), attribute: attribute.to_string() });
+    /// let invalid = |error| Err(SvgError::InvalidDrawable(error));
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// assert_eq!(missing("circle", "r"), canvas.import_svg(r#"<svg><circle cx="1" cy="1" /></svg>"#));
+    /// assert_eq!(missing("line", "x2"), canvas.import_svg("<svg><line /></svg>"));
+    /// assert_eq!(missing("rect", "height"), canvas.import_svg(r#"<svg><rect width="4" /></svg>"#));
+    /// assert_eq!(invalid(CanvasError::NonPositiveRadius(-4.0)), canvas.import_svg(r#"<svg><circle r="-4" /></svg>"#));
+    /// assert_eq!(invalid(CanvasError::NonPositiveRadius(0.0)), canvas.import_svg(r#"<svg><line x2="5" y2="5" /><circle r="0" /></svg>"#));
+    /// assert_eq!(invalid(CanvasError::ZeroArea), canvas.import_svg(r#"<svg><rect width="0" height="3" /></svg>"#));
+    /// assert_eq!(invalid(CanvasError::ZeroLength), canvas.import_svg(r#"<svg><line x1="2" y1="2" x2="2" y2="2" /></svg>"#));
+    /// assert!(canvas.is_empty());
+    ///
+    /// ```
+    ///
+    /// shapes are painted with the gradients they refer to, wherever those are defined.
+    /// ```
+    /// use program_core::{Angle, Canvas, Color, Paint};
+    ///
+    /// let svg = r##"<svg>
+    ///     <circle cx="10" cy="10" r="5" stroke="url(#later)" fill="url(#nowhere)" />
+    ///     <defs><linearGradient id="later"><stop offset="0" /><stop offset="100%" stop-color="#ffffff" stop-opacity="0.5" /></linearGradient></defs>
+    /// </svg>"##;
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let ids = canvas.import_svg(svg).unwrap();
+    /// let style = canvas.get(ids[0]).unwrap().style().unwrap();
+    /// let later = Paint::LinearGradient { stops: vec![(0.0, Color::BLACK), (1.0, Color::rgba(255, 255, 255, 128))], angle: Angle::default() };
+    /// assert_eq!((Some(&later), None), (style.stroke.as_ref(), style.fill.as_ref()));
+    ///
+    /// ```
+    ///
+    /// arcs are read from paths with a single `A` command, other paths are skipped.
+    /// ```
+    /// use program_core::{Canvas, Drawable, Point};
+    ///
+    /// let svg = r#"<svg>
+    ///     <path d="M 60 50 A 10 10 0 1 0 50 60" fill="none" />
+    ///     <path d="M10,20A2,2,0,0,1,20,20" />
+    ///     <path d="M 0 0 L 10 10" />
+    /// </svg>"#;
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let ids = canvas.import_svg(svg).unwrap();
+    /// assert_eq!(2, ids.len());
+    ///
+    /// // drawn the other way, three quarters of a turn from the bottom round to the right
+    /// let Some(Drawable::Arc(large)) = canvas.get(ids[0]).map(|drawable| &**drawable) else { unreachable!() };
+    /// assert!(large.center().approx_eq(&Point::new(50.0, 50.0), 1e-9));
+    /// assert!((large.sweep().to_degrees() - 270.0).abs() < 1e-9);
+    /// assert!(large.start().approx_eq(&Point::new(50.0, 60.0), 1e-9) && large.end().approx_eq(&Point::new(60.0, 50.0), 1e-9));
+    ///
+    /// // a radius shorter than half the way across makes a half turn
+    /// let Some(Drawable::Arc(half)) = canvas.get(ids[1]).map(|drawable| &**drawable) else { unreachable!() };
+    /// assert!(half.center().approx_eq(&Point::new(15.0, 20.0), 1e-9));
+    /// assert!(half.radius() == 5.0 && (half.sweep().to_degrees() - 180.0).abs() < 1e-9);
+    ///
+    /// ```
+    pub fn import_svg(&mut self, svg: &str) -> Result<Vec<DrawableId>, SvgError> {
+        let drawables = super::svg_import::parse_svg(svg)?;
+
+        self.add_drawables(drawables).map_err(SvgError::InvalidDrawable)
+    }
+
+    /// builds the SVG document of the canvas,
+    /// drawables are emitted in stacking order.
+    /// only the gradients of the drawables written are defined.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Canvas;
+    ///
+    /// let svg = Canvas::new(100.0, 50.0).to_svg();
+    ///
+    /// assert!(svg.starts_with("<svg "));
+    /// assert!(svg.contains(" width=\"100\""));
+    /// assert!(svg.ends_with("</svg>"));
+    ///
+    /// ```
+    ///
+    /// a gradient stays defined while anything is painted with it,
+    /// and importing the document paints the shapes with it again.
+    /// ```
+    /// use program_core::{Angle, Canvas, Color, Drawable, Paint, Point, Rect, StyleBuilder};
+    ///
+    /// let fade = Paint::LinearGradient { stops: vec![(0.0, Color::RED), (0.5, Color::rgba(0, 0, 255, 128)), (1.0, Color::WHITE)], angle: Angle::degrees(90.0) };
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let first = canvas.add(Rect::builder(&Point::new(0.0, 0.0), &Point::new(20.0, 10.0)).fill(fade.clone()).build()).unwrap();
+    /// let second = canvas.add(Rect::builder(&Point::new(30.0, 0.0), &Point::new(50.0, 10.0)).fill(fade.clone()).build()).unwrap();
+    ///
+    /// assert!(canvas.delete(first));
+    /// let svg = canvas.to_svg();
+    /// let id = fade.gradient_id().unwrap();
+    /// assert_eq!(1, svg.matches("<linearGradient").count());
+    /// assert!(svg.contains(&format!("<defs><linearGradient id=\"{}\"", id)));
+    /// assert!(canvas.svg_fragment_for(second).unwrap().contains(&format!("fill=\"url(#{})\"", id)));
+    ///
+    /// let mut imported = Canvas::new(100.0, 100.0);
+    /// let ids = imported.import_svg(&svg).unwrap();
+    /// let Some(Drawable::Rect(rect)) = imported.get(ids[0]).map(|drawable| &**drawable) else { unreachable!() };
+    /// assert_eq!(Some(&fade), rect.style().fill.as_ref());
+    /// assert_eq!(svg, imported.to_svg());
+    ///
+    /// // nothing is painted with it anymore
+    /// assert!(canvas.delete(second));
+    /// assert!(!canvas.to_svg().contains("<defs>"));
+    ///
+    /// ```
+    pub fn to_svg(&self) -> String {
+        self.to_svg_with(&ExportOptions::default())
+    }
+
+    /// builds the SVG document of the canvas laid out by `options`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, ExportOptions, Point};
+    ///
+    /// let mut canvas = Canvas::new(400.0, 300.0);
+    /// canvas.set_background(Some(String::from("#ffffff")));
+    /// canvas.add_rect(&Point::new(100.0, 50.0), &Point::new(200.0, 100.0)).unwrap();
+    /// assert_eq!(canvas.to_svg(), canvas.to_svg_with(&ExportOptions::default()));
+    ///
+    /// let options = ExportOptions { fit_to_content: true, margin: 10.0, size_mm: Some((60.0, 35.0)), precision: None };
+    /// let svg = canvas.to_svg_with(&options);
+    /// assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"60mm\" height=\"35mm\" viewBox=\"90 40 120 70\""));
+    /// assert!(svg.contains("<rect x=\"90\" y=\"40\" width=\"120\" height=\"70\" fill=\"#ffffff\" />"));
+    ///
+    /// // an empty canvas keeps its whole area
+    /// let svg = Canvas::new(400.0, 300.0).to_svg_with(&options);
+    /// assert!(svg.contains(" viewBox=\"0 0 400 300\""));
+    ///
+    /// // numbers are rounded as they're written, but not on the canvas
+    /// canvas.add_circle(&Point::new(1.0 / 3.0, 2.0 / 3.0), 10.0).unwrap();
+    /// let svg = canvas.to_svg_with(&ExportOptions { precision: Some(2), ..ExportOptions::default() });
+    /// assert!(svg.contains("<circle cx=\"0.33\" cy=\"0.67\" r=\"10\" />"));
+    /// assert!(canvas.to_svg().contains("<circle cx=\"0.3333333333333333\" cy=\"0.6666666666666666\" r=\"10\" />"));
+    ///
+    /// ```
+    pub fn to_svg_with(&self, options: &ExportOptions) -> String {
+        let tags_len: usize = self.storage.iter().map(|(_, _, tag)| tag.len()).sum();
+        let mut svg = Vec::with_capacity(tags_len + 256);
+
+        self.write_svg(&mut svg, options, |_, _| {}).expect("writing to a Vec can't fail");
+
+        String::from_utf8(svg).expect("the SVG document is built from strings")
+    }
+
+    /// renders the canvas to a PNG image, `scale` pixels to a canvas unit.
+    /// hidden drawables are left out like in the SVG document, and what can't
+    /// be drawn the same, like text, is approximated and listed in the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Angle, Approximation, Arc, Canvas, Circle, Color, Paint, Point, RasterError, Rect, StyleBuilder};
+    /// use tiny_skia::Pixmap;
+    ///
+    /// let mut canvas = Canvas::new(100.0, 50.0);
+    /// canvas.set_background(Some(String::from("#ffffff")));
+    /// canvas.add(Circle::builder(&Point::new(25.0, 25.0), 20.0).fill(Color::RED).build()).unwrap();
+    /// let gradient = Paint::LinearGradient { stops: vec![(0.0, Color::RED), (1.0, Color::BLUE)], angle: Angle::default() };
+    /// canvas.add(Rect::builder(&Point::new(60.0, 25.0), &Point::new(95.0, 45.0)).fill(gradient).build()).unwrap();
+    /// let label = canvas.add_text(String::from("hi"), Point::new(60.0, 10.0)).unwrap();
+    /// let ring = Arc::builder(&Point::new(25.0, 25.0), 23.0, Angle::degrees(90.0), Angle::degrees(0.0)).stroke(Color::BLUE).stroke_width(2.0).build();
+    /// canvas.add(ring).unwrap();
+    ///
+    /// let image = canvas.render_png(2.0).unwrap();
+    /// assert_eq!((200, 100), (image.width, image.height));
+    /// assert_eq!(vec![Approximation::TextAsBox(label)], image.approximations);
+    ///
+    /// let pixmap = Pixmap::decode_png(&image.png).unwrap();
+    /// let rgba = |x, y| {
+    ///     let pixel = pixmap.pixel(x, y).unwrap().demultiply();
+    ///     (pixel.red(), pixel.green(), pixel.blue(), pixel.alpha())
+    /// };
+    /// assert_eq!((255, 0, 0, 255), rgba(50, 50));
+    /// assert_eq!((255, 0, 0, 255), rgba(50, 12));
+    /// assert_eq!((255, 255, 255, 255), rgba(5, 5));
+    /// assert_eq!((255, 255, 255, 255), rgba(195, 95));
+    ///
+    /// // the arc goes round the circle but for the bottom right quarter
+    /// assert_eq!((0, 0, 255, 255), rgba(50, 4));
+    /// assert_eq!((0, 0, 255, 255), rgba(4, 50));
+    /// assert_eq!((255, 255, 255, 255), rgba(82, 82));
+    ///
+    /// // the gradient runs from red on the left of the rect to blue on its right
+    /// let (left, right) = (rgba(122, 70), rgba(188, 70));
+    /// assert!(left.0 > 230 && left.2 < 25);
+    /// assert!(right.0 < 25 && right.2 > 230);
+    ///
+    /// assert!(matches!(canvas.render_png(0.0), Err(RasterError::InvalidScale(_))));
+    /// assert!(matches!(canvas.render_png(1e6), Err(RasterError::InvalidSize { .. })));
+    ///
+    /// ```
+    #[cfg(feature = "raster")]
+    pub fn render_png(&self, scale: f64) -> Result<super::RasterImage, super::RasterError> {
+        super::raster::render_png(self, scale)
+    }
+
+    /// writes the SVG document to `path`, replacing any existing file.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, ExportOperation};
+    ///
+    /// let path = std::env::temp_dir().join("alrassam_export_missing").join("diagram.svg");
+    /// let error = Canvas::new(100.0, 100.0).export(&path).unwrap_err();
+    ///
+    /// assert_eq!(path, error.path);
+    /// assert_eq!(ExportOperation::Create, error.operation);
+    /// assert_eq!(std::io::ErrorKind::NotFound, error.source.kind());
+    /// assert!(error.to_string().starts_with(&format!("couldn't create {}: ", path.display())));
+    ///
+    /// ```
+    pub fn export(&self, path: impl AsRef<Path>) -> Result<(), ExportError> {
+        self.export_with_options(path, &ExportFileOptions::default())
+    }
+
+    /// writes the SVG document to `path`, creating its folders
+    /// and keeping an existing file as `options` ask.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, ExportFileOptions, ExportOperation, OverwritePolicy, Point};
+    /// use std::fs;
+    ///
+    /// let dir = std::env::temp_dir().join("alrassam_export_with_options");
+    /// let _ = fs::remove_dir_all(&dir);
+    /// let path = dir.join("plans").join("diagram.svg");
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// let first = canvas.to_svg();
+    ///
+    /// let create_dirs = ExportFileOptions { create_dirs: true, ..ExportFileOptions::default() };
+    /// canvas.export_with_options(&path, &create_dirs).unwrap();
+    /// assert_eq!(first, fs::read_to_string(&path).unwrap());
+    ///
+    /// // refusing to overwrite leaves the file as it was
+    /// canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    /// let refuse = ExportFileOptions { overwrite: OverwritePolicy::Error, ..ExportFileOptions::default() };
+    /// let error = canvas.export_with_options(&path, &refuse).unwrap_err();
+    /// assert_eq!(ExportOperation::Create, error.operation);
+    /// assert_eq!(std::io::ErrorKind::AlreadyExists, error.source.kind());
+    /// assert_eq!(first, fs::read_to_string(&path).unwrap());
+    ///
+    /// // a backup keeps the previous export next to the new one
+    /// let backup = ExportFileOptions { overwrite: OverwritePolicy::Backup, ..ExportFileOptions::default() };
+    /// canvas.export_with_options(&path, &backup).unwrap();
+    /// assert_eq!(canvas.to_svg(), fs::read_to_string(&path).unwrap());
+    /// assert_eq!(first, fs::read_to_string(dir.join("plans").join("diagram.svg.bak")).unwrap());
+    ///
+    /// // replacing drops the previous export
+    /// canvas.add_line(&Point::new(0.0, 0.0), &Point::new(10.0, 10.0)).unwrap();
+    /// canvas.export_with_options(&path, &ExportFileOptions::default()).unwrap();
+    /// assert_eq!(canvas.to_svg(), fs::read_to_string(&path).unwrap());
+    ///
+    /// fs::remove_dir_all(&dir).unwrap();
+    ///
+    /// ```
+    pub fn export_with_options(&self, path: impl AsRef<Path>, options: &ExportFileOptions) -> Result<(), ExportError> {
+        export::write_file(path.as_ref(), options, |file| self.write_svg(file, &ExportOptions::default(), |_, _| {}))
+    }
+
+    /// writes the SVG document to `path`, calling `progress` with the number
+    /// of drawables written so far and the total after each drawable.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    /// canvas.add_line(&Point::new(0.0, 0.0), &Point::new(100.0, 100.0)).unwrap();
+    ///
+    /// let path = std::env::temp_dir().join("alrassam_export_with_progress.svg");
+    /// let mut reports = Vec::new();
+    /// canvas.export_with_progress(&path, |done, total| reports.push((done, total))).unwrap();
+    ///
+    /// assert_eq!(vec![(1, 2), (2, 2)], reports);
+    /// assert_eq!(canvas.to_svg(), std::fs::read_to_string(&path).unwrap());
+    ///
+    /// std::fs::remove_file(&path).unwrap();
+    ///
+    /// ```
+    pub fn export_with_progress(&self, path: impl AsRef<Path>, progress: impl FnMut(usize, usize)) -> Result<(), ExportError> {
+        export::write_file(path.as_ref(), &ExportFileOptions::default(), |file| {
+            self.write_svg(file, &ExportOptions::default(), progress)
+        })
+    }
+
+    /// streams the SVG document into `out` one drawable at a time,
+    /// without building the whole document in memory.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// canvas.set_title(String::from("small"));
+    /// canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    ///
+    /// let mut out = Vec::new();
+    /// canvas.export_to(&mut out).unwrap();
+    ///
+    /// assert_eq!(canvas.to_svg().as_bytes(), &out[..]);
+    ///
+    /// ```
+    pub fn export_to<W: Write>(&self, mut out: W) -> io::Result<()> {
+        self.write_svg(&mut out, &ExportOptions::default(), |_, _| {})
+    }
+
+    /// streams the SVG document into `out`,
+    /// reporting progress like `export_with_progress`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point, Style};
+    /// use std::io::Write;
+    ///
+    /// struct Counter(usize);
+    ///
+    /// impl Write for Counter {
+    ///     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    ///         self.0 += buf.len();
+    ///         Ok(buf.len())
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> std::io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut canvas = Canvas::new(1000.0, 1000.0);
+    /// canvas.add_lines((0..50000).map(|i| (Point::new(0.0, i as f64), Point::new(10.0, i as f64))), &Style::default()).unwrap();
+    ///
+    /// let mut counter = Counter(0);
+    /// let mut last = (0, 0);
+    /// canvas.export_to_with_progress(&mut counter, |done, total| last = (done, total)).unwrap();
+    ///
+    /// assert_eq!((50000, 50000), last);
+    /// assert_eq!(canvas.to_svg().len(), counter.0);
+    /// assert!(canvas.to_svg().ends_with("<line x1=\"0\" x2=\"10\" y1=\"49999\" y2=\"49999\" /></svg>"));
+    ///
+    /// ```
+    pub fn export_to_with_progress<W: Write>(&self, mut out: W, progress: impl FnMut(usize, usize)) -> io::Result<()> {
+        self.write_svg(&mut out, &ExportOptions::default(), progress)
+    }
+
+    fn write_svg<W: Write>(&self, out: &mut W, options: &ExportOptions, mut progress: impl FnMut(usize, usize)) -> io::Result<()> {
+        let whole = Aabb::new(&Point::new(0f64, 0f64), &Point::new(self.width, self.height));
+        let area = match self.content_bounds() {
+            Some(bounds) if options.fit_to_content => bounds.expand(options.margin),
+            _ => whole.clone(),
+        };
+        let (min, max) = (area.min(), area.max());
+        let (width, height) = (max.x - min.x, max.y - min.y);
+
+        // without a viewBox the document is the canvas at its size in pixels, as it always was
+        let view_box = area != whole || options.size_mm.is_some();
+        match options.size_mm {
+            Some((width_mm, height_mm)) => write!(
+                out,
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}mm\" height=\"{}mm\"",
+                width_mm, height_mm
+            )?,
+            None => write!(out, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\"", width, height)?,
+        }
+        if view_box {
+            write!(out, " viewBox=\"{} {} {} {}\"", min.x, min.y, width, height)?;
+        }
+        let fill_area = if view_box {
+            format!("x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"", min.x, min.y, width, height)
+        } else {
+            String::from("width=\"100%\" height=\"100%\"")
+        };
+
+        for (key, value) in &self.metadata {
+            write!(out, " data-{}=\"{}\"", escape(key), escape(value))?;
+        }
+        out.write_all(b">")?;
+
+        if let Some(title) = &self.title {
+            write!(out, "<title>{}</title>", escape(title))?;
+        }
+        if let Some(description) = &self.description {
+            write!(out, "<desc>{}</desc>", escape(description))?;
+        }
+        if let Some(background) = &self.background {
+            write!(out, "<rect {} fill=\"{}\" />", fill_area, escape(background))?;
+        }
+        if let Some(spacing) = self.grid_spacing.filter(|_| self.export_grid) {
+            write!(
+                out,
+                "<defs><pattern id=\"grid\" width=\"{0}\" height=\"{0}\" patternUnits=\"userSpaceOnUse\">\
+                 <path d=\"M {0} 0 L 0 0 0 {0}\" fill=\"none\" stroke=\"{1}\" stroke-width=\"0.5\" /></pattern></defs>\
+                 <rect {2} fill=\"url(#grid)\" />",
+                spacing, GRID_COLOR, fill_area,
+            )?;
+        }
+
+        // gradients are defined once, however many drawables are painted with them
+        let mut gradients: Vec<(String, &Paint)> = Vec::new();
+        let styles = self.storage.iter().filter(|(id, _, _)| !self.is_hidden(*id)).filter_map(|(_, drawable, _)| drawable.style());
+        for paint in styles.flat_map(|style| [&style.stroke, &style.fill]).flatten() {
+            match paint.gradient_id() {
+                Some(id) if !gradients.iter().any(|(defined, _)| *defined == id) => gradients.push((id, paint)),
+                _ => {}
+            }
+        }
+        if !gradients.is_empty() {
+            let mut defs = String::from("<defs>");
+            for (_, paint) in gradients {
+                paint.write_gradient_def(&mut defs).expect("writing to a String can't fail");
+            }
+            defs.push_str("</defs>");
+            out.write_all(defs.as_bytes())?;
+        }
+
+        let total = self.storage.len() - self.hidden.len();
+        let visible = self.storage.iter().filter(|(id, _, _)| !self.is_hidden(*id));
+        for (done, (_, drawable, tag)) in visible.enumerate() {
+            match options.precision.or(self.svg_precision) {
+                Some(decimals) => out.write_all(drawable.rounded(decimals).to_svg_tag().as_bytes())?,
+                None => out.write_all(tag.as_bytes())?,
+            }
+            progress(done + 1, total);
+        }
+
+        if self.render_guides {
+            for guide in &self.guides {
+                let ((x1, y1), (x2, y2)) = match guide.axis {
+                    GuideAxis::Horizontal => ((min.x, guide.position), (max.x, guide.position)),
+                    GuideAxis::Vertical => ((guide.position, min.y), (guide.position, max.y)),
+                };
+                write!(
+                    out,
+                    "<line x1=\"{}\" x2=\"{}\" y1=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"0.5\" />",
+                    x1, x2, y1, y2, GUIDE_COLOR,
+                )?;
+            }
+            for line in self.construction_guides.iter().filter_map(|guide| guide.line.clip_to(&area)) {
+                let (start, end) = (line.start(), line.end());
+                write!(
+                    out,
+                    "<line x1=\"{}\" x2=\"{}\" y1=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-dasharray=\"4 2\" stroke-width=\"0.5\" />",
+                    start.x, end.x, start.y, end.y, GUIDE_COLOR,
+                )?;
+            }
+        }
+
+        out.write_all(b"</svg>")
+    }
+}
+
+/// the color of grid lines.
+pub const GRID_COLOR: &str = "#d0d0d0";
+
+/// the error returned when a drawable can't be added to a canvas,
+/// because it would be left out of the SVG or couldn't be clicked.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum CanvasError {
+    /// a coordinate or a length is infinite or not a number.
+    NotFinite,
+    /// a circle's or an arc's radius isn't positive.
+    NonPositiveRadius(f64),
+    /// a line's endpoints are the same point.
+    ZeroLength,
+    /// a rect's corners share an x or a y, so it has no area.
+    ZeroArea,
+    /// an arc ends where it starts, so it has no length.
+    ZeroSweep,
+    /// the points a circle was to run through are on one line.
+    CollinearPoints,
+}
+
+impl fmt::Display for CanvasError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CanvasError::NotFinite => write!(f, "coordinates and lengths must be finite"),
+            CanvasError::NonPositiveRadius(radius) => write!(f, "a radius must be positive, not {}", radius),
+            CanvasError::ZeroLength => write!(f, "a line can't start and end at the same point"),
+            CanvasError::ZeroArea => write!(f, "a rect must have a width and a height"),
+            CanvasError::ZeroSweep => write!(f, "an arc can't end where it starts"),
+            CanvasError::CollinearPoints => write!(f, "a circle can't run through three points on one line"),
+        }
+    }
+}
+
+impl std::error::Error for CanvasError {}
+
+impl From<NotFiniteError> for CanvasError {
+    fn from(_: NotFiniteError) -> Self {
+        CanvasError::NotFinite
+    }
+}
+
+/// the error returned when a metadata key can't be part of an attribute's name.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MetadataKeyError(pub String);
+
+impl fmt::Display for MetadataKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"{}\" can't be a metadata key, it must be letters, digits, '-', '_' or '.'", self.0)
+    }
+}
+
+impl std::error::Error for MetadataKeyError {}
+
+/// checks that `drawable` can be drawn and clicked,
+/// lengths are compared with `f64::EPSILON`.
+pub(crate) fn validate(drawable: &Drawable) -> Result<(), CanvasError> {
+    let finite = |values: &[f64]| if values.iter().all(|value| value.is_finite()) { Ok(()) } else { Err(CanvasError::NotFinite) };
+    if let Some(StrokeStyle::Dashed(dashes)) = drawable.style().map(|style| &style.stroke_style) {
+        finite(dashes)?;
+    }
+
+    match drawable {
+        Drawable::Point(point) => finite(&[point.x, point.y]),
+        Drawable::Line(line) => {
+            let (start, end) = (line.start(), line.end());
+            finite(&[start.x, start.y, end.x, end.y])?;
+
+            if line.len() <= f64::EPSILON {
+                return Err(CanvasError::ZeroLength);
+            }
+            Ok(())
+        }
+        Drawable::Circle(circle) => {
+            let center = circle.center();
+            finite(&[center.x, center.y, circle.radius()])?;
+
+            if circle.radius() <= 0f64 {
+                return Err(CanvasError::NonPositiveRadius(circle.radius()));
+            }
+            Ok(())
+        }
+        Drawable::Rect(rect) => {
+            let corner = rect.corner();
+            finite(&[corner.x, corner.y, rect.width(), rect.height(), rect.rotation()])?;
+
+            if rect.width() <= f64::EPSILON || rect.height() <= f64::EPSILON {
+                return Err(CanvasError::ZeroArea);
+            }
+            Ok(())
+        }
+        Drawable::Text(text) => finite(&[text.pos().x, text.pos().y]),
+        Drawable::Arc(arc) => {
+            let center = arc.center();
+            finite(&[center.x, center.y, arc.radius(), arc.start_angle().to_radians(), arc.sweep().to_radians()])?;
+
+            if arc.radius() <= 0f64 {
+                return Err(CanvasError::NonPositiveRadius(arc.radius()));
+            }
+            if arc.sweep().to_radians() <= f64::EPSILON {
+                return Err(CanvasError::ZeroSweep);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// # ExportOptions
+/// how `Canvas::to_svg_with` lays out the SVG document. the default
+/// options give the whole canvas at its size in pixels, like `Canvas::to_svg`.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct ExportOptions {
+    /// crops the document to the visible drawables, `margin` pixels around them.
+    pub fit_to_content: bool,
+    pub margin: f64,
+    /// the width and height the document is shown at, in millimeters,
+    /// with the drawing scaled to fit them.
+    pub size_mm: Option<(f64, f64)>,
+    /// the decimals the drawables' numbers are written with, or the canvas's
+    /// `svg_precision` when it's `None`. they're rounded as they're written,
+    /// the canvas is left as it is.
+    pub precision: Option<u32>,
+}
+
+/// where `Canvas::merge` puts the drawing it adds.
+#[derive(PartialEq, Clone, Debug)]
+pub enum MergePlacement {
+    /// where it was on its own canvas.
+    AtOrigin,
+    /// moved by the offset.
+    Offset(Point),
+    /// to the right of the visible drawables already there, `MERGE_GAP`
+    /// pixels away and lined up with their top.
+    Beside,
+}
+
+/// the space `MergePlacement::Beside` leaves between the two drawings, in pixels.
+pub const MERGE_GAP: f64 = 20.0;
+
+/// serializes the tags of many drawables, spread over all cores
+/// when the `parallel` feature is enabled. tags keep the order of `drawables`.
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+fn serialize_tags(drawables: &[Drawable]) -> Vec<Arc<str>> {
+    use rayon::prelude::*;
+
+    drawables
+        .par_iter()
+        .map_init(String::new, |tag, drawable| {
+            tag.clear();
+            drawable.write_svg_tag(tag).expect("writing to a String can't fail");
+            Arc::from(tag.as_str())
+        })
+        .collect()
+}
+
+#[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+fn serialize_tags(drawables: &[Drawable]) -> Vec<Arc<str>> {
+    // every tag is written into the same buffer, and copied out of it once
+    let mut tag = String::new();
+
+    drawables
+        .iter()
+        .map(|drawable| {
+            tag.clear();
+            drawable.write_svg_tag(&mut tag).expect("writing to a String can't fail");
+            Arc::from(tag.as_str())
+        })
+        .collect()
+}
+
+/// the area in which a click may hit the drawable,
+/// its bounding box grown by the hit tolerance of thin outlines.
+/// a line is hit around its ends too, past where its stroke stops.
+fn hit_bounds(drawable: &Drawable) -> Aabb {
+    match drawable {
+        Drawable::Line(line) => Aabb::new(&line.start(), &line.end()).expand(line.style().painted_stroke_width() / 2f64 + HIT_TOLERANCE),
+        _ => drawable.bounding_box().expand(HIT_TOLERANCE),
+    }
+}
+
+/// # Snapshot
+/// a saved state of a canvas, created by `Canvas::snapshot`
+/// and brought back with `Canvas::restore`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Snapshot(Canvas);
@@ -0,0 +1,66 @@
+use std::ops::{Add, Neg, Sub};
+
+/// # Angle
+/// an angle that knows its unit, so turning a drawable by degrees
+/// or by radians can't be mixed up. it's kept in radians.
+///
+/// # Examples
+/// ```
+/// use std::f64::consts::FRAC_PI_2;
+/// use program_core::Angle;
+///
+/// let quarter = Angle::degrees(90.0);
+/// assert_eq!(FRAC_PI_2, quarter.to_radians());
+/// assert_eq!(90.0, Angle::radians(FRAC_PI_2).to_degrees());
+/// assert_eq!(quarter, Angle::radians(FRAC_PI_2));
+///
+/// assert_eq!(Angle::degrees(45.0), quarter - Angle::degrees(45.0));
+/// assert_eq!(Angle::degrees(180.0), quarter + quarter);
+/// assert_eq!(Angle::degrees(-90.0), -quarter);
+/// assert_eq!(Angle::radians(0.0), Angle::default());
+///
+/// ```
+#[derive(PartialEq, PartialOrd, Clone, Copy, Default, Debug)]
+pub struct Angle(f64);
+
+impl Angle {
+    pub fn radians(radians: f64) -> Angle {
+        Angle(radians)
+    }
+
+    pub fn degrees(degrees: f64) -> Angle {
+        Angle(degrees.to_radians())
+    }
+
+    pub fn to_radians(self) -> f64 {
+        self.0
+    }
+
+    pub fn to_degrees(self) -> f64 {
+        self.0.to_degrees()
+    }
+}
+
+impl Add for Angle {
+    type Output = Angle;
+
+    fn add(self, other: Angle) -> Angle {
+        Angle(self.0 + other.0)
+    }
+}
+
+impl Sub for Angle {
+    type Output = Angle;
+
+    fn sub(self, other: Angle) -> Angle {
+        Angle(self.0 - other.0)
+    }
+}
+
+impl Neg for Angle {
+    type Output = Angle;
+
+    fn neg(self) -> Angle {
+        Angle(-self.0)
+    }
+}
@@ -0,0 +1,306 @@
+use super::angle::Angle;
+use super::bounds::Aabb;
+use super::point2d::Point2D;
+use super::style::{Color, LineCap, Paint, Style, StyleBuilder};
+use super::transform::Transform2D;
+use super::{write_attr, Draw, HIT_TOLERANCE};
+use std::f64::consts::{FRAC_PI_2, PI, SQRT_2, TAU};
+use std::fmt;
+
+/// # Arc
+/// structure to hold circular arcs in 2d cartesian space. it runs from
+/// `start_angle` to `end_angle` along the circle around `center`, with angles
+/// growing clockwise as the y axis points down, like `Point::rotated_about`.
+/// angles are kept from 0 to 2π, so an arc ending where it starts has no sweep.
+///
+/// it's written to SVG as a `<path>` with an `A` command. an arc is open,
+/// so it's left unfilled unless a fill is given.
+///
+/// # Examples
+/// ```
+/// use std::f64::consts::PI;
+/// use program_core::{Angle, Arc, Drawable, Point};
+///
+/// let center = Point::new(50.0, 50.0);
+/// let quarter = Arc::new(&center, 10.0, Angle::degrees(0.0), Angle::degrees(90.0));
+///
+/// assert_eq!(Point::new(60.0, 50.0), quarter.start());
+/// assert_eq!(Point::new(50.0, 60.0), quarter.end());
+/// assert_eq!(Angle::degrees(90.0), quarter.sweep());
+/// assert_eq!(5.0 * PI, quarter.len());
+/// assert_eq!("<path d=\"M 60 50 A 10 10 0 0 1 50 60\" fill=\"none\" />", Drawable::Arc(quarter).to_svg_tag());
+///
+/// // more than half a turn takes the large way around
+/// let large = Arc::new(&center, 10.0, Angle::degrees(90.0), Angle::degrees(0.0));
+/// assert_eq!(Angle::degrees(270.0), large.sweep());
+/// assert_eq!("<path d=\"M 50 60 A 10 10 0 1 1 60 50\" fill=\"none\" />", Drawable::Arc(large).to_svg_tag());
+///
+/// // across 0°, from 300° on to 60°
+/// let wrapped = Arc::new(&center, 10.0, Angle::degrees(300.0), Angle::degrees(60.0));
+/// assert!((wrapped.sweep().to_degrees() - 120.0).abs() < 1e-12);
+/// assert!(wrapped.contains_angle(Angle::degrees(0.0)) && wrapped.contains_angle(Angle::degrees(-30.0)));
+/// assert!(!wrapped.contains_angle(Angle::degrees(180.0)));
+/// let same = Arc::new(&center, 10.0, Angle::degrees(-60.0), Angle::degrees(60.0));
+/// assert!((wrapped.start_angle().to_radians() - same.start_angle().to_radians()).abs() < 1e-12);
+///
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Arc {
+    center: Point2D,
+    radius: f64,
+    /// in radians, from 0 to 2π.
+    start: f64,
+    /// how far the arc runs clockwise from `start`, in radians from 0 to 2π.
+    sweep: f64,
+    style: Style,
+}
+
+/// `angle` turned into the range from 0 to 2π. a tiny negative angle
+/// rounds up to 2π, which is taken as 0.
+fn normalized(angle: f64) -> f64 {
+    let angle = angle.rem_euclid(TAU);
+
+    if angle == TAU { 0f64 } else { angle }
+}
+
+impl Arc {
+    pub fn new(center: &Point2D, radius: f64, start_angle: Angle, end_angle: Angle) -> Arc {
+        Arc {
+            center: center.clone(),
+            radius,
+            start: normalized(start_angle.to_radians()),
+            sweep: normalized(end_angle.to_radians() - start_angle.to_radians()),
+            style: Style { fill: Some(Paint::Solid(Color::NONE)), ..Style::default() },
+        }
+    }
+
+    /// the arc turning `sweep` clockwise from `start_angle`, which
+    /// unlike its end angle is kept as it is. a sweep of a whole turn
+    /// or more is taken a whole number of turns less.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Angle, Arc, Point};
+    ///
+    /// let center = Point::new(0.0, 0.0);
+    /// let arc = Arc::from_sweep(&center, 5.0, Angle::degrees(-90.0), Angle::degrees(200.0));
+    /// assert_eq!(Angle::degrees(200.0), arc.sweep());
+    /// assert!((arc.end_angle().to_degrees() - 110.0).abs() < 1e-12);
+    ///
+    /// ```
+    pub fn from_sweep(center: &Point2D, radius: f64, start_angle: Angle, sweep: Angle) -> Arc {
+        Arc { sweep: normalized(sweep.to_radians()), ..Arc::new(center, radius, start_angle, start_angle) }
+    }
+
+    /// starts building a styled arc, with nothing set
+    /// it builds the same arc as `new`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Angle, Arc, Color, Drawable, LineCap, Point, StyleBuilder};
+    ///
+    /// let (center, start, end) = (Point::new(0.0, 0.0), Angle::degrees(0.0), Angle::degrees(45.0));
+    /// assert_eq!(Arc::new(&center, 3.0, start, end), Arc::builder(&center, 3.0, start, end).build());
+    ///
+    /// let round = Arc::builder(&center, 3.0, start, end).stroke(Color::RED).line_cap(LineCap::Round).build();
+    /// assert!(Drawable::Arc(round).to_svg_tag().contains(" stroke=\"#ff0000\" stroke-linecap=\"round\""));
+    ///
+    /// ```
+    pub fn builder(center: &Point2D, radius: f64, start_angle: Angle, end_angle: Angle) -> ArcBuilder {
+        let arc = Arc::new(center, radius, start_angle, end_angle);
+        let style = arc.style.clone();
+
+        ArcBuilder { arc, style }
+    }
+
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// the same arc painted with `style`.
+    pub fn with_style(self, style: Style) -> Arc {
+        Arc { style, ..self }
+    }
+
+    /// the center of the circle the arc is part of.
+    pub fn center(&self) -> Point2D {
+        self.center.clone()
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn start_angle(&self) -> Angle {
+        Angle::radians(self.start)
+    }
+
+    pub fn end_angle(&self) -> Angle {
+        Angle::radians(normalized(self.start + self.sweep))
+    }
+
+    /// how far the arc turns from its start to its end.
+    pub fn sweep(&self) -> Angle {
+        Angle::radians(self.sweep)
+    }
+
+    fn point_at(&self, angle: f64) -> Point2D {
+        self.center.clone() + Point2D::from_polar(self.radius, angle)
+    }
+
+    pub fn start(&self) -> Point2D {
+        self.point_at(self.start)
+    }
+
+    pub fn end(&self) -> Point2D {
+        self.point_at(self.start + self.sweep)
+    }
+
+    /// the point halfway along the arc.
+    pub fn midpoint(&self) -> Point2D {
+        self.point_at(self.start + self.sweep / 2f64)
+    }
+
+    /// the length along the arc.
+    pub fn len(&self) -> f64 {
+        self.radius * self.sweep
+    }
+
+    /// whether the arc runs through the direction `angle` from its center.
+    pub fn contains_angle(&self, angle: Angle) -> bool {
+        normalized(angle.to_radians() - self.start) <= self.sweep
+    }
+
+    /// the shortest distance from `point` to the arc, to its nearer end
+    /// when `point` isn't in the directions it runs through.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Angle, Arc, Point};
+    ///
+    /// let arc = Arc::new(&Point::new(0.0, 0.0), 10.0, Angle::degrees(0.0), Angle::degrees(90.0));
+    /// assert_eq!(5.0, arc.distance_to(&Point::new(15.0, 0.0)));
+    /// assert_eq!(5.0, arc.distance_to(&Point::new(0.0, 5.0)));
+    /// assert_eq!(5.0, arc.distance_to(&Point::new(10.0, -5.0)));
+    ///
+    /// ```
+    pub fn distance_to(&self, point: &Point2D) -> f64 {
+        let (len, angle) = (point.clone() - self.center.clone()).to_polar();
+        if self.contains_angle(Angle::radians(angle)) {
+            return (len - self.radius).abs();
+        }
+
+        (point.clone() - self.start()).len().min((point.clone() - self.end()).len())
+    }
+
+    /// the `d` attribute of the `<path>` the arc is written as. the large arc
+    /// flag is set past half a turn, and the sweep flag always is, as SVG
+    /// turns the same way the arc's angles grow.
+    pub fn path_data(&self) -> String {
+        let (start, end) = (self.start(), self.end());
+        let large_arc = if self.sweep > PI { 1 } else { 0 };
+
+        format!("M {} {} A {} {} 0 {} 1 {} {}", start.x, start.y, self.radius, self.radius, large_arc, end.x, end.y)
+    }
+}
+
+impl Draw for Arc {
+    fn get_svg_tag_name() -> &'static str {
+        "path"
+    }
+
+    fn write_svg_attrs(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        write_attr(out, "d", self.path_data())?;
+        self.style.write_fill_attr(out)?;
+        self.style.write_stroke_attrs(out)
+    }
+
+    /// the box of the ends and of the points where the arc is furthest
+    /// along an axis, grown by the stroke.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Aabb, Angle, Arc, Drawable, Point};
+    ///
+    /// let center = Point::new(0.0, 0.0);
+    /// let bounds = |start: f64, end: f64| Drawable::Arc(Arc::new(&center, 10.0, Angle::degrees(start), Angle::degrees(end))).bounding_box();
+    ///
+    /// // over 0°, the arc reaches out to x = 10 between its ends
+    /// let wrapped = bounds(315.0, 45.0);
+    /// assert!((wrapped.max().x - 10.0).abs() < 1e-12 && (wrapped.min().x - 50f64.sqrt()).abs() < 1e-12);
+    ///
+    /// // three quarters of a turn reach every way but up and right
+    /// let large = bounds(90.0, 0.0);
+    /// assert!(large.min().approx_eq(&Point::new(-10.0, -10.0), 1e-12));
+    /// assert!(large.max().approx_eq(&Point::new(10.0, 10.0), 1e-12));
+    /// assert!(bounds(0.0, 90.0).min().approx_eq(&Point::new(0.0, 0.0), 1e-12));
+    ///
+    /// ```
+    fn bounding_box(&self) -> Aabb {
+        let quadrants = (0..4).map(|i| f64::from(i) * FRAC_PI_2).filter(|&angle| self.contains_angle(Angle::radians(angle)));
+        let points: Vec<Point2D> = [self.start(), self.end()].into_iter().chain(quadrants.map(|angle| self.point_at(angle))).collect();
+
+        // square caps reach half the stroke past the ends, along the arc
+        let half = self.style.painted_stroke_width() / 2f64;
+        let reach = if self.style.line_cap == LineCap::Square { half * SQRT_2 } else { half };
+
+        Aabb::from_points(points.iter()).expect("an arc has ends").expand(reach)
+    }
+
+    /// within the tolerance of the painted stroke, like a line.
+    fn contains(&self, point: &Point2D) -> bool {
+        self.distance_to(point) <= self.style.painted_stroke_width() / 2f64 + HIT_TOLERANCE
+    }
+
+    fn translate(&mut self, offset: &Point2D) {
+        self.center = Point2D::new(self.center.x + offset.x, self.center.y + offset.y);
+    }
+
+    /// the ends are moved by the transform and the radius is scaled like a
+    /// circle's. a transform that mirrors the arc turns it the other way,
+    /// so its ends are swapped.
+    fn apply_transform(&mut self, transform: &Transform2D) {
+        let direction = |angle: f64| transform.apply_vector(&Point2D::from_polar(1f64, angle)).to_polar().1;
+        let (mut start, mut end) = (direction(self.start), direction(self.start + self.sweep));
+        let (x, y) = (transform.apply_vector(&Point2D::new(1f64, 0f64)), transform.apply_vector(&Point2D::new(0f64, 1f64)));
+        if x.cross(&y) < 0f64 {
+            (start, end) = (end, start);
+        }
+
+        self.center = transform.apply(&self.center);
+        self.radius *= transform.scale_factor();
+        self.start = normalized(start);
+
+        // rounding can take a sweep of almost a whole turn, or of almost none, across
+        // the wrap to the other, where it's kept as it was
+        let sweep = normalized(end - start);
+        if (sweep - self.sweep).abs() < PI {
+            self.sweep = sweep;
+        }
+    }
+}
+
+/// builds an `Arc` with its style, see `Arc::builder`.
+#[derive(Clone, Debug)]
+pub struct ArcBuilder {
+    arc: Arc,
+    style: Style,
+}
+
+impl ArcBuilder {
+    /// how the ends of the arc are painted.
+    pub fn line_cap(mut self, line_cap: LineCap) -> Self {
+        self.style.line_cap = line_cap;
+        self
+    }
+
+    pub fn build(self) -> Arc {
+        self.arc.with_style(self.style)
+    }
+}
+
+impl StyleBuilder for ArcBuilder {
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+}
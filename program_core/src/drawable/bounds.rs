@@ -0,0 +1,123 @@
+use super::point2d::Point2D;
+
+/// # Aabb
+/// structure to hold an axis-aligned bounding box.
+/// it stores the corner with the smallest coordinates and the one
+/// with the largest, whatever order the corners are given in.
+///
+/// # Examples
+/// ```
+/// use program_core::{Aabb, Point};
+///
+/// let aabb = Aabb::new(&Point::new(10.0, 0.0), &Point::new(0.0, 10.0));
+///
+/// assert_eq!(Point::new(0.0, 0.0), aabb.min());
+/// assert_eq!(Point::new(10.0, 10.0), aabb.max());
+/// assert!(aabb.contains(&Point::new(5.0, 5.0)));
+/// assert!(!aabb.contains(&Point::new(11.0, 5.0)));
+///
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Aabb {
+    min: Point2D,
+    max: Point2D,
+}
+
+impl Aabb {
+    pub fn new(a: &Point2D, b: &Point2D) -> Aabb {
+        Aabb { min: Point2D::min(a, b), max: Point2D::max(a, b) }
+    }
+
+    /// the smallest box containing every point, `None` when there are none.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Aabb, Point};
+    ///
+    /// let points = [Point::new(3.0, -1.0), Point::new(-2.0, 4.0), Point::new(0.0, 0.0)];
+    /// let bounds = Aabb::from_points(points.iter()).unwrap();
+    ///
+    /// assert_eq!(Aabb::new(&Point::new(-2.0, -1.0), &Point::new(3.0, 4.0)), bounds);
+    /// assert_eq!((5.0, 5.0), (bounds.width(), bounds.height()));
+    /// assert_eq!(Point::new(0.5, 1.5), bounds.center());
+    /// assert_eq!(None, Aabb::from_points([].iter()));
+    ///
+    /// ```
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Point2D>) -> Option<Aabb> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Aabb::new(first, first), |bounds, point| Aabb {
+            min: Point2D::min(&bounds.min, point),
+            max: Point2D::max(&bounds.max, point),
+        }))
+    }
+
+    pub fn min(&self) -> Point2D {
+        self.min.clone()
+    }
+
+    pub fn max(&self) -> Point2D {
+        self.max.clone()
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+
+    pub fn center(&self) -> Point2D {
+        Point2D::lerp(&self.min, &self.max, 0.5)
+    }
+
+    pub fn contains(&self, point: &Point2D) -> bool {
+        self.min.x <= point.x && point.x <= self.max.x
+            && self.min.y <= point.y && point.y <= self.max.y
+    }
+
+    /// returns a box grown by `margin` on every side.
+    pub fn expand(&self, margin: f64) -> Aabb {
+        Aabb {
+            min: Point2D::new(self.min.x - margin, self.min.y - margin),
+            max: Point2D::new(self.max.x + margin, self.max.y + margin),
+        }
+    }
+
+    /// returns the smallest box containing both boxes.
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb { min: Point2D::min(&self.min, &other.min), max: Point2D::max(&self.max, &other.max) }
+    }
+
+    /// the box where the two boxes overlap, `None` when they don't.
+    /// boxes that only touch overlap in a box with no width or height.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Aabb, Point};
+    ///
+    /// let a = Aabb::new(&Point::new(0.0, 0.0), &Point::new(10.0, 10.0));
+    /// let b = Aabb::new(&Point::new(5.0, -5.0), &Point::new(20.0, 5.0));
+    /// assert_eq!(Some(Aabb::new(&Point::new(5.0, 0.0), &Point::new(10.0, 5.0))), a.intersection(&b));
+    ///
+    /// let touching = Aabb::new(&Point::new(10.0, 0.0), &Point::new(20.0, 10.0));
+    /// assert_eq!(Some(0.0), a.intersection(&touching).map(|overlap| overlap.width()));
+    /// assert_eq!(None, a.intersection(&Aabb::new(&Point::new(11.0, 0.0), &Point::new(20.0, 10.0))));
+    ///
+    /// ```
+    pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+        if !self.intersects(other) {
+            return None;
+        }
+
+        Some(Aabb { min: Point2D::max(&self.min, &other.min), max: Point2D::min(&self.max, &other.max) })
+    }
+
+    /// checks whether the two boxes overlap, touching edges count.
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x
+            && self.min.y <= other.max.y && other.min.y <= self.max.y
+    }
+}
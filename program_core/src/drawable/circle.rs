@@ -1,7 +1,11 @@
 use super::point2d::Point2D;
-use super::Draw;
+use super::bounds::Aabb;
+use super::line2d::{Line2D, PARALLEL_TOLERANCE};
+use super::style::{Paint, StrokeAlign, Style, StyleBuilder};
+use super::transform::Transform2D;
+use super::{write_attr, Draw, EDGE_TOLERANCE, HIT_TOLERANCE};
 use std::f64::consts::PI;
-use std::collections::HashMap;
+use std::fmt;
 
 /// # Circle
 /// structure to hold circles in 2d carteian space
@@ -23,12 +27,13 @@ use std::collections::HashMap;
 /// assert_eq!(PI * 25f64, circle.area());
 ///
 /// ```
+#[derive(PartialEq, Clone, Debug)]
 pub struct Circle {
     center: Point2D,
     radius: f64,
     circumference: f64,
     area: f64,
-
+    style: Style,
 }
 
 impl Circle {
@@ -37,14 +42,101 @@ impl Circle {
             center: center.clone(),
             radius,
             circumference: 2f64 * PI * radius,
-            area: PI * radius.powi(2)
+            area: PI * radius.powi(2),
+            style: Style::default(),
+        }
+    }
+
+    /// starts building a styled circle, with nothing set
+    /// it builds the same circle as `new`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Point, StyleBuilder};
+    ///
+    /// let center = Point::new(1.0, 2.0);
+    /// assert_eq!(Circle::new(&center, 3.0), Circle::builder(&center, 3.0).build());
+    ///
+    /// ```
+    pub fn builder(center: &Point2D, radius: f64) -> CircleBuilder {
+        CircleBuilder { center: center.clone(), radius, style: Style::default() }
+    }
+
+    /// the circle running through `a`, `b` and `c`, centered where the
+    /// perpendicular bisectors between them meet. `None` when the points are
+    /// on one line, within `PARALLEL_TOLERANCE` like `Line::is_parallel_to`,
+    /// or two of them are the same point.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Point};
+    ///
+    /// let (a, b, c) = (Point::new(10.0, 0.0), Point::new(0.0, 10.0), Point::new(-10.0, 0.0));
+    /// let circle = Circle::from_three_points(&a, &b, &c).unwrap();
+    /// assert_eq!(Point::new(0.0, 0.0), circle.center());
+    /// assert_eq!(10.0, circle.radius());
+    ///
+    /// // nearly on one line the circle is huge, but still runs through all three
+    /// let (a, b, c) = (Point::new(0.0, 0.0), Point::new(50.0, 0.001), Point::new(100.0, 0.0));
+    /// let circle = Circle::from_three_points(&a, &b, &c).unwrap();
+    /// assert!((circle.radius() - 1_250_000.0005).abs() < 1e-3);
+    /// for point in [a, b, c] {
+    ///     assert!(((point - circle.center()).len() - circle.radius()).abs() < 1e-6);
+    /// }
+    ///
+    /// let on_a_line = [Point::new(0.0, 0.0), Point::new(1.0, 1.0), Point::new(2.0, 2.0)];
+    /// assert_eq!(None, Circle::from_three_points(&on_a_line[0], &on_a_line[1], &on_a_line[2]));
+    /// let barely_off = Point::new(50.0, 1e-12);
+    /// assert_eq!(None, Circle::from_three_points(&Point::new(0.0, 0.0), &barely_off, &Point::new(100.0, 0.0)));
+    /// assert_eq!(None, Circle::from_three_points(&on_a_line[0], &on_a_line[0], &on_a_line[2]));
+    ///
+    /// ```
+    pub fn from_three_points(a: &Point2D, b: &Point2D, c: &Point2D) -> Option<Circle> {
+        let (u, v) = (b.clone() - a.clone(), c.clone() - a.clone());
+        let cross = u.cross(&v);
+        if cross.abs() <= PARALLEL_TOLERANCE * u.len() * v.len() {
+            return None;
         }
+
+        // the center relative to `a`, solving |center - a| = |center - b| = |center - c|
+        let (u_squared, v_squared) = (u.dot(&u), v.dot(&v));
+        let offset = Point2D::new(v.y * u_squared - u.y * v_squared, u.x * v_squared - v.x * u_squared) * (0.5 / cross);
+        let center = a.clone() + offset.clone();
+
+        Some(Circle::new(&center, offset.len()))
+    }
+
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// the same circle painted with `style`.
+    pub fn with_style(self, style: Style) -> Circle {
+        Circle { style, ..self }
     }
     
     pub fn center(&self) -> Point2D {
         self.center.clone()
     }
 
+    /// mirrors the circle across the line through `line_point` running along
+    /// `line_dir`, see `Point::reflect_across`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Point};
+    ///
+    /// let mut circle = Circle::new(&Point::new(5.0, 2.0), 1.0);
+    /// circle.mirror(&Point::new(0.0, 0.0), &Point::new(0.0, 1.0));
+    ///
+    /// assert_eq!(Point::new(-5.0, 2.0), circle.center());
+    /// assert_eq!(1.0, circle.radius());
+    ///
+    /// ```
+    pub fn mirror(&mut self, line_point: &Point2D, line_dir: &Point2D) {
+        self.center = self.center.reflect_across(line_point, line_dir);
+    }
+
     pub fn radius(&self) -> f64 {
         self.radius 
     }
@@ -57,21 +149,283 @@ impl Circle {
         self.area
     }
 
+    /// the radius written to SVG, moved so the stroke is painted as `stroke_align` asks.
+    pub(crate) fn outline_radius(&self) -> f64 {
+        (self.radius + self.style.outline_offset()).max(0f64)
+    }
+
+    /// how far from the center paint reaches, stroke included.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Color, Point, StrokeAlign, StyleBuilder};
+    ///
+    /// let stroked = |align| Circle::builder(&Point::new(0.0, 0.0), 10.0).stroke(Color::BLACK).stroke_width(4.0).stroke_align(align).build();
+    ///
+    /// assert_eq!(10.0, Circle::new(&Point::new(0.0, 0.0), 10.0).painted_radius());
+    /// assert_eq!(12.0, stroked(StrokeAlign::Center).painted_radius());
+    /// assert_eq!(10.0, stroked(StrokeAlign::Inside).painted_radius());
+    /// assert_eq!(14.0, stroked(StrokeAlign::Outside).painted_radius());
+    ///
+    /// ```
+    pub fn painted_radius(&self) -> f64 {
+        self.outline_radius() + self.style.painted_stroke_width() / 2f64
+    }
+
+    /// whether the inside of the circle is painted and can be clicked. it is
+    /// unless its fill is fully transparent, as SVG fills it black by default.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Color, Point, StyleBuilder};
+    ///
+    /// let center = Point::new(0.0, 0.0);
+    /// assert!(Circle::new(&center, 1.0).is_filled());
+    /// assert!(Circle::builder(&center, 1.0).fill(Color::RED).build().is_filled());
+    /// assert!(!Circle::builder(&center, 1.0).fill_none().build().is_filled());
+    /// assert!(!Circle::builder(&center, 1.0).fill(Color::rgba(255, 0, 0, 0)).build().is_filled());
+    ///
+    /// ```
+    pub fn is_filled(&self) -> bool {
+        self.style.fill.as_ref().is_none_or(Paint::is_visible)
+    }
+
+    /// where `line` crosses the circle, in the order it reaches them from its
+    /// start: two points when it cuts through, one when it touches the circle or
+    /// has only one end inside, and none when it misses or is all inside or outside.
+    /// a line that passes within rounding of touching touches, and a line
+    /// with no length meets nothing, like `Line::intersect`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Line, Point};
+    ///
+    /// let circle = Circle::new(&Point::new(0.0, 0.0), 5.0);
+    /// let line = |x1, y1, x2, y2| Line::new(&Point::new(x1, y1), &Point::new(x2, y2));
+    ///
+    /// // in and back out
+    /// assert_eq!(vec![Point::new(-5.0, 0.0), Point::new(5.0, 0.0)], circle.intersect_line(&line(-10.0, 0.0, 10.0, 0.0)));
+    /// assert_eq!(vec![Point::new(4.0, 3.0), Point::new(-4.0, 3.0)], circle.intersect_line(&line(10.0, 3.0, -10.0, 3.0)));
+    /// // touching
+    /// assert_eq!(vec![Point::new(0.0, 5.0)], circle.intersect_line(&line(-10.0, 5.0, 10.0, 5.0)));
+    /// // from inside out
+    /// assert_eq!(vec![Point::new(5.0, 0.0)], circle.intersect_line(&line(0.0, 0.0, 10.0, 0.0)));
+    ///
+    /// // the circle is crossed before or after the line, or the line is all inside
+    /// assert!(circle.intersect_line(&line(6.0, 0.0, 10.0, 0.0)).is_empty());
+    /// assert!(circle.intersect_line(&line(-3.0, 0.0, 3.0, 0.0)).is_empty());
+    /// assert!(circle.intersect_line(&line(-10.0, 6.0, 10.0, 6.0)).is_empty());
+    /// assert!(circle.intersect_line(&line(5.0, 0.0, 5.0, 0.0)).is_empty());
+    ///
+    /// ```
+    pub fn intersect_line(&self, line: &Line2D) -> Vec<Point2D> {
+        let within = |t: f64| (-PARALLEL_TOLERANCE..=1f64 + PARALLEL_TOLERANCE).contains(&t);
+
+        self.crossings(line).into_iter().filter(|(t, _)| within(*t)).map(|(_, point)| point).collect()
+    }
+
+    /// where `line` extended past its ends forever crosses the circle,
+    /// see `intersect_line`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Line, Point};
+    ///
+    /// let circle = Circle::new(&Point::new(0.0, 0.0), 5.0);
+    /// let beyond = Line::new(&Point::new(6.0, 0.0), &Point::new(10.0, 0.0));
+    /// assert_eq!(vec![Point::new(-5.0, 0.0), Point::new(5.0, 0.0)], circle.intersect_infinite_line(&beyond));
+    ///
+    /// let touching = Line::new(&Point::new(5.0, 10.0), &Point::new(5.0, 20.0));
+    /// assert_eq!(vec![Point::new(5.0, 0.0)], circle.intersect_infinite_line(&touching));
+    /// assert!(circle.intersect_infinite_line(&Line::new(&Point::new(5.0, 0.0), &Point::new(5.0, 0.0))).is_empty());
+    ///
+    /// ```
+    pub fn intersect_infinite_line(&self, line: &Line2D) -> Vec<Point2D> {
+        self.crossings(line).into_iter().map(|(_, point)| point).collect()
+    }
+
+    /// where the line through `line` crosses the circle, with how far along
+    /// `line` each crossing is as `t` of `Line::point_at`.
+    fn crossings(&self, line: &Line2D) -> Vec<(f64, Point2D)> {
+        let (start, direction) = (line.start(), line.end() - line.start());
+        let len = direction.len();
+        if len == 0f64 {
+            return Vec::new();
+        }
+
+        // the point of the line nearest the center, and how far that is from the rim along the line
+        let along = direction.clone() * (1f64 / len);
+        let t = (self.center.clone() - start.clone()).dot(&along);
+        let nearest = start + along.clone() * t;
+        let away = (self.center.clone() - nearest.clone()).len();
+        let squared = self.radius.powi(2) - away.powi(2);
+
+        if squared < -PARALLEL_TOLERANCE * self.radius.powi(2) {
+            Vec::new()
+        } else if squared <= PARALLEL_TOLERANCE * self.radius.powi(2) {
+            vec![(t / len, nearest)]
+        } else {
+            let half = squared.sqrt();
+            vec![((t - half) / len, nearest.clone() - along.clone() * half), ((t + half) / len, nearest + along * half)]
+        }
+    }
+
+    /// where the circle's rim meets `other`'s. circles that pass within
+    /// rounding of touching touch, and ones with the same center meet
+    /// nowhere unless they're the same circle.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, CircleIntersection, Point};
+    ///
+    /// let circle = |x, y, radius| Circle::new(&Point::new(x, y), radius);
+    /// let a = circle(0.0, 0.0, 5.0);
+    ///
+    /// assert_eq!(CircleIntersection::Two(Point::new(4.0, -3.0), Point::new(4.0, 3.0)), a.intersect_circle(&circle(8.0, 0.0, 5.0)));
+    /// assert_eq!(CircleIntersection::Two(Point::new(4.0, 3.0), Point::new(4.0, -3.0)), circle(8.0, 0.0, 5.0).intersect_circle(&a));
+    /// assert_eq!(CircleIntersection::Two(Point::new(3.0, 4.0), Point::new(-3.0, 4.0)), a.intersect_circle(&circle(0.0, 8.0, 5.0)));
+    ///
+    /// // touching from outside, and from inside either way around
+    /// assert_eq!(CircleIntersection::Tangent(Point::new(5.0, 0.0)), a.intersect_circle(&circle(8.0, 0.0, 3.0)));
+    /// assert_eq!(CircleIntersection::Tangent(Point::new(5.0, 0.0)), a.intersect_circle(&circle(2.0, 0.0, 3.0)));
+    /// assert_eq!(CircleIntersection::Tangent(Point::new(-5.0, 0.0)), a.intersect_circle(&circle(3.0, 0.0, 8.0)));
+    ///
+    /// // apart, one inside the other, and around the same center
+    /// assert_eq!(CircleIntersection::None, a.intersect_circle(&circle(20.0, 0.0, 5.0)));
+    /// assert_eq!(CircleIntersection::None, a.intersect_circle(&circle(1.0, 0.0, 2.0)));
+    /// assert_eq!(CircleIntersection::None, circle(1.0, 0.0, 2.0).intersect_circle(&a));
+    /// assert_eq!(CircleIntersection::None, a.intersect_circle(&circle(0.0, 0.0, 3.0)));
+    /// assert_eq!(CircleIntersection::Coincident, a.intersect_circle(&circle(0.0, 0.0, 5.0)));
+    ///
+    /// ```
+    pub fn intersect_circle(&self, other: &Circle) -> CircleIntersection {
+        let between = other.center.clone() - self.center.clone();
+        let distance = between.len();
+        let scale = self.radius.max(other.radius);
+        if distance <= PARALLEL_TOLERANCE * scale {
+            return if (self.radius - other.radius).abs() <= PARALLEL_TOLERANCE * scale {
+                CircleIntersection::Coincident
+            } else {
+                CircleIntersection::None
+            };
+        }
+
+        // how far towards the other center the crossings are, and how far off that line
+        let along = between * (1f64 / distance);
+        let a = (distance.powi(2) + self.radius.powi(2) - other.radius.powi(2)) / (2f64 * distance);
+        let squared = self.radius.powi(2) - a.powi(2);
+        let foot = self.center.clone() + along.clone() * a;
+
+        if squared < -PARALLEL_TOLERANCE * self.radius.powi(2) {
+            CircleIntersection::None
+        } else if squared <= PARALLEL_TOLERANCE * self.radius.powi(2) {
+            CircleIntersection::Tangent(foot)
+        } else {
+            let across = Point2D::new(-along.y, along.x) * squared.sqrt();
+            CircleIntersection::Two(foot.clone() - across.clone(), foot + across)
+        }
+    }
 }
 
 impl Draw for Circle {
-    fn get_svg_tag_name() -> String {
-        String::from("circle")
+    fn get_svg_tag_name() -> &'static str {
+        "circle"
     }
 
-    fn get_svg_tag_properties(&self) -> HashMap<String, String> {
-        let mut props = HashMap::new();
+    fn write_svg_attrs(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        write_attr(out, "cx", self.center.x())?;
+        write_attr(out, "cy", self.center.y())?;
+        self.style.write_fill_attr(out)?;
+        write_attr(out, "r", self.outline_radius())?;
+        self.style.write_stroke_attrs(out)
+    }
 
-        props.insert("cx".to_string(), self.center.x().to_string());
-        props.insert("cy".to_string(), self.center.y().to_string());
-        props.insert("r".to_string(), self.radius.to_string());
+    fn bounding_box(&self) -> Aabb {
+        let radius = self.painted_radius();
 
-        props
+        Aabb::new(
+            &Point2D::new(self.center.x - radius, self.center.y - radius),
+            &Point2D::new(self.center.x + radius, self.center.y + radius),
+        )
     }
 
+    /// anywhere within a filled circle, but only on the band of the stroke
+    /// of an unfilled one, within the tolerance of its painted stroke like a line.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Color, Drawable, Point, StyleBuilder};
+    ///
+    /// let builder = || Circle::builder(&Point::new(0.0, 0.0), 10.0).stroke(Color::BLACK).stroke_width(4.0);
+    /// let (filled, ring) = (Drawable::Circle(builder().build()), Drawable::Circle(builder().fill_none().build()));
+    ///
+    /// assert!(filled.contains(&Point::new(0.0, 0.0)));
+    /// assert!(filled.contains(&Point::new(12.0, 0.0)));
+    /// assert!(!filled.contains(&Point::new(12.1, 0.0)));
+    ///
+    /// // the band runs 2 on each side of the rim for the stroke, and 3 more for the tolerance
+    /// assert!(!ring.contains(&Point::new(0.0, 0.0)));
+    /// assert!(ring.contains(&Point::new(10.0, 0.0)) && ring.contains(&Point::new(0.0, -10.0)));
+    /// assert!(ring.contains(&Point::new(5.1, 0.0)) && ring.contains(&Point::new(14.9, 0.0)));
+    /// assert!(!ring.contains(&Point::new(4.9, 0.0)) && !ring.contains(&Point::new(15.1, 0.0)));
+    ///
+    /// ```
+    fn contains(&self, point: &Point2D) -> bool {
+        let distance = ((point.x - self.center.x).powi(2) + (point.y - self.center.y).powi(2)).sqrt();
+        if self.is_filled() {
+            return distance <= self.painted_radius() + EDGE_TOLERANCE;
+        }
+
+        (distance - self.outline_radius()).abs() <= self.style.painted_stroke_width() / 2f64 + HIT_TOLERANCE
+    }
+
+    fn translate(&mut self, offset: &Point2D) {
+        self.center = Point2D::new(self.center.x + offset.x, self.center.y + offset.y);
+    }
+
+    fn apply_transform(&mut self, transform: &Transform2D) {
+        self.center = transform.apply(&self.center);
+        self.radius *= transform.scale_factor();
+    }
+}
+
+/// where two circles meet, see `Circle::intersect_circle`.
+#[derive(PartialEq, Clone, Debug)]
+pub enum CircleIntersection {
+    /// they're apart, or one is inside the other.
+    None,
+    /// they touch at one point, from outside or from inside.
+    Tangent(Point2D),
+    /// they cross at two points. seen from the first circle's center, the first
+    /// point is counter-clockwise of the other center and the second clockwise,
+    /// angles growing clockwise as the y axis points down.
+    Two(Point2D, Point2D),
+    /// they're the same circle, so they meet all around.
+    Coincident,
+}
+
+/// builds a `Circle` with its style, see `Circle::builder`.
+#[derive(Clone, Debug)]
+pub struct CircleBuilder {
+    center: Point2D,
+    radius: f64,
+    style: Style,
+}
+
+impl CircleBuilder {
+    /// paints the stroke inside, outside, or centered on the outline.
+    pub fn stroke_align(mut self, align: StrokeAlign) -> Self {
+        self.style.stroke_align = align;
+        self
+    }
+
+    pub fn build(self) -> Circle {
+        Circle::new(&self.center, self.radius).with_style(self.style)
+    }
+}
+
+impl StyleBuilder for CircleBuilder {
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
 }
@@ -0,0 +1,43 @@
+//! # legacy_svg
+//! builds SVG tags the way they were built before `Draw::write_svg_attrs`,
+//! with a HashMap and a String for every attribute, sorted, then formatted.
+//! only kept so the svg_export example can time both ways.
+
+use super::{escape, Draw};
+use std::collections::HashMap;
+
+/// the attributes of the tag of `drawable`, by name.
+fn get_svg_tag_properties(drawable: &impl Draw) -> HashMap<String, String> {
+    let mut written = String::new();
+    drawable.write_svg_attrs(&mut written).expect("writing to a String can't fail");
+
+    // each attribute is written as ` name="value"`, and no value holds a quote
+    let mut props = HashMap::new();
+    let mut rest = written.as_str();
+    while let Some((name, after)) = rest.strip_prefix(' ').and_then(|rest| rest.split_once("=\"")) {
+        let (value, after) = after.split_once('"').expect("attribute values are closed by a quote");
+        props.insert(name.to_string(), value.to_string());
+        rest = after;
+    }
+
+    props
+}
+
+/// builds the complete SVG tag of the drawable.
+/// properties are sorted by name so the output is stable.
+pub fn to_svg_tag<D: Draw>(drawable: &D) -> String {
+    let mut props: Vec<(String, String)> = get_svg_tag_properties(drawable).into_iter().collect();
+    props.sort();
+
+    let mut tag = format!("<{}", D::get_svg_tag_name());
+    for (key, value) in props {
+        tag.push_str(&format!(" {}=\"{}\"", key, escape(&value)));
+    }
+
+    match drawable.get_svg_tag_content() {
+        Some(content) => tag.push_str(&format!(">{}</{}>", escape(content), D::get_svg_tag_name())),
+        None => tag.push_str(" />"),
+    }
+
+    tag
+}
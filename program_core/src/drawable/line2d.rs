@@ -1,10 +1,19 @@
 use super::point2d::Point2D;
-use super::Draw;
-use std::collections::HashMap;
+use super::bounds::Aabb;
+use super::style::{LineCap, LineJoin, StrokeStyle, Style, StyleBuilder};
+use super::transform::Transform2D;
+use super::{write_attr, Draw, HIT_TOLERANCE};
+use std::fmt;
+
+/// lines closer than this to parallel, as the sine of the angle between
+/// them, are parallel to `Line::intersect` and `Line::is_parallel_to`.
+/// it's also how close to 0 the cosine must be for `Line::is_perpendicular_to`.
+pub const PARALLEL_TOLERANCE: f64 = 1e-12;
 
 /// # Line2D
 /// structure to hold lines in 2d cartesian space
-/// it stores starting point, ending point, length, and angle in radians.
+/// it stores starting point and ending point, its length and angle
+/// are worked out from them, so they can't fall out of step.
 ///
 /// # Examples
 /// ```
@@ -25,8 +34,7 @@ use std::collections::HashMap;
 pub struct Line2D {
     start: Point2D,
     end: Point2D,
-    len: f64,
-    angle: f64,
+    style: Style,
 }
 
 impl Line2D {
@@ -34,11 +42,181 @@ impl Line2D {
         Line2D {
             start: start.clone(),
             end: end.clone(),
-            len: ((start.x - end.x).powi(2) + (start.y - end.y).powi(2)).sqrt(),
-            angle: ((start.y - end.y) / (start.x - end.x)).atan(),
+            style: Style::default(),
         }
     }
 
+    /// the line from `start` that's `len` long at `angle` radians,
+    /// see `Point::from_polar`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use program_core::{Line, Point};
+    ///
+    /// let line = Line::from_polar(&Point::new(5.0, 5.0), 10.0, PI);
+    /// assert_eq!(Point::new(5.0, 5.0), line.start());
+    /// assert!(line.end().approx_eq(&Point::new(-5.0, 5.0), 1e-12));
+    /// assert_eq!(10.0, line.len());
+    ///
+    /// ```
+    pub fn from_polar(start: &Point2D, len: f64, angle: f64) -> Line2D {
+        Line2D::new(start, &(start.clone() + Point2D::from_polar(len, angle)))
+    }
+
+    /// starts building a styled line, with nothing set
+    /// it builds the same line as `new`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Color, Drawable, Line, Paint, Point, StyleBuilder};
+    ///
+    /// let (start, end) = (Point::new(0.0, 0.0), Point::new(3.0, 4.0));
+    /// assert_eq!(Line::new(&start, &end), Line::builder(&start, &end).build());
+    ///
+    /// let red = Line::builder(&start, &end).stroke(Color::RED).stroke_width(2.0).build();
+    /// assert_eq!(Some(Paint::Solid(Color::RED)), red.style().stroke);
+    /// assert_eq!(5.0, red.len());
+    /// assert_eq!(
+    ///     "<line stroke=\"#ff0000\" stroke-width=\"2\" x1=\"0\" x2=\"3\" y1=\"0\" y2=\"4\" />",
+    ///     Drawable::Line(red).to_svg_tag(),
+    /// );
+    ///
+    /// ```
+    pub fn builder(start: &Point2D, end: &Point2D) -> LineBuilder {
+        LineBuilder { start: start.clone(), end: end.clone(), style: Style::default() }
+    }
+
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// the same line painted with `style`.
+    pub fn with_style(self, style: Style) -> Line2D {
+        Line2D { style, ..self }
+    }
+
+    /// dashes the stroke of the line, or makes it solid again.
+    pub fn set_stroke_style(&mut self, stroke_style: StrokeStyle) {
+        self.style.stroke_style = stroke_style;
+    }
+
+    /// changes how the ends of the stroke are painted.
+    pub fn set_line_cap(&mut self, line_cap: LineCap) {
+        self.style.line_cap = line_cap;
+    }
+
+    /// changes how the corners of the stroke are painted.
+    pub fn set_line_join(&mut self, line_join: LineJoin) {
+        self.style.line_join = line_join;
+    }
+
+    /// swaps the ends of the line, so it runs the other way, half a turn
+    /// from its angle, and is as long as it was.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use program_core::{Line, Point};
+    ///
+    /// let line = Line::new(&Point::new(0.0, 0.0), &Point::new(30.0, 40.0));
+    /// let mut reversed = line.clone();
+    /// reversed.reverse();
+    ///
+    /// assert_eq!((line.end(), line.start()), (reversed.start(), reversed.end()));
+    /// assert_eq!(line.len(), reversed.len());
+    /// assert!((line.angle() - PI - reversed.angle()).abs() < 1e-12);
+    /// assert_eq!(line.point_at(0.75), reversed.point_at(0.25));
+    /// assert_eq!(&line, reversed.reverse());
+    ///
+    /// ```
+    pub fn reverse(&mut self) -> &mut Self {
+        std::mem::swap(&mut self.start, &mut self.end);
+
+        self
+    }
+
+    /// moves the start of the line.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_4;
+    /// use program_core::{Line, Point};
+    ///
+    /// let mut line = Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0));
+    /// line.set_start(&Point::new(7.0, -4.0));
+    /// assert_eq!((5.0, (4.0f64 / 3.0).atan()), (line.len(), line.angle()));
+    ///
+    /// line.set_end(&Point::new(9.0, -2.0));
+    /// assert_eq!(Point::new(7.0, -4.0), line.start());
+    /// assert!((line.len() - 8f64.sqrt()).abs() < 1e-12);
+    /// assert_eq!(FRAC_PI_4, line.angle());
+    ///
+    /// // the end moves along the line, or back past the start for a negative length
+    /// line.set_len(2f64.sqrt());
+    /// assert!(line.end().approx_eq(&Point::new(8.0, -3.0), 1e-12));
+    /// assert!((line.len() - 2f64.sqrt()).abs() < 1e-12);
+    /// assert_eq!(FRAC_PI_4, line.angle());
+    /// line.set_len(-2f64.sqrt());
+    /// assert!(line.end().approx_eq(&Point::new(6.0, -5.0), 1e-12));
+    ///
+    /// // a line with no length has no direction to grow along
+    /// let mut dot = Line::new(&Point::new(1.0, 1.0), &Point::new(1.0, 1.0));
+    /// dot.set_len(5.0);
+    /// assert_eq!((Point::new(1.0, 1.0), 0.0), (dot.end(), dot.len()));
+    ///
+    /// ```
+    pub fn set_start(&mut self, start: &Point2D) {
+        self.start = start.clone();
+    }
+
+    /// moves the end of the line.
+    pub fn set_end(&mut self, end: &Point2D) {
+        self.end = end.clone();
+    }
+
+    /// moves the end along the line so it's `len` long, a negative `len`
+    /// putting it the other side of the start. a line with no length stays as it is.
+    pub fn set_len(&mut self, len: f64) {
+        let end = self.start.clone() + self.direction() * len;
+
+        self.set_end(&end);
+    }
+
+    /// the line made `len` long, like `set_len`.
+    pub fn with_len(mut self, len: f64) -> Line2D {
+        self.set_len(len);
+
+        self
+    }
+
+    /// moves the end along the line by `delta`, making the line longer, or
+    /// shorter when `delta` is negative. trimming by more than the length
+    /// carries on past the start, flipping the line like `set_len` with a
+    /// negative length, rather than stopping at a line without a length.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// let mut line = Line::new(&Point::new(0.0, 0.0), &Point::new(30.0, 40.0));
+    /// line.extend_by(50.0);
+    /// assert_eq!((Point::new(60.0, 80.0), 100.0), (line.end(), line.len()));
+    /// line.extend_by(-75.0);
+    /// assert_eq!((Point::new(15.0, 20.0), 25.0), (line.end(), line.len()));
+    ///
+    /// // trimming past the start flips the line
+    /// line.extend_by(-35.0);
+    /// assert_eq!((Point::new(-6.0, -8.0), 10.0), (line.end(), line.len()));
+    ///
+    /// let line = Line::new(&Point::new(5.0, 5.0), &Point::new(5.0, 10.0)).with_len(100.0);
+    /// assert_eq!((Point::new(5.0, 105.0), 100.0), (line.end(), line.len()));
+    ///
+    /// ```
+    pub fn extend_by(&mut self, delta: f64) {
+        self.set_len(self.len() + delta);
+    }
+
     pub fn start(&self) -> Point2D {
         self.start.clone()
     }
@@ -46,26 +224,621 @@ impl Line2D {
         self.end.clone()
     }
     pub fn len(&self) -> f64 {
-        self.len
+        (self.end.x - self.start.x).hypot(self.end.y - self.start.y)
     }
+
+    /// the angle in radians the line runs at from its start, between -π and π
+    /// like `f64::atan2`, so `Line::from_polar` gives back the angle it's given.
+    /// a line with no length has an angle of 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+    /// use program_core::{Angle, Drawable, Line, Point, Transform2D};
+    ///
+    /// let (origin, left) = (Point::new(0.0, 0.0), Point::new(-1.0, 0.0));
+    /// assert_eq!(PI, Line::new(&origin, &left).angle());
+    /// assert_eq!(-FRAC_PI_2, Line::new(&origin, &Point::new(0.0, -3.0)).angle());
+    /// assert_eq!(-3.0 * FRAC_PI_4, Line::new(&Point::new(1.0, 1.0), &origin).angle());
+    /// assert_eq!(0.0, Line::new(&left, &left).angle());
+    /// assert!((Line::from_polar(&left, 2.0, -2.5).angle() + 2.5).abs() < 1e-12);
+    ///
+    /// // whatever moves the line, its length and angle follow its ends
+    /// let mut line = Drawable::Line(Line::new(&Point::new(10.0, 10.0), &Point::new(40.0, 50.0)));
+    /// line.rotate(Angle::degrees(100.0), &Point::new(3.0, -7.0));
+    /// line.apply_transform(&Transform2D::scaling(-2.0, 0.5));
+    /// line.translate(&Point::new(5.0, 5.0));
+    /// line.apply_transform(&Transform2D::rotation(2.0).then(&Transform2D::scaling(1.5, 3.0)));
+    ///
+    /// let Drawable::Line(line) = line else { unreachable!() };
+    /// let span = line.end() - line.start();
+    /// assert_eq!(span.y.atan2(span.x), line.angle());
+    /// assert_eq!(span.len(), line.len());
+    ///
+    /// ```
     pub fn angle(&self) -> f64 {
-        self.angle
+        (self.end.y - self.start.y).atan2(self.end.x - self.start.x)
+    }
+
+    /// rotates the line by `angle` radians about `pivot`, keeping its style.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    /// use program_core::{Line, Point};
+    ///
+    /// let mut line = Line::new(&Point::new(5.0, 5.0), &Point::new(6.0, 5.0));
+    /// line.rotate_about(&line.start(), FRAC_PI_2);
+    ///
+    /// assert_eq!(Point::new(5.0, 5.0), line.start());
+    /// assert!(line.end().approx_eq(&Point::new(5.0, 6.0), 1e-12));
+    /// assert!((line.len() - 1.0).abs() < 1e-12);
+    ///
+    /// // a line away from the origin turns about its start, not about the origin
+    /// let mut line = Line::new(&Point::new(10.0, 10.0), &Point::new(20.0, 10.0));
+    /// line.rotate_about(&line.start(), FRAC_PI_2);
+    ///
+    /// assert_eq!(Point::new(10.0, 10.0), line.start());
+    /// assert!(line.end().approx_eq(&Point::new(10.0, 20.0), 1e-12));
+    /// assert!((line.len() - 10.0).abs() < 1e-12 && (line.angle() - FRAC_PI_2).abs() < 1e-12);
+    ///
+    /// ```
+    pub fn rotate_about(&mut self, pivot: &Point2D, angle: f64) {
+        self.start.rotate_about(pivot, angle);
+        self.end.rotate_about(pivot, angle);
+    }
+
+    /// mirrors the line across the line through `line_point` running along
+    /// `line_dir`, see `Point::reflect_across`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// let mut line = Line::new(&Point::new(1.0, 2.0), &Point::new(4.0, 6.0));
+    /// line.mirror(&Point::new(0.0, 0.0), &Point::new(1.0, 1.0));
+    ///
+    /// assert_eq!((Point::new(2.0, 1.0), Point::new(6.0, 4.0)), (line.start(), line.end()));
+    /// assert_eq!(5.0, line.len());
+    ///
+    /// ```
+    pub fn mirror(&mut self, line_point: &Point2D, line_dir: &Point2D) {
+        self.start = self.start.reflect_across(line_point, line_dir);
+        self.end = self.end.reflect_across(line_point, line_dir);
+    }
+
+    /// the line moved `distance` across itself, keeping its style. positive
+    /// distances move it the way `Point::perp` turns its direction, below a
+    /// line drawn left to right, and negative ones the other way.
+    /// a line with no length has no direction to move across, so it stays.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::SQRT_2;
+    /// use program_core::{Line, Point};
+    ///
+    /// let horizontal = Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0));
+    /// assert_eq!(Line::new(&Point::new(0.0, 2.0), &Point::new(10.0, 2.0)), horizontal.offset(2.0));
+    /// assert_eq!(Line::new(&Point::new(0.0, -2.0), &Point::new(10.0, -2.0)), horizontal.offset(-2.0));
+    ///
+    /// let vertical = Line::new(&Point::new(5.0, 0.0), &Point::new(5.0, 10.0));
+    /// assert_eq!(Line::new(&Point::new(2.0, 0.0), &Point::new(2.0, 10.0)), vertical.offset(3.0));
+    ///
+    /// let diagonal = Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 10.0));
+    /// let moved = diagonal.offset(SQRT_2);
+    /// assert!(moved.start().approx_eq(&Point::new(-1.0, 1.0), 1e-12));
+    /// assert!(moved.end().approx_eq(&Point::new(9.0, 11.0), 1e-12));
+    ///
+    /// let dot = Line::new(&Point::new(1.0, 1.0), &Point::new(1.0, 1.0));
+    /// assert_eq!((dot.start(), dot.end()), (dot.offset(5.0).start(), dot.offset(5.0).end()));
+    ///
+    /// ```
+    pub fn offset(&self, distance: f64) -> Line2D {
+        let across = self.direction().perp() * distance;
+
+        Line2D::new(&(self.start.clone() + across.clone()), &(self.end.clone() + across)).with_style(self.style.clone())
+    }
+
+    /// a copy of the line moved so it starts at `point`, going the same way for the same length.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// let line = Line::new(&Point::new(1.0, 2.0), &Point::new(4.0, 6.0));
+    /// assert_eq!(Line::new(&Point::new(10.0, -3.0), &Point::new(13.0, 1.0)), line.parallel_through(&Point::new(10.0, -3.0)));
+    ///
+    /// let vertical = Line::new(&Point::new(5.0, 10.0), &Point::new(5.0, 0.0));
+    /// assert_eq!(Line::new(&Point::new(-2.0, 7.0), &Point::new(-2.0, -3.0)), vertical.parallel_through(&Point::new(-2.0, 7.0)));
+    ///
+    /// ```
+    pub fn parallel_through(&self, point: &Point2D) -> Line2D {
+        let end = point.clone() + (self.end.clone() - self.start.clone());
+
+        Line2D::new(point, &end).with_style(self.style.clone())
+    }
+
+    /// a line `len` long at right angles to this one, starting at `point_at(t)`.
+    /// it goes the way `offset` moves the line for a positive distance, or the
+    /// other way for a negative `len`. a line with no length has no direction
+    /// to be at right angles to, so it gives a line without a length at `point_at(t)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// let horizontal = Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0));
+    /// assert_eq!(Line::new(&Point::new(5.0, 0.0), &Point::new(5.0, 3.0)), horizontal.perpendicular_at(0.5, 3.0));
+    /// assert_eq!(Line::new(&Point::new(5.0, 0.0), &Point::new(5.0, -3.0)), horizontal.perpendicular_at(0.5, -3.0));
+    ///
+    /// let vertical = Line::new(&Point::new(5.0, 0.0), &Point::new(5.0, 10.0));
+    /// assert_eq!(Line::new(&Point::new(5.0, 2.5), &Point::new(1.0, 2.5)), vertical.perpendicular_at(0.25, 4.0));
+    /// assert_eq!(Line::new(&Point::new(5.0, 10.0), &Point::new(9.0, 10.0)), vertical.perpendicular_at(1.0, -4.0));
+    ///
+    /// let diagonal = Line::new(&Point::new(0.0, 0.0), &Point::new(3.0, 4.0));
+    /// let across = diagonal.perpendicular_at(0.0, 10.0);
+    /// assert!(across.end().approx_eq(&Point::new(-8.0, 6.0), 1e-12));
+    /// assert!(across.is_perpendicular_to(&diagonal));
+    ///
+    /// ```
+    pub fn perpendicular_at(&self, t: f64, len: f64) -> Line2D {
+        let foot = self.point_at(t);
+        let end = foot.clone() + self.direction().perp() * len;
+
+        Line2D::new(&foot, &end).with_style(self.style.clone())
+    }
+
+    /// whether the lines go the same way or opposite ways, within
+    /// `PARALLEL_TOLERANCE` of the sine of the angle between them.
+    /// lines without a length have no direction, so aren't parallel to anything.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// let line = Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 20.0));
+    /// assert!(line.is_parallel_to(&Line::new(&Point::new(5.0, 0.0), &Point::new(3.0, -4.0))));
+    /// assert!(line.is_parallel_to(&line.parallel_through(&Point::new(-7.0, 1e6))));
+    /// assert!(!line.is_parallel_to(&Line::new(&Point::new(5.0, 0.0), &Point::new(3.0, -4.1))));
+    /// assert!(!line.is_parallel_to(&line.perpendicular_at(0.5, 3.0)));
+    ///
+    /// let vertical = Line::new(&Point::new(5.0, 0.0), &Point::new(5.0, 10.0));
+    /// assert!(vertical.is_parallel_to(&Line::new(&Point::new(-1.0, 3.0), &Point::new(-1.0, -30.0))));
+    ///
+    /// let dot = Line::new(&Point::new(1.0, 1.0), &Point::new(1.0, 1.0));
+    /// assert!(!line.is_parallel_to(&dot));
+    /// assert!(!dot.is_parallel_to(&dot));
+    ///
+    /// ```
+    pub fn is_parallel_to(&self, other: &Line2D) -> bool {
+        let (direction, other_direction) = (self.end.clone() - self.start.clone(), other.end.clone() - other.start.clone());
+        let lens = direction.len() * other_direction.len();
+
+        lens > 0f64 && direction.cross(&other_direction).abs() <= PARALLEL_TOLERANCE * lens
+    }
+
+    /// whether the lines are at right angles, within `PARALLEL_TOLERANCE` of
+    /// the cosine of the angle between them. lines without a length have no
+    /// direction, so aren't perpendicular to anything.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// let line = Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 20.0));
+    /// assert!(line.is_perpendicular_to(&Line::new(&Point::new(100.0, 0.0), &Point::new(80.0, 10.0))));
+    /// assert!(!line.is_perpendicular_to(&Line::new(&Point::new(100.0, 0.0), &Point::new(80.0, 10.1))));
+    /// assert!(!line.is_perpendicular_to(&line));
+    ///
+    /// let vertical = Line::new(&Point::new(5.0, 0.0), &Point::new(5.0, 10.0));
+    /// assert!(vertical.is_perpendicular_to(&Line::new(&Point::new(9.0, 3.0), &Point::new(-9.0, 3.0))));
+    /// assert!(!vertical.is_perpendicular_to(&Line::new(&Point::new(9.0, 3.0), &Point::new(9.0, 3.0))));
+    ///
+    /// ```
+    pub fn is_perpendicular_to(&self, other: &Line2D) -> bool {
+        let (direction, other_direction) = (self.end.clone() - self.start.clone(), other.end.clone() - other.start.clone());
+        let lens = direction.len() * other_direction.len();
+
+        lens > 0f64 && direction.dot(&other_direction).abs() <= PARALLEL_TOLERANCE * lens
+    }
+
+    /// the point `t` of the way along the line, see `Point::lerp`. a `t`
+    /// below 0 or above 1 gives a point on the line extended past its start
+    /// or its end.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// let line = Line::new(&Point::new(0.0, 0.0), &Point::new(30.0, 40.0));
+    /// assert_eq!(line.start(), line.point_at(0.0));
+    /// assert_eq!(line.end(), line.point_at(1.0));
+    /// assert_eq!(Point::new(15.0, 20.0), line.point_at(0.5));
+    /// assert_eq!(Point::new(-30.0, -40.0), line.point_at(-1.0));
+    /// assert_eq!(Point::new(60.0, 80.0), line.point_at(2.0));
+    ///
+    /// let vertical = Line::new(&Point::new(5.0, 10.0), &Point::new(5.0, -10.0));
+    /// assert_eq!(Point::new(5.0, 5.0), vertical.point_at(0.25));
+    ///
+    /// let dot = Line::new(&Point::new(2.0, 2.0), &Point::new(2.0, 2.0));
+    /// assert_eq!(dot.start(), dot.point_at(7.0));
+    ///
+    /// ```
+    pub fn point_at(&self, t: f64) -> Point2D {
+        Point2D::lerp(&self.start, &self.end, t)
+    }
+
+    /// the point halfway between the ends.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// assert_eq!(Point::new(15.0, 20.0), Line::new(&Point::new(0.0, 0.0), &Point::new(30.0, 40.0)).midpoint());
+    /// assert_eq!(Point::new(5.0, 0.0), Line::new(&Point::new(5.0, 10.0), &Point::new(5.0, -10.0)).midpoint());
+    /// assert_eq!(Point::new(2.0, 2.0), Line::new(&Point::new(2.0, 2.0), &Point::new(2.0, 2.0)).midpoint());
+    ///
+    /// ```
+    pub fn midpoint(&self) -> Point2D {
+        Point2D::new((self.start.x + self.end.x) / 2f64, (self.start.y + self.end.y) / 2f64)
+    }
+
+    /// the direction from the start to the end, with a length of 1.
+    /// a line with no length has no direction and gives the zero vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// assert_eq!(Point::new(0.6, 0.8), Line::new(&Point::new(0.0, 0.0), &Point::new(30.0, 40.0)).direction());
+    /// assert_eq!(Point::new(0.0, -1.0), Line::new(&Point::new(5.0, 10.0), &Point::new(5.0, -10.0)).direction());
+    /// assert_eq!(Point::new(0.0, 0.0), Line::new(&Point::new(2.0, 2.0), &Point::new(2.0, 2.0)).direction());
+    ///
+    /// ```
+    pub fn direction(&self) -> Point2D {
+        (self.end.clone() - self.start.clone()).normalized()
+    }
+
+    /// cuts the line in two at `point_at(t)`, with `t` held between 0 and 1.
+    /// both pieces keep the line's style. cutting at an end leaves the piece
+    /// on that side without a length, a line from that end to itself.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Color, Line, Point, StyleBuilder};
+    ///
+    /// let line = Line::builder(&Point::new(0.0, 0.0), &Point::new(30.0, 40.0)).stroke(Color::RED).stroke_width(2.0).build();
+    /// let (first, second) = line.split_at(0.2);
+    /// assert_eq!((line.start(), Point::new(6.0, 8.0)), (first.start(), first.end()));
+    /// assert_eq!((Point::new(6.0, 8.0), line.end()), (second.start(), second.end()));
+    /// assert_eq!((10.0, 40.0), (first.len(), second.len()));
+    /// assert_eq!((line.style(), line.style()), (first.style(), second.style()));
+    ///
+    /// // at or past an end, one piece has no length
+    /// let (first, second) = line.split_at(0.0);
+    /// assert_eq!((line.start(), line.start(), 0.0), (first.start(), first.end(), first.len()));
+    /// assert_eq!((line.start(), line.end()), (second.start(), second.end()));
+    /// let (first, second) = line.split_at(1.5);
+    /// assert_eq!((line.start(), line.end()), (first.start(), first.end()));
+    /// assert_eq!((line.end(), line.end(), 0.0), (second.start(), second.end(), second.len()));
+    ///
+    /// ```
+    pub fn split_at(&self, t: f64) -> (Line2D, Line2D) {
+        let cut = self.point_at(t.clamp(0f64, 1f64));
+
+        (
+            Line2D::new(&self.start, &cut).with_style(self.style.clone()),
+            Line2D::new(&cut, &self.end).with_style(self.style.clone()),
+        )
+    }
+
+    /// cuts the line in two where it passes closest to `point`, `None` when
+    /// `point` is farther than the hit tolerance from it. like `split_at`,
+    /// cutting at an end leaves a piece without a length.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// let line = Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0));
+    /// let (first, second) = line.split_at_point(&Point::new(4.0, 1.0)).unwrap();
+    /// assert_eq!((Point::new(4.0, 0.0), Point::new(4.0, 0.0)), (first.end(), second.start()));
+    /// assert_eq!((4.0, 6.0), (first.len(), second.len()));
+    ///
+    /// let (first, second) = line.split_at_point(&Point::new(12.0, 0.0)).unwrap();
+    /// assert_eq!((10.0, 0.0), (first.len(), second.len()));
+    ///
+    /// assert_eq!(None, line.split_at_point(&Point::new(5.0, 20.0)));
+    ///
+    /// ```
+    pub fn split_at_point(&self, point: &Point2D) -> Option<(Line2D, Line2D)> {
+        if self.distance_to(point) > HIT_TOLERANCE {
+            return None;
+        }
+
+        let cut = self.closest_point(point);
+
+        Some((
+            Line2D::new(&self.start, &cut).with_style(self.style.clone()),
+            Line2D::new(&cut, &self.end).with_style(self.style.clone()),
+        ))
+    }
+
+    /// the smallest box around the painted stroke. with the default butt cap
+    /// the stroke stops square at the ends, so it reaches half its width out
+    /// to either side of the line and no further along it. round and square
+    /// caps reach half the width past the ends too. a line with no length
+    /// and butt caps paints nothing, so its box is its start.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Color, Line, LineCap, Point, StyleBuilder};
+    ///
+    /// let bounds = |start: Point, end: Point, width: f64| {
+    ///     let bounds = Line::builder(&start, &end).stroke(Color::BLACK).stroke_width(width).build().bounding_box();
+    ///     (bounds.min().x, bounds.min().y, bounds.max().x, bounds.max().y)
+    /// };
+    ///
+    /// let (left, right) = (Point::new(0.0, 10.0), Point::new(30.0, 10.0));
+    /// assert_eq!((0.0, 9.5, 30.0, 10.5), bounds(left.clone(), right.clone(), 1.0));
+    /// assert_eq!((0.0, 0.0, 30.0, 20.0), bounds(left, right, 20.0));
+    ///
+    /// let (top, bottom) = (Point::new(10.0, 0.0), Point::new(10.0, 30.0));
+    /// assert_eq!((9.5, 0.0, 10.5, 30.0), bounds(top.clone(), bottom.clone(), 1.0));
+    /// assert_eq!((0.0, 0.0, 20.0, 30.0), bounds(top, bottom, 20.0));
+    ///
+    /// // a diagonal stroke's corners stick out by half the width times the sine and cosine
+    /// let (start, end) = (Point::new(0.0, 0.0), Point::new(30.0, 40.0));
+    /// let expected = |half: f64| [-0.8 * half, -0.6 * half, 30.0 + 0.8 * half, 40.0 + 0.6 * half];
+    /// for width in [1.0, 20.0] {
+    ///     let (min_x, min_y, max_x, max_y) = bounds(start.clone(), end.clone(), width);
+    ///     for (value, expected) in [min_x, min_y, max_x, max_y].into_iter().zip(expected(width / 2.0)) {
+    ///         assert!((value - expected).abs() < 1e-12);
+    ///     }
+    /// }
+    ///
+    /// // without a stroke, the box around the ends
+    /// let plain = Line::new(&start, &end).bounding_box();
+    /// assert_eq!((Point::new(0.0, 0.0), Point::new(30.0, 40.0)), (plain.min(), plain.max()));
+    ///
+    /// // caps past the ends
+    /// let capped = |cap| Line::builder(&start, &end).stroke(Color::BLACK).stroke_width(10.0).line_cap(cap).build().bounding_box();
+    /// assert_eq!((Point::new(-5.0, -5.0), Point::new(35.0, 45.0)), (capped(LineCap::Round).min(), capped(LineCap::Round).max()));
+    /// let square = capped(LineCap::Square);
+    /// assert!(square.min().approx_eq(&Point::new(-7.0, -7.0), 1e-12) && square.max().approx_eq(&Point::new(37.0, 47.0), 1e-12));
+    ///
+    /// ```
+    pub fn bounding_box(&self) -> Aabb {
+        let half = self.style.painted_stroke_width() / 2f64;
+        let (direction, mut start, mut end) = (self.direction(), self.start(), self.end());
+        match self.style.line_cap {
+            LineCap::Butt => {}
+            LineCap::Round => return Aabb::new(&start, &end).expand(half),
+            LineCap::Square => {
+                start = start - direction.clone() * half;
+                end = end + direction.clone() * half;
+            }
+        }
+
+        let across = direction.perp() * half;
+        let corners = [start.clone() + across.clone(), start - across.clone(), end.clone() + across.clone(), end - across];
+
+        Aabb::from_points(corners.iter()).expect("a line's stroke has corners")
+    }
+
+    /// the shortest distance from `point` to the line, to its
+    /// nearer end when `point` is beyond them.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// let line = Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0));
+    /// assert_eq!(4.0, line.distance_to(&Point::new(5.0, -4.0)));
+    /// assert_eq!(0.0, line.distance_to(&Point::new(7.5, 0.0)));
+    ///
+    /// // beyond either end, the distance to that end
+    /// assert_eq!(line.start(), line.closest_point(&Point::new(-3.0, 4.0)));
+    /// assert_eq!(5.0, line.distance_to(&Point::new(-3.0, 4.0)));
+    /// assert_eq!(line.end(), line.closest_point(&Point::new(16.0, -8.0)));
+    /// assert_eq!(10.0, line.distance_to(&Point::new(16.0, -8.0)));
+    ///
+    /// ```
+    pub fn distance_to(&self, point: &Point2D) -> f64 {
+        (point.clone() - self.closest_point(point)).len()
+    }
+
+    /// where the line crosses `other`, `None` when they don't meet.
+    /// lines that touch at an end meet there. lines along each other meet
+    /// where they overlap, so the point given is the end of the overlap
+    /// nearest the start of this line. lines less than `PARALLEL_TOLERANCE`
+    /// radians from parallel are taken as parallel, as where they cross
+    /// can't be found precisely, and a line with no length meets nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// let line = |x1, y1, x2, y2| Line::new(&Point::new(x1, y1), &Point::new(x2, y2));
+    /// let diagonal = line(0.0, 0.0, 10.0, 10.0);
+    ///
+    /// assert_eq!(Some(Point::new(5.0, 5.0)), diagonal.intersect(&line(0.0, 10.0, 10.0, 0.0)));
+    /// assert_eq!(None, diagonal.intersect(&line(0.0, 30.0, 30.0, 0.0)));
+    ///
+    /// // parallel, apart or along each other
+    /// assert_eq!(None, diagonal.intersect(&line(0.0, 1.0, 10.0, 11.0)));
+    /// assert_eq!(Some(Point::new(4.0, 4.0)), diagonal.intersect(&line(4.0, 4.0, 20.0, 20.0)));
+    /// assert_eq!(Some(Point::new(2.0, 2.0)), diagonal.intersect(&line(7.0, 7.0, 2.0, 2.0)));
+    /// assert_eq!(Some(Point::new(0.0, 0.0)), diagonal.intersect(&line(20.0, 20.0, -20.0, -20.0)));
+    /// assert_eq!(None, diagonal.intersect(&line(11.0, 11.0, 20.0, 20.0)));
+    ///
+    /// // touching at an end, along each other or not
+    /// assert_eq!(Some(Point::new(10.0, 10.0)), diagonal.intersect(&line(10.0, 10.0, 20.0, 20.0)));
+    /// assert_eq!(Some(Point::new(10.0, 10.0)), diagonal.intersect(&line(10.0, 10.0, 20.0, 0.0)));
+    /// assert_eq!(Some(Point::new(5.0, 5.0)), diagonal.intersect(&line(5.0, 5.0, 5.0, -5.0)));
+    ///
+    /// // almost parallel lines are parallel, whatever their size
+    /// assert_eq!(None, diagonal.intersect(&line(0.0, 1e-15, 10.0, 10.0 - 1e-15)));
+    /// assert_eq!(None, line(0.0, 0.0, 1e-9, 1e-9).intersect(&line(0.0, 1e-24, 1e-9, 1e-9 - 1e-24)));
+    /// assert!(diagonal.intersect(&line(0.0, 1e-3, 10.0, 10.0 - 1e-3)).unwrap().approx_eq(&Point::new(5.0, 5.0), 1e-9));
+    ///
+    /// assert_eq!(None, line(5.0, 5.0, 5.0, 5.0).intersect(&diagonal));
+    ///
+    /// ```
+    pub fn intersect(&self, other: &Line2D) -> Option<Point2D> {
+        let (direction, other_direction) = (self.end.clone() - self.start.clone(), other.end.clone() - other.start.clone());
+        match self.crossing(other) {
+            Some((t, u)) => {
+                let within = |t: f64| (-PARALLEL_TOLERANCE..=1f64 + PARALLEL_TOLERANCE).contains(&t);
+
+                (within(t) && within(u)).then(|| self.point_at(t.clamp(0f64, 1f64)))
+            }
+            None if direction.len() == 0f64 || other_direction.len() == 0f64 => None,
+            None => {
+                // parallel, they only meet if the other line runs along this one
+                let offset = other.start.clone() - self.start.clone();
+                if offset.cross(&direction).abs() > PARALLEL_TOLERANCE * direction.len() * offset.len() {
+                    return None;
+                }
+
+                let len_squared = direction.dot(&direction);
+                let from = offset.dot(&direction) / len_squared;
+                let to = (other.end.clone() - self.start.clone()).dot(&direction) / len_squared;
+                let (first, last) = (from.min(to).max(0f64), from.max(to).min(1f64));
+
+                (first <= last).then(|| self.point_at(first))
+            }
+        }
+    }
+
+    /// where the line crosses `other` with both extended past their ends
+    /// forever, `None` when they're parallel, even along each other, or
+    /// either has no length. see `intersect` for what counts as parallel.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// let line = |x1, y1, x2, y2| Line::new(&Point::new(x1, y1), &Point::new(x2, y2));
+    /// let diagonal = line(0.0, 0.0, 10.0, 10.0);
+    ///
+    /// assert_eq!(Some(Point::new(15.0, 15.0)), diagonal.intersect_infinite(&line(0.0, 30.0, 30.0, 0.0)));
+    /// assert_eq!(Some(Point::new(-2.0, -2.0)), diagonal.intersect_infinite(&line(-2.0, 5.0, -2.0, 10.0)));
+    /// assert_eq!(None, diagonal.intersect_infinite(&line(4.0, 4.0, 20.0, 20.0)));
+    /// assert_eq!(None, diagonal.intersect_infinite(&line(0.0, 1e-15, 10.0, 10.0 - 1e-15)));
+    ///
+    /// ```
+    pub fn intersect_infinite(&self, other: &Line2D) -> Option<Point2D> {
+        self.crossing(other).map(|(t, _)| self.point_at(t))
+    }
+
+    /// how far along this line and along `other` the two cross, as `t` of
+    /// `point_at`, `None` when they're parallel or either has no length.
+    fn crossing(&self, other: &Line2D) -> Option<(f64, f64)> {
+        let (direction, other_direction) = (self.end.clone() - self.start.clone(), other.end.clone() - other.start.clone());
+        let denominator = direction.cross(&other_direction);
+        // the sine of the angle between them
+        if denominator.abs() <= PARALLEL_TOLERANCE * direction.len() * other_direction.len() {
+            return None;
+        }
+
+        let offset = other.start.clone() - self.start.clone();
+
+        Some((offset.cross(&other_direction) / denominator, offset.cross(&direction) / denominator))
+    }
+
+    /// the point of the line closest to `point`, one of its ends when
+    /// `point` is beyond them. a line with no length is closest at its start.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Line, Point};
+    ///
+    /// let line = Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 10.0));
+    /// assert_eq!(Point::new(5.0, 5.0), line.closest_point(&Point::new(0.0, 10.0)));
+    /// assert_eq!(line.start(), line.closest_point(&Point::new(-5.0, -1.0)));
+    /// assert_eq!(line.end(), line.closest_point(&Point::new(30.0, 12.0)));
+    ///
+    /// let dot = Line::new(&Point::new(2.0, 2.0), &Point::new(2.0, 2.0));
+    /// assert_eq!(dot.start(), dot.closest_point(&Point::new(9.0, 9.0)));
+    ///
+    /// ```
+    pub fn closest_point(&self, point: &Point2D) -> Point2D {
+        let direction = self.end.clone() - self.start.clone();
+        let len_squared = direction.dot(&direction);
+        if len_squared == 0f64 {
+            return self.start();
+        }
+
+        let along = (point.clone() - self.start.clone()).project_onto(&direction);
+
+        self.point_at((along.dot(&direction) / len_squared).clamp(0f64, 1f64))
     }
 }
 
 impl Draw for Line2D {
-    fn get_svg_tag_name() -> String {
-        String::from("line")
+    fn get_svg_tag_name() -> &'static str {
+        "line"
     }
 
-    fn get_svg_tag_properties(&self) -> HashMap<String, String> {
-        let mut props = HashMap::new();
+    fn write_svg_attrs(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        self.style.write_fill_attr(out)?;
+        self.style.write_stroke_attrs(out)?;
+        write_attr(out, "x1", self.start.x())?;
+        write_attr(out, "x2", self.end.x())?;
+        write_attr(out, "y1", self.start.y())?;
+        write_attr(out, "y2", self.end.y())
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        Line2D::bounding_box(self)
+    }
+
+    /// within the tolerance of the painted stroke, so thick lines can be
+    /// picked anywhere on their stroke and thin ones a little off it.
+    fn contains(&self, point: &Point2D) -> bool {
+        self.distance_to(point) <= self.style.painted_stroke_width() / 2f64 + HIT_TOLERANCE
+    }
+
+    fn translate(&mut self, offset: &Point2D) {
+        self.start = Point2D::new(self.start.x + offset.x, self.start.y + offset.y);
+        self.end = Point2D::new(self.end.x + offset.x, self.end.y + offset.y);
+    }
 
-        props.insert("x1".to_string(), self.start.x().to_string());
-        props.insert("y1".to_string(), self.start.y().to_string());
-        props.insert("x2".to_string(), self.end.x().to_string());
-        props.insert("y2".to_string(), self.end.y().to_string());
+    fn apply_transform(&mut self, transform: &Transform2D) {
+        self.start = transform.apply(&self.start);
+        self.end = transform.apply(&self.end);
+    }
+}
+
+/// builds a `Line2D` with its style, see `Line2D::builder`.
+#[derive(Clone, Debug)]
+pub struct LineBuilder {
+    start: Point2D,
+    end: Point2D,
+    style: Style,
+}
+
+impl LineBuilder {
+    pub fn build(self) -> Line2D {
+        Line2D::new(&self.start, &self.end).with_style(self.style)
+    }
+
+    /// paints the ends of the stroke butt, round or square.
+    pub fn line_cap(mut self, line_cap: LineCap) -> Self {
+        self.style.line_cap = line_cap;
+        self
+    }
+
+    /// paints the corners of the stroke mitered, round or beveled.
+    pub fn line_join(mut self, line_join: LineJoin) -> Self {
+        self.style.line_join = line_join;
+        self
+    }
+}
 
-        props
+impl StyleBuilder for LineBuilder {
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
     }
 }
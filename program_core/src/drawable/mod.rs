@@ -2,19 +2,120 @@
 //! this module will contain code for drawable objects
 //! like lines, circles, rectangles, etc.
 
-use std::collections::HashMap;
+use std::fmt;
+use bounds::Aabb;
+use point2d::Point2D;
+use transform::Transform2D;
+
+/// how far, in canvas units, a click may land from
+/// a thin outline and still hit it.
+pub const HIT_TOLERANCE: f64 = 3.0;
+
+/// how far a point worked out to be on the edge of a circle or rect
+/// may be off after rounding, and still be inside it.
+pub(crate) const EDGE_TOLERANCE: f64 = 1e-9;
 
 /// # Draw
 /// A trait for drawable objects.
 /// this trait will contain methods that helps
 /// construct an SVG tag for the drawable object
 pub trait Draw {
-    fn get_svg_tag_name() -> String;
-    fn get_svg_tag_properties(self: &Self) -> HashMap<String, String>;
+    fn get_svg_tag_name() -> &'static str;
+
+    /// writes the attributes of the SVG tag with `write_attr`,
+    /// in alphabetical order so the output is stable.
+    fn write_svg_attrs(&self, out: &mut impl fmt::Write) -> fmt::Result;
+    fn bounding_box(&self) -> Aabb;
+    fn contains(&self, point: &Point2D) -> bool;
+    fn translate(&mut self, offset: &Point2D);
+
+    /// moves the drawable's points with `transform`. shapes that can't be
+    /// sheared or stretched more one way than the other, like circles,
+    /// keep their shape and are scaled by `Transform2D::scale_factor`.
+    fn apply_transform(&mut self, transform: &Transform2D);
+
+    /// the text placed between the opening and closing tags,
+    /// `None` for tags that are self-closing.
+    fn get_svg_tag_content(&self) -> Option<&str> {
+        None
+    }
+
+    /// writes the complete SVG tag of the drawable.
+    fn write_svg_tag(&self, out: &mut impl fmt::Write) -> fmt::Result
+    where
+        Self: Sized,
+    {
+        write!(out, "<{}", Self::get_svg_tag_name())?;
+        self.write_svg_attrs(out)?;
+
+        match self.get_svg_tag_content() {
+            Some(content) => {
+                out.write_char('>')?;
+                write_escaped(out, content)?;
+                write!(out, "</{}>", Self::get_svg_tag_name())
+            }
+            None => out.write_str(" />"),
+        }
+    }
 }
 
+/// the typical length of a tag, used to size buffers up front.
+pub const TAG_CAPACITY: usize = 64;
+
+/// writes a single ` name="value"` attribute.
+/// the value is written as is, so it must not need escaping.
+pub fn write_attr(out: &mut impl fmt::Write, name: &str, value: impl fmt::Display) -> fmt::Result {
+    write!(out, " {}=\"{}\"", name, value)
+}
+
+/// writes `value` with the characters that have a special meaning in XML escaped.
+pub fn write_escaped(out: &mut impl fmt::Write, value: &str) -> fmt::Result {
+    for c in value.chars() {
+        match c {
+            '&' => out.write_str("&amp;")?,
+            '<' => out.write_str("&lt;")?,
+            '>' => out.write_str("&gt;")?,
+            '"' => out.write_str("&quot;")?,
+            '\'' => out.write_str("&apos;")?,
+            _ => out.write_char(c)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// rounds `value` to `decimals` decimals, halves away from zero, and
+/// without a negative zero. values too large to round are left as they are.
+pub(crate) fn round_to(value: f64, decimals: u32) -> f64 {
+    let factor = 10f64.powi(decimals.min(i32::MAX as u32) as i32);
+    let scaled = value * factor;
+
+    if !scaled.is_finite() {
+        return value;
+    }
+
+    (scaled.round() / factor) + 0f64
+}
+
+/// escapes the characters that have a special meaning in XML
+/// so the value can be safely placed in text or attribute values.
+pub fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    write_escaped(&mut escaped, value).expect("writing to a String can't fail");
+
+    escaped
+}
+
+pub mod angle;
+pub mod arc;
+pub mod bounds;
+#[cfg(feature = "legacy-svg")]
+pub mod legacy_svg;
 pub mod line2d;
 pub mod point2d;
 pub mod circle;
+pub mod rect;
+pub mod style;
 pub mod text;
-
+pub mod transform;
+pub mod vector3;
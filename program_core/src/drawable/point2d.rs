@@ -1,20 +1,769 @@
+use super::round_to;
+use super::vector3::Vector3;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+
 /// # Point
 /// structure to hold points in 2d cartesian space
+///
+/// with the `serde` feature a point is stored as its `x` and `y`, which is all it holds.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "serde")] {
+/// use program_core::Point;
+///
+/// for point in [Point::new(1.5, -2.0), Point::new(0.0, -0.0), Point::new(-1e300, 5e-324)] {
+///     let json = serde_json::to_string(&point).unwrap();
+///     assert_eq!(point, serde_json::from_str::<Point>(&json).unwrap());
+/// }
+/// assert_eq!("{\"x\":-3.0,\"y\":0.0}", serde_json::to_string(&Point::new(-3.0, 0.0)).unwrap());
+/// assert!(serde_json::from_str::<Point>("{\"x\": 1}").is_err());
+/// # }
+///
+/// ```
 #[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point2D {
     pub x: f64,
     pub y: f64,
 }
 
 impl Point2D {
+    /// a point at `x` and `y`, which must be finite. debug builds panic when
+    /// they aren't, release builds leave it to `Canvas` to refuse the point.
+    /// a point worked out from anything uncertain, like something typed,
+    /// should be made with `try_new` instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// assert_eq!(Point { x: 1.0, y: -2.0 }, Point::new(1.0, -2.0));
+    ///
+    /// let result = std::panic::catch_unwind(|| Point::new(f64::NAN, 0.0));
+    /// assert_eq!(cfg!(debug_assertions), result.is_err());
+    ///
+    /// ```
     pub fn new(x: f64, y: f64) -> Point2D {
+        debug_assert!(x.is_finite() && y.is_finite(), "a point at ({}, {}) isn't finite, see Point::try_new", x, y);
+
         Point2D { x, y }
     }
 
+    /// a point at `x` and `y`, unless either is infinite or not a number.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, CanvasError, NotFiniteError, Point};
+    ///
+    /// assert_eq!(Ok(Point::new(1.0, -2.0)), Point::try_new(1.0, -2.0));
+    /// assert_eq!(Err(NotFiniteError), Point::try_new(f64::NAN, 0.0));
+    /// assert_eq!(Err(NotFiniteError), Point::try_new(0.0, f64::NEG_INFINITY));
+    ///
+    /// // a point worked out from a division by zero never reaches the drawing
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// canvas.add_line(&Point::new(0.0, 0.0), &Point::new(10.0, 10.0)).unwrap();
+    /// let add = |canvas: &mut Canvas, x: f64| -> Result<_, CanvasError> {
+    ///     let end = Point::try_new(x, 0.0)?;
+    ///     canvas.add_line(&Point::new(0.0, 0.0), &end)
+    /// };
+    /// assert_eq!(Err(CanvasError::NotFinite), add(&mut canvas, 0.0 / 0.0));
+    /// assert_eq!(Err(CanvasError::NotFinite), canvas.add_line(&Point::new(0.0, 0.0), &Point { x: f64::NAN, y: 1.0 }));
+    /// assert_eq!(1, canvas.len());
+    ///
+    /// ```
+    pub fn try_new(x: f64, y: f64) -> Result<Point2D, NotFiniteError> {
+        if !(x.is_finite() && y.is_finite()) {
+            return Err(NotFiniteError);
+        }
+
+        Ok(Point2D { x, y })
+    }
+
     pub fn x(&self) -> f64 {
         self.x
     }
     pub fn y(&self) -> f64 {
         self.y
     }
+
+    /// the vector `len` long at `angle` radians from the x axis, turned the way
+    /// positive angles of `rotated_about` turn: clockwise, with the y axis pointing down.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    /// use program_core::Point;
+    ///
+    /// assert_eq!(Point::new(10.0, 0.0), Point::from_polar(10.0, 0.0));
+    /// assert!(Point::from_polar(10.0, FRAC_PI_2).approx_eq(&Point::new(0.0, 10.0), 1e-12));
+    ///
+    /// // back and forth between cartesian and polar
+    /// for point in [Point::new(3.0, -4.0), Point::new(-0.5, 0.25), Point::new(-7.0, -7.0)] {
+    ///     let (len, angle) = point.to_polar();
+    ///     assert!(Point::from_polar(len, angle).approx_eq(&point, 4.0 * f64::EPSILON * len));
+    /// }
+    ///
+    /// ```
+    pub fn from_polar(len: f64, angle: f64) -> Point2D {
+        let (sin, cos) = angle.sin_cos();
+
+        Point2D::new(len * cos, len * sin)
+    }
+
+    /// the length of the point taken as a vector and its angle from the x axis
+    /// in radians, from -π to π. see `from_polar`.
+    pub fn to_polar(&self) -> (f64, f64) {
+        (self.len(), self.y.atan2(self.x))
+    }
+
+    /// the point written with `decimals` decimals, like `format!("{:.2}", point)`.
+    pub fn to_string_with_precision(&self, decimals: usize) -> String {
+        format!("{:.*}", decimals, self)
+    }
+
+    /// the point as a 3d vector with `z`, like 1 for homogeneous coordinates.
+    pub fn extend(&self, z: f64) -> Vector3 {
+        Vector3::new(self.x, self.y, z)
+    }
+
+    /// the length of the point taken as a vector, its distance from the origin.
+    pub fn len(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    /// the dot product of the points taken as vectors.
+    pub fn dot(&self, other: &Point2D) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// the point taken as a vector, turned a right angle the way positive angles
+    /// of `rotated_about` turn: clockwise, with the y axis pointing down.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    /// use program_core::Point;
+    ///
+    /// let right = Point::new(2.0, 0.0);
+    /// assert_eq!(Point::new(0.0, 2.0), right.perp());
+    /// assert_eq!(Point::new(-0.0, -2.0), right.perp_ccw());
+    /// assert_eq!(Point::new(2.0, 0.0), right);
+    ///
+    /// let vector = Point::new(3.0, -4.0);
+    /// assert_eq!(FRAC_PI_2, vector.angle_between(&vector.perp()));
+    /// assert_eq!(-FRAC_PI_2, vector.angle_between(&vector.perp_ccw()));
+    ///
+    /// ```
+    pub fn perp(&self) -> Point2D {
+        Point2D::new(-self.y, self.x)
+    }
+
+    /// the point taken as a vector, turned a right angle the other way from `perp`.
+    pub fn perp_ccw(&self) -> Point2D {
+        Point2D::new(self.y, -self.x)
+    }
+
+    /// the cross product of the points taken as vectors, the z of their 3d cross product.
+    pub fn cross(&self, other: &Point2D) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// the angle in radians, from -π exclusive to π, the point taken as a
+    /// vector turns through to point the way `other` does. it's signed like
+    /// the angles of `rotated_about`, positive turning clockwise with the y
+    /// axis pointing down. the zero vector points no way, so it's 0 from or to it.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+    /// use program_core::Point;
+    ///
+    /// let right = Point::new(2.0, 0.0);
+    /// assert_eq!(FRAC_PI_2, right.angle_between(&Point::new(0.0, 5.0)));
+    /// assert_eq!(-FRAC_PI_2, right.angle_between(&Point::new(0.0, -5.0)));
+    /// assert_eq!(-3.0 * FRAC_PI_4, right.angle_between(&Point::new(-1.0, -1.0)));
+    ///
+    /// assert_eq!(0.0, right.angle_between(&Point::new(7.0, 0.0)));
+    /// assert_eq!(PI, right.angle_between(&Point::new(-7.0, 0.0)));
+    /// assert_eq!(PI, right.angle_between(&Point::new(-7.0, -0.0)));
+    ///
+    /// // turning by the angle points the same way
+    /// let (a, b) = (Point::new(1.0, 3.0), Point::new(-4.0, 2.0));
+    /// let turned = a.rotated_about(&Point::new(0.0, 0.0), a.angle_between(&b));
+    /// assert!(turned.normalized().approx_eq(&b.normalized(), 1e-12));
+    ///
+    /// // tiny vectors still have a direction
+    /// assert_eq!(FRAC_PI_4, Point::new(1e-300, 0.0).angle_between(&Point::new(1e-300, 1e-300)));
+    /// assert_eq!(0.0, right.angle_between(&Point::new(0.0, 0.0)));
+    ///
+    /// ```
+    pub fn angle_between(&self, other: &Point2D) -> f64 {
+        // normalized so the products of tiny vectors don't underflow to 0
+        let (from, to) = (self.normalized(), other.normalized());
+        let angle = from.cross(&to).atan2(from.dot(&to));
+
+        // anti-parallel vectors with a cross product of -0 come out at -π
+        if angle == -std::f64::consts::PI {
+            return std::f64::consts::PI;
+        }
+
+        angle
+    }
+
+    /// the angle in radians between the points taken as vectors, from 0 to π,
+    /// whichever way it's turned through. see `angle_between`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    /// use program_core::Point;
+    ///
+    /// let right = Point::new(1.0, 0.0);
+    /// assert_eq!(FRAC_PI_2, right.unsigned_angle_between(&Point::new(0.0, -3.0)));
+    /// assert_eq!(FRAC_PI_2, Point::new(0.0, -3.0).unsigned_angle_between(&right));
+    ///
+    /// ```
+    pub fn unsigned_angle_between(&self, other: &Point2D) -> f64 {
+        self.angle_between(other).abs()
+    }
+
+    /// the part of the point, taken as a vector, that runs along `other`.
+    /// the zero vector has no direction to run along, so projecting onto it
+    /// gives the zero vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// let (vector, along) = (Point::new(3.0, 4.0), Point::new(10.0, 0.0));
+    /// assert_eq!(Point::new(3.0, 0.0), vector.project_onto(&along));
+    /// assert_eq!(Point::new(0.0, 4.0), vector.reject_from(&along));
+    ///
+    /// // away from `other` projects onto the opposite direction
+    /// assert_eq!(Point::new(-3.0, 0.0), Point::new(-3.0, 1.0).project_onto(&along));
+    ///
+    /// let zero = Point::new(0.0, 0.0);
+    /// assert_eq!(zero, vector.project_onto(&zero));
+    /// assert_eq!(vector, vector.reject_from(&zero));
+    ///
+    /// ```
+    pub fn project_onto(&self, other: &Point2D) -> Point2D {
+        let len_squared = other.dot(other);
+        if len_squared == 0f64 {
+            return Point2D::new(0f64, 0f64);
+        }
+
+        other.clone() * (self.dot(other) / len_squared)
+    }
+
+    /// the part of the point, taken as a vector, across `other`,
+    /// what's left of it after `project_onto`.
+    pub fn reject_from(&self, other: &Point2D) -> Point2D {
+        self.clone() - self.project_onto(other)
+    }
+
+    /// the smaller of each coordinate of the points, the top left corner of a box around them.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// let (a, b) = (Point::new(-3.0, 4.0), Point::new(2.0, -5.0));
+    /// assert_eq!(Point::new(-3.0, -5.0), Point::min(&a, &b));
+    /// assert_eq!(Point::new(2.0, 4.0), Point::max(&a, &b));
+    /// assert_eq!(Point::new(3.0, 4.0), a.abs());
+    /// assert_eq!(Point::new(2.0, 5.0), b.abs());
+    ///
+    /// ```
+    pub fn min(a: &Point2D, b: &Point2D) -> Point2D {
+        Point2D { x: a.x.min(b.x), y: a.y.min(b.y) }
+    }
+
+    /// the larger of each coordinate of the points, the bottom right corner of a box around them.
+    pub fn max(a: &Point2D, b: &Point2D) -> Point2D {
+        Point2D { x: a.x.max(b.x), y: a.y.max(b.y) }
+    }
+
+    /// the point with its x replaced by `x`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// let point = Point::new(3.0, -4.0);
+    /// assert_eq!(Point::new(10.0, -4.0), point.with_x(10.0));
+    /// assert_eq!(Point::new(3.0, 10.0), point.with_y(10.0));
+    /// assert_eq!(Point::new(3.0, 0.0), point.only_x());
+    /// assert_eq!(Point::new(0.0, -4.0), point.only_y());
+    ///
+    /// ```
+    pub fn with_x(&self, x: f64) -> Point2D {
+        Point2D::new(x, self.y)
+    }
+
+    /// the point with its y replaced by `y`.
+    pub fn with_y(&self, y: f64) -> Point2D {
+        Point2D::new(self.x, y)
+    }
+
+    /// the point with its y made zero, the part of a vector along the x axis.
+    pub fn only_x(&self) -> Point2D {
+        self.with_y(0f64)
+    }
+
+    /// the point with its x made zero, the part of a vector along the y axis.
+    pub fn only_y(&self) -> Point2D {
+        self.with_x(0f64)
+    }
+
+    /// the point with both coordinates made positive.
+    pub fn abs(&self) -> Point2D {
+        Point2D { x: self.x.abs(), y: self.y.abs() }
+    }
+
+    /// the point moved the least it can to be within the box from `min` to `max`,
+    /// each coordinate on its own. a NaN coordinate comes out as the bound.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// let (min, max) = (Point::new(0.0, 0.0), Point::new(100.0, 50.0));
+    /// assert_eq!(Point::new(100.0, 20.0), Point::new(130.0, 20.0).clamp(&min, &max));
+    /// assert_eq!(Point::new(0.0, 50.0), Point::new(-5.0, 80.0).clamp(&min, &max));
+    /// assert_eq!(Point::new(30.0, 20.0), Point::new(30.0, 20.0).clamp(&min, &max));
+    ///
+    /// ```
+    pub fn clamp(&self, min: &Point2D, max: &Point2D) -> Point2D {
+        Point2D::min(&Point2D::max(self, min), max)
+    }
+
+    /// the point mirrored across the line through `line_point` running along
+    /// `line_dir`. a line with no direction is only a point, so the point is
+    /// mirrored through `line_point` instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// let (origin, point) = (Point::new(0.0, 0.0), Point::new(3.0, 1.0));
+    /// assert_eq!(Point::new(3.0, -1.0), point.reflect_across(&origin, &Point::new(5.0, 0.0)));
+    /// assert_eq!(Point::new(-3.0, 1.0), point.reflect_across(&origin, &Point::new(0.0, -1.0)));
+    /// assert_eq!(Point::new(1.0, 3.0), point.reflect_across(&origin, &Point::new(1.0, 1.0)));
+    ///
+    /// // across x = 10
+    /// assert_eq!(Point::new(17.0, 1.0), point.reflect_across(&Point::new(10.0, 50.0), &Point::new(0.0, 1.0)));
+    ///
+    /// assert_eq!(Point::new(-1.0, 1.0), point.reflect_across(&Point::new(1.0, 1.0), &origin));
+    ///
+    /// ```
+    pub fn reflect_across(&self, line_point: &Point2D, line_dir: &Point2D) -> Point2D {
+        let offset = self.clone() - line_point.clone();
+        let along = offset.project_onto(line_dir);
+
+        line_point.clone() + along.clone() + along - offset
+    }
+
+    /// the point `t` of the way from `a` to `b`, `a` at 0 and `b` at 1.
+    /// `t` isn't clamped, so values outside 0 to 1 go on past either point.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// let (a, b) = (Point::new(10.0, 0.0), Point::new(20.0, -40.0));
+    /// assert_eq!(a, Point::lerp(&a, &b, 0.0));
+    /// assert_eq!(b, Point::lerp(&a, &b, 1.0));
+    /// assert_eq!(Point::new(15.0, -20.0), Point::lerp(&a, &b, 0.5));
+    ///
+    /// assert_eq!(Point::new(30.0, -80.0), Point::lerp(&a, &b, 2.0));
+    /// assert_eq!(Point::new(5.0, 20.0), Point::lerp(&a, &b, -0.5));
+    ///
+    /// ```
+    pub fn lerp(a: &Point2D, b: &Point2D, t: f64) -> Point2D {
+        Point2D::new(a.x + t * (b.x - a.x), a.y + t * (b.y - a.y))
+    }
+
+    /// whether `other` is within `tolerance` of the point, like a press landing
+    /// on a handle a few pixels across. points are compared exactly by `==`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// let (origin, far) = (Point::new(0.0, 0.0), Point::new(100.0, 100.0));
+    /// assert_ne!(origin, far);
+    /// assert!(!origin.approx_eq(&far, 1.0));
+    /// assert!(!far.approx_eq(&origin, 1.0));
+    ///
+    /// let near = Point::new(3.0, -4.0);
+    /// assert!(origin.approx_eq(&near, 5.0) && near.approx_eq(&origin, 5.0));
+    /// assert!(!origin.approx_eq(&near, 4.9));
+    ///
+    /// assert!(!origin.approx_eq(&Point { x: f64::NAN, y: 0.0 }, f64::INFINITY));
+    ///
+    /// ```
+    pub fn approx_eq(&self, other: &Point2D, tolerance: f64) -> bool {
+        (other.x - self.x).hypot(other.y - self.y) <= tolerance
+    }
+
+    /// the point taken as a vector scaled to a length of 1, like the direction
+    /// of a line from its start to its end. a vector without a finite length
+    /// other than zero has no direction and gives the zero vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// assert_eq!(Point::new(0.6, -0.8), Point::new(3.0, -4.0).normalized());
+    /// assert_eq!(1.0, Point::new(1e-300, 1e-300).normalized().len());
+    ///
+    /// // lengths too small for their square to be a number still have a direction
+    /// assert_eq!(Point::new(1.0, 0.0), Point::new(5e-324, 0.0).normalized());
+    /// assert_eq!(Point::new(0.0, -1.0), Point::new(0.0, -1e-310).normalized());
+    ///
+    /// let zero = Point::new(0.0, 0.0);
+    /// assert_eq!(zero, zero.normalized());
+    /// assert_eq!(zero, Point { x: f64::INFINITY, y: 1.0 }.normalized());
+    /// assert_eq!(zero, Point { x: f64::NAN, y: 1.0 }.normalized());
+    ///
+    /// ```
+    pub fn normalized(&self) -> Point2D {
+        let len = self.len();
+        if !(len > 0f64 && len.is_finite()) {
+            return Point2D::new(0f64, 0f64);
+        }
+
+        Point2D::new(self.x / len, self.y / len)
+    }
+
+    /// scales the point in place like `normalized`, returning it.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// let mut direction = Point::new(0.0, 20.0);
+    /// assert_eq!(Point::new(0.0, 1.0), direction.normalize());
+    /// assert_eq!(Point::new(0.0, 1.0), direction);
+    ///
+    /// ```
+    pub fn normalize(&mut self) -> Point2D {
+        *self = self.normalized();
+
+        self.clone()
+    }
+
+    /// returns the closest point on a grid with the given spacing
+    /// and a line through the origin. a spacing that isn't positive
+    /// leaves the point as it is. see `snapped_to_grid_from`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// assert_eq!(Point::new(20.0, -10.0), Point::new(23.0, -14.0).snapped_to_grid(10.0));
+    /// assert_eq!(Point::new(0.0, 7.5), Point::new(1.2, 6.3).snapped_to_grid(2.5));
+    /// assert_eq!(Point::new(1.2, 6.3), Point::new(1.2, 6.3).snapped_to_grid(0.0));
+    ///
+    /// ```
+    pub fn snapped_to_grid(&self, spacing: f64) -> Point2D {
+        self.snapped_to_grid_from(&Point2D::new(0f64, 0f64), spacing)
+    }
+
+    /// returns the closest point on a grid with the given spacing and a line
+    /// through `origin`. a point halfway between two lines goes to the one
+    /// further right or down, wherever the origin is, so moving the grid and
+    /// the point together moves the snapped point with them.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// let origin = Point::new(3.0, -2.0);
+    /// assert_eq!(Point::new(13.0, 8.0), Point::new(14.0, 9.5).snapped_to_grid_from(&origin, 10.0));
+    /// assert_eq!(Point::new(4.0, 5.0), Point::new(4.0, 5.0).snapped_to_grid_from(&origin, -1.0));
+    ///
+    /// // halfway between lines
+    /// assert_eq!(Point::new(10.0, 0.0), Point::new(5.0, -5.0).snapped_to_grid(10.0));
+    /// assert_eq!(Point::new(13.0, -2.0), Point::new(8.0, -7.0).snapped_to_grid_from(&origin, 10.0));
+    /// assert_eq!(Point::new(-10.0, 20.0), Point::new(-15.0, 15.0).snapped_to_grid(10.0));
+    ///
+    /// // just short of halfway
+    /// let below = 0.49999999999999994;
+    /// assert_eq!(Point::new(0.0, 0.0), Point::new(below, -below).snapped_to_grid(1.0));
+    ///
+    /// ```
+    pub fn snapped_to_grid_from(&self, origin: &Point2D, spacing: f64) -> Point2D {
+        if !(spacing > 0f64 && spacing.is_finite()) {
+            return self.clone();
+        }
+
+        // halves are rounded up rather than away from the origin
+        let snap = |value: f64, origin: f64| {
+            let lines = (value - origin) / spacing;
+            let below = lines.floor();
+            let nearest = if lines - below >= 0.5 { below + 1f64 } else { below };
+
+            origin + nearest * spacing + 0f64
+        };
+
+        Point2D::new(snap(self.x, origin.x), snap(self.y, origin.y))
+    }
+
+    /// returns the point rotated by `angle` radians about `pivot`.
+    /// with the y axis pointing down, positive angles turn clockwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    /// use program_core::Point;
+    ///
+    /// let turned = Point::new(3.0, 1.0).rotated_about(&Point::new(1.0, 1.0), FRAC_PI_2);
+    ///
+    /// assert!((turned.x - 1.0).abs() < 1e-9);
+    /// assert!((turned.y - 3.0).abs() < 1e-9);
+    ///
+    /// ```
+    pub fn rotated_about(&self, pivot: &Point2D, angle: f64) -> Point2D {
+        let (sin, cos) = angle.sin_cos();
+        let (dx, dy) = (self.x - pivot.x, self.y - pivot.y);
+
+        Point2D::new(pivot.x + dx * cos - dy * sin, pivot.y + dx * sin + dy * cos)
+    }
+
+    /// rotates the point in place like `rotated_about`, returning it.
+    pub fn rotate_about(&mut self, pivot: &Point2D, angle: f64) -> Point2D {
+        *self = self.rotated_about(pivot, angle);
+
+        self.clone()
+    }
+
+    /// returns the closest point on the nearest of the lines through `origin`
+    /// at multiples of `step` radians, so a line from `origin` to it is straight
+    /// or at a clean angle. a step that isn't positive leaves the point as it is.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+    /// use program_core::Point;
+    ///
+    /// let origin = Point::new(0.0, 0.0);
+    /// assert_eq!(Point::new(10.0, 0.0), Point::new(10.0, 3.0).snapped_to_angle(&origin, FRAC_PI_2));
+    ///
+    /// let up = Point::new(12.0, 30.0).snapped_to_angle(&Point::new(10.0, 0.0), FRAC_PI_2);
+    /// assert!((up.x - 10.0).abs() < 1e-9 && (up.y - 30.0).abs() < 1e-9);
+    ///
+    /// let diagonal = Point::new(9.0, -11.0).snapped_to_angle(&origin, FRAC_PI_4);
+    /// assert!((diagonal.x - 10.0).abs() < 1e-9 && (diagonal.y + 10.0).abs() < 1e-9);
+    ///
+    /// assert_eq!(Point::new(10.0, 3.0), Point::new(10.0, 3.0).snapped_to_angle(&origin, 0.0));
+    ///
+    /// ```
+    pub fn snapped_to_angle(&self, origin: &Point2D, step: f64) -> Point2D {
+        if !(step > 0f64 && step.is_finite()) {
+            return self.clone();
+        }
+
+        let (dx, dy) = (self.x - origin.x, self.y - origin.y);
+        let angle = (dy.atan2(dx) / step).round() * step;
+        let (sin, cos) = angle.sin_cos();
+        let along = dx * cos + dy * sin;
+
+        Point2D::new(origin.x + along * cos, origin.y + along * sin)
+    }
+
+    /// returns the point with its coordinates rounded to `decimals` decimals,
+    /// halves away from zero. rounding again changes nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    ///
+    /// assert_eq!(Point::new(10.12, -20.99), Point::new(10.123456, -20.987654).rounded(2));
+    /// assert_eq!(Point::new(0.13, -0.13), Point::new(0.125, -0.125).rounded(2));
+    /// assert_eq!(Point::new(3.0, -3.0), Point::new(2.5, -2.5).rounded(0));
+    ///
+    /// // no negative zero is left to be written as -0
+    /// assert_eq!("0", Point::new(-0.001, 0.0).rounded(2).x.to_string());
+    ///
+    /// let once = Point::new(1.0 / 3.0, -2.0 / 3.0).rounded(4);
+    /// assert_eq!(once, once.rounded(4));
+    ///
+    /// ```
+    pub fn rounded(&self, decimals: u32) -> Point2D {
+        Point2D::new(round_to(self.x, decimals), round_to(self.y, decimals))
+    }
+}
+
+/// writes the point as `x, y`, which parses back to it. a precision, like
+/// in `{:.2}`, writes both coordinates with that many decimals.
+///
+/// # Examples
+/// ```
+/// use program_core::Point;
+///
+/// let point = Point::new(0.1 + 0.2, -2.0 / 3.0);
+/// assert_eq!("0.30000000000000004, -0.6666666666666666", point.to_string());
+/// assert_eq!("0.30, -0.67", format!("{:.2}", point));
+/// assert_eq!("0.300, -0.667", point.to_string_with_precision(3));
+/// assert_eq!(Ok(point.clone()), point.to_string().parse());
+///
+/// assert_eq!("0.0000001, 100000000000000000000", Point::new(1e-7, 1e20).to_string());
+///
+/// ```
+impl fmt::Display for Point2D {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match f.precision() {
+            Some(decimals) => write!(f, "{:.*}, {:.*}", decimals, self.x, decimals, self.y),
+            None => write!(f, "{}, {}", self.x, self.y),
+        }
+    }
+}
+
+/// the error returned when a string can't be parsed as a point.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ParsePointError;
+
+impl fmt::Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("expected two numbers, like \"10, 20\"")
+    }
+}
+
+impl std::error::Error for ParsePointError {}
+
+/// the error returned when a point's coordinates are infinite or not a number.
+#[derive(PartialEq, Clone, Debug)]
+pub struct NotFiniteError;
+
+impl fmt::Display for NotFiniteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a point's coordinates must be finite")
+    }
+}
+
+impl std::error::Error for NotFiniteError {}
+
+/// adds the points like vectors, so a point plus an offset is the point moved by it.
+/// like the other arithmetic here, it doesn't assert the result is finite, since
+/// coordinates that big can overflow. the canvas refuses those points instead.
+///
+/// # Examples
+/// ```
+/// use program_core::Point;
+///
+/// let (start, end) = (Point::new(10.0, 10.0), Point::new(30.0, 0.0));
+/// let direction = end.clone() - start.clone();
+/// assert_eq!(Point::new(20.0, -10.0), direction);
+///
+/// // halfway along the line
+/// assert_eq!(Point::new(20.0, 5.0), start + direction * 0.5);
+///
+/// ```
+impl Add for Point2D {
+    type Output = Point2D;
+
+    fn add(self, other: Point2D) -> Point2D {
+        Point2D { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl Sub for Point2D {
+    type Output = Point2D;
+
+    fn sub(self, other: Point2D) -> Point2D {
+        Point2D { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+/// scales the point like a vector, a negative factor flipping its direction.
+///
+/// # Examples
+/// ```
+/// use program_core::Point;
+///
+/// assert_eq!(Point::new(6.0, -8.0), Point::new(3.0, -4.0) * 2.0);
+/// assert_eq!(Point::new(-1.5, 2.0), -0.5 * Point::new(3.0, -4.0));
+///
+/// let direction = Point::new(3.0, -4.0).normalized();
+/// assert_eq!(direction.clone() * -1.0, (direction * -5.0).normalized());
+///
+/// ```
+impl Mul<f64> for Point2D {
+    type Output = Point2D;
+
+    fn mul(self, factor: f64) -> Point2D {
+        Point2D { x: self.x * factor, y: self.y * factor }
+    }
+}
+
+impl Mul<Point2D> for f64 {
+    type Output = Point2D;
+
+    fn mul(self, point: Point2D) -> Point2D {
+        point * self
+    }
+}
+
+/// divides the point like a vector.
+///
+/// # Panics
+/// when `divisor` is zero, rather than giving a point at infinity or NaN.
+///
+/// # Examples
+/// ```
+/// use program_core::Point;
+///
+/// assert_eq!(Point::new(1.5, -2.0), Point::new(3.0, -4.0) / 2.0);
+/// assert_eq!(Point::new(-6.0, 8.0), Point::new(3.0, -4.0) / -0.5);
+///
+/// let result = std::panic::catch_unwind(|| Point::new(1.0, 1.0) / 0.0);
+/// assert!(result.is_err());
+///
+/// ```
+impl Div<f64> for Point2D {
+    type Output = Point2D;
+
+    fn div(self, divisor: f64) -> Point2D {
+        assert!(divisor != 0f64, "can't divide the point ({}, {}) by zero", self.x, self.y);
+
+        Point2D::new(self.x / divisor, self.y / divisor)
+    }
+}
+
+/// parses two numbers separated by a comma or whitespace,
+/// optionally wrapped in parentheses.
+///
+/// # Examples
+/// ```
+/// use program_core::Point;
+///
+/// assert_eq!(Ok(Point::new(10.0, -2.5)), "10, -2.5".parse());
+/// assert_eq!(Ok(Point::new(1.0, 2.0)), " (1 2) ".parse());
+/// assert!("1, 2, 3".parse::<Point>().is_err());
+/// assert!("1, x".parse::<Point>().is_err());
+/// assert!("NaN, 2".parse::<Point>().is_err());
+///
+/// ```
+impl FromStr for Point2D {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')).unwrap_or(s);
+
+        let mut coordinates = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse::<f64>().ok().filter(|value| value.is_finite()));
+
+        match (coordinates.next(), coordinates.next(), coordinates.next()) {
+            (Some(Some(x)), Some(Some(y)), None) => Ok(Point2D::new(x, y)),
+            _ => Err(ParsePointError),
+        }
+    }
 }
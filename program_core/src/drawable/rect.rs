@@ -0,0 +1,336 @@
+use super::point2d::Point2D;
+use super::bounds::Aabb;
+use super::style::{StrokeAlign, StrokeStyle, Style, StyleBuilder};
+use super::transform::Transform2D;
+use super::{round_to, write_attr, Draw, EDGE_TOLERANCE};
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::fmt;
+
+/// # Rect
+/// structure to hold rectangles in 2d cartesian space
+/// it takes 2 opposite corners as arguments, in any order.
+/// a rect can be rotated about its center, `corner`, `width` and
+/// `height` describe it before the rotation.
+///
+/// # Examples
+/// ```
+/// use program_core::{Point, Rect};
+///
+/// let rect = Rect::new(&Point::new(10.0, 5.0), &Point::new(2.0, 9.0));
+///
+/// assert_eq!(Point::new(2.0, 5.0), rect.corner());
+/// assert_eq!(8f64, rect.width());
+/// assert_eq!(4f64, rect.height());
+/// assert_eq!(32f64, rect.area());
+///
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Rect {
+    corner: Point2D,
+    width: f64,
+    height: f64,
+    /// clockwise, in radians, about the center.
+    rotation: f64,
+    style: Style,
+}
+
+impl Rect {
+    pub fn new(a: &Point2D, b: &Point2D) -> Rect {
+        let size = (a.clone() - b.clone()).abs();
+
+        Rect {
+            corner: Point2D::min(a, b),
+            width: size.x,
+            height: size.y,
+            rotation: 0f64,
+            style: Style::default(),
+        }
+    }
+
+    /// starts building a styled rect from 2 opposite corners,
+    /// with nothing set it builds the same rect as `new`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    /// use program_core::{Color, Drawable, Point, Rect, StyleBuilder};
+    ///
+    /// let (a, b) = (Point::new(0.0, 0.0), Point::new(4.0, 2.0));
+    /// assert_eq!(Rect::new(&a, &b), Rect::builder(&a, &b).build());
+    ///
+    /// let turned = Rect::builder(&a, &b).rotation(FRAC_PI_2).fill(Color::rgb(255, 248, 224)).build();
+    /// assert_eq!(FRAC_PI_2, turned.rotation());
+    /// assert_eq!(
+    ///     "<rect fill=\"#fff8e0\" height=\"2\" transform=\"rotate(90 2 1)\" width=\"4\" x=\"0\" y=\"0\" />",
+    ///     Drawable::Rect(turned).to_svg_tag(),
+    /// );
+    ///
+    /// ```
+    pub fn builder(a: &Point2D, b: &Point2D) -> RectBuilder {
+        RectBuilder { a: a.clone(), b: b.clone(), rotation: 0f64, style: Style::default() }
+    }
+
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// the same rect painted with `style`.
+    pub fn with_style(self, style: Style) -> Rect {
+        Rect { style, ..self }
+    }
+
+    /// dashes the stroke of the rect, or makes it solid again.
+    pub fn set_stroke_style(&mut self, stroke_style: StrokeStyle) {
+        self.style.stroke_style = stroke_style;
+    }
+
+    /// builds a rect of the given size around `center`,
+    /// rotated clockwise by `rotation` radians.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    /// use program_core::{Aabb, Drawable, Point, Rect};
+    ///
+    /// let rect = Rect::from_center(&Point::new(10.0, 10.0), 8.0, 2.0, FRAC_PI_2);
+    ///
+    /// assert_eq!(Point::new(6.0, 9.0), rect.corner());
+    /// assert_eq!(Point::new(10.0, 10.0), rect.center());
+    /// assert!(Drawable::Rect(rect.clone()).contains(&Point::new(10.0, 13.5)));
+    /// assert!(!Drawable::Rect(rect.clone()).contains(&Point::new(13.5, 10.0)));
+    /// assert_eq!(
+    ///     "<rect height=\"2\" transform=\"rotate(90 10 10)\" width=\"8\" x=\"6\" y=\"9\" />",
+    ///     Drawable::Rect(rect).to_svg_tag(),
+    /// );
+    ///
+    /// ```
+    pub fn from_center(center: &Point2D, width: f64, height: f64, rotation: f64) -> Rect {
+        Rect {
+            corner: Point2D { x: center.x - width / 2f64, y: center.y - height / 2f64 },
+            width,
+            height,
+            rotation,
+            style: Style::default(),
+        }
+    }
+
+    /// the corner with the smallest coordinates, before the rotation.
+    pub fn corner(&self) -> Point2D {
+        self.corner.clone()
+    }
+
+    pub fn center(&self) -> Point2D {
+        Point2D::new(self.corner.x + self.width / 2f64, self.corner.y + self.height / 2f64)
+    }
+
+    pub fn rotation(&self) -> f64 {
+        self.rotation
+    }
+
+    /// the four corners after the rotation, clockwise from
+    /// the one that is the top left before it.
+    pub fn corners(&self) -> [Point2D; 4] {
+        let (center, (x, y)) = (self.center(), (self.corner.x, self.corner.y));
+
+        [
+            Point2D::new(x, y),
+            Point2D::new(x + self.width, y),
+            Point2D::new(x + self.width, y + self.height),
+            Point2D::new(x, y + self.height),
+        ]
+        .map(|corner| if self.rotation == 0f64 { corner } else { corner.rotated_about(&center, self.rotation) })
+    }
+
+    /// the rect with its corner and size rounded to `decimals` decimals,
+    /// keeping its rotation.
+    pub(crate) fn rounded(&self, decimals: u32) -> Rect {
+        Rect {
+            corner: self.corner.rounded(decimals),
+            width: round_to(self.width, decimals),
+            height: round_to(self.height, decimals),
+            rotation: self.rotation,
+            style: self.style.clone(),
+        }
+    }
+
+    /// the rect grown by `by` on every side, or shrunk when it's negative,
+    /// about the same center and never below nothing.
+    fn outset(&self, by: f64) -> Rect {
+        let (width, height) = ((self.width + 2f64 * by).max(0f64), (self.height + 2f64 * by).max(0f64));
+
+        Rect::from_center(&self.center(), width, height, self.rotation).with_style(self.style.clone())
+    }
+
+    /// the rect written to SVG, grown or shrunk so the stroke is painted as `stroke_align` asks.
+    pub(crate) fn outline(&self) -> Rect {
+        let offset = self.style.outline_offset();
+
+        if offset == 0f64 { self.clone() } else { self.outset(offset) }
+    }
+
+    /// the area paint covers, stroke included, as a rect with the same rotation.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Color, Point, Rect, StrokeAlign, StyleBuilder};
+    ///
+    /// let (a, b) = (Point::new(0.0, 0.0), Point::new(20.0, 10.0));
+    /// let stroked = |align| Rect::builder(&a, &b).stroke(Color::BLACK).stroke_width(2.0).stroke_align(align).build();
+    /// let size = |rect: Rect| (rect.corner(), rect.width(), rect.height());
+    ///
+    /// assert_eq!((a.clone(), 20.0, 10.0), size(Rect::new(&a, &b).painted()));
+    /// assert_eq!((Point::new(-1.0, -1.0), 22.0, 12.0), size(stroked(StrokeAlign::Center).painted()));
+    /// assert_eq!((a.clone(), 20.0, 10.0), size(stroked(StrokeAlign::Inside).painted()));
+    /// assert_eq!((Point::new(-2.0, -2.0), 24.0, 14.0), size(stroked(StrokeAlign::Outside).painted()));
+    ///
+    /// ```
+    pub fn painted(&self) -> Rect {
+        let width = self.style.painted_stroke_width();
+
+        if width == 0f64 { self.clone() } else { self.outline().outset(width / 2f64) }
+    }
+
+    /// rotates the rect by `angle` radians about `pivot`.
+    pub fn rotate_about(&mut self, pivot: &Point2D, angle: f64) {
+        let center = self.center().rotated_about(pivot, angle);
+
+        *self = Rect::from_center(&center, self.width, self.height, self.rotation + angle).with_style(self.style.clone());
+    }
+
+    /// mirrors the rect across the line through `line_point` running along
+    /// `line_dir`, see `Point::reflect_across`. a rect looks the same mirrored
+    /// about its own axes, so it's its center that's reflected and its
+    /// rotation that's turned the other way about the line.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    /// use program_core::{Point, Rect};
+    ///
+    /// // across the x axis, an upright rect stays upright
+    /// let mut rect = Rect::new(&Point::new(0.0, 2.0), &Point::new(20.0, 12.0));
+    /// rect.mirror(&Point::new(0.0, 0.0), &Point::new(1.0, 0.0));
+    /// assert_eq!((Point::new(0.0, -12.0), 20.0, 10.0, 0.0), (rect.corner(), rect.width(), rect.height(), rect.rotation()));
+    ///
+    /// // across y = x, a turned rect turns the other way about the diagonal
+    /// let mut turned = Rect::builder(&Point::new(10.0, 0.0), &Point::new(30.0, 10.0)).rotation(0.25).build();
+    /// turned.mirror(&Point::new(0.0, 0.0), &Point::new(1.0, 1.0));
+    /// assert!(turned.center().approx_eq(&Point::new(5.0, 20.0), 1e-12));
+    /// assert!((turned.rotation() - (FRAC_PI_2 - 0.25)).abs() < 1e-12);
+    ///
+    /// ```
+    pub fn mirror(&mut self, line_point: &Point2D, line_dir: &Point2D) {
+        let center = self.center().reflect_across(line_point, line_dir);
+        let rotation = match line_dir.normalized() {
+            direction if direction.len() == 0f64 => self.rotation,
+            direction => 2f64 * direction.y.atan2(direction.x) - self.rotation + 0f64,
+        };
+
+        *self = Rect::from_center(&center, self.width, self.height, rotation).with_style(self.style.clone());
+    }
+
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    pub fn area(&self) -> f64 {
+        self.width * self.height
+    }
+}
+
+impl Draw for Rect {
+    fn get_svg_tag_name() -> &'static str {
+        "rect"
+    }
+
+    fn write_svg_attrs(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let outline = self.outline();
+
+        self.style.write_fill_attr(out)?;
+        write_attr(out, "height", outline.height)?;
+        self.style.write_stroke_attrs(out)?;
+        if self.rotation != 0f64 {
+            let center = self.center();
+            write_attr(out, "transform", format_args!("rotate({} {} {})", self.rotation.to_degrees(), center.x, center.y))?;
+        }
+        write_attr(out, "width", outline.width)?;
+        write_attr(out, "x", outline.corner.x())?;
+        write_attr(out, "y", outline.corner.y())
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_points(self.painted().corners().iter()).expect("a rect has corners")
+    }
+
+    fn contains(&self, point: &Point2D) -> bool {
+        let local = if self.rotation == 0f64 { point.clone() } else { point.rotated_about(&self.center(), -self.rotation) };
+        let painted = self.painted();
+
+        Aabb::new(&painted.corner, &Point2D::new(painted.corner.x + painted.width, painted.corner.y + painted.height))
+            .expand(EDGE_TOLERANCE)
+            .contains(&local)
+    }
+
+    fn translate(&mut self, offset: &Point2D) {
+        self.corner = Point2D::new(self.corner.x + offset.x, self.corner.y + offset.y);
+    }
+
+    /// the sides are scaled and turned the way the transform takes them,
+    /// which stays a rect as long as it isn't sheared. a rect looks the same
+    /// half a turn around, so a mirrored one is turned the shorter way.
+    fn apply_transform(&mut self, transform: &Transform2D) {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (across, down) = (Point2D::new(cos, sin), Point2D::new(-sin, cos));
+        let (new_across, new_down) = (transform.apply_vector(&across), transform.apply_vector(&down));
+
+        let mut turned = across.angle_between(&new_across);
+        if turned > FRAC_PI_2 {
+            turned -= PI;
+        } else if turned < -FRAC_PI_2 {
+            turned += PI;
+        }
+
+        let center = transform.apply(&self.center());
+        *self = Rect::from_center(&center, self.width * new_across.len(), self.height * new_down.len(), self.rotation + turned)
+            .with_style(self.style.clone());
+    }
+}
+
+/// builds a `Rect` with its style, see `Rect::builder`.
+#[derive(Clone, Debug)]
+pub struct RectBuilder {
+    a: Point2D,
+    b: Point2D,
+    rotation: f64,
+    style: Style,
+}
+
+impl RectBuilder {
+    /// turns the rect clockwise by `rotation` radians about its center.
+    pub fn rotation(mut self, rotation: f64) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// paints the stroke inside, outside, or centered on the outline.
+    pub fn stroke_align(mut self, align: StrokeAlign) -> Self {
+        self.style.stroke_align = align;
+        self
+    }
+
+    pub fn build(self) -> Rect {
+        let rect = Rect::new(&self.a, &self.b);
+
+        Rect::from_center(&rect.center(), rect.width, rect.height, self.rotation).with_style(self.style)
+    }
+}
+
+impl StyleBuilder for RectBuilder {
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+}
@@ -0,0 +1,553 @@
+use super::angle::Angle;
+use super::{round_to, write_attr};
+use std::fmt;
+use std::str::FromStr;
+
+/// # Color
+/// an sRGB color with an alpha channel, written to SVG as `#rrggbb`,
+/// `#rrggbbaa` when it's translucent, and `none` when it's fully transparent.
+///
+/// # Examples
+/// ```
+/// use program_core::Color;
+///
+/// assert_eq!("#ff0000", Color::RED.to_string());
+/// assert_eq!("#0078d780", Color::rgba(0, 120, 215, 128).to_string());
+/// assert_eq!("none", Color::NONE.to_string());
+///
+/// assert_eq!(Ok(Color::rgb(0x12, 0xab, 0xef)), "#12ABef".parse());
+/// assert_eq!(Ok(Color::NONE), "none".parse());
+/// assert!("red".parse::<Color>().is_err());
+///
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const RED: Color = Color::rgb(255, 0, 0);
+    pub const GREEN: Color = Color::rgb(0, 128, 0);
+    pub const BLUE: Color = Color::rgb(0, 0, 255);
+    /// fully transparent, nothing is painted.
+    pub const NONE: Color = Color::rgba(0, 0, 0, 0);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b, a: 255 }
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color { r, g, b, a }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.a {
+            0 => f.write_str("none"),
+            255 => write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b),
+            a => write!(f, "#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, a),
+        }
+    }
+}
+
+/// the error returned when a color isn't `none`, `#rrggbb` or `#rrggbbaa`.
+#[derive(PartialEq, Debug)]
+pub struct ParseColorError(String);
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"{}\" isn't a color like #rrggbb", self.0)
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "none" {
+            return Ok(Color::NONE);
+        }
+
+        let error = || ParseColorError(s.to_string());
+        let hex = match s.strip_prefix('#') {
+            Some(hex) if (hex.len() == 6 || hex.len() == 8) && hex.is_ascii() => hex,
+            _ => return Err(error()),
+        };
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| error());
+        let a = if hex.len() == 8 { channel(6)? } else { 255 };
+
+        Ok(Color::rgba(channel(0)?, channel(2)?, channel(4)?, a))
+    }
+}
+
+/// # Paint
+/// what a stroke or a fill is painted with, one color or a gradient of them.
+/// a gradient is written to SVG as `url(#id)`, referring to the
+/// `<linearGradient>` that `Canvas::to_svg` defines once for every drawable
+/// painted with it. the id follows from the gradient, so the same
+/// gradient always gets the same one.
+///
+/// gradients stretch across the box of the drawable, which a straight
+/// horizontal or vertical line doesn't have, so SVG viewers don't paint them there.
+///
+/// # Examples
+/// ```
+/// use program_core::{Angle, Canvas, Circle, Color, Paint, Point, Rect, StyleBuilder};
+///
+/// let sunset = Paint::LinearGradient { stops: vec![(0.0, Color::RED), (1.0, Color::rgba(0, 0, 255, 128))], angle: Angle::degrees(90.0) };
+/// let id = sunset.gradient_id().unwrap();
+/// assert_eq!(format!("url(#{})", id), sunset.to_string());
+/// assert_eq!("#ff0000", Paint::from(Color::RED).to_string());
+/// assert_eq!(None, Paint::Solid(Color::RED).gradient_id());
+///
+/// let mut canvas = Canvas::new(100.0, 100.0);
+/// canvas.add(Rect::builder(&Point::new(0.0, 0.0), &Point::new(20.0, 10.0)).fill(sunset.clone()).build()).unwrap();
+/// canvas.add(Circle::builder(&Point::new(50.0, 50.0), 10.0).stroke(sunset.clone()).fill(Color::WHITE).build()).unwrap();
+///
+/// // both are painted with the one gradient defined
+/// let svg = canvas.to_svg();
+/// let def = format!(
+///     "<linearGradient id=\"{}\" gradientTransform=\"rotate(90 0.5 0.5)\">\
+///      <stop offset=\"0\" stop-color=\"#ff0000\" /><stop offset=\"1\" stop-color=\"#0000ff\" stop-opacity=\"0.502\" /></linearGradient>",
+///     id,
+/// );
+/// assert_eq!(1, svg.matches("<linearGradient").count());
+/// assert!(svg.contains(&format!("<defs>{}</defs>", def)));
+/// assert!(svg.contains(&format!("<rect fill=\"url(#{})\" height=\"10\" width=\"20\" x=\"0\" y=\"0\" />", id)));
+/// assert!(svg.contains(&format!("stroke=\"url(#{})\"", id)));
+///
+/// // another gradient is defined beside it
+/// let flipped = Paint::LinearGradient { stops: vec![(0.0, Color::BLUE), (1.0, Color::RED)], angle: Angle::default() };
+/// canvas.add(Rect::builder(&Point::new(0.0, 50.0), &Point::new(20.0, 60.0)).fill(flipped.clone()).build()).unwrap();
+/// assert_ne!(flipped.gradient_id(), sunset.gradient_id());
+/// assert_eq!(2, canvas.to_svg().matches("<linearGradient").count());
+///
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub enum Paint {
+    Solid(Color),
+    /// colors blending into each other from left to right, turned by `angle`
+    /// about the middle of the drawable. `stops` are the colors at offsets
+    /// from 0 to 1 along the way, in order.
+    LinearGradient { stops: Vec<(f64, Color)>, angle: Angle },
+}
+
+impl Paint {
+    /// whether anything is painted with it, which a fully transparent color isn't.
+    pub fn is_visible(&self) -> bool {
+        match self {
+            Paint::Solid(color) => color.a > 0,
+            Paint::LinearGradient { stops, .. } => stops.iter().any(|(_, color)| color.a > 0),
+        }
+    }
+
+    /// the id the gradient is defined with, `None` for a solid color.
+    pub fn gradient_id(&self) -> Option<String> {
+        let Paint::LinearGradient { stops, angle } = self else {
+            return None;
+        };
+
+        // FNV-1a, so the id only depends on the gradient and not on where it's used
+        let stop_bytes = stops.iter().flat_map(|(offset, color)| offset.to_bits().to_le_bytes().into_iter().chain([color.r, color.g, color.b, color.a]));
+        let hash = angle.to_radians().to_bits().to_le_bytes().into_iter().chain(stop_bytes).fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+
+        Some(format!("gradient-{:016x}", hash))
+    }
+
+    /// writes the `<linearGradient>` of the gradient, nothing for a solid color.
+    pub(crate) fn write_gradient_def(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let (Some(id), Paint::LinearGradient { stops, angle }) = (self.gradient_id(), self) else {
+            return Ok(());
+        };
+
+        write!(out, "<linearGradient id=\"{}\"", id)?;
+        if angle.to_radians() != 0f64 {
+            write!(out, " gradientTransform=\"rotate({} 0.5 0.5)\"", angle.to_degrees())?;
+        }
+        out.write_char('>')?;
+        for (offset, color) in stops {
+            write!(out, "<stop offset=\"{}\" stop-color=\"{}\"", offset, Color::rgb(color.r, color.g, color.b))?;
+            if color.a < 255 {
+                write!(out, " stop-opacity=\"{}\"", round_to(f64::from(color.a) / 255f64, 3))?;
+            }
+            out.write_str(" />")?;
+        }
+        out.write_str("</linearGradient>")
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Paint::Solid(color)
+    }
+}
+
+impl fmt::Display for Paint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self, self.gradient_id()) {
+            (_, Some(id)) => write!(f, "url(#{})", id),
+            (Paint::Solid(color), None) => color.fmt(f),
+            (Paint::LinearGradient { .. }, None) => unreachable!("gradients have an id"),
+        }
+    }
+}
+
+/// where the stroke of a rect or a circle is painted, relative to its
+/// outline. SVG only paints strokes centered on the outline, so the others
+/// are written as a smaller or larger shape. lines and texts ignore it.
+///
+/// # Examples
+/// ```
+/// use program_core::{Aabb, Circle, Color, Drawable, Point, Rect, StrokeAlign, StyleBuilder};
+///
+/// let rect = |align| {
+///     Drawable::Rect(Rect::builder(&Point::new(0.0, 0.0), &Point::new(20.0, 10.0)).stroke(Color::BLACK).stroke_width(2.0).stroke_align(align).build())
+/// };
+/// let circle = |align| Drawable::Circle(Circle::builder(&Point::new(0.0, 0.0), 10.0).stroke(Color::BLACK).stroke_width(4.0).stroke_align(align).build());
+/// let square = |min: f64, max: f64| Aabb::new(&Point::new(min, min), &Point::new(max, max));
+///
+/// // inside, the outer edge stays where it was drawn
+/// assert_eq!("<rect height=\"8\" stroke=\"#000000\" stroke-width=\"2\" width=\"18\" x=\"1\" y=\"1\" />", rect(StrokeAlign::Inside).to_svg_tag());
+/// assert_eq!("<rect height=\"12\" stroke=\"#000000\" stroke-width=\"2\" width=\"22\" x=\"-1\" y=\"-1\" />", rect(StrokeAlign::Outside).to_svg_tag());
+/// assert_eq!("<circle cx=\"0\" cy=\"0\" r=\"8\" stroke=\"#000000\" stroke-width=\"4\" />", circle(StrokeAlign::Inside).to_svg_tag());
+///
+/// // the bounds are what gets painted
+/// assert_eq!(Aabb::new(&Point::new(0.0, 0.0), &Point::new(20.0, 10.0)), rect(StrokeAlign::Inside).bounding_box());
+/// assert_eq!(Aabb::new(&Point::new(-1.0, -1.0), &Point::new(21.0, 11.0)), rect(StrokeAlign::Center).bounding_box());
+/// assert_eq!(Aabb::new(&Point::new(-2.0, -2.0), &Point::new(22.0, 12.0)), rect(StrokeAlign::Outside).bounding_box());
+/// assert_eq!(square(-10.0, 10.0), circle(StrokeAlign::Inside).bounding_box());
+/// assert_eq!(square(-12.0, 12.0), circle(StrokeAlign::Center).bounding_box());
+/// assert_eq!(square(-14.0, 14.0), circle(StrokeAlign::Outside).bounding_box());
+///
+/// // and so is what a click hits
+/// assert!(!rect(StrokeAlign::Inside).contains(&Point::new(20.5, 5.0)));
+/// assert!(rect(StrokeAlign::Center).contains(&Point::new(20.5, 5.0)));
+/// assert!(rect(StrokeAlign::Outside).contains(&Point::new(21.5, 5.0)));
+/// assert!(!circle(StrokeAlign::Inside).contains(&Point::new(11.0, 0.0)));
+/// assert!(circle(StrokeAlign::Outside).contains(&Point::new(13.0, 0.0)));
+///
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum StrokeAlign {
+    #[default]
+    Center,
+    /// the stroke is painted within the outline, which stays the outer edge.
+    Inside,
+    /// the stroke is painted around the outline, which stays the inner edge.
+    Outside,
+}
+
+/// how a stroke is broken up into dashes, written to SVG as `stroke-dasharray`.
+///
+/// # Examples
+/// ```
+/// use program_core::{Canvas, Color, Drawable, Line, Point, Rect, StrokeStyle, StyleBuilder};
+///
+/// let (start, end) = (Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+/// let line = |style| Drawable::Line(Line::builder(&start, &end).stroke(Color::BLACK).stroke_width(2.0).stroke_style(style).build());
+///
+/// assert_eq!(
+///     "<line stroke=\"#000000\" stroke-dasharray=\"4 1.5\" stroke-width=\"2\" x1=\"0\" x2=\"10\" y1=\"0\" y2=\"0\" />",
+///     line(StrokeStyle::Dashed(vec![4.0, 1.5])).to_svg_tag(),
+/// );
+/// assert!(line(StrokeStyle::Dotted).to_svg_tag().contains("stroke-dasharray=\"2 4\""));
+/// assert!(!line(StrokeStyle::Solid).to_svg_tag().contains("stroke-dasharray"));
+/// assert!(!line(StrokeStyle::Dashed(Vec::new())).to_svg_tag().contains("stroke-dasharray"));
+///
+/// let mut rect = Rect::new(&Point::new(0.0, 0.0), &Point::new(20.0, 10.0));
+/// rect.set_stroke_style(StrokeStyle::Dashed(vec![5.0, 5.0]));
+/// assert_eq!("<rect height=\"10\" stroke-dasharray=\"5 5\" width=\"20\" x=\"0\" y=\"0\" />", Drawable::Rect(rect.clone()).to_svg_tag());
+///
+/// // imported back as they were, dots as dashes
+/// let mut canvas = Canvas::new(100.0, 100.0);
+/// let ids = canvas.import_svg(r#"<svg><rect height="10" stroke-dasharray="5, 5" width="20" x="0" y="0" /></svg>"#).unwrap();
+/// assert_eq!(Some(&Drawable::Rect(rect)), canvas.get(ids[0]).map(|drawable| &**drawable));
+///
+/// ```
+#[derive(PartialEq, Clone, Debug, Default)]
+pub enum StrokeStyle {
+    #[default]
+    Solid,
+    /// the lengths of the dashes and of the gaps between them, in turn.
+    Dashed(Vec<f64>),
+    /// dots as long as the stroke is wide, with gaps twice as long.
+    Dotted,
+}
+
+impl StrokeStyle {
+    /// the dash and gap lengths of a stroke `width` wide,
+    /// `None` when it's painted in one piece.
+    pub fn dash_array(&self, width: f64) -> Option<Vec<f64>> {
+        match self {
+            StrokeStyle::Solid => None,
+            StrokeStyle::Dashed(dashes) if dashes.is_empty() => None,
+            StrokeStyle::Dashed(dashes) => Some(dashes.clone()),
+            StrokeStyle::Dotted => Some(vec![width, 2f64 * width]),
+        }
+    }
+
+    /// the stroke style with its dashes `factor` times as long.
+    /// dots follow the stroke width, so they stay as they are.
+    pub fn scaled(&self, factor: f64) -> StrokeStyle {
+        match self {
+            StrokeStyle::Dashed(dashes) => StrokeStyle::Dashed(dashes.iter().map(|dash| dash * factor).collect()),
+            other => other.clone(),
+        }
+    }
+}
+
+/// how the ends of a stroke are painted, written to SVG as `stroke-linecap`.
+///
+/// # Examples
+/// ```
+/// use program_core::{Color, Drawable, Line, LineCap, LineJoin, Point, StyleBuilder};
+///
+/// let (start, end) = (Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+/// let line = |cap, join| Drawable::Line(Line::builder(&start, &end).stroke(Color::BLACK).stroke_width(4.0).line_cap(cap).line_join(join).build());
+///
+/// assert_eq!(
+///     "<line stroke=\"#000000\" stroke-linecap=\"round\" stroke-linejoin=\"bevel\" stroke-width=\"4\" x1=\"0\" x2=\"10\" y1=\"0\" y2=\"0\" />",
+///     line(LineCap::Round, LineJoin::Bevel).to_svg_tag(),
+/// );
+/// assert!(line(LineCap::Square, LineJoin::Miter).to_svg_tag().contains("stroke-linecap=\"square\""));
+///
+/// // the defaults aren't written
+/// let plain = line(LineCap::Butt, LineJoin::Miter).to_svg_tag();
+/// assert!(!plain.contains("stroke-linecap") && !plain.contains("stroke-linejoin"));
+/// assert_eq!(plain, line(LineCap::default(), LineJoin::default()).to_svg_tag());
+///
+/// assert_eq!(Ok(LineCap::Square), "square".parse());
+/// assert_eq!("bevel", LineJoin::Bevel.to_string());
+/// assert!("flat".parse::<LineCap>().is_err());
+///
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum LineCap {
+    /// the stroke stops square at the ends.
+    #[default]
+    Butt,
+    /// the stroke ends in a half circle past the ends.
+    Round,
+    /// the stroke stops square, half its width past the ends.
+    Square,
+}
+
+impl LineCap {
+    /// every line cap, in the order they're offered.
+    pub const ALL: [LineCap; 3] = [LineCap::Butt, LineCap::Round, LineCap::Square];
+
+    /// the name SVG gives the line cap.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LineCap::Butt => "butt",
+            LineCap::Round => "round",
+            LineCap::Square => "square",
+        }
+    }
+}
+
+/// how the corners of a stroke are painted, written to SVG as `stroke-linejoin`.
+/// see `LineCap` for examples.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum LineJoin {
+    /// the edges carry on until they meet in a point.
+    #[default]
+    Miter,
+    Round,
+    /// the corner is cut off straight.
+    Bevel,
+}
+
+impl LineJoin {
+    /// every line join, in the order they're offered.
+    pub const ALL: [LineJoin; 3] = [LineJoin::Miter, LineJoin::Round, LineJoin::Bevel];
+
+    /// the name SVG gives the line join.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LineJoin::Miter => "miter",
+            LineJoin::Round => "round",
+            LineJoin::Bevel => "bevel",
+        }
+    }
+}
+
+impl fmt::Display for LineCap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl fmt::Display for LineJoin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// the error returned when parsing a line cap or a line join SVG doesn't have.
+#[derive(PartialEq, Eq, Debug)]
+pub struct ParseStrokeOptionError(String);
+
+impl fmt::Display for ParseStrokeOptionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown stroke option \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for ParseStrokeOptionError {}
+
+impl FromStr for LineCap {
+    type Err = ParseStrokeOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LineCap::ALL
+            .into_iter()
+            .find(|cap| cap.name() == s.trim())
+            .ok_or_else(|| ParseStrokeOptionError(s.to_string()))
+    }
+}
+
+impl FromStr for LineJoin {
+    type Err = ParseStrokeOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LineJoin::ALL
+            .into_iter()
+            .find(|join| join.name() == s.trim())
+            .ok_or_else(|| ParseStrokeOptionError(s.to_string()))
+    }
+}
+
+/// # Style
+/// how a drawable is painted. anything left `None` isn't written
+/// to the SVG, so the renderer's default, or the page's CSS, applies.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Style {
+    pub stroke: Option<Paint>,
+    pub stroke_width: Option<f64>,
+    pub fill: Option<Paint>,
+    pub stroke_align: StrokeAlign,
+    pub stroke_style: StrokeStyle,
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+}
+
+impl Style {
+    /// the width the stroke is painted with, SVG's default of 1 when only
+    /// its color is set, and 0 when there's no stroke to paint.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Color, Style};
+    ///
+    /// assert_eq!(0.0, Style { stroke_width: Some(4.0), ..Style::default() }.painted_stroke_width());
+    /// assert_eq!(1.0, Style { stroke: Some(Color::BLACK.into()), ..Style::default() }.painted_stroke_width());
+    /// assert_eq!(4.0, Style { stroke: Some(Color::BLACK.into()), stroke_width: Some(4.0), ..Style::default() }.painted_stroke_width());
+    /// assert_eq!(0.0, Style { stroke: Some(Color::NONE.into()), stroke_width: Some(4.0), ..Style::default() }.painted_stroke_width());
+    ///
+    /// ```
+    pub fn painted_stroke_width(&self) -> f64 {
+        match &self.stroke {
+            Some(stroke) if stroke.is_visible() => self.stroke_width.unwrap_or(1f64).max(0f64),
+            _ => 0f64,
+        }
+    }
+
+    /// how far out the outline written to SVG is moved,
+    /// so its centered stroke lands where `stroke_align` puts it.
+    pub(crate) fn outline_offset(&self) -> f64 {
+        let half = self.painted_stroke_width() / 2f64;
+
+        match self.stroke_align {
+            StrokeAlign::Center => 0f64,
+            StrokeAlign::Inside => -half,
+            StrokeAlign::Outside => half,
+        }
+    }
+
+    pub(crate) fn write_fill_attr(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        match &self.fill {
+            Some(fill) => write_attr(out, "fill", fill),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn write_stroke_attrs(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        if let Some(stroke) = &self.stroke {
+            write_attr(out, "stroke", stroke)?;
+        }
+        if let Some(dashes) = self.stroke_style.dash_array(self.stroke_width.unwrap_or(1f64)) {
+            let dashes: Vec<String> = dashes.iter().map(f64::to_string).collect();
+            write_attr(out, "stroke-dasharray", dashes.join(" "))?;
+        }
+        if self.line_cap != LineCap::Butt {
+            write_attr(out, "stroke-linecap", self.line_cap)?;
+        }
+        if self.line_join != LineJoin::Miter {
+            write_attr(out, "stroke-linejoin", self.line_join)?;
+        }
+        match self.stroke_width {
+            Some(width) => write_attr(out, "stroke-width", width),
+            None => Ok(()),
+        }
+    }
+}
+
+/// the style options shared by the builders of every drawable.
+///
+/// # Examples
+/// ```
+/// use program_core::{Circle, Color, Drawable, Point, StyleBuilder};
+///
+/// let circle = Circle::builder(&Point::new(10.0, 20.0), 5.0).stroke(Color::RED).stroke_width(2.0).fill_none().build();
+///
+/// assert_eq!(
+///     "<circle cx=\"10\" cy=\"20\" fill=\"none\" r=\"5\" stroke=\"#ff0000\" stroke-width=\"2\" />",
+///     Drawable::Circle(circle).to_svg_tag(),
+/// );
+///
+/// ```
+pub trait StyleBuilder: Sized {
+    fn style_mut(&mut self) -> &mut Style;
+
+    fn stroke(mut self, paint: impl Into<Paint>) -> Self {
+        self.style_mut().stroke = Some(paint.into());
+        self
+    }
+
+    fn stroke_width(mut self, width: f64) -> Self {
+        self.style_mut().stroke_width = Some(width);
+        self
+    }
+
+    fn fill(mut self, paint: impl Into<Paint>) -> Self {
+        self.style_mut().fill = Some(paint.into());
+        self
+    }
+
+    fn stroke_style(mut self, stroke_style: StrokeStyle) -> Self {
+        self.style_mut().stroke_style = stroke_style;
+        self
+    }
+
+    /// leaves the inside unpainted, even where CSS would fill it.
+    fn fill_none(self) -> Self {
+        self.fill(Color::NONE)
+    }
+
+    /// replaces every style option at once.
+    fn style(mut self, style: Style) -> Self {
+        *self.style_mut() = style;
+        self
+    }
+}
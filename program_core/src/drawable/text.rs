@@ -1,24 +1,85 @@
 use super::point2d::Point2D;
-use super::Draw;
-use std::collections::HashMap;
+use super::bounds::Aabb;
+use super::style::{Style, StyleBuilder};
+use super::transform::Transform2D;
+use super::{write_attr, Draw};
+use std::fmt;
+
+/// the SVG default font size, used when no other size is given.
+pub const DEFAULT_FONT_SIZE: f64 = 16.0;
+/// the average glyph width relative to the font size.
+const GLYPH_WIDTH: f64 = 0.6;
 
 /// # Text
 /// a structure to represent text.
 /// it takes a String reference and a point as arguments.
 /// the point is the top left corner of the text's bounding box.
 ///
+#[derive(PartialEq, Clone, Debug)]
 pub struct Text {
     text: String,
     pos: Point2D,
+    font_size: f64,
+    style: Style,
 }
 
 impl Text {
     pub fn new(text: String, pos: Point2D) -> Text {
+        Text::with_font_size(text, pos, DEFAULT_FONT_SIZE)
+    }
+
+    /// builds a text with the given font size, sizes that
+    /// aren't positive fall back to the default one.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Drawable, Point, Text};
+    ///
+    /// let big = Text::with_font_size(String::from("hi"), Point::new(1.0, 2.0), 32.0);
+    /// assert_eq!(32.0, big.font_size());
+    /// assert_eq!("<text font-size=\"32\" x=\"1\" y=\"2\">hi</text>", Drawable::Text(big).to_svg_tag());
+    ///
+    /// assert_eq!(16.0, Text::with_font_size(String::from("hi"), Point::new(1.0, 2.0), -1.0).font_size());
+    ///
+    /// ```
+    pub fn with_font_size(text: String, pos: Point2D, font_size: f64) -> Text {
+        let font_size = if font_size > 0f64 && font_size.is_finite() { font_size } else { DEFAULT_FONT_SIZE };
+
         Text {
-            text, pos
+            text, pos, font_size, style: Style::default()
         }
     }
 
+    /// starts building a styled text, with nothing set
+    /// it builds the same text as `new`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Color, Drawable, Point, StyleBuilder, Text};
+    ///
+    /// let pos = Point::new(1.0, 2.0);
+    /// assert_eq!(Text::new(String::from("hi"), pos.clone()), Text::builder(String::from("hi"), pos.clone()).build());
+    ///
+    /// let label = Text::builder(String::from("hi"), pos).font_size(24.0).fill(Color::BLUE).build();
+    /// assert_eq!(
+    ///     "<text fill=\"#0000ff\" font-size=\"24\" x=\"1\" y=\"2\">hi</text>",
+    ///     Drawable::Text(label).to_svg_tag(),
+    /// );
+    ///
+    /// ```
+    pub fn builder(text: String, pos: Point2D) -> TextBuilder {
+        TextBuilder { text, pos, font_size: DEFAULT_FONT_SIZE, style: Style::default() }
+    }
+
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// the same text painted with `style`.
+    pub fn with_style(self, style: Style) -> Text {
+        Text { style, ..self }
+    }
+
     pub fn text(&self) -> &String {
         &self.text
     }
@@ -26,20 +87,79 @@ impl Text {
     pub fn pos(&self) -> Point2D {
         self.pos.clone()
     }
+
+    pub fn font_size(&self) -> f64 {
+        self.font_size
+    }
 }
 
 impl Draw for Text {
-    fn get_svg_tag_name() -> String {
-        String::from("text")
+    fn get_svg_tag_name() -> &'static str {
+        "text"
     }
 
-    fn get_svg_tag_properties(&self) -> HashMap<String, String> {
-        let mut props = HashMap::new();
-        
-        props.insert("x".to_string(), self.pos.x().to_string());
-        props.insert("y".to_string(), self.pos.y().to_string());
+    fn write_svg_attrs(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        self.style.write_fill_attr(out)?;
+        // the default size is left to the renderer
+        if self.font_size != DEFAULT_FONT_SIZE {
+            write_attr(out, "font-size", self.font_size)?;
+        }
+        self.style.write_stroke_attrs(out)?;
+        write_attr(out, "x", self.pos.x())?;
+        write_attr(out, "y", self.pos.y())
+    }
 
-        props
+    /// estimated from the character count, the real extent
+    /// depends on the font the SVG is rendered with.
+    fn bounding_box(&self) -> Aabb {
+        let width = self.text.chars().count() as f64 * self.font_size * GLYPH_WIDTH;
+
+        Aabb::new(&self.pos, &Point2D::new(self.pos.x + width, self.pos.y + self.font_size))
+    }
+
+    fn contains(&self, point: &Point2D) -> bool {
+        self.bounding_box().contains(point)
+    }
+
+    fn translate(&mut self, offset: &Point2D) {
+        self.pos = Point2D::new(self.pos.x + offset.x, self.pos.y + offset.y);
+    }
+
+    /// texts can't be turned yet, so only their position moves and their font size scales.
+    fn apply_transform(&mut self, transform: &Transform2D) {
+        self.pos = transform.apply(&self.pos);
+        self.font_size *= transform.scale_factor();
+    }
+
+    fn get_svg_tag_content(&self) -> Option<&str> {
+        Some(&self.text)
     }
 }
 
+
+/// builds a `Text` with its style, see `Text::builder`.
+#[derive(Clone, Debug)]
+pub struct TextBuilder {
+    text: String,
+    pos: Point2D,
+    font_size: f64,
+    style: Style,
+}
+
+impl TextBuilder {
+    /// sizes that aren't positive fall back to the default one, as in `Text::with_font_size`.
+    pub fn font_size(mut self, font_size: f64) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    pub fn build(self) -> Text {
+        Text::with_font_size(self.text, self.pos, self.font_size).with_style(self.style)
+    }
+}
+
+impl StyleBuilder for TextBuilder {
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+}
@@ -0,0 +1,155 @@
+use super::point2d::Point2D;
+
+/// # Transform2D
+/// an affine transformation of the plane, like the `matrix(a b c d e f)`
+/// of an SVG `transform` attribute. a point `(x, y)` goes to
+/// `(a x + c y + e, b x + d y + f)`.
+///
+/// transforms are combined with `then`, so rotating a shape about its
+/// center and then scaling it is one transform applied once.
+///
+/// # Examples
+/// ```
+/// use std::f64::consts::FRAC_PI_2;
+/// use program_core::{Point, Transform2D};
+///
+/// let pivot = Point::new(10.0, 10.0);
+/// let turn = Transform2D::translation(&Point::new(-10.0, -10.0))
+///     .then(&Transform2D::rotation(FRAC_PI_2))
+///     .then(&Transform2D::translation(&pivot));
+///
+/// let point = Point::new(20.0, 10.0);
+/// assert!(turn.apply(&point).approx_eq(&point.rotated_about(&pivot, FRAC_PI_2), 1e-12));
+/// assert!(turn.approx_eq(&Transform2D::rotation_about(&pivot, FRAC_PI_2), 1e-12));
+///
+/// // the order matters
+/// let (double, shift) = (Transform2D::scaling(2.0, 2.0), Transform2D::translation(&Point::new(5.0, 0.0)));
+/// assert_eq!(Point::new(7.0, 2.0), double.then(&shift).apply(&Point::new(1.0, 1.0)));
+/// assert_eq!(Point::new(12.0, 2.0), shift.then(&double).apply(&Point::new(1.0, 1.0)));
+///
+/// assert_eq!(point, Transform2D::identity().apply(&point));
+///
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Transform2D {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+    e: f64,
+    f: f64,
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Transform2D::identity()
+    }
+}
+
+impl Transform2D {
+    /// the transform that leaves every point where it is.
+    pub fn identity() -> Transform2D {
+        Transform2D { a: 1f64, b: 0f64, c: 0f64, d: 1f64, e: 0f64, f: 0f64 }
+    }
+
+    /// moves points by `offset`.
+    pub fn translation(offset: &Point2D) -> Transform2D {
+        Transform2D { e: offset.x, f: offset.y, ..Transform2D::identity() }
+    }
+
+    /// turns points by `angle` radians about the origin, clockwise
+    /// with the y axis pointing down like `Point::rotated_about`.
+    pub fn rotation(angle: f64) -> Transform2D {
+        let (sin, cos) = angle.sin_cos();
+
+        Transform2D { a: cos, b: sin, c: -sin, d: cos, e: 0f64, f: 0f64 }
+    }
+
+    /// turns points by `angle` radians about `pivot`.
+    pub fn rotation_about(pivot: &Point2D, angle: f64) -> Transform2D {
+        Transform2D::translation(&Point2D::new(-pivot.x, -pivot.y))
+            .then(&Transform2D::rotation(angle))
+            .then(&Transform2D::translation(pivot))
+    }
+
+    /// scales points away from the origin, by `x` along the x axis and `y` along the y axis.
+    pub fn scaling(x: f64, y: f64) -> Transform2D {
+        Transform2D { a: x, d: y, ..Transform2D::identity() }
+    }
+
+    /// the transform that applies this one and then `other`.
+    pub fn then(&self, other: &Transform2D) -> Transform2D {
+        Transform2D {
+            a: other.a * self.a + other.c * self.b,
+            b: other.b * self.a + other.d * self.b,
+            c: other.a * self.c + other.c * self.d,
+            d: other.b * self.c + other.d * self.d,
+            e: other.a * self.e + other.c * self.f + other.e,
+            f: other.b * self.e + other.d * self.f + other.f,
+        }
+    }
+
+    /// where the transform takes `point`.
+    pub fn apply(&self, point: &Point2D) -> Point2D {
+        Point2D::new(self.a * point.x + self.c * point.y + self.e, self.b * point.x + self.d * point.y + self.f)
+    }
+
+    /// where the transform takes the point taken as a vector, which
+    /// unlike a point isn't moved by translation.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Point, Transform2D};
+    ///
+    /// let transform = Transform2D::scaling(2.0, 3.0).then(&Transform2D::translation(&Point::new(100.0, 100.0)));
+    /// assert_eq!(Point::new(2.0, -3.0), transform.apply_vector(&Point::new(1.0, -1.0)));
+    ///
+    /// ```
+    pub fn apply_vector(&self, vector: &Point2D) -> Point2D {
+        Point2D::new(self.a * vector.x + self.c * vector.y, self.b * vector.x + self.d * vector.y)
+    }
+
+    /// how much the transform scales lengths on average, the square root of
+    /// how much it scales areas. it's what circles and font sizes are scaled by,
+    /// as they can't be stretched more one way than the other.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Transform2D;
+    ///
+    /// assert_eq!(3.0, Transform2D::scaling(3.0, 3.0).then(&Transform2D::rotation(1.0)).scale_factor());
+    /// assert_eq!(2.0, Transform2D::scaling(-1.0, 4.0).scale_factor());
+    ///
+    /// ```
+    pub fn scale_factor(&self) -> f64 {
+        (self.a * self.d - self.b * self.c).abs().sqrt()
+    }
+
+    /// whether every number of the transform is within `tolerance` of `other`'s.
+    pub fn approx_eq(&self, other: &Transform2D, tolerance: f64) -> bool {
+        [
+            self.a - other.a,
+            self.b - other.b,
+            self.c - other.c,
+            self.d - other.d,
+            self.e - other.e,
+            self.f - other.f,
+        ]
+        .iter()
+        .all(|difference| difference.abs() <= tolerance)
+    }
+
+    /// the transform as the value of an SVG `transform` attribute.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Point, Transform2D};
+    ///
+    /// let transform = Transform2D::scaling(2.0, 0.5).then(&Transform2D::translation(&Point::new(10.0, -4.5)));
+    /// assert_eq!("matrix(2 0 0 0.5 10 -4.5)", transform.to_svg_transform());
+    ///
+    /// ```
+    pub fn to_svg_transform(&self) -> String {
+        format!("matrix({} {} {} {} {} {})", self.a, self.b, self.c, self.d, self.e, self.f)
+    }
+}
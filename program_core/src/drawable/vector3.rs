@@ -0,0 +1,170 @@
+use super::point2d::Point2D;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// # Vector3
+/// structure to hold vectors in 3d cartesian space, for working on points
+/// in homogeneous coordinates. a `Point` becomes one with `Point::extend`.
+///
+/// # Examples
+/// ```
+/// use program_core::{Point, Vector3};
+///
+/// let point = Point::new(3.0, -4.0);
+/// let homogeneous = point.extend(1.0);
+///
+/// assert_eq!(Vector3::new(3.0, -4.0, 1.0), homogeneous);
+/// assert_eq!(point, homogeneous.truncate());
+///
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Vector3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vector3 {
+    pub fn new(x: f64, y: f64, z: f64) -> Vector3 {
+        Vector3 { x, y, z }
+    }
+
+    /// the point of the x and y of the vector, dropping its z.
+    pub fn truncate(&self) -> Point2D {
+        Point2D::new(self.x, self.y)
+    }
+
+    pub fn dot(&self, other: &Vector3) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// the vector at right angles to both vectors, as long as the area
+    /// of the parallelogram they span.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Point, Vector3};
+    ///
+    /// let (x, y, z) = (Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 0.0, 1.0));
+    /// assert_eq!(z, x.cross(&y));
+    /// assert_eq!(x, y.cross(&z));
+    /// assert_eq!(Vector3::new(0.0, 0.0, -1.0), y.cross(&x));
+    ///
+    /// let (a, b) = (Vector3::new(2.0, 3.0, 4.0), Vector3::new(5.0, 6.0, 7.0));
+    /// assert_eq!(Vector3::new(-3.0, 6.0, -3.0), a.cross(&b));
+    /// assert_eq!(0.0, a.cross(&b).dot(&a));
+    /// assert_eq!(0.0, a.cross(&b).dot(&b));
+    ///
+    /// // the z of the cross product of two flat vectors is their 2d cross product
+    /// let (p, q) = (Point::new(1.0, 3.0), Point::new(-4.0, 2.0));
+    /// assert_eq!(p.cross(&q), p.extend(0.0).cross(&q.extend(0.0)).z);
+    ///
+    /// ```
+    pub fn cross(&self, other: &Vector3) -> Vector3 {
+        Vector3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// the length of the vector.
+    pub fn len(&self) -> f64 {
+        self.x.hypot(self.y).hypot(self.z)
+    }
+
+    /// the vector scaled to a length of 1. like `Point::normalized`, a vector
+    /// without a finite length other than zero gives the zero vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Vector3;
+    ///
+    /// assert_eq!(Vector3::new(0.0, 0.6, -0.8), Vector3::new(0.0, 3.0, -4.0).normalized());
+    /// assert_eq!(Vector3::new(2.0 / 3.0, 1.0 / 3.0, 2.0 / 3.0), Vector3::new(2.0, 1.0, 2.0).normalized());
+    /// assert_eq!(1.0, Vector3::new(1e-300, 1e-300, 1e-300).normalized().len());
+    /// assert_eq!(Vector3::new(0.0, 0.0, 1.0), Vector3::new(0.0, 0.0, 5e-324).normalized());
+    ///
+    /// let zero = Vector3::new(0.0, 0.0, 0.0);
+    /// assert_eq!(zero, zero.normalized());
+    /// assert_eq!(zero, Vector3::new(f64::INFINITY, 0.0, 0.0).normalized());
+    ///
+    /// let mut vector = Vector3::new(0.0, -9.0, 0.0);
+    /// assert_eq!(Vector3::new(0.0, -1.0, 0.0), vector.normalize());
+    /// assert_eq!(Vector3::new(0.0, -1.0, 0.0), vector);
+    ///
+    /// ```
+    pub fn normalized(&self) -> Vector3 {
+        let len = self.len();
+        if !(len > 0f64 && len.is_finite()) {
+            return Vector3::new(0f64, 0f64, 0f64);
+        }
+
+        Vector3::new(self.x / len, self.y / len, self.z / len)
+    }
+
+    /// scales the vector in place like `normalized`, returning it.
+    pub fn normalize(&mut self) -> Vector3 {
+        *self = self.normalized();
+
+        self.clone()
+    }
+}
+
+/// # Examples
+/// ```
+/// use program_core::Vector3;
+///
+/// let (a, b) = (Vector3::new(1.0, 2.0, 3.0), Vector3::new(0.5, -1.0, 4.0));
+/// assert_eq!(Vector3::new(1.5, 1.0, 7.0), a.clone() + b.clone());
+/// assert_eq!(Vector3::new(0.5, 3.0, -1.0), a.clone() - b);
+///
+/// assert_eq!(Vector3::new(-2.0, -4.0, -6.0), a.clone() * -2.0);
+/// assert_eq!(Vector3::new(-2.0, -4.0, -6.0), -2.0 * a.clone());
+/// assert_eq!(Vector3::new(0.5, 1.0, 1.5), a / 2.0);
+///
+/// assert!(std::panic::catch_unwind(|| Vector3::new(1.0, 1.0, 1.0) / 0.0).is_err());
+///
+/// ```
+impl Add for Vector3 {
+    type Output = Vector3;
+
+    fn add(self, other: Vector3) -> Vector3 {
+        Vector3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vector3 {
+    type Output = Vector3;
+
+    fn sub(self, other: Vector3) -> Vector3 {
+        Vector3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<f64> for Vector3 {
+    type Output = Vector3;
+
+    fn mul(self, factor: f64) -> Vector3 {
+        Vector3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl Mul<Vector3> for f64 {
+    type Output = Vector3;
+
+    fn mul(self, vector: Vector3) -> Vector3 {
+        vector * self
+    }
+}
+
+/// # Panics
+/// when `divisor` is zero, like dividing a `Point`.
+impl Div<f64> for Vector3 {
+    type Output = Vector3;
+
+    fn div(self, divisor: f64) -> Vector3 {
+        assert!(divisor != 0f64, "can't divide the vector ({}, {}, {}) by zero", self.x, self.y, self.z);
+
+        Vector3::new(self.x / divisor, self.y / divisor, self.z / divisor)
+    }
+}
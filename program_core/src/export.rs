@@ -0,0 +1,103 @@
+//! # export
+//! this module writes exported documents to files, creating the folders
+//! they go in and keeping or refusing to replace the files already there.
+
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// what to do when the file being exported already exists.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum OverwritePolicy {
+    /// write over it.
+    #[default]
+    Replace,
+    /// fail with an `AlreadyExists` error, leaving it as it was.
+    Error,
+    /// rename it by adding `.bak` to its name first, replacing an older backup.
+    Backup,
+}
+
+/// how an export is written to its file. the default replaces an existing
+/// file and fails when the folder the file goes in doesn't exist.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct ExportFileOptions {
+    /// creates the missing folders of the path.
+    pub create_dirs: bool,
+    pub overwrite: OverwritePolicy,
+}
+
+/// the step of an export that failed.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ExportOperation {
+    CreateDirs,
+    Backup,
+    Create,
+    Write,
+}
+
+/// the error returned when an export can't be written, with the file it was written to.
+#[derive(Debug)]
+pub struct ExportError {
+    pub path: PathBuf,
+    pub operation: ExportOperation,
+    pub source: io::Error,
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operation = match self.operation {
+            ExportOperation::CreateDirs => "create the folders of",
+            ExportOperation::Backup => "back up",
+            ExportOperation::Create => "create",
+            ExportOperation::Write => "write",
+        };
+
+        write!(f, "couldn't {} {}: {}", operation, self.path.display(), self.source)
+    }
+}
+
+impl std::error::Error for ExportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// the path an existing file is renamed to by `OverwritePolicy::Backup`.
+fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+
+    PathBuf::from(backup)
+}
+
+/// opens `path` as `options` ask, and writes the document into it with `write`.
+pub(crate) fn write_file(
+    path: &Path,
+    options: &ExportFileOptions,
+    write: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+) -> Result<(), ExportError> {
+    let error = |operation: ExportOperation| {
+        move |source: io::Error| ExportError { path: path.to_path_buf(), operation, source }
+    };
+
+    if options.create_dirs {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(error(ExportOperation::CreateDirs))?;
+        }
+    }
+
+    if options.overwrite == OverwritePolicy::Backup && path.exists() {
+        fs::rename(path, backup_path(path)).map_err(error(ExportOperation::Backup))?;
+    }
+
+    let mut open = OpenOptions::new();
+    match options.overwrite {
+        OverwritePolicy::Error => open.write(true).create_new(true),
+        OverwritePolicy::Replace | OverwritePolicy::Backup => open.write(true).create(true).truncate(true),
+    };
+    let mut file = BufWriter::new(open.open(path).map_err(error(ExportOperation::Create))?);
+
+    write(&mut file).and_then(|_| file.flush()).map_err(error(ExportOperation::Write))
+}
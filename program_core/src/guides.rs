@@ -0,0 +1,242 @@
+//! # guides
+//! this module contains ruler guides, the horizontal and vertical lines at
+//! exact coordinates that the cursor snaps to, and construction guides, the
+//! rays and endless lines running any way. they belong to the canvas
+//! but aren't drawables, so they're never selected and only exported when asked to.
+
+use super::{Aabb, Line, Point, PARALLEL_TOLERANCE};
+use std::fmt;
+
+/// which way a guide runs.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum GuideAxis {
+    /// along x, at a given y.
+    Horizontal,
+    /// along y, at a given x.
+    Vertical,
+}
+
+/// # GuideId
+/// tells the guides of a canvas apart. ids aren't reused once their guide is removed.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct GuideId(pub(crate) u32);
+
+impl fmt::Display for GuideId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// # Guide
+/// a line across the whole drawing, added by `Canvas::add_h_guide` or `Canvas::add_v_guide`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Guide {
+    pub id: GuideId,
+    pub axis: GuideAxis,
+    /// the y of a horizontal guide, the x of a vertical one.
+    pub position: f64,
+}
+
+impl Guide {
+    /// how far `point` is from the guide.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    ///
+    /// let mut canvas = Canvas::new(100.0, 100.0);
+    /// canvas.add_h_guide(10.0).unwrap();
+    /// canvas.add_v_guide(30.0).unwrap();
+    ///
+    /// let (horizontal, vertical) = (&canvas.guides()[0], &canvas.guides()[1]);
+    /// assert_eq!(5.0, horizontal.distance_to(&Point::new(50.0, 15.0)));
+    /// assert_eq!(20.0, vertical.distance_to(&Point::new(50.0, 15.0)));
+    ///
+    /// ```
+    pub fn distance_to(&self, point: &Point) -> f64 {
+        match self.axis {
+            GuideAxis::Horizontal => (point.y - self.position).abs(),
+            GuideAxis::Vertical => (point.x - self.position).abs(),
+        }
+    }
+}
+
+/// # InfiniteLine
+/// a line through `point` running along `direction` both ways without end.
+#[derive(PartialEq, Clone, Debug)]
+pub struct InfiniteLine {
+    pub point: Point,
+    pub direction: Point,
+}
+
+impl InfiniteLine {
+    pub fn new(point: &Point, direction: &Point) -> InfiniteLine {
+        InfiniteLine { point: point.clone(), direction: direction.clone() }
+    }
+
+    /// the line through both `a` and `b`.
+    pub fn through(a: &Point, b: &Point) -> InfiniteLine {
+        InfiniteLine::new(a, &(b.clone() - a.clone()))
+    }
+}
+
+/// # Ray
+/// a line from `origin` running along `direction` without end.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Ray {
+    pub origin: Point,
+    pub direction: Point,
+}
+
+impl Ray {
+    pub fn new(origin: &Point, direction: &Point) -> Ray {
+        Ray { origin: origin.clone(), direction: direction.clone() }
+    }
+}
+
+/// # GuideLine
+/// a construction guide, endless one way or both, added by `Canvas::add_guide`.
+/// it has no ends to draw, so it's drawn cut to the part within the
+/// drawing, see `clip_to`.
+///
+/// # Examples
+/// ```
+/// use program_core::{Aabb, GuideLine, InfiniteLine, Line, Point, Ray};
+///
+/// let diagonal = GuideLine::from(InfiniteLine::through(&Point::new(10.0, 10.0), &Point::new(20.0, 20.0)));
+/// let page = Aabb::new(&Point::new(0.0, 0.0), &Point::new(100.0, 50.0));
+/// let drawn = diagonal.clip_to(&page).unwrap();
+/// assert_eq!((Point::new(0.0, 0.0), Point::new(50.0, 50.0)), (drawn.start(), drawn.end()));
+///
+/// // a ray only runs one way from its origin
+/// let ray = GuideLine::from(Ray::new(&Point::new(30.0, 30.0), &Point::new(-1.0, -1.0)));
+/// let drawn = ray.clip_to(&page).unwrap();
+/// assert_eq!((Point::new(30.0, 30.0), Point::new(0.0, 0.0)), (drawn.start(), drawn.end()));
+/// assert_eq!(None, GuideLine::from(Ray::new(&Point::new(0.0, 60.0), &Point::new(1.0, 0.0))).clip_to(&page));
+///
+/// // they're as close to a point as their closest point
+/// assert_eq!(Point::new(15.0, 15.0), diagonal.closest_point(&Point::new(20.0, 10.0)));
+/// assert_eq!(Point::new(30.0, 30.0), ray.closest_point(&Point::new(40.0, 40.0)));
+/// assert_eq!(50f64.sqrt(), ray.distance_to(&Point::new(35.0, 25.0)));
+///
+/// // and cross lines within the line's ends, and past the ray's origin
+/// let line = Line::new(&Point::new(0.0, 40.0), &Point::new(40.0, 0.0));
+/// assert_eq!(Some(Point::new(20.0, 20.0)), diagonal.intersect(&line));
+/// assert_eq!(Some(Point::new(20.0, 20.0)), ray.intersect(&line));
+/// assert_eq!(None, ray.intersect(&Line::new(&Point::new(40.0, 80.0), &Point::new(80.0, 40.0))));
+/// assert_eq!(None, diagonal.intersect(&Line::new(&Point::new(0.0, 10.0), &Point::new(10.0, 20.0))));
+///
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub enum GuideLine {
+    Infinite(InfiniteLine),
+    Ray(Ray),
+}
+
+impl From<InfiniteLine> for GuideLine {
+    fn from(line: InfiniteLine) -> Self {
+        GuideLine::Infinite(line)
+    }
+}
+
+impl From<Ray> for GuideLine {
+    fn from(ray: Ray) -> Self {
+        GuideLine::Ray(ray)
+    }
+}
+
+impl GuideLine {
+    /// where the guide starts from, and the way it runs.
+    pub fn origin(&self) -> &Point {
+        match self {
+            GuideLine::Infinite(line) => &line.point,
+            GuideLine::Ray(ray) => &ray.origin,
+        }
+    }
+
+    pub fn direction(&self) -> &Point {
+        match self {
+            GuideLine::Infinite(line) => &line.direction,
+            GuideLine::Ray(ray) => &ray.direction,
+        }
+    }
+
+    /// how far back from its origin the guide runs, in lengths of its direction.
+    fn min_t(&self) -> f64 {
+        match self {
+            GuideLine::Infinite(_) => f64::NEG_INFINITY,
+            GuideLine::Ray(_) => 0f64,
+        }
+    }
+
+    fn point_at(&self, t: f64) -> Point {
+        self.origin().clone() + self.direction().clone() * t
+    }
+
+    /// the point of the guide closest to `point`. a guide without a
+    /// direction is only its origin.
+    pub fn closest_point(&self, point: &Point) -> Point {
+        let direction = self.direction();
+        let len_squared = direction.dot(direction);
+        if len_squared == 0f64 {
+            return self.origin().clone();
+        }
+
+        let t = (point.clone() - self.origin().clone()).dot(direction) / len_squared;
+
+        self.point_at(t.max(self.min_t()))
+    }
+
+    /// how far `point` is from the guide.
+    pub fn distance_to(&self, point: &Point) -> f64 {
+        (point.clone() - self.closest_point(point)).len()
+    }
+
+    /// where the guide crosses `line`, `None` when they don't meet or run along each other.
+    pub fn intersect(&self, line: &Line) -> Option<Point> {
+        let (direction, span) = (self.direction().clone(), line.end() - line.start());
+        let denominator = direction.cross(&span);
+        if denominator.abs() <= PARALLEL_TOLERANCE * direction.len() * span.len() {
+            return None;
+        }
+
+        let offset = line.start() - self.origin().clone();
+        let (t, u) = (offset.cross(&span) / denominator, offset.cross(&direction) / denominator);
+
+        (t >= self.min_t() && (0f64..=1f64).contains(&u)).then(|| line.point_at(u))
+    }
+
+    /// the part of the guide within `bounds`, `None` when it misses them
+    /// or only touches a corner.
+    pub fn clip_to(&self, bounds: &Aabb) -> Option<Line> {
+        let (origin, direction) = (self.origin(), self.direction());
+        let (min, max) = (bounds.min(), bounds.max());
+        let (mut from, mut to) = (self.min_t(), f64::INFINITY);
+
+        for (start, way, low, high) in [(origin.x, direction.x, min.x, max.x), (origin.y, direction.y, min.y, max.y)] {
+            if way == 0f64 {
+                if start < low || start > high {
+                    return None;
+                }
+                continue;
+            }
+
+            let (a, b) = ((low - start) / way, (high - start) / way);
+            from = from.max(a.min(b));
+            to = to.min(a.max(b));
+        }
+
+        (from < to).then(|| Line::new(&self.point_at(from), &self.point_at(to)))
+    }
+}
+
+/// # ConstructionGuide
+/// a ray or an endless line added to a canvas by `Canvas::add_guide`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ConstructionGuide {
+    pub id: GuideId,
+    pub line: GuideLine,
+}
+
+/// the color guides are drawn in.
+pub const GUIDE_COLOR: &str = "#00a0e0";
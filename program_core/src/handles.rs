@@ -0,0 +1,140 @@
+//! # handles
+//! this module contains the handles shown around a selected drawable,
+//! the points that are dragged to reshape or rotate it.
+
+use super::{Angle, Arc, Circle, Drawable, Line, Point, Rect};
+
+/// what dragging a handle changes.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum HandleKind {
+    /// the start of a line or an arc.
+    Start,
+    /// the end of a line or an arc.
+    End,
+    /// a corner of a rect, clockwise from the top left one before rotating.
+    Corner(usize),
+    /// the rim of a circle, or the middle of an arc.
+    Radius,
+    /// rotates the drawable about its center.
+    Rotate,
+}
+
+/// # Handle
+/// a handle of a drawable and where it is, in drawing coordinates.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Handle {
+    pub kind: HandleKind,
+    pub pos: Point,
+}
+
+/// the handles of `drawable`. the rotation handle is placed
+/// `rotate_distance` away from the top of the drawable.
+pub(crate) fn handles_of(drawable: &Drawable, rotate_distance: f64) -> Vec<Handle> {
+    let handle = |kind, pos| Handle { kind, pos };
+
+    match drawable {
+        Drawable::Line(line) => {
+            let (start, end) = (line.start(), line.end());
+            let middle = line.midpoint();
+            let len = line.len();
+
+            // along the normal that points up, or straight up for a line with no length
+            let (mut nx, mut ny) = if len > 0f64 { ((end.y - start.y) / len, (start.x - end.x) / len) } else { (0f64, -1f64) };
+            if ny > 0f64 {
+                (nx, ny) = (-nx, -ny);
+            }
+
+            vec![
+                handle(HandleKind::Start, start),
+                handle(HandleKind::End, end),
+                handle(HandleKind::Rotate, Point::new(middle.x + nx * rotate_distance, middle.y + ny * rotate_distance)),
+            ]
+        }
+        Drawable::Circle(circle) => {
+            let center = circle.center();
+            vec![handle(HandleKind::Radius, Point::new(center.x + circle.radius(), center.y))]
+        }
+        Drawable::Rect(rect) => {
+            let center = rect.center();
+            let top = Point::new(center.x, center.y - rect.height() / 2f64 - rotate_distance);
+
+            let mut handles: Vec<Handle> = rect
+                .corners()
+                .into_iter()
+                .enumerate()
+                .map(|(i, corner)| handle(HandleKind::Corner(i), corner))
+                .collect();
+            handles.push(handle(HandleKind::Rotate, top.rotated_about(&center, rect.rotation())));
+
+            handles
+        }
+        Drawable::Arc(arc) => vec![handle(HandleKind::Start, arc.start()), handle(HandleKind::End, arc.end()), handle(HandleKind::Radius, arc.midpoint())],
+        Drawable::Point(_) | Drawable::Text(_) => Vec::new(),
+    }
+}
+
+/// moves the handle `kind` of `drawable` to `to`. with `keep_aspect` a rect
+/// keeps its proportions and a line endpoint stays on the line. the ends of
+/// an arc turn to face `to` and stay on its circle.
+/// rotating is done with `Drawable::rotate` instead.
+pub(crate) fn drag_handle(drawable: &mut Drawable, kind: HandleKind, to: &Point, keep_aspect: bool) {
+    match (drawable, kind) {
+        (Drawable::Line(line), HandleKind::Start | HandleKind::End) => {
+            let (moved, fixed) = match kind {
+                HandleKind::Start => (line.start(), line.end()),
+                _ => (line.end(), line.start()),
+            };
+            let to = if keep_aspect { project(to, &fixed, &moved) } else { to.clone() };
+
+            *line = match kind {
+                HandleKind::Start => Line::new(&to, &fixed),
+                _ => Line::new(&fixed, &to),
+            }
+            .with_style(line.style().clone());
+        }
+        (Drawable::Circle(circle), HandleKind::Radius) => {
+            let center = circle.center();
+            *circle = Circle::new(&center, ((to.x - center.x).powi(2) + (to.y - center.y).powi(2)).sqrt()).with_style(circle.style().clone());
+        }
+        (Drawable::Arc(arc), HandleKind::Start | HandleKind::End | HandleKind::Radius) => {
+            let (len, angle) = (to.clone() - arc.center()).to_polar();
+            let radius = if kind == HandleKind::Radius { len } else { arc.radius() };
+            let start = if kind == HandleKind::Start { Angle::radians(angle) } else { arc.start_angle() };
+            let end = if kind == HandleKind::End { Angle::radians(angle) } else { arc.end_angle() };
+
+            *arc = Arc::new(&arc.center(), radius, start, end).with_style(arc.style().clone());
+        }
+        (Drawable::Rect(rect), HandleKind::Corner(i)) if i < 4 => {
+            let fixed = rect.corners()[(i + 2) % 4].clone();
+
+            // the drag in the rect's own frame, before its rotation
+            let local = Point::new(to.x - fixed.x, to.y - fixed.y).rotated_about(&Point::new(0f64, 0f64), -rect.rotation());
+            let (mut dx, mut dy) = (local.x, local.y);
+
+            if keep_aspect && rect.width() > 0f64 && rect.height() > 0f64 {
+                let scale = (dx.abs() / rect.width()).max(dy.abs() / rect.height());
+                dx = rect.width() * scale * if dx < 0f64 { -1f64 } else { 1f64 };
+                dy = rect.height() * scale * if dy < 0f64 { -1f64 } else { 1f64 };
+            }
+
+            let half = Point::new(dx / 2f64, dy / 2f64).rotated_about(&Point::new(0f64, 0f64), rect.rotation());
+            let center = Point::new(fixed.x + half.x, fixed.y + half.y);
+
+            *rect = Rect::from_center(&center, dx.abs(), dy.abs(), rect.rotation()).with_style(rect.style().clone());
+        }
+        _ => {}
+    }
+}
+
+/// the closest point to `point` on the line through `from` and `through`.
+fn project(point: &Point, from: &Point, through: &Point) -> Point {
+    let (dx, dy) = (through.x - from.x, through.y - from.y);
+    let len_squared = dx.powi(2) + dy.powi(2);
+
+    if len_squared == 0f64 {
+        return point.clone();
+    }
+
+    let t = ((point.x - from.x) * dx + (point.y - from.y) * dy) / len_squared;
+    Point::new(from.x + t * dx, from.y + t * dy)
+}
@@ -0,0 +1,591 @@
+//! # json
+//! this module contains the JSON format drawings are saved in.
+//! only the source of truth of every drawable is stored, cached values
+//! like a line's length are recomputed by the constructors when loading.
+
+use super::canvas::validate;
+use super::{Angle, Arc, Canvas, CanvasError, Circle, Color, Drawable, Line, LineCap, LineJoin, MetadataKeyError, Paint, Point, Props, Rect, StrokeAlign, StrokeStyle, Style, Text, Unit, DEFAULT_FONT_SIZE};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// the version written to every document,
+/// bumped whenever the format changes incompatibly.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// the error returned when a drawing can't be loaded from JSON.
+#[derive(Debug)]
+pub enum JsonError {
+    /// the text isn't a valid drawing.
+    Syntax(serde_json::Error),
+    /// the drawing was saved in a format this version can't read.
+    UnsupportedVersion(u32),
+    /// a drawable was read but can't be on a canvas, like a line without a length.
+    InvalidDrawable(CanvasError),
+    /// a metadata key can't be written as an attribute's name.
+    InvalidMetadataKey(MetadataKeyError),
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonError::Syntax(error) => write!(f, "invalid drawing: {}", error),
+            JsonError::UnsupportedVersion(version) => write!(f, "unsupported drawing version {}", version),
+            JsonError::InvalidDrawable(error) => write!(f, "invalid drawable: {}", error),
+            JsonError::InvalidMetadataKey(error) => write!(f, "invalid metadata: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonError::Syntax(error) => Some(error),
+            JsonError::InvalidMetadataKey(error) => Some(error),
+            JsonError::UnsupportedVersion(_) | JsonError::InvalidDrawable(_) => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JsonError {
+    fn from(error: serde_json::Error) -> Self {
+        JsonError::Syntax(error)
+    }
+}
+
+#[derive(Deserialize)]
+struct Version {
+    version: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Document {
+    version: u32,
+    width: f64,
+    height: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    background: Option<String>,
+    /// the unit lengths are shown in, with how many pixels make one of it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unit: Option<DisplayUnit>,
+    drawables: Vec<Entry>,
+}
+
+/// a drawable with what the canvas knows about it besides its shape.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    #[serde(flatten)]
+    shape: Styled,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    hidden: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+#[derive(Serialize, Deserialize)]
+struct DisplayUnit {
+    name: String,
+    px_per_unit: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Shape {
+    Point { pos: Point },
+    Line { start: Point, end: Point },
+    Circle { center: Point, radius: f64 },
+    Rect {
+        corner: Point,
+        width: f64,
+        height: f64,
+        #[serde(default)]
+        rotation: f64,
+    },
+    Text {
+        text: String,
+        pos: Point,
+        #[serde(default = "default_font_size")]
+        font_size: f64,
+    },
+    /// its angles in radians, like a rect's rotation, and how far it turns
+    /// rather than where it ends, so it loads back exactly.
+    Arc { center: Point, radius: f64, start_angle: f64, sweep: f64 },
+}
+
+/// drawings saved before texts had a size use the default one.
+fn default_font_size() -> f64 {
+    DEFAULT_FONT_SIZE
+}
+
+impl From<&Drawable> for Shape {
+    fn from(drawable: &Drawable) -> Self {
+        match drawable {
+            Drawable::Point(point) => Shape::Point { pos: point.clone() },
+            Drawable::Line(line) => Shape::Line { start: line.start(), end: line.end() },
+            Drawable::Circle(circle) => Shape::Circle { center: circle.center(), radius: circle.radius() },
+            Drawable::Rect(rect) => Shape::Rect {
+                corner: rect.corner(),
+                width: rect.width(),
+                height: rect.height(),
+                rotation: rect.rotation(),
+            },
+            Drawable::Text(text) => Shape::Text { text: text.text().clone(), pos: text.pos(), font_size: text.font_size() },
+            Drawable::Arc(arc) => Shape::Arc {
+                center: arc.center(),
+                radius: arc.radius(),
+                start_angle: arc.start_angle().to_radians(),
+                sweep: arc.sweep().to_radians(),
+            },
+        }
+    }
+}
+
+impl From<Shape> for Drawable {
+    fn from(shape: Shape) -> Self {
+        match shape {
+            Shape::Point { pos } => Drawable::Point(pos),
+            Shape::Line { start, end } => Drawable::Line(Line::new(&start, &end)),
+            Shape::Circle { center, radius } => Drawable::Circle(Circle::new(&center, radius)),
+            Shape::Rect { corner, width, height, rotation } => {
+                let center = Point::new(corner.x + width / 2f64, corner.y + height / 2f64);
+                Drawable::Rect(Rect::from_center(&center, width, height, rotation))
+            }
+            Shape::Text { text, pos, font_size } => Drawable::Text(Text::with_font_size(text, pos, font_size)),
+            Shape::Arc { center, radius, start_angle, sweep } => {
+                Drawable::Arc(Arc::from_sweep(&center, radius, Angle::radians(start_angle), Angle::radians(sweep)))
+            }
+        }
+    }
+}
+
+/// a shape with how it's painted, only the style options
+/// that were set are saved so unstyled drawings stay as they were.
+#[derive(Serialize, Deserialize)]
+struct Styled {
+    #[serde(flatten)]
+    shape: Shape,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stroke: Option<Paint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stroke_width: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fill: Option<Paint>,
+    #[serde(default, skip_serializing_if = "is_centered")]
+    stroke_align: StrokeAlign,
+    #[serde(default, skip_serializing_if = "is_solid")]
+    stroke_style: StrokeStyle,
+    #[serde(default, skip_serializing_if = "is_butt")]
+    line_cap: LineCap,
+    #[serde(default, skip_serializing_if = "is_miter")]
+    line_join: LineJoin,
+}
+
+fn is_centered(align: &StrokeAlign) -> bool {
+    *align == StrokeAlign::Center
+}
+
+fn is_solid(stroke_style: &StrokeStyle) -> bool {
+    *stroke_style == StrokeStyle::Solid
+}
+
+fn is_butt(line_cap: &LineCap) -> bool {
+    *line_cap == LineCap::Butt
+}
+
+fn is_miter(line_join: &LineJoin) -> bool {
+    *line_join == LineJoin::Miter
+}
+
+impl From<&Drawable> for Styled {
+    fn from(drawable: &Drawable) -> Self {
+        let style = drawable.style().cloned().unwrap_or_default();
+
+        Styled {
+            shape: drawable.into(),
+            stroke: style.stroke,
+            stroke_width: style.stroke_width,
+            fill: style.fill,
+            stroke_align: style.stroke_align,
+            stroke_style: style.stroke_style,
+            line_cap: style.line_cap,
+            line_join: style.line_join,
+        }
+    }
+}
+
+impl From<Styled> for Drawable {
+    fn from(styled: Styled) -> Self {
+        let mut drawable = Drawable::from(styled.shape);
+        drawable.set_style(Style {
+            stroke: styled.stroke,
+            stroke_width: styled.stroke_width,
+            fill: styled.fill,
+            stroke_align: styled.stroke_align,
+            stroke_style: styled.stroke_style,
+            line_cap: styled.line_cap,
+            line_join: styled.line_join,
+        });
+
+        drawable
+    }
+}
+
+/// colors are saved as they're written to SVG, like `#ff0000`.
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// paints are saved as their color, or as a gradient's stops
+/// and its angle in radians, like a rect's rotation.
+impl Serialize for Paint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Paint::Solid(color) => color.serialize(serializer),
+            Paint::LinearGradient { stops, angle } => SavedGradient { stops: stops.clone(), angle: angle.to_radians() }.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Paint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Saved {
+            Solid(Color),
+            LinearGradient(SavedGradient),
+        }
+
+        Ok(match Saved::deserialize(deserializer)? {
+            Saved::Solid(color) => Paint::Solid(color),
+            Saved::LinearGradient(gradient) => Paint::LinearGradient { stops: gradient.stops, angle: Angle::radians(gradient.angle) },
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedGradient {
+    stops: Vec<(f64, Color)>,
+    angle: f64,
+}
+
+/// stroke alignments are saved by name, like `inside`.
+impl Serialize for StrokeAlign {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self {
+            StrokeAlign::Center => "center",
+            StrokeAlign::Inside => "inside",
+            StrokeAlign::Outside => "outside",
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for StrokeAlign {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "center" => Ok(StrokeAlign::Center),
+            "inside" => Ok(StrokeAlign::Inside),
+            "outside" => Ok(StrokeAlign::Outside),
+            other => Err(de::Error::unknown_variant(other, &["center", "inside", "outside"])),
+        }
+    }
+}
+
+/// stroke styles are saved by name, like `dotted`, and dashes as their lengths.
+impl Serialize for StrokeStyle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            StrokeStyle::Solid => serializer.serialize_str("solid"),
+            StrokeStyle::Dashed(dashes) => dashes.serialize(serializer),
+            StrokeStyle::Dotted => serializer.serialize_str("dotted"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for StrokeStyle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Saved {
+            Name(String),
+            Dashes(Vec<f64>),
+        }
+
+        match Saved::deserialize(deserializer)? {
+            Saved::Dashes(dashes) => Ok(StrokeStyle::Dashed(dashes)),
+            Saved::Name(name) => match name.as_str() {
+                "solid" => Ok(StrokeStyle::Solid),
+                "dotted" => Ok(StrokeStyle::Dotted),
+                other => Err(de::Error::unknown_variant(other, &["solid", "dotted"])),
+            },
+        }
+    }
+}
+
+/// line caps and joins are saved by their SVG names, like `round`.
+impl Serialize for LineCap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for LineCap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for LineJoin {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for LineJoin {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// drawables are saved the way drawings store them, their kind beside
+/// their shape and the style options that were set.
+///
+/// # Examples
+/// ```
+/// use program_core::{Angle, Arc, Circle, Color, Drawable, Line, Point, Rect, StrokeStyle, StyleBuilder, Text};
+///
+/// let line = Line::builder(&Point::new(0.0, 0.0), &Point::new(3.0, 4.0)).stroke(Color::RED).stroke_style(StrokeStyle::Dotted).build();
+/// let json = serde_json::to_string(&line).unwrap();
+/// assert_eq!(r##"{"kind":"line","start":{"x":0.0,"y":0.0},"end":{"x":3.0,"y":4.0},"stroke":"#ff0000","stroke_style":"dotted"}"##, json);
+/// assert_eq!(line, serde_json::from_str::<Line>(&json).unwrap());
+///
+/// let circle = Circle::builder(&Point::new(5.0, 5.0), 2.0).fill(Color::BLUE).build();
+/// assert_eq!(circle, serde_json::from_str::<Circle>(&serde_json::to_string(&circle).unwrap()).unwrap());
+/// let rect = Rect::from_center(&Point::new(10.0, 10.0), 4.0, 2.0, 0.5);
+/// assert_eq!(rect, serde_json::from_str::<Rect>(&serde_json::to_string(&rect).unwrap()).unwrap());
+/// let text = Text::with_font_size(String::from("<N>"), Point::new(1.0, 2.0), 18.0);
+/// assert_eq!(text, serde_json::from_str::<Text>(&serde_json::to_string(&text).unwrap()).unwrap());
+/// let arc = Arc::new(&Point::new(0.0, 0.0), 10.0, Angle::degrees(300.0), Angle::degrees(60.0));
+/// assert_eq!(arc, serde_json::from_str::<Arc>(&serde_json::to_string(&arc).unwrap()).unwrap());
+///
+/// // a mix of kinds, each read back as what it was
+/// let drawables = vec![Drawable::Line(line), Drawable::Circle(circle.clone()), Drawable::Rect(rect), Drawable::Text(text), Drawable::Point(Point::new(7.0, 8.0))];
+/// let json = serde_json::to_string(&drawables).unwrap();
+/// assert_eq!(drawables, serde_json::from_str::<Vec<Drawable>>(&json).unwrap());
+///
+/// // a shape is only read back from its own kind
+/// assert!(serde_json::from_str::<Line>(&serde_json::to_string(&circle).unwrap()).is_err());
+///
+/// ```
+impl Serialize for Drawable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Styled::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Drawable {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Styled::deserialize(deserializer).map(Drawable::from)
+    }
+}
+
+/// saves each kind of shape like the drawable holding it, see `Drawable`.
+macro_rules! serialize_as_drawable {
+    ($($shape:ident => $kind:literal),* $(,)?) => {$(
+        impl Serialize for $shape {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                Styled::from(&Drawable::$shape(self.clone())).serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $shape {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                match Drawable::deserialize(deserializer)? {
+                    Drawable::$shape(shape) => Ok(shape),
+                    other => Err(de::Error::custom(format!("expected a {}, found a {}", $kind, Props::from(&other).kind()))),
+                }
+            }
+        }
+    )*};
+}
+
+serialize_as_drawable!(Line => "line", Circle => "circle", Rect => "rect", Text => "text", Arc => "arc");
+
+/// drawables saved apart from any drawing.
+#[derive(Serialize, Deserialize)]
+struct Clip {
+    version: u32,
+    drawables: Vec<Styled>,
+}
+
+/// saves `drawables` as JSON on their own, like the ones making up a stamp,
+/// to be loaded back with `drawables_from_json`.
+///
+/// # Examples
+/// ```
+/// use program_core::{
+///     drawables_from_json, drawables_to_json, Circle, Color, Drawable, JsonError, Line, LineCap, LineJoin, Point, Rect, StrokeAlign, StrokeStyle, StyleBuilder, Text,
+/// };
+///
+/// let drawables = vec![
+///     Drawable::Line(Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0))),
+///     Drawable::Line(Line::builder(&Point::new(0.0, 5.0), &Point::new(10.0, 5.0)).stroke_style(StrokeStyle::Dashed(vec![3.0, 1.0])).build()),
+///     Drawable::Line(Line::builder(&Point::new(0.0, 9.0), &Point::new(10.0, 9.0)).stroke_style(StrokeStyle::Dotted).build()),
+///     Drawable::Line(Line::builder(&Point::new(0.0, 2.0), &Point::new(10.0, 2.0)).line_cap(LineCap::Square).line_join(LineJoin::Round).build()),
+///     Drawable::Circle(Circle::builder(&Point::new(5.0, 5.0), 2.0).stroke(Color::rgba(0, 0, 255, 128)).fill_none().build()),
+///     Drawable::Rect(Rect::builder(&Point::new(0.0, 0.0), &Point::new(4.0, 3.0)).stroke_width(0.5).stroke_align(StrokeAlign::Inside).build()),
+///     Drawable::Text(Text::new(String::from("N"), Point::new(4.0, -3.0))),
+/// ];
+///
+/// let json = drawables_to_json(&drawables);
+/// assert_eq!(drawables, drawables_from_json(&json).unwrap());
+///
+/// assert!(matches!(drawables_from_json(r#"{"version": 99, "drawables": []}"#), Err(JsonError::UnsupportedVersion(99))));
+/// assert!(matches!(drawables_from_json("[]"), Err(JsonError::Syntax(_))));
+///
+/// let flat = r#"{"version": 1, "drawables": [{"kind": "line", "start": {"x": 1.0, "y": 1.0}, "end": {"x": 1.0, "y": 1.0}}]}"#;
+/// assert!(matches!(drawables_from_json(flat), Err(JsonError::InvalidDrawable(program_core::CanvasError::ZeroLength))));
+///
+/// ```
+pub fn drawables_to_json(drawables: &[Drawable]) -> String {
+    let clip = Clip { version: FORMAT_VERSION, drawables: drawables.iter().map(Styled::from).collect() };
+
+    serde_json::to_string(&clip).expect("drawables only hold strings and numbers")
+}
+
+/// loads drawables saved with `drawables_to_json`.
+///
+/// # Examples
+/// random drawables load back with the same properties and style.
+/// ```
+/// use program_core::{drawables_from_json, drawables_to_json, Angle, Arc, Circle, Color, Drawable, Line, Paint, Point, Props, Rect, Style, Text};
+///
+/// // a xorshift generator, so every run checks the same drawables
+/// let mut seed: u64 = 0x2545f4914f6cdd1d;
+/// let mut number = move |max: f64| {
+///     seed ^= seed << 13;
+///     seed ^= seed >> 7;
+///     seed ^= seed << 17;
+///     (seed % 1000000007) as f64 / 1000000007.0 * max
+/// };
+///
+/// let drawables: Vec<Drawable> = (0..360)
+///     .map(|i| {
+///         let (a, b) = (Point::new(number(1000.0), number(1000.0)), Point::new(number(1000.0), number(1000.0)));
+///         let color = Color::rgba(number(256.0) as u8, number(256.0) as u8, number(256.0) as u8, 1 + number(255.0) as u8);
+///         let fill = match i % 3 {
+///             0 => Some(Paint::Solid(Color::NONE)),
+///             1 => Some(Paint::LinearGradient { stops: vec![(0.0, color), (number(1.0), Color::WHITE)], angle: Angle::radians(number(7.0)) }),
+///             _ => None,
+///         };
+///         let style = Style { stroke: Some(color.into()), stroke_width: Some(number(10.0)), fill, ..Style::default() };
+///         match i % 6 {
+///             0 => Drawable::Point(a),
+///             1 => Line::new(&a, &b).with_style(style).into(),
+///             2 => Circle::new(&a, 1.0 + b.x).with_style(style).into(),
+///             3 => Rect::from_center(&a, 1.0 + b.x, 1.0 + b.y, number(7.0) - 3.5).with_style(style).into(),
+///             4 => Text::with_font_size(format!("\"{}\" & <{}>", b.x, b.y), a, 1.0 + number(50.0)).with_style(style).into(),
+///             _ => Arc::new(&a, 1.0 + b.x, Angle::radians(number(14.0) - 7.0), Angle::radians(number(14.0) - 7.0)).with_style(style).into(),
+///         }
+///     })
+///     .collect();
+///
+/// let loaded = drawables_from_json(&drawables_to_json(&drawables)).unwrap();
+/// assert_eq!(drawables, loaded);
+/// for (drawable, loaded) in drawables.iter().zip(&loaded) {
+///     assert_eq!(Props::from(drawable), Props::from(loaded));
+///     assert_eq!(drawable.style(), loaded.style());
+/// }
+///
+/// ```
+pub fn drawables_from_json(json: &str) -> Result<Vec<Drawable>, JsonError> {
+    let Version { version } = serde_json::from_str(json)?;
+    if version != FORMAT_VERSION {
+        return Err(JsonError::UnsupportedVersion(version));
+    }
+
+    let clip: Clip = serde_json::from_str(json)?;
+    let drawables: Vec<Drawable> = clip.drawables.into_iter().map(Drawable::from).collect();
+    for drawable in &drawables {
+        validate(drawable).map_err(JsonError::InvalidDrawable)?;
+    }
+
+    Ok(drawables)
+}
+
+pub(crate) fn to_json(canvas: &Canvas) -> String {
+    let document = Document {
+        version: FORMAT_VERSION,
+        width: canvas.width(),
+        height: canvas.height(),
+        title: canvas.title().cloned(),
+        description: canvas.description().cloned(),
+        metadata: canvas.metadata().clone(),
+        background: canvas.background().map(String::from),
+        unit: Some(canvas.unit()).filter(|unit| *unit != Unit::Px).map(|unit| DisplayUnit {
+            name: unit.symbol().to_string(),
+            px_per_unit: unit.px_per_unit(),
+        }),
+        drawables: canvas
+            .drawables()
+            .map(|(id, drawable)| Entry {
+                shape: Styled::from(&**drawable),
+                name: canvas.name(id).map(String::from),
+                hidden: canvas.is_hidden(id),
+            })
+            .collect(),
+    };
+
+    serde_json::to_string(&document).expect("a document only holds strings and numbers")
+}
+
+pub(crate) fn from_json(json: &str) -> Result<Canvas, JsonError> {
+    let Version { version } = serde_json::from_str(json)?;
+    if version != FORMAT_VERSION {
+        return Err(JsonError::UnsupportedVersion(version));
+    }
+
+    let document: Document = serde_json::from_str(json)?;
+    let mut canvas = Canvas::new(document.width, document.height);
+
+    if let Some(title) = document.title {
+        canvas.set_title(title);
+    }
+    if let Some(description) = document.description {
+        canvas.set_description(description);
+    }
+    for (key, value) in document.metadata {
+        canvas.set_metadata(key, value).map_err(JsonError::InvalidMetadataKey)?;
+    }
+    canvas.set_background(document.background);
+    // a unit this version doesn't know falls back to pixels
+    if let Some(unit) = document.unit.and_then(|unit| unit.name.parse::<Unit>().ok()) {
+        canvas.set_unit(unit);
+    }
+    let (shapes, entries): (Vec<Styled>, Vec<(Option<String>, bool)>) =
+        document.drawables.into_iter().map(|entry| (entry.shape, (entry.name, entry.hidden))).unzip();
+    let ids = canvas.add_drawables(shapes.into_iter().map(Drawable::from).collect()).map_err(JsonError::InvalidDrawable)?;
+    for (id, (name, hidden)) in ids.into_iter().zip(entries) {
+        canvas.set_name(id, name);
+        canvas.set_hidden(id, hidden);
+    }
+
+    Ok(canvas)
+}
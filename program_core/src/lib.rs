@@ -1,12 +1,759 @@
 mod drawable;
+mod canvas;
+mod export;
+mod props;
+#[cfg(feature = "raster")]
+mod raster;
+mod guides;
+mod handles;
+mod viewport;
+#[cfg(feature = "serde")]
+mod json;
+mod snap;
+mod spatial_index;
+mod svg_import;
+mod storage;
+mod units;
 
+use drawable::{Draw, HIT_TOLERANCE, TAG_CAPACITY};
+use std::fmt;
+
+/// # Drawable
+/// any of the shapes a canvas holds.
+///
+/// # Examples
+/// random drawables keep their geometry and style when exported to SVG
+/// and imported back, their bounding boxes hold their outlines, and clicking
+/// anywhere on their outlines hits them.
+/// ```
+/// use std::f64::consts::PI;
+/// use program_core::{Angle, Arc, Canvas, Circle, Color, Drawable, Line, Paint, Point, Props, Rect, Style, Text};
+///
+/// // a xorshift generator, so every run checks the same drawables
+/// struct Random(u64);
+///
+/// impl Random {
+///     fn number(&mut self, max: f64) -> f64 {
+///         self.0 ^= self.0 << 13;
+///         self.0 ^= self.0 >> 7;
+///         self.0 ^= self.0 << 17;
+///         (self.0 % 100000) as f64 / 100000.0 * max
+///     }
+///
+///     fn point(&mut self) -> Point {
+///         Point::new(self.number(1000.0), self.number(1000.0))
+///     }
+///
+///     fn color(&mut self) -> Option<Paint> {
+///         let (r, g, b) = (self.number(256.0) as u8, self.number(256.0) as u8, self.number(256.0) as u8);
+///         match self.number(4.0) as u8 {
+///             0 => None,
+///             1 => Some(Paint::Solid(Color::NONE)),
+///             2 => Some(Paint::Solid(Color::rgba(r, g, b, 1 + self.number(255.0) as u8))),
+///             _ => Some(Paint::Solid(Color::rgb(r, g, b))),
+///         }
+///     }
+///
+///     fn drawable(&mut self) -> Drawable {
+///         let style = Style { stroke: self.color(), stroke_width: (self.number(2.0) < 1.0).then(|| 0.5 + self.number(10.0)), fill: self.color(), ..Style::default() };
+///         match self.number(5.0) as u8 {
+///             0 => Line::new(&self.point(), &self.point()).with_style(style).into(),
+///             1 => Circle::new(&self.point(), 1.0 + self.number(200.0)).with_style(style).into(),
+///             2 => Rect::from_center(&self.point(), 1.0 + self.number(300.0), 1.0 + self.number(300.0), self.number(2.0 * PI) - PI)
+///                 .with_style(style)
+///                 .into(),
+///             3 => Arc::new(&self.point(), 1.0 + self.number(200.0), Angle::degrees(self.number(720.0) - 360.0), Angle::degrees(self.number(720.0) - 360.0))
+///                 .with_style(style)
+///                 .into(),
+///             _ => {
+///                 let content = (0..1 + self.number(8.0) as usize).map(|_| b"ab<>&\"'x"[self.number(8.0) as usize] as char).collect();
+///                 Text::with_font_size(content, self.point(), 4.0 + self.number(40.0)).with_style(style).into()
+///             }
+///         }
+///     }
+/// }
+///
+/// // the numbers of the properties, compared within a tolerance
+/// fn numbers(drawable: &Drawable) -> Vec<f64> {
+///     match Props::from(drawable) {
+///         Props::Point(point) => vec![point.pos.x, point.pos.y],
+///         Props::Line(line) => vec![line.start.x, line.start.y, line.end.x, line.end.y],
+///         Props::Circle(circle) => vec![circle.center.x, circle.center.y, circle.radius],
+///         Props::Rect(rect) => vec![rect.corner.x, rect.corner.y, rect.width, rect.height, rect.rotation.to_radians()],
+///         Props::Text(text) => vec![text.pos.x, text.pos.y, text.font_size],
+///         // the center of an arc of about half a turn moves a lot for a small
+///         // change of its ends, so its ends are compared instead
+///         Props::Arc(_) => {
+///             let Drawable::Arc(arc) = drawable else { unreachable!() };
+///             vec![arc.radius(), arc.start().x, arc.start().y, arc.end().x, arc.end().y]
+///         }
+///     }
+/// }
+///
+/// fn outline(drawable: &Drawable) -> Vec<Point> {
+///     let along = |a: &Point, b: &Point| (0..=10).map(|i| i as f64 / 10.0).map(|t| Point::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)).collect::<Vec<_>>();
+///     match drawable {
+///         Drawable::Line(line) => along(&line.start(), &line.end()),
+///         Drawable::Circle(circle) => (0..16)
+///             .map(|i| i as f64 * PI / 8.0)
+///             .map(|angle| Point::new(circle.center().x + circle.radius() * angle.cos(), circle.center().y + circle.radius() * angle.sin()))
+///             .collect(),
+///         Drawable::Rect(rect) => {
+///             let corners = rect.corners();
+///             (0..4).flat_map(|i| along(&corners[i], &corners[(i + 1) % 4])).collect()
+///         }
+///         Drawable::Arc(arc) => (0..=10)
+///             .map(|i| arc.start_angle().to_radians() + arc.sweep().to_radians() * i as f64 / 10.0)
+///             .map(|angle| Point::new(arc.center().x + arc.radius() * angle.cos(), arc.center().y + arc.radius() * angle.sin()))
+///             .collect(),
+///         Drawable::Point(_) | Drawable::Text(_) => vec![drawable.center()],
+///     }
+/// }
+///
+/// let mut random = Random(0x2545f4914f6cdd1d);
+/// let mut original = Canvas::new(1000.0, 1000.0);
+/// for _ in 0..200 {
+///     original.add(random.drawable()).unwrap();
+/// }
+///
+/// let mut imported = Canvas::new(1000.0, 1000.0);
+/// let ids = imported.import_svg(&original.to_svg()).unwrap();
+/// assert_eq!(original.len(), ids.len());
+///
+/// for ((_, drawable), id) in original.drawables().zip(ids) {
+///     let back = imported.get(id).unwrap();
+///     assert_eq!(drawable.style(), back.style());
+///     match (&**drawable, &**back) {
+///         (Drawable::Text(text), Drawable::Text(back)) => assert_eq!(text.text(), back.text()),
+///         (drawable, back) => assert_eq!(std::mem::discriminant(drawable), std::mem::discriminant(back)),
+///     }
+///     for (number, back) in numbers(drawable).into_iter().zip(numbers(back)) {
+///         assert!((number - back).abs() < 1e-9, "{:?} was imported as {:?}", drawable, back);
+///     }
+///
+///     let bounds = drawable.bounding_box().expand(1e-9);
+///     for point in outline(drawable) {
+///         assert!(bounds.contains(&point), "{:?} is outside of {:?}", point, drawable);
+///         assert!(drawable.contains(&point), "{:?} doesn't hit {:?}", point, drawable);
+///     }
+/// }
+///
+/// // cases that went wrong before: the corners of a turned rect, the
+/// // default colors, and lines going every way from the same point
+/// let turned = Drawable::Rect(Rect::from_center(&Point::new(0.0, 0.0), 40.0, 10.0, PI / 2.0));
+/// assert!(turned.contains(&Point::new(0.0, 19.0)));
+/// assert!(!turned.contains(&Point::new(19.0, 0.0)));
+/// assert_eq!(("#000000", "#ffffff"), (Color::BLACK.to_string().as_str(), Color::WHITE.to_string().as_str()));
+///
+/// // the angle of a line was once the atan of its slope, which is
+/// // the same for lines going opposite ways
+/// for (end, angle) in [
+///     (Point::new(10.0, 10.0), PI / 4.0),
+///     (Point::new(-10.0, 10.0), 3.0 * PI / 4.0),
+///     (Point::new(-10.0, -10.0), -3.0 * PI / 4.0),
+///     (Point::new(10.0, -10.0), -PI / 4.0),
+/// ] {
+///     let Drawable::Line(line) = Drawable::Line(Line::new(&Point::new(0.0, 0.0), &end)) else { unreachable!() };
+///     assert!((line.angle() - angle).abs() < 1e-12, "{:?} has an angle of {}", line, line.angle());
+///
+///     let line = Drawable::Line(line);
+///     let bounds = line.bounding_box();
+///     assert!(bounds.contains(&end) && bounds.contains(&Point::new(0.0, 0.0)));
+///     assert!(line.contains(&Point::new(end.x / 2.0, end.y / 2.0)));
+///     assert!(!line.contains(&Point::new(end.x / 2.0, -end.y / 2.0)));
+/// }
+///
+/// ```
+///
+/// random lines in each quadrant keep their ends and have an angle in
+/// that quadrant, which a line drawn from it at that angle gives back.
+/// ```
+/// use std::f64::consts::{FRAC_PI_2, PI};
+/// use program_core::{Line, Point};
+///
+/// let mut seed: u64 = 0xd1b54a32d192ed03;
+/// let mut number = move |max: f64| {
+///     seed ^= seed << 13;
+///     seed ^= seed >> 7;
+///     seed ^= seed << 17;
+///     (seed % 1000000007) as f64 / 1000000007.0 * max
+/// };
+///
+/// // the signs of the run and the rise, and the angles, of each quadrant
+/// let quadrants = [((1.0, 1.0), 0.0..FRAC_PI_2), ((-1.0, 1.0), FRAC_PI_2..PI), ((-1.0, -1.0), -PI..-FRAC_PI_2), ((1.0, -1.0), -FRAC_PI_2..0.0)];
+///
+/// for i in 0..4000 {
+///     let ((sign_x, sign_y), angles) = quadrants[i % 4].clone();
+///     let start = Point::new(number(2000.0) - 1000.0, number(2000.0) - 1000.0);
+///     let (run, rise) = (sign_x * (0.001 + number(500.0)), sign_y * (0.001 + number(500.0)));
+///     let end = Point::new(start.x + run, start.y + rise);
+///     let line = Line::new(&start, &end);
+///
+///     assert_eq!((start.clone(), end.clone()), (line.start(), line.end()));
+///     assert!(angles.contains(&line.angle()), "{:?} has an angle of {} outside {:?}", line, line.angle(), angles);
+///     assert!((line.angle() - (end.y - start.y).atan2(end.x - start.x)).abs() < 1e-12);
+///
+///     let back = Line::from_polar(&start, line.len(), line.angle());
+///     assert!(back.end().approx_eq(&end, 1e-9), "{:?} came back as {:?}", line, back);
+/// }
+///
+/// ```
+///
+/// moving, turning or scaling a drawable then doing the opposite gives it back.
+/// ```
+/// use program_core::{Angle, Arc, Circle, Drawable, Line, Point, Rect, Text};
+///
+/// // coordinates are up to ten thousand after scaling, so they're off by more than `f64::EPSILON`
+/// const EPSILON: f64 = 1e-9;
+///
+/// // the numbers that place the drawable, a rect's corners rather than
+/// // its rotation and an arc's ends rather than its angles, which may
+/// // come back a whole turn away
+/// fn numbers(drawable: &Drawable) -> Vec<f64> {
+///     match drawable {
+///         Drawable::Point(point) => vec![point.x, point.y],
+///         Drawable::Line(line) => vec![line.start().x, line.start().y, line.end().x, line.end().y],
+///         Drawable::Circle(circle) => vec![circle.center().x, circle.center().y, circle.radius()],
+///         Drawable::Rect(rect) => rect.corners().iter().flat_map(|corner| [corner.x, corner.y]).chain([rect.width(), rect.height()]).collect(),
+///         Drawable::Text(text) => vec![text.pos().x, text.pos().y, text.font_size()],
+///         Drawable::Arc(arc) => vec![arc.center().x, arc.center().y, arc.radius(), arc.start().x, arc.start().y, arc.end().x, arc.end().y],
+///     }
+/// }
+///
+/// let mut seed: u64 = 0x9e3779b97f4a7c15;
+/// let mut number = move |max: f64| {
+///     seed ^= seed << 13;
+///     seed ^= seed >> 7;
+///     seed ^= seed << 17;
+///     (seed % 100000) as f64 / 100000.0 * max
+/// };
+///
+/// for _ in 0..100 {
+///     let (a, b) = (Point::new(number(1000.0), number(1000.0)), Point::new(number(1000.0), number(1000.0)));
+///     let pivot = Point::new(number(1000.0), number(1000.0));
+///     let (angle, factor) = (number(6.0) - 3.0, 0.1 + number(10.0));
+///
+///     for drawable in [
+///         Drawable::Point(a.clone()),
+///         Drawable::Line(Line::new(&a, &b)),
+///         Drawable::Circle(Circle::new(&a, 1.0 + b.x)),
+///         Drawable::Rect(Rect::from_center(&a, 1.0 + b.x, 1.0 + b.y, angle)),
+///         Drawable::Text(Text::with_font_size(String::from("x"), a.clone(), 1.0 + b.x / 10.0)),
+///         Drawable::Arc(Arc::new(&a, 1.0 + b.x, Angle::radians(angle), Angle::radians(b.y))),
+///     ] {
+///         let mut moved = drawable.clone();
+///         moved.translate(&pivot);
+///         moved.translate(&Point::new(-pivot.x, -pivot.y));
+///         moved.rotate(Angle::radians(angle), &pivot);
+///         moved.rotate(Angle::radians(-angle), &pivot);
+///         moved.scale(factor, &pivot);
+///         moved.scale(1.0 / factor, &pivot);
+///
+///         assert_eq!(std::mem::discriminant(&drawable), std::mem::discriminant(&moved));
+///         for (before, after) in numbers(&drawable).into_iter().zip(numbers(&moved)) {
+///             assert!((before - after).abs() < EPSILON, "{:?} became {:?}", drawable, moved);
+///         }
+///     }
+/// }
+///
+/// ```
 #[derive(PartialEq, Clone, Debug)]
 pub enum Drawable {
     Point(drawable::point2d::Point2D),
     Line(drawable::line2d::Line2D),
+    Circle(drawable::circle::Circle),
+    Rect(drawable::rect::Rect),
+    Text(drawable::text::Text),
+    Arc(drawable::arc::Arc),
+}
+
+impl Drawable {
+    /// writes the SVG tag of the wrapped drawable.
+    /// points have no visual representation of their own,
+    /// so they write nothing.
+    pub fn write_svg_tag(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            Drawable::Point(_) => Ok(()),
+            Drawable::Line(line) => line.write_svg_tag(out),
+            Drawable::Circle(circle) => circle.write_svg_tag(out),
+            Drawable::Rect(rect) => rect.write_svg_tag(out),
+            Drawable::Text(text) => text.write_svg_tag(out),
+            Drawable::Arc(arc) => arc.write_svg_tag(out),
+        }
+    }
+
+    /// builds the SVG tag of the wrapped drawable.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Drawable, Line, Point, Rect, Text};
+    ///
+    /// let line = Drawable::Line(Line::new(&Point::new(0.5, -1.0), &Point::new(3.0, 4.25)));
+    /// let circle = Drawable::Circle(Circle::new(&Point::new(10.0, 20.0), 5.5));
+    /// let rect = Drawable::Rect(Rect::new(&Point::new(4.0, 8.0), &Point::new(1.0, 2.0)));
+    /// let text = Drawable::Text(Text::new(String::from("a < b & c"), Point::new(1.0, 2.0)));
+    ///
+    /// assert_eq!("<line x1=\"0.5\" x2=\"3\" y1=\"-1\" y2=\"4.25\" />", line.to_svg_tag());
+    /// assert_eq!("<circle cx=\"10\" cy=\"20\" r=\"5.5\" />", circle.to_svg_tag());
+    /// assert_eq!("<rect height=\"6\" width=\"3\" x=\"1\" y=\"2\" />", rect.to_svg_tag());
+    /// assert_eq!("<text x=\"1\" y=\"2\">a &lt; b &amp; c</text>", text.to_svg_tag());
+    /// assert_eq!("", Drawable::Point(Point::new(1.0, 1.0)).to_svg_tag());
+    ///
+    /// ```
+    pub fn to_svg_tag(&self) -> String {
+        let mut tag = String::with_capacity(TAG_CAPACITY);
+        self.write_svg_tag(&mut tag).expect("writing to a String can't fail");
+
+        tag
+    }
+
+    /// builds the SVG tag of the wrapped drawable the old way, through a
+    /// HashMap of its attributes. it gives the same tag as `to_svg_tag`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Color, Drawable, Point, StyleBuilder, Text};
+    ///
+    /// let circle = Drawable::Circle(Circle::builder(&Point::new(10.0, 20.0), 5.5).fill(Color::RED).build());
+    /// let text = Drawable::Text(Text::new(String::from("a < b & c"), Point::new(1.0, 2.0)));
+    ///
+    /// assert_eq!(circle.to_svg_tag(), circle.to_svg_tag_legacy());
+    /// assert_eq!(text.to_svg_tag(), text.to_svg_tag_legacy());
+    /// assert_eq!("", Drawable::Point(Point::new(1.0, 1.0)).to_svg_tag_legacy());
+    ///
+    /// ```
+    #[cfg(feature = "legacy-svg")]
+    pub fn to_svg_tag_legacy(&self) -> String {
+        use drawable::legacy_svg::to_svg_tag;
+
+        match self {
+            Drawable::Point(_) => String::new(),
+            Drawable::Line(line) => to_svg_tag(line),
+            Drawable::Circle(circle) => to_svg_tag(circle),
+            Drawable::Rect(rect) => to_svg_tag(rect),
+            Drawable::Text(text) => to_svg_tag(text),
+            Drawable::Arc(arc) => to_svg_tag(arc),
+        }
+    }
+
+    /// the smallest box the drawable is painted in, its stroke included.
+    /// a text's is estimated, as its size depends on the font.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_4;
+    /// use program_core::{Aabb, Circle, Color, Drawable, Line, Point, Rect, StyleBuilder, Text};
+    ///
+    /// let bounds = |drawable: Drawable| {
+    ///     let bounds = drawable.bounding_box();
+    ///     (bounds.min().x, bounds.min().y, bounds.max().x, bounds.max().y)
+    /// };
+    ///
+    /// let line = Line::builder(&Point::new(10.0, 0.0), &Point::new(0.0, 0.0)).stroke(Color::BLACK).stroke_width(2.0).build();
+    /// assert_eq!((0.0, -1.0, 10.0, 1.0), bounds(line.into()));
+    /// assert_eq!((0.0, 0.0, 10.0, 5.0), bounds(Line::new(&Point::new(10.0, 0.0), &Point::new(0.0, 5.0)).into()));
+    ///
+    /// assert_eq!((-2.0, 1.0, 4.0, 7.0), bounds(Circle::new(&Point::new(1.0, 4.0), 3.0).into()));
+    /// assert_eq!((0.0, 0.0, 20.0, 10.0), bounds(Rect::new(&Point::new(20.0, 10.0), &Point::new(0.0, 0.0)).into()));
+    ///
+    /// // a square turned an eighth of a turn reaches out to its diagonals
+    /// let turned = Rect::from_center(&Point::new(0.0, 0.0), 2.0, 2.0, FRAC_PI_4);
+    /// let (min_x, min_y, max_x, max_y) = bounds(turned.into());
+    /// for (value, expected) in [(min_x, -2f64.sqrt()), (min_y, -2f64.sqrt()), (max_x, 2f64.sqrt()), (max_y, 2f64.sqrt())] {
+    ///     assert!((value - expected).abs() < 1e-12);
+    /// }
+    ///
+    /// // 2 characters, each 0.6 of the font size wide
+    /// assert_eq!((0.0, 0.0, 12.0, 10.0), bounds(Text::with_font_size(String::from("hi"), Point::new(0.0, 0.0), 10.0).into()));
+    /// assert_eq!(Aabb::new(&Point::new(3.0, 3.0), &Point::new(3.0, 3.0)), Drawable::Point(Point::new(3.0, 3.0)).bounding_box());
+    ///
+    /// ```
+    pub fn bounding_box(&self) -> Aabb {
+        match self {
+            Drawable::Point(point) => Aabb::new(point, point),
+            Drawable::Line(line) => line.bounding_box(),
+            Drawable::Circle(circle) => circle.bounding_box(),
+            Drawable::Rect(rect) => rect.bounding_box(),
+            Drawable::Text(text) => text.bounding_box(),
+            Drawable::Arc(arc) => arc.bounding_box(),
+        }
+    }
+
+    /// checks whether a click at `point` hits the drawable.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Color, Drawable, Line, Point, StyleBuilder};
+    ///
+    /// let line = Drawable::Line(Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0)));
+    ///
+    /// assert!(line.contains(&Point::new(5.0, 1.0)));
+    /// assert!(!line.contains(&Point::new(5.0, 10.0)));
+    /// assert!(line.contains(&Point::new(12.0, 0.0)));
+    ///
+    /// // a thick line is hit anywhere on its stroke
+    /// let thick = Drawable::Line(Line::builder(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0)).stroke(Color::BLACK).stroke_width(14.0).build());
+    /// assert!(thick.contains(&Point::new(5.0, 10.0)));
+    /// assert!(!thick.contains(&Point::new(5.0, 10.5)));
+    ///
+    /// ```
+    pub fn contains(&self, point: &Point) -> bool {
+        match self {
+            Drawable::Point(p) => ((p.x - point.x).powi(2) + (p.y - point.y).powi(2)).sqrt() <= HIT_TOLERANCE,
+            Drawable::Line(line) => line.contains(point),
+            Drawable::Circle(circle) => circle.contains(point),
+            Drawable::Rect(rect) => rect.contains(point),
+            Drawable::Text(text) => text.contains(point),
+            Drawable::Arc(arc) => arc.contains(point),
+        }
+    }
+
+    /// moves the drawable by `offset`. translation keeps lengths and angles,
+    /// so cached values like a line's length are left untouched.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Drawable, Line, Point};
+    ///
+    /// let mut moved = Drawable::Line(Line::new(&Point::new(1.0, 2.0), &Point::new(4.0, 6.0)));
+    /// moved.translate(&Point::new(10.0, -3.0));
+    ///
+    /// let Drawable::Line(moved) = moved else { unreachable!() };
+    /// let built = Line::new(&Point::new(11.0, -1.0), &Point::new(14.0, 3.0));
+    ///
+    /// assert_eq!(built.start(), moved.start());
+    /// assert_eq!(built.end(), moved.end());
+    /// assert!((built.len() - moved.len()).abs() < f64::EPSILON);
+    /// assert!((built.angle() - moved.angle()).abs() < f64::EPSILON);
+    ///
+    /// ```
+    pub fn translate(&mut self, offset: &Point) {
+        if offset.x == 0f64 && offset.y == 0f64 {
+            return;
+        }
+
+        match self {
+            Drawable::Point(point) => *point = Point::new(point.x + offset.x, point.y + offset.y),
+            Drawable::Line(line) => line.translate(offset),
+            Drawable::Circle(circle) => circle.translate(offset),
+            Drawable::Rect(rect) => rect.translate(offset),
+            Drawable::Text(text) => text.translate(offset),
+            Drawable::Arc(arc) => arc.translate(offset),
+        }
+    }
+
+    /// moves the drawable's points with `transform`. circles and texts keep
+    /// their shape and are scaled by `Transform2D::scale_factor`, and rects
+    /// stay rects, so a transform that shears them is only approximated.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    /// use program_core::{Circle, Drawable, Line, Point, Rect, Transform2D};
+    ///
+    /// let mut line = Drawable::Line(Line::new(&Point::new(1.0, 1.0), &Point::new(2.0, 1.0)));
+    /// line.apply_transform(&Transform2D::scaling(3.0, 1.0).then(&Transform2D::translation(&Point::new(0.0, 5.0))));
+    /// assert_eq!("<line x1=\"3\" x2=\"6\" y1=\"6\" y2=\"6\" />", line.to_svg_tag());
+    ///
+    /// let mut circle = Drawable::Circle(Circle::new(&Point::new(10.0, 0.0), 5.0));
+    /// circle.apply_transform(&Transform2D::scaling(2.0, 8.0));
+    /// assert_eq!("<circle cx=\"20\" cy=\"0\" r=\"20\" />", circle.to_svg_tag());
+    ///
+    /// let rect = Rect::new(&Point::new(0.0, 0.0), &Point::new(20.0, 10.0));
+    /// let mut drawable = Drawable::Rect(rect);
+    /// drawable.apply_transform(&Transform2D::scaling(-2.0, 3.0));
+    /// let Drawable::Rect(rect) = drawable else { unreachable!() };
+    /// assert_eq!((Point::new(-20.0, 15.0), 40.0, 30.0, 0.0), (rect.center(), rect.width(), rect.height(), rect.rotation()));
+    ///
+    /// // turned about its center, a rect turns in place
+    /// let mut drawable = Drawable::Rect(rect.clone());
+    /// drawable.apply_transform(&Transform2D::rotation_about(&rect.center(), FRAC_PI_2));
+    /// let Drawable::Rect(turned) = drawable else { unreachable!() };
+    /// assert!(turned.center().approx_eq(&rect.center(), 1e-12));
+    /// assert!((turned.rotation() - FRAC_PI_2).abs() < 1e-12);
+    /// assert!((turned.width() - 40.0).abs() < 1e-12 && (turned.height() - 30.0).abs() < 1e-12);
+    ///
+    /// ```
+    pub fn apply_transform(&mut self, transform: &Transform2D) {
+        match self {
+            Drawable::Point(point) => *point = transform.apply(point),
+            Drawable::Line(line) => line.apply_transform(transform),
+            Drawable::Circle(circle) => circle.apply_transform(transform),
+            Drawable::Rect(rect) => rect.apply_transform(transform),
+            Drawable::Text(text) => text.apply_transform(transform),
+            Drawable::Arc(arc) => arc.apply_transform(transform),
+        }
+    }
+
+    /// the point the drawable rotates about, the center of an arc's circle for an arc.
+    pub fn center(&self) -> Point {
+        match self {
+            Drawable::Point(point) => point.clone(),
+            Drawable::Circle(circle) => circle.center(),
+            Drawable::Rect(rect) => rect.center(),
+            Drawable::Arc(arc) => arc.center(),
+            Drawable::Line(_) | Drawable::Text(_) => {
+                let bounds = self.bounding_box();
+                let (min, max) = (bounds.min(), bounds.max());
+
+                Point::new((min.x + max.x) / 2f64, (min.y + max.y) / 2f64)
+            }
+        }
+    }
+
+    /// rotates the drawable clockwise by `angle` about `pivot`.
+    /// texts can't be turned yet, so only their position moves.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    /// use program_core::{Angle, Drawable, Line, Point};
+    ///
+    /// let mut line = Drawable::Line(Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0)));
+    /// line.rotate(Angle::radians(FRAC_PI_2), &Point::new(5.0, 0.0));
+    ///
+    /// let Drawable::Line(line) = line else { unreachable!() };
+    /// assert!((line.start().x - 5.0).abs() < 1e-9 && (line.start().y + 5.0).abs() < 1e-9);
+    /// assert!((line.end().x - 5.0).abs() < 1e-9 && (line.end().y - 5.0).abs() < 1e-9);
+    /// ```
+    ///
+    /// turning by 90 degrees and by a half pi radians is the same turn.
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    /// use program_core::{Angle, Circle, Drawable, Line, Point, Rect};
+    ///
+    /// let pivot = Point::new(3.0, -7.0);
+    /// for drawable in [
+    ///     Drawable::Point(Point::new(1.0, 2.0)),
+    ///     Drawable::Line(Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 4.0))),
+    ///     Drawable::Circle(Circle::new(&Point::new(5.0, 5.0), 2.0)),
+    ///     Drawable::Rect(Rect::from_center(&Point::new(-4.0, 8.0), 6.0, 2.0, 0.3)),
+    /// ] {
+    ///     let (mut by_degrees, mut by_radians) = (drawable.clone(), drawable);
+    ///     by_degrees.rotate(Angle::degrees(90.0), &pivot);
+    ///     by_radians.rotate(Angle::radians(FRAC_PI_2), &pivot);
+    ///     assert_eq!(by_radians, by_degrees);
+    /// }
+    ///
+    /// ```
+    pub fn rotate(&mut self, angle: Angle, pivot: &Point) {
+        let angle = angle.to_radians();
+        if angle == 0f64 {
+            return;
+        }
+
+        match self {
+            Drawable::Point(point) => *point = point.rotated_about(pivot, angle),
+            Drawable::Line(line) => line.rotate_about(pivot, angle),
+            Drawable::Circle(circle) => {
+                *circle = Circle::new(&circle.center().rotated_about(pivot, angle), circle.radius()).with_style(circle.style().clone())
+            }
+            Drawable::Rect(rect) => rect.rotate_about(pivot, angle),
+            Drawable::Text(text) => {
+                *text = Text::with_font_size(text.text().clone(), text.pos().rotated_about(pivot, angle), text.font_size())
+                    .with_style(text.style().clone())
+            }
+            Drawable::Arc(arc) => {
+                let start = arc.start_angle() + Angle::radians(angle);
+                *arc = Arc::from_sweep(&arc.center().rotated_about(pivot, angle), arc.radius(), start, arc.sweep()).with_style(arc.style().clone())
+            }
+        }
+    }
+
+    /// scales the drawable by `factor` away from `origin`.
+    /// a text's font size is scaled along with it.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Drawable, Point};
+    ///
+    /// let mut circle = Drawable::Circle(Circle::new(&Point::new(20.0, 10.0), 5.0));
+    /// circle.scale(2.0, &Point::new(10.0, 10.0));
+    /// assert_eq!("<circle cx=\"30\" cy=\"10\" r=\"10\" />", circle.to_svg_tag());
+    ///
+    /// ```
+    pub fn scale(&mut self, factor: f64, origin: &Point) {
+        if factor == 1f64 {
+            return;
+        }
+
+        let scaled = |point: Point| origin.clone() + (point - origin.clone()) * factor;
+
+        match self {
+            Drawable::Point(point) => *point = scaled(point.clone()),
+            Drawable::Line(line) => *line = Line::new(&scaled(line.start()), &scaled(line.end())).with_style(line.style().clone()),
+            Drawable::Circle(circle) => {
+                *circle = Circle::new(&scaled(circle.center()), circle.radius() * factor).with_style(circle.style().clone())
+            }
+            Drawable::Rect(rect) => {
+                *rect = Rect::from_center(&scaled(rect.center()), rect.width() * factor, rect.height() * factor, rect.rotation())
+                    .with_style(rect.style().clone())
+            }
+            Drawable::Text(text) => {
+                *text = Text::with_font_size(text.text().clone(), scaled(text.pos()), text.font_size() * factor)
+                    .with_style(text.style().clone())
+            }
+            Drawable::Arc(arc) => {
+                *arc = Arc::from_sweep(&scaled(arc.center()), arc.radius() * factor, arc.start_angle(), arc.sweep()).with_style(arc.style().clone())
+            }
+        }
+    }
+
+    /// the drawable with its coordinates and lengths rounded to `decimals`
+    /// decimals, its cached lengths worked out again. a rect keeps its
+    /// rotation, an arc its angles, and every drawable keeps its style.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Drawable, Line, Point};
+    ///
+    /// let line = Drawable::Line(Line::new(&Point::new(0.004, 0.0), &Point::new(3.0, 4.0001)));
+    /// let Drawable::Line(rounded) = line.rounded(2) else { unreachable!() };
+    ///
+    /// assert_eq!(Point::new(0.0, 0.0), rounded.start());
+    /// assert_eq!(5.0, rounded.len());
+    /// assert_eq!(Drawable::Line(rounded.clone()), Drawable::Line(rounded).rounded(2));
+    ///
+    /// ```
+    pub fn rounded(&self, decimals: u32) -> Drawable {
+        let round = |value: f64| drawable::round_to(value, decimals);
+
+        match self {
+            Drawable::Point(point) => Drawable::Point(point.rounded(decimals)),
+            Drawable::Line(line) => {
+                Drawable::Line(Line::new(&line.start().rounded(decimals), &line.end().rounded(decimals)).with_style(line.style().clone()))
+            }
+            Drawable::Circle(circle) => {
+                Drawable::Circle(Circle::new(&circle.center().rounded(decimals), round(circle.radius())).with_style(circle.style().clone()))
+            }
+            Drawable::Rect(rect) => Drawable::Rect(rect.rounded(decimals)),
+            Drawable::Text(text) => Drawable::Text(
+                Text::with_font_size(text.text().clone(), text.pos().rounded(decimals), round(text.font_size()))
+                    .with_style(text.style().clone()),
+            ),
+            Drawable::Arc(arc) => Drawable::Arc(
+                Arc::from_sweep(&arc.center().rounded(decimals), round(arc.radius()), arc.start_angle(), arc.sweep()).with_style(arc.style().clone()),
+            ),
+        }
+    }
+
+    /// the handles shown around the drawable while it's selected,
+    /// with the rotation handle `rotate_distance` away from its top.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Drawable, HandleKind, Point, Rect};
+    ///
+    /// let rect = Drawable::Rect(Rect::new(&Point::new(0.0, 0.0), &Point::new(10.0, 4.0)));
+    /// let handles = rect.handles(5.0);
+    ///
+    /// assert_eq!(5, handles.len());
+    /// assert_eq!(Point::new(10.0, 4.0), handles[2].pos);
+    /// assert_eq!(HandleKind::Rotate, handles[4].kind);
+    /// assert_eq!(Point::new(5.0, -5.0), handles[4].pos);
+    ///
+    /// ```
+    pub fn handles(&self, rotate_distance: f64) -> Vec<Handle> {
+        handles::handles_of(self, rotate_distance)
+    }
+
+    /// moves one of the drawable's handles to `to`, keeping the opposite
+    /// corner or endpoint in place. with `keep_aspect` a rect keeps its
+    /// proportions and a line keeps its direction.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Drawable, HandleKind, Point, Rect};
+    ///
+    /// let mut rect = Drawable::Rect(Rect::new(&Point::new(0.0, 0.0), &Point::new(10.0, 4.0)));
+    /// rect.drag_handle(HandleKind::Corner(2), &Point::new(20.0, 6.0), false);
+    /// assert_eq!("<rect height=\"6\" width=\"20\" x=\"0\" y=\"0\" />", rect.to_svg_tag());
+    ///
+    /// rect.drag_handle(HandleKind::Corner(0), &Point::new(-20.0, 0.0), true);
+    /// assert_eq!("<rect height=\"12\" width=\"40\" x=\"-20\" y=\"-6\" />", rect.to_svg_tag());
+    ///
+    /// ```
+    pub fn drag_handle(&mut self, kind: HandleKind, to: &Point, keep_aspect: bool) {
+        handles::drag_handle(self, kind, to, keep_aspect);
+    }
+
+    /// how the drawable is painted, `None` for points which aren't painted at all.
+    pub fn style(&self) -> Option<&Style> {
+        match self {
+            Drawable::Point(_) => None,
+            Drawable::Line(line) => Some(line.style()),
+            Drawable::Circle(circle) => Some(circle.style()),
+            Drawable::Rect(rect) => Some(rect.style()),
+            Drawable::Text(text) => Some(text.style()),
+            Drawable::Arc(arc) => Some(arc.style()),
+        }
+    }
+
+    /// paints the drawable with `style`, points are left as they are.
+    pub(crate) fn set_style(&mut self, style: Style) {
+        match self {
+            Drawable::Point(_) => {}
+            Drawable::Line(line) => *line = line.clone().with_style(style),
+            Drawable::Circle(circle) => *circle = circle.clone().with_style(style),
+            Drawable::Rect(rect) => *rect = rect.clone().with_style(style),
+            Drawable::Text(text) => *text = text.clone().with_style(style),
+            Drawable::Arc(arc) => *arc = arc.clone().with_style(style),
+        }
+    }
+}
+
+impl From<Point> for Drawable {
+    fn from(point: Point) -> Self {
+        Drawable::Point(point)
+    }
+}
+
+impl From<Line> for Drawable {
+    fn from(line: Line) -> Self {
+        Drawable::Line(line)
+    }
+}
+
+impl From<Circle> for Drawable {
+    fn from(circle: Circle) -> Self {
+        Drawable::Circle(circle)
+    }
+}
+
+impl From<Rect> for Drawable {
+    fn from(rect: Rect) -> Self {
+        Drawable::Rect(rect)
+    }
+}
+
+impl From<Text> for Drawable {
+    fn from(text: Text) -> Self {
+        Drawable::Text(text)
+    }
+}
+
+impl From<Arc> for Drawable {
+    fn from(arc: Arc) -> Self {
+        Drawable::Arc(arc)
+    }
 }
 
 pub use drawable::{
-    line2d::Line2D as Line, point2d::Point2D as Point,
-    circle::Circle, text::Text 
+    angle::Angle, line2d::{Line2D as Line, LineBuilder, PARALLEL_TOLERANCE}, point2d::{Point2D as Point, NotFiniteError, ParsePointError},
+    circle::{Circle, CircleBuilder, CircleIntersection}, arc::{Arc, ArcBuilder}, rect::{Rect, RectBuilder}, text::{Text, TextBuilder, DEFAULT_FONT_SIZE}, bounds::Aabb,
+    style::{Color, LineCap, LineJoin, Paint, ParseColorError, ParseStrokeOptionError, StrokeAlign, StrokeStyle, Style, StyleBuilder}, transform::Transform2D, vector3::Vector3
 };
+pub use canvas::{Axis, Canvas, CanvasError, ExportOptions, MergePlacement, MetadataKeyError, ResizeMode, Snapshot, GRID_COLOR, MERGE_GAP};
+pub use export::{ExportError, ExportFileOptions, ExportOperation, OverwritePolicy};
+pub use guides::{ConstructionGuide, Guide, GuideAxis, GuideId, GuideLine, InfiniteLine, Ray, GUIDE_COLOR};
+pub use handles::{Handle, HandleKind};
+#[cfg(feature = "raster")]
+pub use raster::{Approximation, RasterError, RasterImage};
+pub use props::{ApplyPropsError, ArcProps, CircleProps, DrawableProps, LineProps, PivotSpec, PointProps, Props, RectProps, TextProps};
+pub use snap::{Snap, SnapKind, SnapKinds};
+pub use storage::DrawableId;
+pub use svg_import::SvgError;
+pub use units::{ParseUnitError, Unit};
+pub use viewport::{Viewport, MAX_ZOOM, MIN_ZOOM};
+#[cfg(feature = "serde")]
+pub use json::{drawables_from_json, drawables_to_json, JsonError, FORMAT_VERSION};
@@ -0,0 +1,499 @@
+//! # props
+//! this module contains the editable properties of drawables,
+//! the values a properties panel shows and writes back to the canvas.
+
+use super::storage::DrawableId;
+use super::{Angle, Arc, CanvasError, Circle, Color, Drawable, Line, LineCap, LineJoin, Paint, Point, Rect, StrokeAlign, StrokeStyle, Text};
+use std::fmt;
+
+/// # PointProps
+/// the editable properties of a point.
+#[derive(PartialEq, Clone, Debug)]
+pub struct PointProps {
+    pub pos: Point,
+}
+
+/// # LineProps
+/// the editable properties of a line.
+#[derive(PartialEq, Clone, Debug)]
+pub struct LineProps {
+    pub start: Point,
+    pub end: Point,
+    pub stroke_style: StrokeStyle,
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+    pub stroke: Option<Paint>,
+}
+
+/// # CircleProps
+/// the editable properties of a circle. `filled` is whether its inside is
+/// painted and clicked, see `Circle::is_filled`. it wins over `fill` when they
+/// disagree, an unfilled circle is filled with nothing and a filled one with
+/// the default black.
+///
+/// # Examples
+/// ```
+/// use program_core::{Canvas, Point, Props};
+///
+/// let mut canvas = Canvas::new(100.0, 100.0);
+/// let center = Point::new(50.0, 50.0);
+/// let id = canvas.add_circle(&center, 20.0).unwrap();
+///
+/// let mut selected = canvas.props_of(id).unwrap();
+/// let Props::Circle(circle) = &mut selected.props else { unreachable!() };
+/// assert!(circle.filled);
+/// circle.filled = false;
+/// canvas.apply_props(&selected).unwrap();
+///
+/// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" fill=\"none\" r=\"20\" />"), canvas.svg_fragment_for(id));
+/// assert_eq!(None, canvas.topmost_at(&center));
+/// assert_eq!(Some(id), canvas.topmost_at(&Point::new(50.0, 31.0)));
+///
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct CircleProps {
+    pub center: Point,
+    pub radius: f64,
+    pub stroke_align: StrokeAlign,
+    pub stroke: Option<Paint>,
+    pub fill: Option<Paint>,
+    pub filled: bool,
+}
+
+/// # ArcProps
+/// the editable properties of an arc, its angles as `Arc::new` takes them.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ArcProps {
+    pub center: Point,
+    pub radius: f64,
+    pub start_angle: Angle,
+    pub end_angle: Angle,
+    pub stroke_style: StrokeStyle,
+    pub line_cap: LineCap,
+    pub stroke: Option<Paint>,
+}
+
+/// # PivotSpec
+/// the point a drawable turns about when its rotation changes, or is scaled
+/// about. `Center`, the default, is the one to use unless something else
+/// should stay put.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub enum PivotSpec {
+    /// the center of the drawable.
+    #[default]
+    Center,
+    /// where the drawable starts: the start of a line or an arc, the first of
+    /// the `corners` of a rect or the position of a text, else its center.
+    Start,
+    Point(Point),
+}
+
+impl PivotSpec {
+    /// the point `drawable` turns or is scaled about.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Drawable, Line, PivotSpec, Point};
+    ///
+    /// let line = Drawable::Line(Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0)));
+    /// assert_eq!(Point::new(5.0, 0.0), PivotSpec::Center.pivot_of(&line));
+    /// assert_eq!(Point::new(0.0, 0.0), PivotSpec::Start.pivot_of(&line));
+    /// assert_eq!(Point::new(1.0, 2.0), PivotSpec::Point(Point::new(1.0, 2.0)).pivot_of(&line));
+    ///
+    /// let circle = Drawable::Circle(Circle::new(&Point::new(3.0, 4.0), 2.0));
+    /// assert_eq!(Point::new(3.0, 4.0), PivotSpec::Start.pivot_of(&circle));
+    ///
+    /// ```
+    pub fn pivot_of(&self, drawable: &Drawable) -> Point {
+        match (self, drawable) {
+            (PivotSpec::Point(point), _) => point.clone(),
+            (PivotSpec::Start, Drawable::Line(line)) => line.start(),
+            (PivotSpec::Start, Drawable::Rect(rect)) => rect.corners()[0].clone(),
+            (PivotSpec::Start, Drawable::Text(text)) => text.pos(),
+            (PivotSpec::Start, Drawable::Arc(arc)) => arc.start(),
+            _ => drawable.center(),
+        }
+    }
+}
+
+/// # RectProps
+/// the editable properties of a rectangle.
+/// `corner` is the corner with the smallest coordinates before the rect
+/// is turned by `rotation` about its center. a change of `rotation`
+/// is made about `rotation_pivot`, which moves the rect unless it's the center.
+#[derive(PartialEq, Clone, Debug)]
+pub struct RectProps {
+    pub corner: Point,
+    pub width: f64,
+    pub height: f64,
+    pub rotation: Angle,
+    pub rotation_pivot: PivotSpec,
+    pub stroke_align: StrokeAlign,
+    pub stroke_style: StrokeStyle,
+    pub stroke: Option<Paint>,
+    pub fill: Option<Paint>,
+}
+
+/// # TextProps
+/// the editable properties of a text.
+#[derive(PartialEq, Clone, Debug)]
+pub struct TextProps {
+    pub content: String,
+    pub pos: Point,
+    pub font_size: f64,
+    pub fill: Option<Paint>,
+}
+
+/// # Props
+/// the editable properties of any drawable. every kind of drawable has
+/// its own, so a new kind can't be added without making it editable.
+#[derive(PartialEq, Clone, Debug)]
+pub enum Props {
+    Point(PointProps),
+    Line(LineProps),
+    Circle(CircleProps),
+    Rect(RectProps),
+    Text(TextProps),
+    Arc(ArcProps),
+}
+
+/// the properties of a drawable, which always apply back to it.
+///
+/// # Examples
+/// ```
+/// use program_core::{
+///     Angle, Arc, ArcProps, Circle, CircleProps, Color, Drawable, Line, LineCap, LineJoin, LineProps, Paint, PivotSpec, Point, PointProps, Props, Rect, RectProps, StrokeAlign,
+///     StrokeStyle, StyleBuilder, Text, TextProps,
+/// };
+///
+/// let gradient = Paint::LinearGradient { stops: vec![(0.0, Color::RED), (1.0, Color::BLUE)], angle: Angle::degrees(45.0) };
+/// let every_kind = [
+///     (Drawable::Point(Point::new(1.0, 2.0)), Props::Point(PointProps { pos: Point::new(1.0, 2.0) })),
+///     (
+///         Drawable::Line(Line::builder(&Point::new(1.0, 2.0), &Point::new(3.0, 4.0)).stroke_style(StrokeStyle::Dotted).line_cap(LineCap::Round).build()),
+///         Props::Line(LineProps {
+///             start: Point::new(1.0, 2.0), end: Point::new(3.0, 4.0), stroke_style: StrokeStyle::Dotted, line_cap: LineCap::Round, line_join: LineJoin::Miter,
+///             stroke: None,
+///         }),
+///     ),
+///     (
+///         Drawable::Circle(Circle::builder(&Point::new(1.0, 2.0), 3.0).fill(gradient.clone()).build()),
+///         Props::Circle(CircleProps { center: Point::new(1.0, 2.0), radius: 3.0, stroke_align: StrokeAlign::Center, stroke: None, fill: Some(gradient), filled: true }),
+///     ),
+///     (
+///         Drawable::Rect(Rect::builder(&Point::new(4.0, 4.0), &Point::new(1.0, 2.0)).stroke_align(StrokeAlign::Inside).stroke(Color::RED).build()),
+///         Props::Rect(RectProps {
+///             corner: Point::new(1.0, 2.0), width: 3.0, height: 2.0, rotation: Angle::default(), rotation_pivot: PivotSpec::Center, stroke_align: StrokeAlign::Inside,
+///             stroke_style: StrokeStyle::Solid, stroke: Some(Paint::Solid(Color::RED)), fill: None,
+///         }),
+///     ),
+///     (
+///         Drawable::Text(Text::with_font_size(String::from("N"), Point::new(1.0, 2.0), 12.0)),
+///         Props::Text(TextProps { content: String::from("N"), pos: Point::new(1.0, 2.0), font_size: 12.0, fill: None }),
+///     ),
+///     (
+///         Drawable::Arc(Arc::builder(&Point::new(1.0, 2.0), 3.0, Angle::degrees(0.0), Angle::degrees(90.0)).line_cap(LineCap::Square).build()),
+///         Props::Arc(ArcProps {
+///             center: Point::new(1.0, 2.0), radius: 3.0, start_angle: Angle::degrees(0.0), end_angle: Angle::degrees(90.0), stroke_style: StrokeStyle::Solid,
+///             line_cap: LineCap::Square, stroke: None,
+///         }),
+///     ),
+/// ];
+///
+/// for (drawable, props) in every_kind {
+///     assert_eq!(props, Props::from(&drawable));
+/// }
+///
+/// ```
+impl From<&Drawable> for Props {
+    fn from(drawable: &Drawable) -> Self {
+        match drawable {
+            Drawable::Point(point) => Props::Point(PointProps { pos: point.clone() }),
+            Drawable::Line(line) => Props::Line(LineProps {
+                start: line.start(),
+                end: line.end(),
+                stroke_style: line.style().stroke_style.clone(),
+                line_cap: line.style().line_cap,
+                line_join: line.style().line_join,
+                stroke: line.style().stroke.clone(),
+            }),
+            Drawable::Circle(circle) => Props::Circle(CircleProps {
+                center: circle.center(),
+                radius: circle.radius(),
+                stroke_align: circle.style().stroke_align,
+                stroke: circle.style().stroke.clone(),
+                fill: circle.style().fill.clone(),
+                filled: circle.is_filled(),
+            }),
+            Drawable::Rect(rect) => Props::Rect(RectProps {
+                corner: rect.corner(),
+                width: rect.width(),
+                height: rect.height(),
+                rotation: Angle::radians(rect.rotation()),
+                rotation_pivot: PivotSpec::Center,
+                stroke_align: rect.style().stroke_align,
+                stroke_style: rect.style().stroke_style.clone(),
+                stroke: rect.style().stroke.clone(),
+                fill: rect.style().fill.clone(),
+            }),
+            Drawable::Text(text) => Props::Text(TextProps {
+                content: text.text().clone(),
+                pos: text.pos(),
+                font_size: text.font_size(),
+                fill: text.style().fill.clone(),
+            }),
+            Drawable::Arc(arc) => Props::Arc(ArcProps {
+                center: arc.center(),
+                radius: arc.radius(),
+                start_angle: arc.start_angle(),
+                end_angle: arc.end_angle(),
+                stroke_style: arc.style().stroke_style.clone(),
+                line_cap: arc.style().line_cap,
+                stroke: arc.style().stroke.clone(),
+            }),
+        }
+    }
+}
+
+impl Props {
+    /// the name of the kind of drawable these properties are for,
+    /// the same one drawings are saved with.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Props::Point(_) => "point",
+            Props::Line(_) => "line",
+            Props::Circle(_) => "circle",
+            Props::Rect(_) => "rect",
+            Props::Text(_) => "text",
+            Props::Arc(_) => "arc",
+        }
+    }
+
+    /// these properties with every position and length multiplied by
+    /// `factor`, to show them in another unit, dashes included. rotations,
+    /// angles, font sizes and paints aren't lengths on the drawing and stay as they are.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Angle, PivotSpec, Point, Props, RectProps, StrokeAlign, StrokeStyle};
+    ///
+    /// let pivot = |x, y| PivotSpec::Point(Point::new(x, y));
+    /// let rect = Props::Rect(RectProps {
+    ///     corner: Point::new(10.0, 20.0), width: 4.0, height: 2.0, rotation: Angle::degrees(30.0), rotation_pivot: pivot(2.0, 2.0), stroke_align: StrokeAlign::Outside,
+    ///     stroke_style: StrokeStyle::Dashed(vec![3.0, 1.0]), stroke: None, fill: None,
+    /// });
+    /// let scaled = Props::Rect(RectProps {
+    ///     corner: Point::new(5.0, 10.0), width: 2.0, height: 1.0, rotation: Angle::degrees(30.0), rotation_pivot: pivot(1.0, 1.0), stroke_align: StrokeAlign::Outside,
+    ///     stroke_style: StrokeStyle::Dashed(vec![1.5, 0.5]), stroke: None, fill: None,
+    /// });
+    ///
+    /// assert_eq!(scaled, rect.scaled(0.5));
+    ///
+    /// ```
+    pub fn scaled(&self, factor: f64) -> Props {
+        let point = |point: &Point| Point::new(point.x * factor, point.y * factor);
+
+        match self {
+            Props::Point(props) => Props::Point(PointProps { pos: point(&props.pos) }),
+            Props::Line(props) => Props::Line(LineProps {
+                start: point(&props.start),
+                end: point(&props.end),
+                stroke_style: props.stroke_style.scaled(factor),
+                line_cap: props.line_cap,
+                line_join: props.line_join,
+                stroke: props.stroke.clone(),
+            }),
+            Props::Circle(props) => Props::Circle(CircleProps {
+                center: point(&props.center),
+                radius: props.radius * factor,
+                stroke_align: props.stroke_align,
+                stroke: props.stroke.clone(),
+                fill: props.fill.clone(),
+                filled: props.filled,
+            }),
+            Props::Rect(props) => Props::Rect(RectProps {
+                corner: point(&props.corner),
+                width: props.width * factor,
+                height: props.height * factor,
+                rotation: props.rotation,
+                rotation_pivot: match &props.rotation_pivot {
+                    PivotSpec::Point(pivot) => PivotSpec::Point(point(pivot)),
+                    pivot => pivot.clone(),
+                },
+                stroke_align: props.stroke_align,
+                stroke_style: props.stroke_style.scaled(factor),
+                stroke: props.stroke.clone(),
+                fill: props.fill.clone(),
+            }),
+            Props::Text(props) => Props::Text(TextProps { pos: point(&props.pos), ..props.clone() }),
+            Props::Arc(props) => Props::Arc(ArcProps {
+                center: point(&props.center),
+                radius: props.radius * factor,
+                stroke_style: props.stroke_style.scaled(factor),
+                ..props.clone()
+            }),
+        }
+    }
+
+    /// checks whether these properties can be applied to `drawable`.
+    pub(crate) fn applies_to(&self, drawable: &Drawable) -> bool {
+        matches!(
+            (self, drawable),
+            (Props::Point(_), Drawable::Point(_))
+                | (Props::Line(_), Drawable::Line(_))
+                | (Props::Circle(_), Drawable::Circle(_))
+                | (Props::Rect(_), Drawable::Rect(_))
+                | (Props::Text(_), Drawable::Text(_))
+                | (Props::Arc(_), Drawable::Arc(_))
+        )
+    }
+
+    /// rebuilds `drawable` from these properties,
+    /// which must apply to it. the rest of its style is kept.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Drawable, PivotSpec, Point, Props};
+    ///
+    /// // a 10 by 10 square turned a quarter turn about each pivot, and where its first corner goes
+    /// let first_corner = |pivot: PivotSpec| {
+    ///     let mut canvas = Canvas::new(100.0, 100.0);
+    ///     canvas.add_rect(&Point::new(10.0, 10.0), &Point::new(20.0, 20.0)).unwrap();
+    ///     let mut selected = canvas.props_of(canvas.ids()[0]).unwrap();
+    ///     let Props::Rect(rect) = &mut selected.props else { unreachable!() };
+    ///     rect.rotation = program_core::Angle::degrees(90.0);
+    ///     rect.rotation_pivot = pivot;
+    ///     canvas.apply_props(&selected).unwrap();
+    ///
+    ///     let Some((_, drawable)) = canvas.drawables().next() else { unreachable!() };
+    ///     let Drawable::Rect(rect) = &**drawable else { unreachable!() };
+    ///     let corner = &rect.corners()[0];
+    ///     (corner.x.round(), corner.y.round())
+    /// };
+    ///
+    /// assert_eq!((20.0, 10.0), first_corner(PivotSpec::Center));
+    /// assert_eq!((10.0, 10.0), first_corner(PivotSpec::Start));
+    /// assert_eq!((0.0, 0.0), first_corner(PivotSpec::Point(Point::new(10.0, 0.0))));
+    ///
+    /// ```
+    pub(crate) fn apply(&self, drawable: &mut Drawable) {
+        let style = drawable.style().cloned();
+        let stroke_align = match self {
+            Props::Circle(props) => Some(props.stroke_align),
+            Props::Rect(props) => Some(props.stroke_align),
+            _ => None,
+        };
+        let stroke_style = match self {
+            Props::Line(props) => Some(props.stroke_style.clone()),
+            Props::Rect(props) => Some(props.stroke_style.clone()),
+            Props::Arc(props) => Some(props.stroke_style.clone()),
+            _ => None,
+        };
+        let (stroke, fill) = match self {
+            Props::Point(_) => (None, None),
+            Props::Line(props) => (Some(&props.stroke), None),
+            Props::Circle(props) => (Some(&props.stroke), Some(&props.fill)),
+            Props::Rect(props) => (Some(&props.stroke), Some(&props.fill)),
+            Props::Text(props) => (None, Some(&props.fill)),
+            Props::Arc(props) => (Some(&props.stroke), None),
+        };
+
+        match self {
+            Props::Point(props) => *drawable = Drawable::Point(props.pos.clone()),
+            Props::Line(props) => *drawable = Drawable::Line(Line::new(&props.start, &props.end)),
+            Props::Circle(props) => *drawable = Drawable::Circle(Circle::new(&props.center, props.radius)),
+            Props::Rect(props) => {
+                // not checked yet, the canvas refuses the rect after when it isn't finite
+                let center = Point { x: props.corner.x + props.width / 2f64, y: props.corner.y + props.height / 2f64 };
+                let turned_from = match drawable {
+                    Drawable::Rect(rect) if props.rotation_pivot != PivotSpec::Center => Some(rect.rotation()),
+                    _ => None,
+                };
+
+                // turned from where it was, about a pivot of the rect before it turns
+                *drawable = match turned_from {
+                    Some(rotation) => {
+                        let mut rect = Drawable::Rect(Rect::from_center(&center, props.width, props.height, rotation));
+                        let pivot = props.rotation_pivot.pivot_of(&rect);
+                        rect.rotate(props.rotation - Angle::radians(rotation), &pivot);
+                        rect
+                    }
+                    None => Drawable::Rect(Rect::from_center(&center, props.width, props.height, props.rotation.to_radians())),
+                };
+            }
+            Props::Text(props) => {
+                *drawable = Drawable::Text(Text::with_font_size(props.content.clone(), props.pos.clone(), props.font_size))
+            }
+            Props::Arc(props) => *drawable = Drawable::Arc(Arc::new(&props.center, props.radius, props.start_angle, props.end_angle)),
+        }
+
+        if let Some(mut style) = style {
+            style.stroke_align = stroke_align.unwrap_or(style.stroke_align);
+            style.stroke_style = stroke_style.unwrap_or(style.stroke_style);
+            style.stroke = stroke.cloned().unwrap_or(style.stroke);
+            style.fill = fill.cloned().unwrap_or(style.fill);
+            if let Props::Line(props) = self {
+                style.line_cap = props.line_cap;
+                style.line_join = props.line_join;
+            }
+            if let Props::Arc(props) = self {
+                style.line_cap = props.line_cap;
+            }
+            if let Props::Circle(props) = self {
+                if props.filled != style.fill.as_ref().is_none_or(Paint::is_visible) {
+                    style.fill = if props.filled { None } else { Some(Paint::Solid(Color::NONE)) };
+                }
+            }
+            drawable.set_style(style);
+        }
+    }
+}
+
+/// # DrawableProps
+/// the properties of a drawable on a canvas, with what tells it apart from
+/// the others there, built by `Canvas::props_of`. they're written back to the
+/// drawable with the same `id`, whatever is selected by then.
+#[derive(PartialEq, Clone, Debug)]
+pub struct DrawableProps {
+    pub id: DrawableId,
+    /// its place in the stacking order, 0 for the bottom one.
+    pub z_index: usize,
+    pub locked: bool,
+    pub props: Props,
+}
+
+impl DrawableProps {
+    pub fn kind(&self) -> &'static str {
+        self.props.kind()
+    }
+
+    /// these properties with their lengths scaled, like `Props::scaled`.
+    pub fn scaled(&self, factor: f64) -> DrawableProps {
+        DrawableProps { props: self.props.scaled(factor), ..self.clone() }
+    }
+}
+
+/// the error returned when props can't be written back to their drawable.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ApplyPropsError {
+    /// the drawable has been deleted.
+    Missing(DrawableId),
+    Locked(DrawableId),
+    /// the props are for another kind of drawable.
+    WrongKind(DrawableId),
+    /// the props would leave a drawable that can't be on the canvas.
+    Invalid(CanvasError),
+}
+
+impl fmt::Display for ApplyPropsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ApplyPropsError::Missing(id) => write!(f, "drawable {} no longer exists", id),
+            ApplyPropsError::Locked(id) => write!(f, "drawable {} is locked", id),
+            ApplyPropsError::WrongKind(id) => write!(f, "the props are for another kind of drawable than {}", id),
+            ApplyPropsError::Invalid(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ApplyPropsError {}
@@ -0,0 +1,245 @@
+//! # raster
+//! this module renders drawings to PNG images for the places that don't
+//! take SVG. drawables are painted the way an SVG viewer paints `Canvas::to_svg`,
+//! except for what can't be drawn without fonts, which is approximated and reported.
+
+use super::storage::DrawableId;
+use super::{Aabb, Arc, Canvas, Color, Drawable, GuideAxis, LineCap, LineJoin, Paint, Point, StrokeStyle, Style, GUIDE_COLOR};
+use std::f64::consts::FRAC_PI_2;
+use std::fmt;
+use tiny_skia::{FillRule, GradientStop, LinearGradient, Path, PathBuilder, Pixmap, SpreadMode, Stroke, StrokeDash, Transform};
+
+/// # RasterImage
+/// a drawing rendered by `Canvas::render_png`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct RasterImage {
+    /// the PNG file.
+    pub png: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    /// what was drawn differently than in the SVG document, in stacking order.
+    pub approximations: Vec<Approximation>,
+}
+
+/// something the rasterizer can't draw like an SVG viewer would.
+#[derive(PartialEq, Clone, Debug)]
+pub enum Approximation {
+    /// the text has no fonts to be drawn with, so its box is outlined instead.
+    TextAsBox(DrawableId),
+    /// the background isn't a color like `#rrggbb`, so it's left transparent.
+    Background(String),
+    /// the grid pattern isn't drawn.
+    Grid,
+}
+
+/// the error returned when a drawing can't be rendered.
+#[derive(Debug)]
+pub enum RasterError {
+    /// the scale isn't a positive number.
+    InvalidScale(f64),
+    /// the image would have no pixels, or too many to hold.
+    InvalidSize { width: f64, height: f64 },
+    /// the PNG file couldn't be written.
+    Encode(String),
+}
+
+impl fmt::Display for RasterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RasterError::InvalidScale(scale) => write!(f, "can't render at a scale of {}", scale),
+            RasterError::InvalidSize { width, height } => write!(f, "can't render an image of {} \u{d7} {} pixels", width, height),
+            RasterError::Encode(error) => write!(f, "couldn't write the PNG: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for RasterError {}
+
+/// the largest side an image may have, in pixels.
+const MAX_SIDE: f64 = 16384f64;
+
+fn skia_color(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba8(color.r, color.g, color.b, color.a)
+}
+
+/// the tiny-skia paint of `paint` on a path within `bounds`. like SVG, gradients
+/// run across the bounds before they're stretched to fit them.
+fn skia_paint(paint: &Paint, bounds: tiny_skia::Rect) -> tiny_skia::Paint<'static> {
+    let mut skia_paint = tiny_skia::Paint { anti_alias: true, ..tiny_skia::Paint::default() };
+
+    match paint {
+        Paint::Solid(color) => skia_paint.set_color(skia_color(*color)),
+        Paint::LinearGradient { stops, angle } => {
+            let (sin, cos) = (angle.to_radians().sin() as f32 / 2f32, angle.to_radians().cos() as f32 / 2f32);
+            let stops = stops.iter().map(|(offset, color)| GradientStop::new(*offset as f32, skia_color(*color))).collect();
+            let to_bounds = Transform::from_row(bounds.width(), 0f32, 0f32, bounds.height(), bounds.x(), bounds.y());
+            let (start, end) = (tiny_skia::Point::from_xy(0.5 - cos, 0.5 - sin), tiny_skia::Point::from_xy(0.5 + cos, 0.5 + sin));
+            match LinearGradient::new(start, end, stops, SpreadMode::Pad, to_bounds) {
+                Some(shader) => skia_paint.shader = shader,
+                None => skia_paint.set_color(tiny_skia::Color::TRANSPARENT),
+            }
+        }
+    }
+
+    skia_paint
+}
+
+/// paints `path` the way SVG paints a shape with `style`:
+/// filled black unless told otherwise, and only stroked when told to.
+fn paint_path(pixmap: &mut Pixmap, path: &Path, style: &Style, filled: bool, transform: Transform) {
+    if filled {
+        let fill = style.fill.clone().unwrap_or(Paint::Solid(Color::BLACK));
+        if fill.is_visible() {
+            pixmap.fill_path(path, &skia_paint(&fill, path.bounds()), FillRule::Winding, transform, None);
+        }
+    }
+
+    let width = style.painted_stroke_width();
+    if let (Some(stroke), true) = (&style.stroke, width > 0f64) {
+        // tiny-skia can't dash with an odd number of lengths, so they're repeated like SVG does
+        let dashes = style.stroke_style.dash_array(width).map(|dashes| dashes.repeat(if dashes.len() % 2 == 1 { 2 } else { 1 }));
+        let dash = dashes.and_then(|dashes| StrokeDash::new(dashes.iter().map(|&dash| dash as f32).collect(), 0f32));
+        let line_cap = match style.line_cap {
+            LineCap::Butt => tiny_skia::LineCap::Butt,
+            LineCap::Round => tiny_skia::LineCap::Round,
+            LineCap::Square => tiny_skia::LineCap::Square,
+        };
+        let line_join = match style.line_join {
+            LineJoin::Miter => tiny_skia::LineJoin::Miter,
+            LineJoin::Round => tiny_skia::LineJoin::Round,
+            LineJoin::Bevel => tiny_skia::LineJoin::Bevel,
+        };
+        let line = Stroke { width: width as f32, line_cap, line_join, dash, ..Stroke::default() };
+        pixmap.stroke_path(path, &skia_paint(stroke, path.bounds()), &line, transform, None);
+    }
+}
+
+/// the path of `arc`, made of a cubic curve for every quarter turn or less of it.
+fn arc_path(arc: &Arc) -> Option<Path> {
+    let (center, radius) = (arc.center(), arc.radius());
+    let (start, sweep) = (arc.start_angle().to_radians(), arc.sweep().to_radians());
+    let pieces = (sweep / FRAC_PI_2).ceil().max(1f64);
+    let step = sweep / pieces;
+    // how far along the tangents the control points are, for a curve that hugs the circle
+    let reach = radius * 4f64 / 3f64 * (step / 4f64).tan();
+    let at = |angle: f64, along: f64| {
+        let (sin, cos) = angle.sin_cos();
+        ((center.x + radius * cos - along * sin) as f32, (center.y + radius * sin + along * cos) as f32)
+    };
+
+    let mut path = PathBuilder::new();
+    let (x, y) = at(start, 0f64);
+    path.move_to(x, y);
+    for i in 0..pieces as usize {
+        let (from, to) = (start + step * i as f64, start + step * (i + 1) as f64);
+        let ((x1, y1), (x2, y2), (x, y)) = (at(from, reach), at(to, -reach), at(to, 0f64));
+        path.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    path.finish()
+}
+
+pub(crate) fn render_png(canvas: &Canvas, scale: f64) -> Result<RasterImage, RasterError> {
+    if !(scale > 0f64 && scale.is_finite()) {
+        return Err(RasterError::InvalidScale(scale));
+    }
+
+    let (width, height) = ((canvas.width() * scale).ceil(), (canvas.height() * scale).ceil());
+    let invalid_size = || RasterError::InvalidSize { width, height };
+    if !(1f64..=MAX_SIDE).contains(&width) || !(1f64..=MAX_SIDE).contains(&height) {
+        return Err(invalid_size());
+    }
+    let mut pixmap = Pixmap::new(width as u32, height as u32).ok_or_else(invalid_size)?;
+    let mut approximations = Vec::new();
+
+    if let Some(background) = canvas.background() {
+        match background.parse::<Color>() {
+            Ok(color) => pixmap.fill(skia_color(color)),
+            Err(_) => approximations.push(Approximation::Background(background.to_string())),
+        }
+    }
+    if canvas.grid_spacing().is_some() && canvas.export_grid() {
+        approximations.push(Approximation::Grid);
+    }
+
+    let scaled = Transform::from_scale(scale as f32, scale as f32);
+    for (id, drawable) in canvas.drawables().filter(|(id, _)| !canvas.is_hidden(*id)) {
+        match &**drawable {
+            Drawable::Point(_) => {}
+            Drawable::Line(line) => {
+                let mut path = PathBuilder::new();
+                path.move_to(line.start().x as f32, line.start().y as f32);
+                path.line_to(line.end().x as f32, line.end().y as f32);
+                if let Some(path) = path.finish() {
+                    paint_path(&mut pixmap, &path, line.style(), false, scaled);
+                }
+            }
+            Drawable::Circle(circle) => {
+                let center = circle.center();
+                if let Some(path) = PathBuilder::from_circle(center.x as f32, center.y as f32, circle.outline_radius() as f32) {
+                    paint_path(&mut pixmap, &path, circle.style(), true, scaled);
+                }
+            }
+            Drawable::Rect(rect) => {
+                let (outline, center) = (rect.outline(), rect.center());
+                let corner = outline.corner();
+                let bounds = tiny_skia::Rect::from_xywh(corner.x as f32, corner.y as f32, outline.width() as f32, outline.height() as f32);
+                if let Some(bounds) = bounds {
+                    let rotation = Transform::from_rotate_at(rect.rotation().to_degrees() as f32, center.x as f32, center.y as f32);
+                    paint_path(&mut pixmap, &PathBuilder::from_rect(bounds), rect.style(), true, scaled.pre_concat(rotation));
+                }
+            }
+            Drawable::Text(text) => {
+                let (min, max) = (drawable.bounding_box().min(), drawable.bounding_box().max());
+                let bounds = tiny_skia::Rect::from_ltrb(min.x as f32, min.y as f32, max.x as f32, max.y as f32);
+                if let Some(bounds) = bounds {
+                    let paint = text.style().fill.clone().unwrap_or(Paint::Solid(Color::BLACK));
+                    let style = Style { stroke: Some(paint), stroke_width: Some(1f64 / scale), ..Style::default() };
+                    paint_path(&mut pixmap, &PathBuilder::from_rect(bounds), &style, false, scaled);
+                }
+                approximations.push(Approximation::TextAsBox(id));
+            }
+            Drawable::Arc(arc) => {
+                if let Some(path) = arc_path(arc) {
+                    paint_path(&mut pixmap, &path, arc.style(), true, scaled);
+                }
+            }
+        }
+    }
+
+    if canvas.render_guides() {
+        let color = GUIDE_COLOR.parse::<Color>().expect("the guide color is a color");
+        let style = Style { stroke: Some(Paint::Solid(color)), stroke_width: Some(0.5), ..Style::default() };
+        for guide in canvas.guides() {
+            let mut path = PathBuilder::new();
+            match guide.axis {
+                GuideAxis::Horizontal => {
+                    path.move_to(0f32, guide.position as f32);
+                    path.line_to(canvas.width() as f32, guide.position as f32);
+                }
+                GuideAxis::Vertical => {
+                    path.move_to(guide.position as f32, 0f32);
+                    path.line_to(guide.position as f32, canvas.height() as f32);
+                }
+            }
+            if let Some(path) = path.finish() {
+                paint_path(&mut pixmap, &path, &style, false, scaled);
+            }
+        }
+
+        let page = Aabb::new(&Point::new(0f64, 0f64), &Point::new(canvas.width(), canvas.height()));
+        let dashed = Style { stroke_style: StrokeStyle::Dashed(vec![4f64, 2f64]), ..style };
+        for line in canvas.construction_guides().iter().filter_map(|guide| guide.line.clip_to(&page)) {
+            let mut path = PathBuilder::new();
+            path.move_to(line.start().x as f32, line.start().y as f32);
+            path.line_to(line.end().x as f32, line.end().y as f32);
+            if let Some(path) = path.finish() {
+                paint_path(&mut pixmap, &path, &dashed, false, scaled);
+            }
+        }
+    }
+
+    let png = pixmap.encode_png().map_err(|error| RasterError::Encode(error.to_string()))?;
+
+    Ok(RasterImage { png, width: pixmap.width(), height: pixmap.height(), approximations })
+}
@@ -0,0 +1,130 @@
+//! # snap
+//! this module contains the points of drawables that the cursor snaps to:
+//! their ends, the middles of their edges, their centers, and where
+//! their edges cross, as well as the guides and the grid.
+
+use super::{Drawable, Line, Point};
+
+/// a kind of point the cursor can snap to.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum SnapKind {
+    /// the end of a line or the corner of a rect.
+    Endpoint,
+    /// the middle of a line or of an edge of a rect.
+    Midpoint,
+    /// the center of a circle or a rect.
+    Center,
+    /// where two edges cross.
+    Intersection,
+    /// a crossing of the grid.
+    Grid,
+    /// a ruler guide, or where two of them cross, or a construction guide.
+    Guide,
+}
+
+impl SnapKind {
+    pub const ALL: [SnapKind; 6] =
+        [SnapKind::Endpoint, SnapKind::Midpoint, SnapKind::Center, SnapKind::Intersection, SnapKind::Grid, SnapKind::Guide];
+}
+
+/// # SnapKinds
+/// the kinds of points `Canvas::snap` looks for.
+///
+/// # Examples
+/// ```
+/// use program_core::{SnapKind, SnapKinds};
+///
+/// let mut kinds = SnapKinds::default();
+/// assert!(SnapKind::ALL.iter().all(|&kind| kinds.contains(kind)));
+///
+/// kinds.set(SnapKind::Grid, false);
+/// assert!(!kinds.contains(SnapKind::Grid) && kinds.contains(SnapKind::Center));
+/// assert!(SnapKinds::NONE.is_empty());
+///
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct SnapKinds(u8);
+
+impl SnapKinds {
+    pub const NONE: SnapKinds = SnapKinds(0);
+    pub const ALL: SnapKinds = SnapKinds(0b111111);
+
+    fn bit(kind: SnapKind) -> u8 {
+        1 << kind as u8
+    }
+
+    pub fn contains(&self, kind: SnapKind) -> bool {
+        self.0 & SnapKinds::bit(kind) != 0
+    }
+
+    pub fn set(&mut self, kind: SnapKind, on: bool) {
+        if on {
+            self.0 |= SnapKinds::bit(kind);
+        } else {
+            self.0 &= !SnapKinds::bit(kind);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl Default for SnapKinds {
+    fn default() -> SnapKinds {
+        SnapKinds::ALL
+    }
+}
+
+/// # Snap
+/// where a point snapped to, and what it snapped to.
+/// `kind` is `None` when nothing was close enough and the point stayed as it was.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Snap {
+    pub point: Point,
+    pub kind: Option<SnapKind>,
+}
+
+fn middle(a: &Point, b: &Point) -> Point {
+    Point::new((a.x + b.x) / 2f64, (a.y + b.y) / 2f64)
+}
+
+/// the points of `drawable` that can be snapped to, except intersections.
+pub(crate) fn snap_points(drawable: &Drawable) -> Vec<(SnapKind, Point)> {
+    match drawable {
+        Drawable::Point(point) => vec![(SnapKind::Endpoint, point.clone())],
+        Drawable::Line(line) => {
+            vec![(SnapKind::Endpoint, line.start()), (SnapKind::Endpoint, line.end()), (SnapKind::Midpoint, line.midpoint())]
+        }
+        Drawable::Circle(circle) => vec![(SnapKind::Center, circle.center())],
+        Drawable::Rect(rect) => {
+            let corners = rect.corners();
+            let mut points: Vec<(SnapKind, Point)> = corners.iter().map(|corner| (SnapKind::Endpoint, corner.clone())).collect();
+            for i in 0..4 {
+                points.push((SnapKind::Midpoint, middle(&corners[i], &corners[(i + 1) % 4])));
+            }
+            points.push((SnapKind::Center, rect.center()));
+
+            points
+        }
+        Drawable::Text(text) => vec![(SnapKind::Endpoint, text.pos())],
+        Drawable::Arc(arc) => vec![
+            (SnapKind::Endpoint, arc.start()),
+            (SnapKind::Endpoint, arc.end()),
+            (SnapKind::Midpoint, arc.midpoint()),
+            (SnapKind::Center, arc.center()),
+        ],
+    }
+}
+
+/// the straight edges of `drawable`, the ones intersections are looked for on.
+pub(crate) fn segments(drawable: &Drawable) -> Vec<Line> {
+    match drawable {
+        Drawable::Line(line) => vec![Line::new(&line.start(), &line.end())],
+        Drawable::Rect(rect) => {
+            let corners = rect.corners();
+            (0..4).map(|i| Line::new(&corners[i], &corners[(i + 1) % 4])).collect()
+        }
+        Drawable::Point(_) | Drawable::Circle(_) | Drawable::Text(_) | Drawable::Arc(_) => Vec::new(),
+    }
+}
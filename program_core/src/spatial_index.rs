@@ -0,0 +1,119 @@
+//! # spatial_index
+//! this module contains a uniform grid over the bounding boxes
+//! of drawables, so point and rectangle queries only have to look
+//! at the drawables near the query instead of every drawable.
+
+use super::drawable::bounds::Aabb;
+use super::storage::DrawableId;
+use super::Point;
+use std::collections::{BTreeSet, HashMap};
+
+/// the side length of a grid cell in canvas units.
+const CELL_SIZE: f64 = 64.0;
+/// drawables covering more cells than this are kept in a separate
+/// list that every query checks, so one huge shape can't flood the grid.
+const MAX_CELLS: i64 = 256;
+
+/// # SpatialIndex
+/// a uniform grid mapping each cell to the drawables whose
+/// bounding box overlaps it.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct SpatialIndex {
+    cells: HashMap<(i64, i64), Vec<DrawableId>>,
+    oversized: Vec<DrawableId>,
+}
+
+impl SpatialIndex {
+    pub fn new() -> SpatialIndex {
+        SpatialIndex::default()
+    }
+
+    pub fn insert(&mut self, id: DrawableId, bounds: &Aabb) {
+        let ((min_x, min_y), (max_x, max_y)) = cell_range(bounds);
+
+        if cell_count(bounds) > MAX_CELLS {
+            self.oversized.push(id);
+            return;
+        }
+
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                self.cells.entry((x, y)).or_default().push(id);
+            }
+        }
+    }
+
+    /// inserts many drawables at once.
+    pub fn extend(&mut self, entries: impl IntoIterator<Item = (DrawableId, Aabb)>) {
+        for (id, bounds) in entries {
+            self.insert(id, &bounds);
+        }
+    }
+
+    /// removes a drawable, `bounds` must be the ones it was inserted with.
+    pub fn remove(&mut self, id: DrawableId, bounds: &Aabb) {
+        let ((min_x, min_y), (max_x, max_y)) = cell_range(bounds);
+
+        if cell_count(bounds) > MAX_CELLS {
+            self.oversized.retain(|&other| other != id);
+            return;
+        }
+
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                if let Some(indices) = self.cells.get_mut(&(x, y)) {
+                    indices.retain(|&other| other != id);
+                    if indices.is_empty() {
+                        self.cells.remove(&(x, y));
+                    }
+                }
+            }
+        }
+    }
+
+    /// returns the drawables whose bounding box may contain the point.
+    pub fn query_point(&self, point: &Point) -> Vec<DrawableId> {
+        let mut candidates: BTreeSet<DrawableId> = self.oversized.iter().copied().collect();
+
+        if let Some(indices) = self.cells.get(&cell_of(point)) {
+            candidates.extend(indices);
+        }
+
+        candidates.into_iter().collect()
+    }
+
+    /// returns the drawables whose bounding box may overlap the rectangle.
+    pub fn query_rect(&self, rect: &Aabb) -> Vec<DrawableId> {
+        let mut candidates: BTreeSet<DrawableId> = self.oversized.iter().copied().collect();
+        let ((min_x, min_y), (max_x, max_y)) = cell_range(rect);
+
+        if cell_count(rect) > self.cells.len() as i64 {
+            candidates.extend(self.cells.values().flatten());
+        } else {
+            for x in min_x..=max_x {
+                for y in min_y..=max_y {
+                    if let Some(indices) = self.cells.get(&(x, y)) {
+                        candidates.extend(indices);
+                    }
+                }
+            }
+        }
+
+        candidates.into_iter().collect()
+    }
+}
+
+fn cell_of(point: &Point) -> (i64, i64) {
+    ((point.x / CELL_SIZE).floor() as i64, (point.y / CELL_SIZE).floor() as i64)
+}
+
+fn cell_range(bounds: &Aabb) -> ((i64, i64), (i64, i64)) {
+    (cell_of(&bounds.min()), cell_of(&bounds.max()))
+}
+
+fn cell_count(bounds: &Aabb) -> i64 {
+    let ((min_x, min_y), (max_x, max_y)) = cell_range(bounds);
+
+    (max_x.saturating_sub(min_x).saturating_add(1))
+        .saturating_mul(max_y.saturating_sub(min_y).saturating_add(1))
+}
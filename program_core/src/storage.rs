@@ -0,0 +1,240 @@
+//! # storage
+//! this module contains the slab the canvas keeps its drawables in.
+//! drawables are looked up by generational ids, so finding one is O(1)
+//! and an id that outlived its drawable is detected instead of silently
+//! pointing at whatever reused the slot. the stacking order is a map from
+//! each drawable's position to its id, so removing a drawable, or bringing
+//! it to the front or the back, is O(log n).
+
+use super::Drawable;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// # DrawableId
+/// a stable handle to a drawable on a canvas.
+/// it stays valid until the drawable is deleted, after which
+/// every lookup with it returns `None`.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct DrawableId {
+    index: u32,
+    generation: u32,
+}
+
+impl fmt::Display for DrawableId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}v{}", self.index, self.generation)
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+struct Entry {
+    drawable: Arc<Drawable>,
+    tag: Arc<str>,
+    /// position in the stacking order, increasing from bottom to top.
+    z: i64,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+struct Slot {
+    /// the generation of the entry, or of the last one while the slot is free.
+    generation: u32,
+    /// the newest generation ever handed out for the slot, which
+    /// restoring an older storage doesn't roll back.
+    issued: u32,
+    entry: Option<Entry>,
+}
+
+/// # Storage
+/// a slab of drawables and their cached SVG tags,
+/// plus the ids of the live drawables in stacking order.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Storage {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    /// the live ids by their position in the stacking order.
+    order: BTreeMap<i64, DrawableId>,
+    next_z: i64,
+    /// the lowest position handed out, sending a drawable to the back goes below it.
+    lowest_z: i64,
+}
+
+impl Storage {
+    pub fn new() -> Storage {
+        Storage::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        let reused = additional.min(self.free.len());
+
+        self.slots.reserve(additional - reused);
+    }
+
+    /// the ids of the live drawables, from the bottom of the stack to the top.
+    pub fn order(&self) -> impl DoubleEndedIterator<Item = DrawableId> + ExactSizeIterator + '_ {
+        self.order.values().copied()
+    }
+
+    /// stores a drawable on top of the stack.
+    pub fn insert(&mut self, drawable: Arc<Drawable>, tag: Arc<str>) -> DrawableId {
+        let z = self.next_z;
+        let entry = Some(Entry { drawable, tag, z });
+        self.next_z += 1;
+
+        let id = match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.issued = slot.issued.wrapping_add(1);
+                slot.generation = slot.issued;
+                slot.entry = entry;
+
+                DrawableId { index, generation: slot.generation }
+            }
+            None => {
+                self.slots.push(Slot { generation: 0, issued: 0, entry });
+
+                DrawableId { index: (self.slots.len() - 1) as u32, generation: 0 }
+            }
+        };
+
+        self.order.insert(z, id);
+
+        id
+    }
+
+    /// stores many drawables on top of the stack, in order.
+    pub fn extend(&mut self, drawables: impl IntoIterator<Item = (Arc<Drawable>, Arc<str>)>) -> Vec<DrawableId> {
+        let below = std::mem::take(&mut self.order);
+        let ids = drawables.into_iter().map(|(drawable, tag)| self.insert(drawable, tag)).collect();
+
+        // the new drawables all went above the old ones, so their part of the
+        // order was built on its own, merging it in is O(n) and inserting it O(m log n)
+        let mut above = std::mem::replace(&mut self.order, below);
+        if above.len() >= self.order.len() {
+            self.order.append(&mut above);
+        } else {
+            self.order.extend(above);
+        }
+
+        ids
+    }
+
+    /// removes a drawable, returning it if `id` was still valid.
+    /// its slot is freed in O(1), taking it out of the stacking order is O(log n).
+    pub fn remove(&mut self, id: DrawableId) -> Option<Arc<Drawable>> {
+        self.entry(id)?;
+
+        let slot = &mut self.slots[id.index as usize];
+        let entry = slot.entry.take().expect("the id was checked to be live");
+        self.order.remove(&entry.z);
+        self.free.push(id.index);
+
+        Some(entry.drawable)
+    }
+
+    /// carries over which generations `newer` handed out, after the storage
+    /// replaced it to go back to an older state. the slots it has that this
+    /// one doesn't are kept free, so no id handed out by either storage is
+    /// ever handed out again.
+    pub fn keep_generations_of(&mut self, newer: &Storage) {
+        for (index, newer) in newer.slots.iter().enumerate() {
+            match self.slots.get_mut(index) {
+                Some(slot) => slot.issued = slot.issued.max(newer.issued),
+                None => {
+                    self.slots.push(Slot { generation: newer.generation, issued: newer.issued, entry: None });
+                    self.free.push(index as u32);
+                }
+            }
+        }
+    }
+
+    pub fn get(&self, id: DrawableId) -> Option<&Arc<Drawable>> {
+        self.entry(id).map(|entry| &entry.drawable)
+    }
+
+    pub fn get_mut(&mut self, id: DrawableId) -> Option<&mut Arc<Drawable>> {
+        self.entry_mut(id).map(|entry| &mut entry.drawable)
+    }
+
+    pub fn tag(&self, id: DrawableId) -> Option<&Arc<str>> {
+        self.entry(id).map(|entry| &entry.tag)
+    }
+
+    pub fn set_tag(&mut self, id: DrawableId, tag: Arc<str>) {
+        if let Some(entry) = self.entry_mut(id) {
+            entry.tag = tag;
+        }
+    }
+
+    /// moves a drawable to the top of the stack, returning whether `id` was valid.
+    pub fn raise(&mut self, id: DrawableId) -> bool {
+        let z = self.next_z;
+        self.next_z += 1;
+
+        self.restack(id, z)
+    }
+
+    /// moves a drawable to the bottom of the stack, returning whether `id` was valid.
+    pub fn lower(&mut self, id: DrawableId) -> bool {
+        self.lowest_z -= 1;
+        let z = self.lowest_z;
+
+        self.restack(id, z)
+    }
+
+    /// moves a drawable to position `z`, returning whether `id` was valid.
+    fn restack(&mut self, id: DrawableId, z: i64) -> bool {
+        let entry = match self.entry_mut(id) {
+            Some(entry) => entry,
+            None => return false,
+        };
+        let old = std::mem::replace(&mut entry.z, z);
+
+        self.order.remove(&old);
+        self.order.insert(z, id);
+
+        true
+    }
+
+    /// the live drawable right above `id` in the stacking order,
+    /// `None` if `id` is on top or no longer valid.
+    pub fn above(&self, id: DrawableId) -> Option<DrawableId> {
+        let z = self.entry(id)?.z;
+
+        self.order.range(z + 1..).next().map(|(_, &above)| above)
+    }
+
+    /// sorts `ids` from the bottom of the stack to the top,
+    /// dropping the ones that are no longer valid.
+    pub fn sort_by_stacking(&self, ids: &mut Vec<DrawableId>) {
+        ids.retain(|&id| self.entry(id).is_some());
+        ids.sort_by_key(|&id| self.entry(id).map(|entry| entry.z));
+    }
+
+    /// the live drawables and their cached tags in stacking order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (DrawableId, &Arc<Drawable>, &Arc<str>)> + '_ {
+        self.order.values().map(move |&id| {
+            let entry = self.entry(id).expect("every id in the stacking order is live");
+
+            (id, &entry.drawable, &entry.tag)
+        })
+    }
+
+    fn entry(&self, id: DrawableId) -> Option<&Entry> {
+        self.slots
+            .get(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.entry.as_ref())
+    }
+
+    fn entry_mut(&mut self, id: DrawableId) -> Option<&mut Entry> {
+        self.slots
+            .get_mut(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.entry.as_mut())
+    }
+}
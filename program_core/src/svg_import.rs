@@ -0,0 +1,522 @@
+//! # svg_import
+//! this module reads drawables back from SVG documents. it understands the
+//! `line`, `circle`, `rect` and `text` elements `Canvas::to_svg` writes, the
+//! `path` elements of arcs, and the `linearGradient` elements they're painted
+//! with. groups are looked into and every other element is skipped with its
+//! content, paths of other shapes included.
+//! a shape without the attributes it needs, or one a canvas would refuse,
+//! like a circle without a radius, fails the whole document.
+
+use super::canvas::validate;
+use super::{Angle, Arc, CanvasError, Circle, Color, Drawable, Line, LineCap, LineJoin, Paint, Point, Rect, StrokeStyle, Style, Text, DEFAULT_FONT_SIZE};
+use std::fmt;
+
+/// the error returned when drawables can't be read from an SVG document.
+#[derive(PartialEq, Debug)]
+pub enum SvgError {
+    /// the document has no `svg` element.
+    NotSvg,
+    /// the document ends inside a tag, a comment or an element.
+    UnexpectedEnd,
+    /// a closing tag doesn't match the element it closes.
+    MismatchedTag { expected: String, found: String },
+    /// an attribute that should be a number isn't one.
+    InvalidNumber { element: String, attribute: String, value: String },
+    /// an attribute without which the element draws nothing, like the radius of a circle.
+    MissingAttribute { element: String, attribute: String },
+    /// a transform the drawables can't keep, anything but rotating a
+    /// line, circle, rect or arc.
+    UnsupportedTransform(String),
+    /// a drawable was read but can't be on a canvas, like a circle without a radius.
+    InvalidDrawable(CanvasError),
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SvgError::NotSvg => write!(f, "not an SVG document"),
+            SvgError::UnexpectedEnd => write!(f, "the document ends unexpectedly"),
+            SvgError::MismatchedTag { expected, found } => {
+                write!(f, "expected </{}> but found </{}>", expected, found)
+            }
+            SvgError::InvalidNumber { element, attribute, value } => {
+                write!(f, "invalid {} of <{}>: \"{}\"", attribute, element, value)
+            }
+            SvgError::MissingAttribute { element, attribute } => write!(f, "<{}> has no {}", element, attribute),
+            SvgError::UnsupportedTransform(transform) => write!(f, "unsupported transform \"{}\"", transform),
+            SvgError::InvalidDrawable(error) => write!(f, "invalid drawable: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+/// the gradients of a document by their id.
+type Gradients = Vec<(String, Paint)>;
+
+/// an opening tag and its attributes, with entities already replaced.
+struct Tag<'a> {
+    name: &'a str,
+    attrs: Vec<(&'a str, String)>,
+    /// whether it closes itself, like `<line />`.
+    empty: bool,
+}
+
+impl Tag<'_> {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.iter().find(|(attr, _)| *attr == name).map(|(_, value)| value.as_str())
+    }
+
+    /// the attribute as a number, for the ones without which the element draws nothing.
+    fn required(&self, name: &str) -> Result<f64, SvgError> {
+        match self.attr(name) {
+            Some(_) => self.number(name),
+            None => Err(SvgError::MissingAttribute { element: self.name.to_string(), attribute: name.to_string() }),
+        }
+    }
+
+    /// the attribute as a number, zero when it's missing like SVG does.
+    fn number(&self, name: &str) -> Result<f64, SvgError> {
+        let value = match self.attr(name) {
+            Some(value) => value.trim(),
+            None => return Ok(0f64),
+        };
+
+        value
+            .strip_suffix("px")
+            .unwrap_or(value)
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite())
+            .ok_or_else(|| SvgError::InvalidNumber {
+                element: self.name.to_string(),
+                attribute: name.to_string(),
+                value: value.to_string(),
+            })
+    }
+
+    /// whether any of the attributes is a percentage, which is relative
+    /// to the viewport and has no place in a drawing. the grid background
+    /// `Canvas::to_svg` writes is one.
+    fn relative(&self, names: &[&str]) -> bool {
+        names.iter().any(|name| self.attr(name).is_some_and(|value| value.trim().ends_with('%')))
+    }
+
+    /// the paints, stroke width, dashes, caps and joins of the element. paints
+    /// are the `#rrggbb` colors `Canvas::to_svg` writes or `url(#id)` references
+    /// to `gradients`. other colors, like `red`, references to anything else,
+    /// and caps and joins SVG doesn't have are left to the renderer.
+    /// dots are read back as dashes, they're written the same.
+    fn style(&self, gradients: &Gradients) -> Result<Style, SvgError> {
+        let color = |name: &str| {
+            let value = self.attr(name)?.trim();
+            match value.strip_prefix("url(#").and_then(|id| id.strip_suffix(')')) {
+                Some(id) => gradients.iter().find(|(other, _)| other == id.trim()).map(|(_, paint)| paint.clone()),
+                None => value.parse::<Color>().ok().map(Paint::Solid),
+            }
+        };
+        let stroke_width = match self.attr("stroke-width") {
+            Some(_) => Some(self.number("stroke-width")?),
+            None => None,
+        };
+
+        Ok(Style {
+            stroke: color("stroke"),
+            stroke_width,
+            fill: color("fill"),
+            stroke_style: self.stroke_style()?,
+            line_cap: self.attr("stroke-linecap").and_then(|value| value.parse::<LineCap>().ok()).unwrap_or_default(),
+            line_join: self.attr("stroke-linejoin").and_then(|value| value.parse::<LineJoin>().ok()).unwrap_or_default(),
+            ..Style::default()
+        })
+    }
+
+    /// the dashes of a `stroke-dasharray`, which are lengths apart by commas or spaces.
+    fn stroke_style(&self) -> Result<StrokeStyle, SvgError> {
+        let value = match self.attr("stroke-dasharray").map(str::trim) {
+            Some(value) if !value.is_empty() && value != "none" => value,
+            _ => return Ok(StrokeStyle::Solid),
+        };
+
+        value
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|dash| !dash.is_empty())
+            .map(|dash| dash.strip_suffix("px").unwrap_or(dash).parse::<f64>().ok().filter(|dash| dash.is_finite() && *dash >= 0f64))
+            .collect::<Option<Vec<f64>>>()
+            .map(StrokeStyle::Dashed)
+            .ok_or_else(|| SvgError::InvalidNumber {
+                element: self.name.to_string(),
+                attribute: String::from("stroke-dasharray"),
+                value: value.to_string(),
+            })
+    }
+
+    /// the angle and the pivot of a `rotate(angle [cx cy])` transform.
+    fn rotation(&self) -> Result<Option<(Angle, Point)>, SvgError> {
+        self.rotation_in("transform")
+    }
+
+    /// the rotation in the transform attribute `name`, like a gradient's `gradientTransform`.
+    fn rotation_in(&self, name: &str) -> Result<Option<(Angle, Point)>, SvgError> {
+        let transform = match self.attr(name).map(str::trim) {
+            Some(transform) if !transform.is_empty() => transform,
+            _ => return Ok(None),
+        };
+        let unsupported = || SvgError::UnsupportedTransform(transform.to_string());
+
+        let args = transform
+            .strip_prefix("rotate(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(unsupported)?;
+        let args = args
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| arg.parse::<f64>().ok().filter(|arg| arg.is_finite()))
+            .collect::<Option<Vec<f64>>>()
+            .ok_or_else(unsupported)?;
+
+        match args[..] {
+            [angle] => Ok(Some((Angle::degrees(angle), Point::new(0f64, 0f64)))),
+            [angle, cx, cy] => Ok(Some((Angle::degrees(angle), Point::new(cx, cy)))),
+            _ => Err(unsupported()),
+        }
+    }
+}
+
+/// reads the drawables of an SVG document, in document order.
+pub(crate) fn parse_svg(svg: &str) -> Result<Vec<Drawable>, SvgError> {
+    // shapes may refer to gradients defined after them
+    let gradients = parse_gradients(svg)?;
+    let mut drawables = Vec::new();
+    // the elements that are open, and how many of the innermost ones are skipped
+    let mut open: Vec<&str> = Vec::new();
+    let mut skipping = 0usize;
+    let mut seen_svg = false;
+    let mut rest = svg;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = &after[after.find("-->").ok_or(SvgError::UnexpectedEnd)? + 3..];
+            continue;
+        }
+        if rest.starts_with("<?") || rest.starts_with("<!") {
+            rest = &rest[rest.find('>').ok_or(SvgError::UnexpectedEnd)? + 1..];
+            continue;
+        }
+
+        let end = rest.find('>').ok_or(SvgError::UnexpectedEnd)?;
+        let inner = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = inner.strip_prefix('/') {
+            let name = name.trim();
+            match open.pop() {
+                Some(expected) if expected == name => {}
+                Some(expected) => return Err(SvgError::MismatchedTag { expected: expected.to_string(), found: name.to_string() }),
+                None => return Err(SvgError::MismatchedTag { expected: String::new(), found: name.to_string() }),
+            }
+            skipping = skipping.saturating_sub(1);
+            continue;
+        }
+
+        let tag = parse_tag(inner);
+        if skipping > 0 {
+            if !tag.empty {
+                open.push(tag.name);
+                skipping += 1;
+            }
+            continue;
+        }
+
+        match tag.name {
+            "svg" => seen_svg = true,
+            // moving every drawable in the group isn't supported yet
+            "g" if tag.attr("transform").is_some() => {
+                return Err(SvgError::UnsupportedTransform(tag.attr("transform").unwrap_or_default().to_string()));
+            }
+            "g" => {}
+            "text" if !tag.empty => {
+                let close = rest.find("</text>").ok_or(SvgError::UnexpectedEnd)?;
+                let content = unescape(&strip_tags(&rest[..close]));
+                rest = &rest[close + "</text>".len()..];
+
+                drawables.push(checked(text(&tag, content, &gradients)?)?);
+                continue;
+            }
+            _ => {
+                if let Some(drawable) = drawable(&tag, &gradients)? {
+                    drawables.push(checked(drawable)?);
+                }
+                if !tag.empty {
+                    skipping += 1;
+                }
+            }
+        }
+
+        if !tag.empty {
+            open.push(tag.name);
+        }
+    }
+
+    if !seen_svg {
+        return Err(SvgError::NotSvg);
+    }
+    if !open.is_empty() {
+        return Err(SvgError::UnexpectedEnd);
+    }
+
+    Ok(drawables)
+}
+
+/// the `linearGradient` elements of a document, wherever they are, with
+/// the rotation and stops `Paint::LinearGradient` writes. gradients without
+/// an id can't be referred to and are left out.
+fn parse_gradients(svg: &str) -> Result<Gradients, SvgError> {
+    let mut gradients = Gradients::new();
+    let mut rest = svg;
+
+    while let Some(start) = rest.find("<linearGradient") {
+        rest = &rest[start..];
+        let end = rest.find('>').ok_or(SvgError::UnexpectedEnd)?;
+        let tag = parse_tag(&rest[1..end]);
+        rest = &rest[end + 1..];
+
+        let content = if tag.empty {
+            ""
+        } else {
+            let close = rest.find("</linearGradient>").ok_or(SvgError::UnexpectedEnd)?;
+            let (content, after) = rest.split_at(close);
+            rest = after;
+            content
+        };
+        let id = match tag.attr("id") {
+            Some(id) => id.trim().to_string(),
+            None => continue,
+        };
+
+        let angle = tag.rotation_in("gradientTransform")?.map(|(angle, _)| angle).unwrap_or_default();
+        let mut stops = Vec::new();
+        let mut inner = content;
+        while let Some(start) = inner.find("<stop") {
+            inner = &inner[start..];
+            let end = inner.find('>').ok_or(SvgError::UnexpectedEnd)?;
+            stops.push(stop(&parse_tag(&inner[1..end]))?);
+            inner = &inner[end + 1..];
+        }
+
+        gradients.push((id, Paint::LinearGradient { stops, angle }));
+    }
+
+    Ok(gradients)
+}
+
+/// the offset and color of a gradient's `stop`, its opacity as the alpha of the color.
+fn stop(tag: &Tag) -> Result<(f64, Color), SvgError> {
+    let offset = match tag.attr("offset").map(str::trim) {
+        Some(percent) if percent.ends_with('%') => percent.trim_end_matches('%').parse::<f64>().ok().map(|percent| percent / 100f64),
+        Some(offset) => offset.parse::<f64>().ok(),
+        None => Some(0f64),
+    };
+    let invalid = |attribute: &str| SvgError::InvalidNumber {
+        element: tag.name.to_string(),
+        attribute: attribute.to_string(),
+        value: tag.attr(attribute).unwrap_or_default().to_string(),
+    };
+    let offset = offset.filter(|offset| offset.is_finite()).ok_or_else(|| invalid("offset"))?;
+
+    let color = tag.attr("stop-color").and_then(|value| value.trim().parse::<Color>().ok()).unwrap_or(Color::BLACK);
+    let opacity = match tag.attr("stop-opacity") {
+        Some(_) => tag.number("stop-opacity")?.clamp(0f64, 1f64),
+        None => 1f64,
+    };
+
+    Ok((offset, Color::rgba(color.r, color.g, color.b, (opacity * 255f64).round() as u8)))
+}
+
+/// the drawable an element stands for, `None` for elements that aren't drawables.
+fn drawable(tag: &Tag, gradients: &Gradients) -> Result<Option<Drawable>, SvgError> {
+    let drawable = match tag.name {
+        "line" if !tag.relative(&["x1", "y1", "x2", "y2"]) => {
+            let start = Point::new(tag.number("x1")?, tag.number("y1")?);
+            let end = Point::new(tag.required("x2")?, tag.required("y2")?);
+            styled(tag, gradients, rotated(tag, Drawable::Line(Line::new(&start, &end)))?)?
+        }
+        "circle" if !tag.relative(&["cx", "cy", "r"]) => {
+            let center = Point::new(tag.number("cx")?, tag.number("cy")?);
+            styled(tag, gradients, rotated(tag, Drawable::Circle(Circle::new(&center, tag.required("r")?)))?)?
+        }
+        "rect" if !tag.relative(&["x", "y", "width", "height"]) => {
+            let (width, height) = (tag.required("width")?, tag.required("height")?);
+            let center = Point::new(tag.number("x")? + width / 2f64, tag.number("y")? + height / 2f64);
+            styled(tag, gradients, rotated(tag, Drawable::Rect(Rect::from_center(&center, width, height, 0f64)))?)?
+        }
+        "text" => text(tag, String::new(), gradients)?,
+        "path" => match arc(tag) {
+            Some(arc) => styled(tag, gradients, rotated(tag, arc)?)?,
+            None => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+
+    Ok(Some(drawable))
+}
+
+/// the arc of a path like `M x y A r r 0 large-arc sweep x y`, the way `Arc`
+/// writes itself, `None` for any other path. its center is the one on the
+/// side the flags pick, and a radius too short to reach from one end to the
+/// other is lengthened like SVG does.
+fn arc(tag: &Tag) -> Option<Drawable> {
+    let d = tag.attr("d")?.replace('M', " M ").replace('A', " A ");
+    let tokens: Vec<&str> = d.split(|c: char| c == ',' || c.is_whitespace()).filter(|token| !token.is_empty()).collect();
+    let ["M", x0, y0, "A", rx, ry, _, large_arc, sweep, x1, y1] = tokens[..] else { return None };
+
+    let number = |token: &str| token.parse::<f64>().ok().filter(|number| number.is_finite());
+    let flag = |token: &str| match token {
+        "0" => Some(false),
+        "1" => Some(true),
+        _ => None,
+    };
+    let (mut start, mut end) = (Point::new(number(x0)?, number(y0)?), Point::new(number(x1)?, number(y1)?));
+    let (rx, ry, large_arc, sweep) = (number(rx)?.abs(), number(ry)?.abs(), flag(large_arc)?, flag(sweep)?);
+    if rx != ry || start == end {
+        return None;
+    }
+
+    // an arc drawn against the way angles grow is the same arc from its end
+    if !sweep {
+        (start, end) = (end, start);
+    }
+    let across = end.clone() - start.clone();
+    let half = across.len() / 2f64;
+    let radius = rx.max(half);
+    let away = (radius.powi(2) - half.powi(2)).max(0f64).sqrt() * if large_arc { -1f64 } else { 1f64 };
+    let center = Point::new((start.x + end.x) / 2f64 - across.y / across.len() * away, (start.y + end.y) / 2f64 + across.x / across.len() * away);
+
+    let angle = |point: &Point| Angle::radians((point.clone() - center.clone()).to_polar().1);
+
+    Some(Drawable::Arc(Arc::new(&center, radius, angle(&start), angle(&end))))
+}
+
+/// `drawable` when it can be on a canvas, like a line with a length.
+fn checked(drawable: Drawable) -> Result<Drawable, SvgError> {
+    validate(&drawable).map_err(SvgError::InvalidDrawable)?;
+
+    Ok(drawable)
+}
+
+/// gives `drawable` the style of its element.
+fn styled(tag: &Tag, gradients: &Gradients, mut drawable: Drawable) -> Result<Drawable, SvgError> {
+    drawable.set_style(tag.style(gradients)?);
+
+    Ok(drawable)
+}
+
+fn text(tag: &Tag, content: String, gradients: &Gradients) -> Result<Drawable, SvgError> {
+    let pos = Point::new(tag.number("x")?, tag.number("y")?);
+    let font_size = match tag.attr("font-size") {
+        Some(_) => tag.number("font-size")?,
+        None => DEFAULT_FONT_SIZE,
+    };
+
+    styled(tag, gradients, rotated(tag, Drawable::Text(Text::with_font_size(content, pos, font_size)))?)
+}
+
+/// applies the element's rotation to `drawable`. texts can't be
+/// rotated, their glyphs would stay upright.
+fn rotated(tag: &Tag, mut drawable: Drawable) -> Result<Drawable, SvgError> {
+    match tag.rotation()? {
+        None => {}
+        Some((angle, pivot)) => match drawable {
+            Drawable::Line(_) | Drawable::Circle(_) | Drawable::Rect(_) | Drawable::Arc(_) => drawable.rotate(angle, &pivot),
+            _ => return Err(SvgError::UnsupportedTransform(tag.attr("transform").unwrap_or_default().to_string())),
+        },
+    }
+
+    Ok(drawable)
+}
+
+/// splits `<name attr="value" ... />` without its angle brackets.
+fn parse_tag(inner: &str) -> Tag<'_> {
+    let empty = inner.trim_end().ends_with('/');
+    let inner = inner.trim_end().trim_end_matches('/');
+    let name_end = inner.find(|c: char| c.is_whitespace()).unwrap_or(inner.len());
+    let (name, mut rest) = inner.split_at(name_end);
+
+    let mut attrs = Vec::new();
+    while let Some(eq) = rest.find('=') {
+        let attr = rest[..eq].trim();
+        let value = rest[eq + 1..].trim_start();
+
+        let quote = match value.chars().next() {
+            Some(quote) if quote == '"' || quote == '\'' => quote,
+            _ => break,
+        };
+        let close = match value[1..].find(quote) {
+            Some(close) => close + 1,
+            None => break,
+        };
+        attrs.push((attr, unescape(&value[1..close])));
+        rest = &value[close + 1..];
+    }
+
+    Tag { name, attrs, empty }
+}
+
+/// the character data of `content`, without the tags of any child elements.
+fn strip_tags(content: &str) -> String {
+    let mut text = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = rest[start..].find('>').map_or("", |end| &rest[start + end + 1..]);
+    }
+    text.push_str(rest);
+
+    text
+}
+
+/// replaces the predefined XML entities and character references.
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let end = match rest.find(';') {
+            Some(end) => end,
+            None => break,
+        };
+        let entity = &rest[1..end];
+        let replacement = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16).ok())
+                .unwrap_or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+
+        match replacement {
+            Some(c) => {
+                unescaped.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+
+    unescaped
+}
@@ -0,0 +1,82 @@
+//! # units
+//! this module contains the units lengths are shown in. drawings are always
+//! stored in pixels, a unit only changes how their lengths are displayed.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// a unit to show lengths in.
+///
+/// # Examples
+/// ```
+/// use program_core::Unit;
+///
+/// assert_eq!(1.0, Unit::Px.px_per_unit());
+/// assert!((Unit::Cm.px_per_unit() - 10.0 * Unit::Mm.px_per_unit()).abs() < 1e-9);
+/// assert_eq!(Ok(Unit::Mm), "mm".parse());
+/// assert_eq!("cm", Unit::Cm.to_string());
+/// assert!("in".parse::<Unit>().is_err());
+///
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub enum Unit {
+    #[default]
+    Px,
+    Mm,
+    Cm,
+}
+
+/// pixels per inch, the resolution CSS assumes.
+const PX_PER_INCH: f64 = 96.0;
+const MM_PER_INCH: f64 = 25.4;
+
+impl Unit {
+    /// every unit, in the order they're offered.
+    pub const ALL: [Unit; 3] = [Unit::Px, Unit::Mm, Unit::Cm];
+
+    /// how many pixels make up one of this unit.
+    pub fn px_per_unit(&self) -> f64 {
+        match self {
+            Unit::Px => 1f64,
+            Unit::Mm => PX_PER_INCH / MM_PER_INCH,
+            Unit::Cm => PX_PER_INCH / MM_PER_INCH * 10f64,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Unit::Px => "px",
+            Unit::Mm => "mm",
+            Unit::Cm => "cm",
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// the error returned when parsing a unit that isn't supported.
+#[derive(PartialEq, Eq, Debug)]
+pub struct ParseUnitError(String);
+
+impl fmt::Display for ParseUnitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown unit \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for ParseUnitError {}
+
+impl FromStr for Unit {
+    type Err = ParseUnitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Unit::ALL
+            .into_iter()
+            .find(|unit| unit.symbol() == s.trim())
+            .ok_or_else(|| ParseUnitError(s.to_string()))
+    }
+}
@@ -0,0 +1,98 @@
+//! # viewport
+//! this module contains the part of a drawing shown on screen,
+//! and the conversions between screen and drawing coordinates.
+
+use super::{Aabb, Point};
+
+/// the closest the view can zoom out.
+pub const MIN_ZOOM: f64 = 0.05;
+/// the closest the view can zoom in.
+pub const MAX_ZOOM: f64 = 50.0;
+
+/// # Viewport
+/// structure to hold the view of a drawing.
+/// it stores the drawing coordinates shown at the top left corner
+/// of the screen, and the zoom, in screen pixels per drawing unit.
+///
+/// # Examples
+/// ```
+/// use program_core::{Point, Viewport};
+///
+/// let mut viewport = Viewport::default();
+/// viewport.zoom_at(&Point::new(100.0, 100.0), 2.0);
+///
+/// assert_eq!(Point::new(100.0, 100.0), viewport.screen_to_world(&Point::new(100.0, 100.0)));
+/// assert_eq!(Point::new(75.0, 50.0), viewport.screen_to_world(&Point::new(50.0, 0.0)));
+/// assert_eq!(Point::new(50.0, 0.0), viewport.world_to_screen(&Point::new(75.0, 50.0)));
+///
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Viewport {
+    origin: Point,
+    zoom: f64,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Viewport { origin: Point::new(0.0, 0.0), zoom: 1.0 }
+    }
+}
+
+impl Viewport {
+    /// the drawing coordinates shown at the top left corner of the screen.
+    pub fn origin(&self) -> Point {
+        self.origin.clone()
+    }
+
+    pub fn zoom(&self) -> f64 {
+        self.zoom
+    }
+
+    pub fn screen_to_world(&self, point: &Point) -> Point {
+        Point::new(self.origin.x + point.x / self.zoom, self.origin.y + point.y / self.zoom)
+    }
+
+    pub fn world_to_screen(&self, point: &Point) -> Point {
+        Point::new((point.x - self.origin.x) * self.zoom, (point.y - self.origin.y) * self.zoom)
+    }
+
+    /// multiplies the zoom by `factor`, keeping the drawing under the screen
+    /// point `anchor` in place. the zoom is clamped to `MIN_ZOOM..=MAX_ZOOM`.
+    pub fn zoom_at(&mut self, anchor: &Point, factor: f64) {
+        if !factor.is_finite() || factor <= 0f64 {
+            return;
+        }
+
+        let fixed = self.screen_to_world(anchor);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.origin = Point::new(fixed.x - anchor.x / self.zoom, fixed.y - anchor.y / self.zoom);
+    }
+
+    /// moves the view so the drawing follows a drag of `offset` screen pixels.
+    pub fn pan_by(&mut self, offset: &Point) {
+        self.origin = Point::new(self.origin.x - offset.x / self.zoom, self.origin.y - offset.y / self.zoom);
+    }
+
+    /// zooms and pans so `bounds` fills a screen of the given size, centered.
+    pub fn fit(&mut self, bounds: &Aabb, screen_width: f64, screen_height: f64) {
+        let (min, max) = (bounds.min(), bounds.max());
+        let (width, height) = (max.x - min.x, max.y - min.y);
+
+        self.zoom = if width == 0f64 && height == 0f64 {
+            1f64
+        } else {
+            (screen_width / width).min(screen_height / height).clamp(MIN_ZOOM, MAX_ZOOM)
+        };
+
+        let center = Point::new((min.x + max.x) / 2f64, (min.y + max.y) / 2f64);
+        self.origin = Point::new(
+            center.x - screen_width / self.zoom / 2f64,
+            center.y - screen_height / self.zoom / 2f64,
+        );
+    }
+
+    /// the part of the drawing shown on a screen of the given size.
+    pub fn visible(&self, screen_width: f64, screen_height: f64) -> Aabb {
+        Aabb::new(&self.origin, &self.screen_to_world(&Point::new(screen_width, screen_height)))
+    }
+}
@@ -1,51 +1,1928 @@
-use program_core::{Drawable, Point};
+use program_core::{Aabb, Angle, ApplyPropsError, CanvasError, Circle, Drawable, DrawableId, DrawableProps, GuideAxis, GuideId, HandleKind, Line, MergePlacement, Point, Rect, ResizeMode, Snap, SnapKind, SnapKinds, Snapshot, DEFAULT_FONT_SIZE};
+use std::f64::consts::FRAC_PI_2;
+use std::rc::Rc;
+use super::autosave::SaveStatus;
+use super::i18n::{Language, Strings};
+use super::context_menu::MenuAction;
+use super::settings::DocumentSettings;
+use super::shortcuts::{Shortcut, DUPLICATE_OFFSET};
 
-#[derive(Clone, PartialEq)]
-pub enum Message {
+/// a tool that draws on the canvas. with none selected clicks
+/// select drawables and drag them.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Tool {
     Line,
-    FinishLine(Point),
+    Circle,
+    /// draws the circle through three clicks.
+    CircleThreePoints,
+    Rect,
+    Text,
+    /// places the armed stamp, centered on the click.
+    Stamp,
+}
+
+/// a text being typed in place on the canvas.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TextEdit {
+    /// where the text starts, in drawing coordinates.
+    pub pos: Point,
+    pub content: String,
+    pub font_size: f64,
+    /// the text being changed, `None` for a new one.
+    pub editing: Option<DrawableId>,
+}
+
+/// a value typed in for the current tool instead of clicking.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ToolInput {
+    /// the distance from the first point, or a circle's radius.
+    Length(f64),
+    /// the direction from the first point, in radians counter-clockwise
+    /// from the x axis as seen on screen, like in CAD programs.
+    Angle(f64),
+    /// a point in drawing coordinates, placed like a click that doesn't snap.
+    Point(Point),
+    /// an offset from the first point.
+    Relative(Point),
+}
+
+/// what a `ToolInput` did.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ToolProgress {
+    /// the first point of the shape was placed.
+    FirstPoint,
+    /// the input is kept until the rest is given, like a length waiting for its angle.
+    Pending,
+    /// the shape was drawn.
+    Drawn(DrawableId),
+    /// a stamp was placed, or a text was started.
+    Placed,
+    /// the shape couldn't be drawn, see `AppState::draw_error`.
+    Invalid(CanvasError),
+    /// the input means nothing right now, like an angle before the first point.
+    Ignored,
+}
+
+/// the width and height of a new drawing, in pixels.
+pub const DEFAULT_CANVAS_SIZE: (f64, f64) = (1200.0, 800.0);
+
+/// the side, in screen pixels, of the handles around the selection.
+pub const HANDLE_SIZE: f64 = 8.0;
+/// how far, in screen pixels, the rotation handle is above the selection.
+pub const ROTATE_HANDLE_DISTANCE: f64 = 24.0;
+/// how close, in screen pixels, the pointer has to be to a point to snap to it.
+pub const SNAP_DISTANCE: f64 = 8.0;
+/// the steps, in degrees, rotating snaps to with shift held.
+pub const ROTATION_STEP: f64 = 15.0;
+/// how close, in screen pixels, a press has to be to a guide to drag it.
+pub const GUIDE_GRAB_DISTANCE: f64 = 4.0;
+
+/// the modifier keys held during a pointer event.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub alt: bool,
+}
+
+/// a drag of the selected drawable that is in progress.
+#[derive(Clone, PartialEq)]
+struct Drag {
+    action: DragAction,
+    before: Snapshot,
+    moved: bool,
+}
+
+/// what a drag does to the selected drawable.
+#[derive(Clone, PartialEq)]
+enum DragAction {
+    /// moves it along with the pointer.
+    Move { last: Point },
+    /// reshapes or rotates it with one of its handles. every step starts
+    /// over from the `original` drawable so rounding errors don't pile up.
+    Handle { kind: HandleKind, original: Drawable, start: Point, angle: f64 },
+    /// draws a box selecting the drawables it overlaps, or with
+    /// `contained` set only the ones inside it. with `add` they join the
+    /// selection instead of replacing it.
+    Marquee { start: Point, end: Point, contained: bool, add: bool },
+    /// moves a ruler guide, removing it when it's let go outside the view.
+    Guide { id: GuideId },
 }
 
 #[derive(Clone, PartialEq)]
 pub struct AppState {
-    drawables: Box<Vec<Drawable>>,
-    current_message: Option<Message>,
-    prev_message: Option<Message>,
-    current_point: Option<Point>,
+    canvas: program_core::Canvas,
+    tool: Option<Tool>,
+    /// the first point of the shape being drawn by `tool`.
+    pending_point: Option<Point>,
+    /// the second point of a circle through three points, once it's placed.
+    second_point: Option<Point>,
+    /// the drawables the stamp tool places.
+    stamp: Option<Rc<[Drawable]>>,
+    keep_tool_armed: bool,
+    drag: Option<Drag>,
+    history: Vec<Snapshot>,
+    cursor: Option<Point>,
+    /// the drawable under the pointer, which a click would select.
+    hover: Option<DrawableId>,
+    snapping: bool,
+    /// the kinds of points snapped to while snapping is on.
+    snap_kinds: SnapKinds,
+    /// what the pointer snapped to, while drawing or dragging.
+    snap: Option<Snap>,
+    /// the drawable copied from the context menu, pasted from it too.
+    clipboard: Option<Drawable>,
+    text_edit: Option<TextEdit>,
+    /// the size new texts are given.
+    font_size: f64,
+    /// why the last file couldn't be opened, until it's dismissed.
+    import_error: Option<String>,
+    /// why the last shape couldn't be drawn, until another one is.
+    draw_error: Option<CanvasError>,
+    /// whether shift is held, flipping ortho mode for the shape being drawn.
+    ortho_inverted: bool,
+    /// a length and an angle typed in, waiting for each other.
+    typed_length: Option<f64>,
+    typed_angle: Option<f64>,
+    /// whether the last attempt to save the drawing failed.
+    save_failed: bool,
+    language: Language,
+    /// the revision of the canvas its dirty ids were last taken at, so a
+    /// render that drew that revision only has to patch them. `None` when
+    /// they can't be told apart from what was there before, like after
+    /// swapping in another drawing.
+    dirty_from: Option<u64>,
 }
 
 impl AppState {
     pub fn new() -> AppState {
         AppState { 
-            drawables: Box::new(Vec::new()),
-            current_message: None,
-            prev_message: None,
-            current_point: None,
+            canvas: program_core::Canvas::new(DEFAULT_CANVAS_SIZE.0, DEFAULT_CANVAS_SIZE.1),
+            tool: None,
+            pending_point: None,
+            second_point: None,
+            stamp: None,
+            keep_tool_armed: true,
+            drag: None,
+            history: Vec::new(),
+            cursor: None,
+            hover: None,
+            snapping: true,
+            snap_kinds: SnapKinds::default(),
+            snap: None,
+            clipboard: None,
+            text_edit: None,
+            font_size: DEFAULT_FONT_SIZE,
+            import_error: None,
+            draw_error: None,
+            ortho_inverted: false,
+            typed_length: None,
+            typed_angle: None,
+            save_failed: false,
+            language: Language::default(),
+            dirty_from: None,
+        }
+    }
+
+    /// starts editing a drawing loaded from elsewhere.
+    pub fn with_canvas(canvas: program_core::Canvas) -> AppState {
+        AppState { canvas, ..AppState::new() }
+    }
+
+    pub fn canvas(&self) -> &program_core::Canvas {
+        &self.canvas
+    }
+
+    pub fn canvas_mut(&mut self) -> &mut program_core::Canvas {
+        &mut self.canvas
+    }
+
+    /// a copy of the state to change and set in its place. the drawables
+    /// it marked dirty are already drawn, so the copy starts without them.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::AppState;
+    ///
+    /// let mut state = AppState::new();
+    /// let id = state.canvas_mut().add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// assert_eq!(None, state.dirty_from());
+    ///
+    /// let mut edited = state.edited();
+    /// assert_eq!(Some(state.canvas().revision()), edited.dirty_from());
+    /// edited.canvas_mut().set_selected_drawable(Some(id));
+    /// edited.canvas_mut().translate_selected_drawable(&Point::new(5.0, 0.0));
+    /// assert_eq!(vec![id], edited.canvas_mut().take_dirty_ids());
+    ///
+    /// edited.replace_canvas(program_core::Canvas::new(100.0, 100.0));
+    /// assert_eq!(None, edited.dirty_from());
+    ///
+    /// ```
+    pub fn edited(&self) -> AppState {
+        let mut state = self.clone();
+        state.canvas.take_dirty_ids();
+        state.dirty_from = Some(state.canvas.revision());
+
+        state
+    }
+
+    /// the revision of the canvas the dirty ids of its canvas start from,
+    /// `None` when the whole drawing has to be drawn again.
+    pub fn dirty_from(&self) -> Option<u64> {
+        self.dirty_from
+    }
+
+    /// the selected tool, `None` while selecting and dragging drawables.
+    pub fn tool(&self) -> Option<Tool> {
+        self.tool
+    }
+
+    /// selects a tool, dropping any point the previous one was holding on to.
+    /// the panel's tool buttons and the canvas both go through the tool
+    /// kept here, so selecting one changes what clicking the canvas does.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::{AppState, Modifiers, Tool};
+    ///
+    /// let mut state = AppState::new();
+    /// let circle = state.canvas_mut().add_circle(&Point::new(10.0, 10.0), 5.0).unwrap();
+    ///
+    /// // without a tool a press selects and a click draws nothing
+    /// state.press(Point::new(10.0, 10.0), Modifiers::default());
+    /// state.release();
+    /// state.click(Point::new(10.0, 10.0));
+    /// assert_eq!(Some(circle), state.canvas().selected_drawable());
+    /// assert_eq!(1, state.canvas().len());
+    ///
+    /// state.set_tool(Some(Tool::Line));
+    /// state.canvas_mut().set_selected_drawable(None);
+    /// state.press(Point::new(10.0, 10.0), Modifiers::default());
+    /// state.release();
+    /// state.click(Point::new(10.0, 10.0));
+    /// assert_eq!(Some(Point::new(10.0, 10.0)), state.pending_point());
+    /// state.click(Point::new(40.0, 10.0));
+    /// assert_eq!(None, state.canvas().selected_drawable());
+    /// assert_eq!(2, state.canvas().len());
+    ///
+    /// state.click(Point::new(0.0, 0.0));
+    /// state.set_tool(Some(Tool::Rect));
+    /// assert_eq!(None, state.pending_point());
+    ///
+    /// ```
+    pub fn set_tool(&mut self, tool: Option<Tool>) {
+        if tool != Some(Tool::Stamp) {
+            self.stamp = None;
+        }
+        self.tool = tool;
+        self.pending_point = None;
+        self.second_point = None;
+        self.draw_error = None;
+        self.typed_length = None;
+        self.typed_angle = None;
+    }
+
+    /// selects the stamp tool with `drawables` as the stamp, so the next
+    /// click places copies of them centered on it.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Circle, Drawable, Line, Point};
+    /// use web::{AppState, Tool};
+    ///
+    /// let stamp = vec![
+    ///     Drawable::Line(Line::new(&Point::new(0.0, 0.0), &Point::new(20.0, 0.0))),
+    ///     Drawable::Circle(Circle::new(&Point::new(10.0, 0.0), 5.0)),
+    /// ];
+    /// let mut state = AppState::new();
+    /// state.set_keep_tool_armed(false);
+    /// state.arm_stamp(&stamp);
+    /// assert_eq!(Some(Tool::Stamp), state.tool());
+    ///
+    /// state.click(Point::new(100.0, 100.0));
+    /// assert_eq!(2, state.canvas().selection().len());
+    /// assert_eq!(Some("<circle cx=\"100\" cy=\"100\" r=\"5\" />"), state.canvas().svg_fragment_for(state.canvas().selection()[1]));
+    /// assert_eq!(None, state.tool());
+    /// assert_eq!(None, state.stamp());
+    ///
+    /// assert!(state.undo());
+    /// assert!(state.canvas().is_empty());
+    ///
+    /// ```
+    pub fn arm_stamp(&mut self, drawables: &[Drawable]) {
+        self.set_tool(Some(Tool::Stamp));
+        self.stamp = Some(Rc::from(drawables));
+    }
+
+    /// the drawables the stamp tool places, while it's selected.
+    pub fn stamp(&self) -> Option<&[Drawable]> {
+        self.stamp.as_deref()
+    }
+
+    /// whether a tool stays selected after it finishes a shape,
+    /// so several shapes can be drawn without going back to the panel.
+    /// otherwise the canvas goes back to selecting after every shape.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::{AppState, Tool};
+    ///
+    /// let mut state = AppState::new();
+    /// assert_eq!(None, state.tool());
+    ///
+    /// // one-shot: back to selecting once the line is done, not before
+    /// state.set_keep_tool_armed(false);
+    /// state.set_tool(Some(Tool::Line));
+    /// state.click(Point::new(0.0, 0.0));
+    /// assert_eq!(Some(Tool::Line), state.tool());
+    /// state.click(Point::new(10.0, 0.0));
+    /// assert_eq!(None, state.tool());
+    ///
+    /// // sticky: the tool stays for the next line
+    /// state.lock_tool(Tool::Line);
+    /// assert!(state.keep_tool_armed());
+    /// state.click(Point::new(0.0, 10.0));
+    /// state.click(Point::new(10.0, 10.0));
+    /// assert_eq!(Some(Tool::Line), state.tool());
+    /// assert_eq!(None, state.pending_point());
+    ///
+    /// state.cancel_tool();
+    /// assert_eq!(None, state.tool());
+    /// assert_eq!(2, state.canvas().len());
+    ///
+    /// ```
+    pub fn keep_tool_armed(&self) -> bool {
+        self.keep_tool_armed
+    }
+
+    pub fn set_keep_tool_armed(&mut self, keep_tool_armed: bool) {
+        self.keep_tool_armed = keep_tool_armed;
+    }
+
+    pub fn snapping(&self) -> bool {
+        self.snapping
+    }
+
+    /// turns snapping to the drawables and the grid on or off.
+    pub fn set_snapping(&mut self, snapping: bool) {
+        self.snapping = snapping;
+        self.snap = None;
+    }
+
+    pub fn snap_kinds(&self) -> SnapKinds {
+        self.snap_kinds
+    }
+
+    /// chooses whether points of the given `kind` are snapped to.
+    pub fn set_snap_kind(&mut self, kind: SnapKind, on: bool) {
+        self.snap_kinds.set(kind, on);
+        self.snap = None;
+    }
+
+    /// where `point` snaps to. the drawables being dragged aren't snapped to.
+    fn snap_point(&self, point: &Point) -> Snap {
+        if !self.snapping {
+            return Snap { point: point.clone(), kind: None };
+        }
+
+        let ignore = match self.drag {
+            Some(_) => self.canvas.selection(),
+            None => &[],
+        };
+        self.canvas.snap(point, self.snap_kinds, SNAP_DISTANCE / self.canvas.viewport().zoom(), ignore)
+    }
+
+    /// whether the pointer snaps, which is only while drawing or while
+    /// dragging the selection or one of its handles other than the rotation one.
+    fn snaps_pointer(&self) -> bool {
+        match self.drag.as_ref().map(|drag| &drag.action) {
+            Some(DragAction::Move { .. }) => true,
+            Some(DragAction::Handle { kind, .. }) => *kind != HandleKind::Rotate,
+            Some(DragAction::Marquee { .. } | DragAction::Guide { .. }) => false,
+            None => self.tool.is_some(),
+        }
+    }
+
+    /// what the pointer snapped to and where, shown with a marker
+    /// while drawing or dragging.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Point, SnapKind};
+    /// use web::{AppState, Tool};
+    ///
+    /// let mut state = AppState::new();
+    /// state.canvas_mut().add_line(&Point::new(0.0, 0.0), &Point::new(100.0, 0.0)).unwrap();
+    ///
+    /// state.set_cursor(Some(Point::new(51.0, 3.0)));
+    /// assert_eq!(None, state.snap());
+    ///
+    /// state.set_tool(Some(Tool::Circle));
+    /// state.set_cursor(Some(Point::new(51.0, 3.0)));
+    /// assert_eq!(Some(SnapKind::Midpoint), state.snap().and_then(|snap| snap.kind));
+    /// state.click(Point::new(51.0, 3.0));
+    /// assert_eq!(Some(Point::new(50.0, 0.0)), state.pending_point());
+    ///
+    /// state.set_snap_kind(SnapKind::Midpoint, false);
+    /// state.set_cursor(Some(Point::new(51.0, 3.0)));
+    /// assert_eq!(None, state.snap());
+    ///
+    /// state.set_snapping(false);
+    /// state.set_cursor(Some(Point::new(98.0, 1.0)));
+    /// assert_eq!(None, state.snap());
+    /// state.set_snapping(true);
+    /// state.set_cursor(Some(Point::new(98.0, 1.0)));
+    /// assert_eq!(Some(Point::new(100.0, 0.0)), state.snap().map(|snap| snap.point.clone()));
+    ///
+    /// ```
+    pub fn snap(&self) -> Option<&Snap> {
+        self.snap.as_ref().filter(|_| self.snaps_pointer())
+    }
+
+    /// selects `tool` and keeps it selected after every shape.
+    pub fn lock_tool(&mut self, tool: Tool) {
+        self.set_tool(Some(tool));
+        self.keep_tool_armed = true;
+    }
+
+    /// handles a click on the canvas with the current tool.
+    /// lines and rects take two corner clicks, circles take the center
+    /// and then a point on the rim, or three points on the rim with
+    /// `Tool::CircleThreePoints`. texts open an editor at the click.
+    /// clicks snap to the grid while it's on, a click while a text is being
+    /// typed drops that text instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::{AppState, Tool};
+    ///
+    /// let mut state = AppState::new();
+    /// state.set_tool(Some(Tool::Circle));
+    /// state.click(Point::new(10.0, 10.0));
+    /// state.click(Point::new(13.0, 14.0));
+    ///
+    /// state.set_tool(Some(Tool::Rect));
+    /// state.click(Point::new(50.0, 60.0));
+    /// state.click(Point::new(40.0, 80.0));
+    ///
+    /// assert_eq!(Some(Tool::Rect), state.tool());
+    /// assert_eq!(
+    ///     vec!["<circle cx=\"10\" cy=\"10\" r=\"5\" />", "<rect height=\"20\" width=\"10\" x=\"40\" y=\"60\" />"],
+    ///     state.canvas().ids().iter().map(|&id| state.canvas().svg_fragment_for(id).unwrap()).collect::<Vec<_>>(),
+    /// );
+    ///
+    /// state.set_tool(Some(Tool::CircleThreePoints));
+    /// state.click(Point::new(20.0, 40.0));
+    /// state.click(Point::new(40.0, 20.0));
+    /// assert_eq!(vec![Point::new(20.0, 40.0), Point::new(40.0, 20.0)], state.pending_points());
+    /// state.click(Point::new(60.0, 40.0));
+    /// assert_eq!(Some("<circle cx=\"40\" cy=\"40\" r=\"20\" />"), state.canvas().svg_fragment_for(state.canvas().ids()[2]));
+    /// assert!(state.pending_points().is_empty());
+    ///
+    /// state.set_keep_tool_armed(false);
+    /// state.set_tool(Some(Tool::Rect));
+    /// state.click(Point::new(0.0, 0.0));
+    /// state.click(Point::new(1.0, 1.0));
+    /// state.click(Point::new(2.0, 2.0));
+    ///
+    /// assert_eq!(None, state.tool());
+    /// assert_eq!(4, state.canvas().len());
+    ///
+    /// state.canvas_mut().set_grid_spacing(Some(10.0));
+    /// state.set_tool(Some(Tool::Line));
+    /// state.click(Point::new(12.0, 9.0));
+    /// assert_eq!(Some(Point::new(10.0, 10.0)), state.pending_point());
+    ///
+    /// ```
+    pub fn click(&mut self, point: Point) {
+        if self.text_edit.take().is_some() {
+            return;
+        }
+
+        let point = self.snap_point(&point).point;
+        self.place(point, true);
+    }
+
+    /// places the next point of the current tool at `point`, kept
+    /// at the angle of ortho mode with `constrain`.
+    fn place(&mut self, point: Point, constrain: bool) -> ToolProgress {
+        self.typed_length = None;
+        self.typed_angle = None;
+
+        let (tool, first) = match (self.tool, self.pending_point.take()) {
+            (None, _) => return ToolProgress::Ignored,
+            (Some(Tool::Stamp), _) => {
+                let stamp = match self.stamp.clone() {
+                    Some(stamp) => stamp,
+                    None => return ToolProgress::Ignored,
+                };
+                let before = self.canvas.snapshot();
+                if let Err(error) = self.canvas.paste(&stamp, &point) {
+                    self.draw_error = Some(error);
+                    return ToolProgress::Invalid(error);
+                }
+                self.history.push(before);
+                self.draw_error = None;
+                if !self.keep_tool_armed {
+                    self.set_tool(None);
+                }
+                return ToolProgress::Placed;
+            }
+            (Some(Tool::Text), _) => {
+                self.text_edit = Some(TextEdit { pos: point, content: String::new(), font_size: self.font_size, editing: None });
+                return ToolProgress::Placed;
+            }
+            (Some(_), None) => {
+                self.pending_point = Some(point);
+                return ToolProgress::FirstPoint;
+            }
+            (Some(Tool::CircleThreePoints), Some(first)) if self.second_point.is_none() => {
+                self.pending_point = Some(first);
+                self.second_point = Some(point);
+                return ToolProgress::Pending;
+            }
+            (Some(tool), Some(first)) => (tool, first),
+        };
+        let point = if constrain { self.drawn_point(tool, &first, point) } else { point };
+        let second = self.second_point.take();
+
+        let before = self.canvas.snapshot();
+        let added = match tool {
+            Tool::Line => self.canvas.add_line(&first, &point),
+            Tool::Circle => {
+                let radius = ((point.x - first.x).powi(2) + (point.y - first.y).powi(2)).sqrt();
+                self.canvas.add_circle(&first, radius)
+            }
+            Tool::CircleThreePoints => {
+                let second = second.expect("the second point is placed before the third");
+                self.canvas.add_circle_from_points(&first, &second, &point)
+            }
+            Tool::Rect => self.canvas.add_rect(&first, &point),
+            Tool::Text | Tool::Stamp => unreachable!("texts and stamps are placed with a single click"),
+        };
+
+        // the tool stays armed so the shape can be drawn again
+        let id = match added {
+            Ok(id) => id,
+            Err(error) => {
+                self.draw_error = Some(error);
+                return ToolProgress::Invalid(error);
+            }
+        };
+        self.history.push(before);
+        self.draw_error = None;
+
+        if !self.keep_tool_armed {
+            self.set_tool(None);
+        }
+        ToolProgress::Drawn(id)
+    }
+
+    /// feeds a typed value to the current tool, to draw exact shapes.
+    /// after the first point a length and an angle, in any order, or an offset
+    /// give the second one, and a length alone gives a circle's radius.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::{AppState, Tool, ToolInput, ToolProgress};
+    ///
+    /// let mut state = AppState::new();
+    /// state.set_tool(Some(Tool::Line));
+    /// assert_eq!(ToolProgress::Ignored, state.tool_numeric_input(ToolInput::Length(100.0)));
+    ///
+    /// assert_eq!(ToolProgress::FirstPoint, state.tool_numeric_input(ToolInput::Point(Point::new(10.0, 200.0))));
+    /// assert_eq!(ToolProgress::Pending, state.tool_numeric_input(ToolInput::Length(100.0)));
+    /// assert!(matches!(state.tool_numeric_input(ToolInput::Angle(90f64.to_radians())), ToolProgress::Drawn(_)));
+    ///
+    /// state.tool_numeric_input(ToolInput::Point(Point::new(10.0, 200.0)));
+    /// state.tool_numeric_input(ToolInput::Angle(30f64.to_radians()));
+    /// state.tool_numeric_input(ToolInput::Length(100.0));
+    ///
+    /// state.set_tool(Some(Tool::Rect));
+    /// state.tool_numeric_input(ToolInput::Point(Point::new(0.0, 0.0)));
+    /// let rect = match state.tool_numeric_input(ToolInput::Relative(Point::new(40.0, 25.0))) {
+    ///     ToolProgress::Drawn(id) => id,
+    ///     progress => panic!("{:?}", progress),
+    /// };
+    ///
+    /// state.set_tool(Some(Tool::Circle));
+    /// state.tool_numeric_input(ToolInput::Point(Point::new(50.0, 50.0)));
+    /// assert_eq!(ToolProgress::Ignored, state.tool_numeric_input(ToolInput::Angle(1.0)));
+    /// state.tool_numeric_input(ToolInput::Length(12.5));
+    ///
+    /// let fragments: Vec<_> = state.canvas().ids().iter().map(|&id| state.canvas().svg_fragment_for(id).unwrap()).collect();
+    /// assert_eq!(
+    ///     vec![
+    ///         "<line x1=\"10\" x2=\"10\" y1=\"200\" y2=\"100\" />",
+    ///         "<line x1=\"10\" x2=\"96.602540378\" y1=\"200\" y2=\"150\" />",
+    ///         "<rect height=\"25\" width=\"40\" x=\"0\" y=\"0\" />",
+    ///         "<circle cx=\"50\" cy=\"50\" r=\"12.5\" />",
+    ///     ],
+    ///     fragments,
+    /// );
+    /// assert_eq!(state.canvas().ids()[2], rect);
+    ///
+    /// // an offset of nothing can't be drawn
+    /// state.set_tool(Some(Tool::Line));
+    /// state.tool_numeric_input(ToolInput::Point(Point::new(0.0, 0.0)));
+    /// assert!(matches!(state.tool_numeric_input(ToolInput::Relative(Point::new(0.0, 0.0))), ToolProgress::Invalid(_)));
+    ///
+    /// ```
+    pub fn tool_numeric_input(&mut self, input: ToolInput) -> ToolProgress {
+        if self.tool.is_none() || self.text_edit.is_some() {
+            return ToolProgress::Ignored;
+        }
+        let first = match (&input, &self.pending_point) {
+            (ToolInput::Point(point), _) => return self.place(point.clone(), false),
+            (_, None) => return ToolProgress::Ignored,
+            (_, Some(first)) => first.clone(),
+        };
+
+        match input {
+            ToolInput::Point(_) => unreachable!("points are placed as they are"),
+            ToolInput::Relative(offset) => self.place(Point::new(first.x + offset.x, first.y + offset.y), false),
+            ToolInput::Length(length) if self.tool == Some(Tool::Circle) => self.place(Point::new(first.x + length, first.y), false),
+            ToolInput::Angle(_) if self.tool == Some(Tool::Circle) => ToolProgress::Ignored,
+            ToolInput::Length(length) => {
+                self.typed_length = Some(length);
+                self.place_typed(&first)
+            }
+            ToolInput::Angle(angle) => {
+                self.typed_angle = Some(angle);
+                self.place_typed(&first)
+            }
+        }
+    }
+
+    /// places the second point once both a length and an angle were typed.
+    fn place_typed(&mut self, first: &Point) -> ToolProgress {
+        match (self.typed_length, self.typed_angle) {
+            (Some(length), Some(angle)) => {
+                // typed angles turn counter-clockwise, the other way from the canvas's.
+                // the offset is rounded to a billionth so 100 at 30° ends 50 up and not 49.99999999999999
+                self.place(first.clone() + Point::from_polar(length, -angle).rounded(9), false)
+            }
+            _ => ToolProgress::Pending,
+        }
+    }
+
+    /// flips ortho mode while shift is held: a drawing in ortho mode is
+    /// drawn freely, and any other is drawn horizontally or vertically.
+    pub fn set_ortho_inverted(&mut self, inverted: bool) {
+        self.ortho_inverted = inverted;
+    }
+
+    /// the angular step shapes are drawn at right now, see `Canvas::ortho_mode`.
+    fn ortho_step(&self) -> Option<f64> {
+        match (self.canvas.ortho_mode(), self.ortho_inverted) {
+            (step, false) => step,
+            (Some(_), true) => None,
+            (None, true) => Some(FRAC_PI_2),
+        }
+    }
+
+    /// where the second point of a shape started at `first` goes, kept
+    /// at the angle of ortho mode. a rect is left as drawn when keeping
+    /// its diagonal at that angle would flatten it.
+    fn drawn_point(&self, tool: Tool, first: &Point, point: Point) -> Point {
+        let step = match self.ortho_step() {
+            Some(step) => step,
+            None => return point,
+        };
+        let constrained = point.snapped_to_angle(first, step);
+
+        match tool {
+            Tool::Line => constrained,
+            Tool::Rect if (constrained.x - first.x).abs() > f64::EPSILON && (constrained.y - first.y).abs() > f64::EPSILON => constrained,
+            _ => point,
+        }
+    }
+
+    /// the shape the current tool would draw if the pointer were clicked
+    /// where it is, shown until the second click places it.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::f64::consts::FRAC_PI_2;
+    /// use program_core::{Drawable, Line, Point};
+    /// use web::{AppState, Tool};
+    ///
+    /// let mut state = AppState::new();
+    /// state.set_snapping(false);
+    /// state.canvas_mut().set_ortho_mode(Some(FRAC_PI_2));
+    /// state.set_tool(Some(Tool::Line));
+    /// state.click(Point::new(0.0, 0.0));
+    ///
+    /// state.set_cursor(Some(Point::new(10.0, 3.0)));
+    /// let preview = Drawable::Line(Line::new(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0)));
+    /// assert_eq!(Some(preview.clone()), state.tool_preview());
+    ///
+    /// state.click(Point::new(10.0, 3.0));
+    /// assert_eq!(None, state.tool_preview());
+    /// assert_eq!(Some("<line x1=\"0\" x2=\"10\" y1=\"0\" y2=\"0\" />"), state.canvas().svg_fragment_for(state.canvas().ids()[0]));
+    ///
+    /// // holding shift draws freely for a moment
+    /// state.click(Point::new(0.0, 0.0));
+    /// state.set_ortho_inverted(true);
+    /// state.click(Point::new(10.0, 3.0));
+    /// assert_eq!(Some("<line x1=\"0\" x2=\"10\" y1=\"0\" y2=\"3\" />"), state.canvas().svg_fragment_for(state.canvas().ids()[1]));
+    ///
+    /// ```
+    pub fn tool_preview(&self) -> Option<Drawable> {
+        let (tool, first) = match (self.tool, &self.pending_point) {
+            (Some(tool), Some(first)) => (tool, first),
+            _ => return None,
+        };
+        let point = match (&self.snap, &self.cursor) {
+            (Some(snap), _) => snap.point.clone(),
+            (None, Some(cursor)) => cursor.clone(),
+            (None, None) => return None,
+        };
+        let point = self.drawn_point(tool, first, point);
+
+        match tool {
+            Tool::Line => Some(Drawable::Line(Line::new(first, &point))),
+            Tool::Circle => {
+                let radius = ((point.x - first.x).powi(2) + (point.y - first.y).powi(2)).sqrt();
+                Some(Drawable::Circle(Circle::new(first, radius)))
+            }
+            // the chord to the second point, then the circle through all three
+            Tool::CircleThreePoints => match &self.second_point {
+                Some(second) => Circle::from_three_points(first, second, &point).map(Drawable::Circle),
+                None => Some(Drawable::Line(Line::new(first, &point))),
+            },
+            Tool::Rect => Some(Drawable::Rect(Rect::new(first, &point))),
+            Tool::Text | Tool::Stamp => None,
+        }
+    }
+
+    /// drops the current tool and any point it was holding on to.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::{AppState, Tool};
+    ///
+    /// let mut state = AppState::new();
+    /// state.set_tool(Some(Tool::Line));
+    /// state.click(Point::new(10.0, 10.0));
+    /// assert_eq!(Some(Point::new(10.0, 10.0)), state.pending_point());
+    ///
+    /// state.cancel_tool();
+    /// assert_eq!(None, state.pending_point());
+    /// assert_eq!(None, state.tool());
+    ///
+    /// state.click(Point::new(20.0, 20.0));
+    /// state.click(Point::new(30.0, 30.0));
+    ///
+    /// assert!(state.canvas().is_empty());
+    ///
+    /// ```
+    pub fn cancel_tool(&mut self) {
+        self.set_tool(None);
+        self.text_edit = None;
+    }
+
+    /// drops the text being typed, keeping the tool selected.
+    pub fn cancel_text(&mut self) {
+        self.text_edit = None;
+    }
+
+    /// the text being typed on the canvas, if any.
+    pub fn text_edit(&self) -> Option<&TextEdit> {
+        self.text_edit.as_ref()
+    }
+
+    pub fn font_size(&self) -> f64 {
+        self.font_size
+    }
+
+    /// sets the size of new texts, sizes that aren't positive are ignored.
+    pub fn set_font_size(&mut self, font_size: f64) {
+        if font_size > 0f64 && font_size.is_finite() {
+            self.font_size = font_size;
+        }
+    }
+
+    /// reopens the editor on the topmost text at `point`,
+    /// returning whether there was a text there.
+    pub fn edit_text_at(&mut self, point: &Point) -> bool {
+        let id = match self.canvas.topmost_at(point) {
+            Some(id) => id,
+            None => return false,
+        };
+        let text = match self.canvas.get(id).map(|drawable| &**drawable) {
+            Some(Drawable::Text(text)) => text,
+            _ => return false,
+        };
+
+        self.text_edit = Some(TextEdit {
+            pos: text.pos(),
+            content: text.text().clone(),
+            font_size: text.font_size(),
+            editing: Some(id),
+        });
+        self.canvas.set_selected_drawable(Some(id));
+
+        true
+    }
+
+    /// puts the typed text on the canvas, or into the text being edited,
+    /// as a single undo step. empty text is dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::{AppState, Tool};
+    ///
+    /// let mut state = AppState::new();
+    /// state.set_font_size(24.0);
+    /// state.set_tool(Some(Tool::Text));
+    /// state.click(Point::new(10.0, 20.0));
+    /// assert_eq!(Some(Point::new(10.0, 20.0)), state.text_edit().map(|edit| edit.pos.clone()));
+    ///
+    /// assert!(state.commit_text(String::from("hello")));
+    /// assert!(state.text_edit().is_none());
+    /// let id = state.canvas().ids()[0];
+    /// assert_eq!(Some("<text font-size=\"24\" x=\"10\" y=\"20\">hello</text>"), state.canvas().svg_fragment_for(id));
+    ///
+    /// assert!(state.edit_text_at(&Point::new(12.0, 30.0)));
+    /// assert_eq!(Some("hello"), state.text_edit().map(|edit| edit.content.as_str()));
+    /// assert!(state.commit_text(String::from("bye")));
+    /// assert_eq!(Some("<text font-size=\"24\" x=\"10\" y=\"20\">bye</text>"), state.canvas().svg_fragment_for(id));
+    ///
+    /// state.click(Point::new(50.0, 50.0));
+    /// state.cancel_tool();
+    /// assert!(!state.commit_text(String::from("dropped")));
+    ///
+    /// assert!(state.undo());
+    /// assert!(state.undo());
+    /// assert!(state.canvas().is_empty());
+    ///
+    /// ```
+    pub fn commit_text(&mut self, content: String) -> bool {
+        let edit = match self.text_edit.take() {
+            Some(edit) if !content.is_empty() => edit,
+            _ => return false,
+        };
+
+        match edit.editing {
+            Some(id) => {
+                let before = self.canvas.snapshot();
+                self.canvas.set_selected_drawable(Some(id));
+                let changed = self.canvas.set_selected_text_content(content);
+                if changed {
+                    self.history.push(before);
+                }
+                changed
+            }
+            None => {
+                let before = self.canvas.snapshot();
+                if let Err(error) = self.canvas.add_text_with_font_size(content, edit.pos, edit.font_size) {
+                    self.draw_error = Some(error);
+                    return false;
+                }
+                self.history.push(before);
+                self.draw_error = None;
+
+                if !self.keep_tool_armed {
+                    self.set_tool(None);
+                }
+                true
+            }
+        }
+    }
+
+    /// where the mouse is, in drawing coordinates,
+    /// `None` while it's outside the canvas.
+    pub fn cursor(&self) -> Option<Point> {
+        self.cursor.clone()
+    }
+
+    /// moves the pointer, finding the drawable under it. nothing is hovered
+    /// while a tool is selected or something is being dragged, since a
+    /// click wouldn't select it then.
+    pub fn set_cursor(&mut self, cursor: Option<Point>) {
+        self.hover = match (&cursor, &self.drag, self.tool) {
+            (Some(point), None, None) => self.canvas.topmost_at(point),
+            _ => None,
+        };
+        self.snap = match &cursor {
+            Some(point) if self.snaps_pointer() => Some(self.snap_point(point)).filter(|snap| snap.kind.is_some()),
+            _ => None,
+        };
+        self.cursor = cursor;
+    }
+
+    /// the drawable under the pointer, which a click would select.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::{AppState, Modifiers, Tool};
+    ///
+    /// let mut state = AppState::new();
+    /// let id = state.canvas_mut().add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    ///
+    /// state.set_cursor(Some(Point::new(200.0, 200.0)));
+    /// assert_eq!(None, state.hover());
+    /// assert_eq!("default", state.pointer_cursor());
+    ///
+    /// state.set_cursor(Some(Point::new(55.0, 50.0)));
+    /// assert_eq!(Some(id), state.hover());
+    /// assert_eq!("default", state.pointer_cursor());
+    ///
+    /// state.press(Point::new(55.0, 50.0), Modifiers::default());
+    /// state.set_cursor(Some(Point::new(56.0, 50.0)));
+    /// assert_eq!(None, state.hover());
+    /// assert_eq!("move", state.pointer_cursor());
+    /// state.release();
+    ///
+    /// state.set_cursor(Some(Point::new(45.0, 50.0)));
+    /// assert_eq!("move", state.pointer_cursor());
+    /// state.set_cursor(Some(Point::new(60.0, 50.0)));
+    /// assert_eq!("ew-resize", state.pointer_cursor());
+    ///
+    /// state.set_tool(Some(Tool::Line));
+    /// state.set_cursor(Some(Point::new(55.0, 50.0)));
+    /// assert_eq!(None, state.hover());
+    /// assert_eq!("crosshair", state.pointer_cursor());
+    ///
+    /// state.set_cursor(None);
+    /// assert_eq!(None, state.hover());
+    ///
+    /// ```
+    pub fn hover(&self) -> Option<DrawableId> {
+        // things may have changed since the pointer last moved
+        if self.drag.is_some() || self.tool.is_some() {
+            return None;
+        }
+        self.hover.filter(|&id| self.canvas.get(id).is_some() && !self.canvas.is_hidden(id))
+    }
+
+    /// the CSS cursor over the canvas: a crosshair while drawing, the
+    /// cursor of the handle or drag in progress, or a move cursor over
+    /// the selection.
+    pub fn pointer_cursor(&self) -> &'static str {
+        if self.tool.is_some() {
+            return "crosshair";
+        }
+
+        match self.drag.as_ref().map(|drag| &drag.action) {
+            Some(DragAction::Move { .. }) => return "move",
+            Some(DragAction::Handle { kind: HandleKind::Rotate, .. }) => return "grabbing",
+            Some(DragAction::Handle { kind, .. }) => return handle_cursor(*kind),
+            Some(DragAction::Marquee { .. }) => return "default",
+            Some(DragAction::Guide { id }) => return self.canvas.guide(*id).map_or("default", |guide| guide_cursor(guide.axis)),
+            None => {}
+        }
+
+        let point = match &self.cursor {
+            Some(point) => point,
+            None => return "default",
+        };
+        let zoom = self.canvas.viewport().zoom();
+        if let Some(kind) = self.canvas.handle_at(point, HANDLE_SIZE / zoom, ROTATE_HANDLE_DISTANCE / zoom) {
+            return handle_cursor(kind);
+        }
+        if self.hover().is_none() {
+            if let Some(guide) = self.canvas.guide_at(point, GUIDE_GRAB_DISTANCE / zoom).and_then(|id| self.canvas.guide(id)) {
+                return guide_cursor(guide.axis);
+            }
+        }
+
+        match self.hover() {
+            Some(id) if self.canvas.is_selected(id) && !self.canvas.is_locked(id) => "move",
+            _ => "default",
+        }
+    }
+
+    /// the first point of the shape being drawn,
+    /// shown until the shape is finished or the tool is cancelled.
+    pub fn pending_point(&self) -> Option<Point> {
+        self.pending_point.clone()
+    }
+
+    /// every point placed for the shape being drawn, the first one
+    /// and the second of a circle through three points.
+    pub fn pending_points(&self) -> Vec<Point> {
+        self.pending_point.iter().chain(&self.second_point).cloned().collect()
+    }
+
+    /// handles a mouse press on the canvas. with no tool selected it selects
+    /// the drawable under `point` and starts dragging it along with the rest
+    /// of the selection. with shift held it adds the drawable to the selection
+    /// or takes it out instead. pressing empty space starts a marquee.
+    /// while a tool is selected presses belong to it.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::{AppState, Modifiers};
+    ///
+    /// let mut state = AppState::new();
+    /// let id = state.canvas_mut().add_circle(&Point::new(100.0, 100.0), 10.0).unwrap();
+    ///
+    /// state.press(Point::new(105.0, 100.0), Modifiers::default());
+    /// state.drag_to(Point::new(110.0, 100.0), Modifiers::default());
+    /// state.drag_to(Point::new(125.0, 120.0), Modifiers::default());
+    /// state.release();
+    ///
+    /// assert!(!state.is_dragging());
+    /// assert_eq!(Some("<circle cx=\"120\" cy=\"120\" r=\"10\" />"), state.canvas().svg_fragment_for(id));
+    ///
+    /// assert!(state.undo());
+    /// assert_eq!(Some("<circle cx=\"100\" cy=\"100\" r=\"10\" />"), state.canvas().svg_fragment_for(id));
+    /// assert!(!state.undo());
+    ///
+    /// state.press(Point::new(105.0, 100.0), Modifiers::default());
+    /// state.release();
+    /// assert!(!state.undo());
+    ///
+    /// let other = state.canvas_mut().add_circle(&Point::new(300.0, 300.0), 10.0).unwrap();
+    /// let shift = Modifiers { shift: true, ..Modifiers::default() };
+    /// state.press(Point::new(300.0, 300.0), shift);
+    /// state.release();
+    /// assert_eq!(&[id, other], state.canvas().selection());
+    ///
+    /// state.press(Point::new(100.0, 100.0), Modifiers::default());
+    /// state.drag_to(Point::new(100.0, 110.0), Modifiers::default());
+    /// state.release();
+    /// assert_eq!(Some("<circle cx=\"300\" cy=\"310\" r=\"10\" />"), state.canvas().svg_fragment_for(other));
+    ///
+    /// state.press(Point::new(500.0, 500.0), Modifiers::default());
+    /// assert_eq!(None, state.canvas().selected_drawable());
+    /// state.release();
+    /// assert!(!state.is_dragging());
+    ///
+    /// ```
+    pub fn press(&mut self, point: Point, modifiers: Modifiers) {
+        if self.tool.is_some() {
+            return;
+        }
+
+        let zoom = self.canvas.viewport().zoom();
+        let handle = self
+            .canvas
+            .handle_at(&point, HANDLE_SIZE / zoom, ROTATE_HANDLE_DISTANCE / zoom)
+            .zip(self.canvas.selected_drawable().and_then(|id| self.canvas.get(id)));
+
+        let action = match (handle, self.canvas.topmost_at(&point)) {
+            (Some((kind, original)), _) => {
+                DragAction::Handle { kind, original: Drawable::clone(original), start: point, angle: 0f64 }
+            }
+            (None, Some(id)) if modifiers.shift => {
+                self.canvas.toggle_selected(id);
+                return self.drag = None;
+            }
+            (None, Some(id)) => {
+                if !self.canvas.is_selected(id) {
+                    self.canvas.set_selected_drawable(Some(id));
+                }
+                DragAction::Move { last: point }
+            }
+            (None, None) => match self.canvas.guide_at(&point, GUIDE_GRAB_DISTANCE / zoom) {
+                Some(id) => DragAction::Guide { id },
+                None => {
+                    if !modifiers.shift {
+                        self.canvas.set_selected_drawable(None);
+                    }
+                    DragAction::Marquee { start: point.clone(), end: point, contained: modifiers.alt, add: modifiers.shift }
+                }
+            },
+        };
+
+        self.drag = Some(Drag { action, before: self.canvas.snapshot(), moved: false });
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// moves the dragged drawables, handle or marquee so it follows the pointer
+    /// to `point`. with shift held a resized rect keeps its proportions, a line
+    /// endpoint keeps the line's direction and rotating snaps to `ROTATION_STEP`.
+    /// with alt held the marquee only selects the drawables inside it.
+    /// moving and reshaping snap the pointer to the other drawables and the grid.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::{AppState, Modifiers};
+    ///
+    /// let (none, shift) = (Modifiers::default(), Modifiers { shift: true, alt: false });
+    /// let mut state = AppState::new();
+    /// let id = state.canvas_mut().add_rect(&Point::new(100.0, 100.0), &Point::new(140.0, 120.0)).unwrap();
+    /// state.press(Point::new(120.0, 110.0), none);
+    /// state.release();
+    ///
+    /// // the bottom right corner, the top left one stays
+    /// state.press(Point::new(141.0, 121.0), none);
+    /// state.drag_to(Point::new(180.0, 125.0), shift);
+    /// state.release();
+    /// assert_eq!(Some("<rect height=\"40\" width=\"80\" x=\"100\" y=\"100\" />"), state.canvas().svg_fragment_for(id));
+    ///
+    /// // the rotation handle, a quarter turn about the center
+    /// state.press(Point::new(140.0, 76.0), none);
+    /// state.drag_to(Point::new(200.0, 118.0), shift);
+    /// assert_eq!(Some(90.0), state.rotation_angle());
+    /// state.release();
+    /// assert_eq!(None, state.rotation_angle());
+    /// assert!(state.canvas().svg_fragment_for(id).unwrap().contains("transform=\"rotate(90 140 120)\""));
+    ///
+    /// assert!(state.undo());
+    /// assert!(state.undo());
+    /// assert_eq!(Some("<rect height=\"20\" width=\"40\" x=\"100\" y=\"100\" />"), state.canvas().svg_fragment_for(id));
+    ///
+    /// // a marquee over part of the rect, only selecting it without alt
+    /// let alt = Modifiers { shift: false, alt: true };
+    /// state.press(Point::new(90.0, 90.0), none);
+    /// state.drag_to(Point::new(120.0, 130.0), alt);
+    /// state.release();
+    /// assert!(state.canvas().selection().is_empty());
+    ///
+    /// state.press(Point::new(90.0, 90.0), none);
+    /// state.drag_to(Point::new(120.0, 130.0), none);
+    /// assert!(state.marquee().is_some());
+    /// state.release();
+    /// assert_eq!(&[id], state.canvas().selection());
+    ///
+    /// ```
+    pub fn drag_to(&mut self, point: Point, modifiers: Modifiers) {
+        let constrain = modifiers.shift;
+        let snapped = self.snap_point(&point).point;
+        let drag = match &mut self.drag {
+            Some(drag) => drag,
+            None => return,
+        };
+
+        match &mut drag.action {
+            DragAction::Move { last } => {
+                let offset = Point::new(snapped.x - last.x, snapped.y - last.y);
+
+                self.canvas.translate_selected_drawable(&offset);
+                drag.moved |= offset.x != 0f64 || offset.y != 0f64;
+                *last = snapped;
+            }
+            DragAction::Handle { kind: HandleKind::Rotate, original, start, angle } => {
+                let center = original.center();
+                let mut turned = (point.y - center.y).atan2(point.x - center.x) - (start.y - center.y).atan2(start.x - center.x);
+                if constrain {
+                    let step = ROTATION_STEP.to_radians();
+                    turned = (turned / step).round() * step;
+                }
+
+                let mut rotated = original.clone();
+                rotated.rotate(Angle::radians(turned), &center);
+                match self.canvas.replace_selected(rotated) {
+                    Ok(replaced) => drag.moved |= replaced,
+                    Err(error) => self.draw_error = Some(error),
+                }
+                *angle = turned;
+            }
+            DragAction::Handle { kind, original, .. } => {
+                let mut reshaped = original.clone();
+                reshaped.drag_handle(*kind, &snapped, constrain);
+                // a handle dragged onto the opposite one would flatten the shape, it stays as it was
+                match self.canvas.replace_selected(reshaped) {
+                    Ok(replaced) => drag.moved |= replaced,
+                    Err(error) => self.draw_error = Some(error),
+                }
+            }
+            DragAction::Marquee { end, contained, .. } => {
+                *end = point;
+                *contained = modifiers.alt;
+            }
+            DragAction::Guide { id } => {
+                let position = match self.canvas.guide(*id).map(|guide| guide.axis) {
+                    Some(GuideAxis::Horizontal) => point.y,
+                    Some(GuideAxis::Vertical) => point.x,
+                    None => return,
+                };
+                self.canvas.move_guide(*id, position);
+            }
         }
     }
 
-    pub fn add(&mut self, drawable: &Drawable) {
-        (*self.drawables).push(drawable.clone());
+    /// adds a guide at `point` and starts dragging it, like pressing on
+    /// one of the rulers does: the top one gives horizontal guides and the
+    /// left one vertical ones. guides let go outside the view are removed,
+    /// so dragging one back onto its ruler takes it away.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{GuideAxis, Point};
+    /// use web::{AppState, Modifiers};
+    ///
+    /// let none = Modifiers::default();
+    /// let mut state = AppState::new();
+    ///
+    /// // a guide dragged out of the top ruler, which is above the view
+    /// state.drag_out_guide(GuideAxis::Horizontal, &Point::new(100.0, -8.0));
+    /// assert!(state.is_dragging_guide());
+    /// state.drag_to(Point::new(120.0, 80.0), none);
+    /// state.release();
+    /// let guide = state.canvas().guides()[0];
+    /// assert_eq!((GuideAxis::Horizontal, 80.0), (guide.axis, guide.position));
+    /// assert!(!state.undo());
+    ///
+    /// // grabbed where nothing else is, moved, then dragged back onto its ruler
+    /// state.press(Point::new(300.0, 82.0), none);
+    /// state.drag_to(Point::new(300.0, 60.0), none);
+    /// state.release();
+    /// assert_eq!(60.0, state.canvas().guides()[0].position);
+    /// state.press(Point::new(300.0, 60.0), none);
+    /// state.drag_to(Point::new(300.0, -2.0), none);
+    /// state.release();
+    /// assert!(state.canvas().guides().is_empty());
+    ///
+    /// // a drawable on a guide is grabbed before it
+    /// let id = state.canvas_mut().add_circle(&Point::new(200.0, 200.0), 10.0).unwrap();
+    /// state.drag_out_guide(GuideAxis::Vertical, &Point::new(-5.0, 100.0));
+    /// state.drag_to(Point::new(200.0, 100.0), none);
+    /// state.release();
+    /// state.press(Point::new(200.0, 200.0), none);
+    /// assert!(!state.is_dragging_guide());
+    /// assert_eq!(Some(id), state.canvas().selected_drawable());
+    ///
+    /// ```
+    pub fn drag_out_guide(&mut self, axis: GuideAxis, point: &Point) {
+        let added = match axis {
+            GuideAxis::Horizontal => self.canvas.add_h_guide(point.y),
+            GuideAxis::Vertical => self.canvas.add_v_guide(point.x),
+        };
+
+        self.drag = added.ok().map(|id| Drag { action: DragAction::Guide { id }, before: self.canvas.snapshot(), moved: false });
+    }
 
+    pub fn is_dragging_guide(&self) -> bool {
+        matches!(self.drag.as_ref().map(|drag| &drag.action), Some(DragAction::Guide { .. }))
     }
 
-    pub fn drawables(&self) -> &Vec<Drawable> {
-        self.drawables.as_ref()
+    /// the box being dragged out to select drawables, and whether
+    /// it only selects the ones inside it.
+    pub fn marquee(&self) -> Option<(Aabb, bool)> {
+        match self.drag.as_ref().map(|drag| &drag.action) {
+            Some(DragAction::Marquee { start, end, contained, .. }) => Some((Aabb::new(start, end), *contained)),
+            _ => None,
+        }
     }
 
-    pub fn current_message(&self) -> Option<Message> {
-        self.current_message.clone()
+    /// how far, in degrees, the rotation being dragged has turned the
+    /// selection, `None` when nothing is being rotated.
+    pub fn rotation_angle(&self) -> Option<f64> {
+        match self.drag.as_ref().map(|drag| &drag.action) {
+            Some(DragAction::Handle { kind: HandleKind::Rotate, angle, .. }) => Some(angle.to_degrees()),
+            _ => None,
+        }
     }
-    
-    pub fn prev_message(&self) -> Option<Message> {
-        self.prev_message.clone()
+
+    /// ends the drag, recording the whole move as a single undo step
+    /// or selecting the drawables under the marquee. a guide let go
+    /// outside the view is removed.
+    pub fn release(&mut self) {
+        match self.drag.take() {
+            Some(Drag { action: DragAction::Marquee { start, end, contained, add }, .. }) if start != end => {
+                self.canvas.select_drawables_in_rect(&Aabb::new(&start, &end), contained, add);
+            }
+            Some(Drag { action: DragAction::Guide { id }, .. }) => {
+                let outside = self.canvas.guide(id).is_some_and(|guide| {
+                    let screen = self.canvas.viewport().world_to_screen(&Point::new(guide.position, guide.position));
+                    match guide.axis {
+                        GuideAxis::Horizontal => !(0f64..=self.canvas.height()).contains(&screen.y),
+                        GuideAxis::Vertical => !(0f64..=self.canvas.width()).contains(&screen.x),
+                    }
+                });
+                if outside {
+                    self.canvas.remove_guide(id);
+                }
+            }
+            Some(drag) if drag.moved => self.history.push(drag.before),
+            _ => {}
+        }
     }
 
-    pub fn set_message(&mut self, new_message: Option<Message>) {
-        self.prev_message = self.current_message.clone();
-        self.current_message = new_message;
+    /// brings the canvas back to how it was before the last change,
+    /// returning whether there was anything to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(snapshot) => {
+                self.drag = None;
+                self.canvas.restore(snapshot);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// writes the props edited in the properties panel back to their
+    /// drawable, as a single undo step. props the canvas refuses, like a
+    /// rect without a width, change nothing and the panel shows why.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{ApplyPropsError, CanvasError, Point, Props};
+    /// use web::{invalid_field, AppState, Tool};
+    ///
+    /// let mut state = AppState::new();
+    /// state.set_tool(Some(Tool::Rect));
+    /// state.click(Point::new(10.0, 10.0));
+    /// state.click(Point::new(30.0, 20.0));
+    /// let rect = state.canvas().ids()[0];
+    ///
+    /// let mut props = state.canvas().props_of(rect).unwrap();
+    /// let Props::Rect(rect_props) = &mut props.props else { unreachable!() };
+    /// rect_props.height = 0.0;
+    ///
+    /// let error = state.apply_props(&props).unwrap_err();
+    /// assert_eq!(ApplyPropsError::Invalid(CanvasError::ZeroArea), error);
+    /// assert_eq!(Some("height"), invalid_field(&props.props, CanvasError::ZeroArea));
+    /// assert_eq!(Some("<rect height=\"10\" width=\"20\" x=\"10\" y=\"10\" />"), state.canvas().svg_fragment_for(rect));
+    ///
+    /// assert!(state.undo());
+    /// assert!(state.canvas().is_empty());
+    ///
+    /// ```
+    pub fn apply_props(&mut self, props: &DrawableProps) -> Result<(), ApplyPropsError> {
+        let before = self.canvas.snapshot();
+        self.canvas.apply_props(props)?;
+        self.history.push(before);
+
+        Ok(())
+    }
+
+    /// rounds the coordinates of the unlocked drawables to `decimals`
+    /// decimals as a single undo step, returning how many changed.
+    pub fn round_all_coordinates(&mut self, decimals: u32) -> usize {
+        let before = self.canvas.snapshot();
+        let rounded = self.canvas.round_all_coordinates(decimals);
+
+        if rounded > 0 {
+            self.history.push(before);
+        }
+
+        rounded
+    }
+
+    /// deletes the selected drawable as a single undo step.
+    pub fn delete_selected(&mut self) -> bool {
+        let before = self.canvas.snapshot();
+        let deleted = self.canvas.delete_selected();
+
+        if deleted {
+            self.drag = None;
+            self.history.push(before);
+        }
+
+        deleted
+    }
+
+    /// swaps in another drawing, as a single undo step.
+    pub fn replace_canvas(&mut self, canvas: program_core::Canvas) {
+        self.checkpoint();
+        self.drag = None;
+        self.canvas = canvas;
+        self.dirty_from = None;
+    }
+
+    /// changes the size, background, grid, unit and export precision of the
+    /// drawing, resizing it according to `mode`. everything but the grid and
+    /// the precision, which undo leaves alone, is undone as a single step.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Point, ResizeMode, Unit};
+    /// use web::{AppState, DocumentSettings};
+    ///
+    /// let mut state = AppState::new();
+    /// let id = state.canvas_mut().add_circle(&Point::new(100.0, 100.0), 10.0).unwrap();
+    ///
+    /// let mut settings = DocumentSettings::of(state.canvas());
+    /// assert!(!state.apply_settings(&settings, ResizeMode::Anchor));
+    ///
+    /// settings.width = 600.0;
+    /// settings.height = 400.0;
+    /// settings.background = Some(String::from("#ffffff"));
+    /// settings.unit = Unit::Mm;
+    /// assert!(state.apply_settings(&settings, ResizeMode::Scale));
+    /// assert_eq!(settings, DocumentSettings::of(state.canvas()));
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" r=\"5\" />"), state.canvas().svg_fragment_for(id));
+    ///
+    /// assert!(state.undo());
+    /// assert_eq!((1200.0, 800.0), (state.canvas().width(), state.canvas().height()));
+    /// assert_eq!(Unit::Px, state.canvas().unit());
+    ///
+    /// // the export precision alone isn't an undo step
+    /// settings = DocumentSettings::of(state.canvas());
+    /// settings.svg_precision = Some(2);
+    /// assert!(state.apply_settings(&settings, ResizeMode::Anchor));
+    /// assert!(!state.undo());
+    /// assert_eq!(Some(2), state.canvas().svg_precision());
+    ///
+    /// ```
+    pub fn apply_settings(&mut self, settings: &DocumentSettings, mode: ResizeMode) -> bool {
+        let current = DocumentSettings::of(&self.canvas);
+        if *settings == current {
+            return false;
+        }
+
+        let before = self.canvas.snapshot();
+        if (settings.width, settings.height) != (current.width, current.height) {
+            self.canvas.resize(settings.width, settings.height, mode);
+        }
+        self.canvas.set_background(settings.background.clone());
+        self.canvas.set_unit(settings.unit);
+        self.canvas.set_grid_spacing(settings.grid_spacing);
+        self.canvas.set_svg_precision(settings.svg_precision);
+
+        let kept_by_undo = DocumentSettings { grid_spacing: settings.grid_spacing, svg_precision: settings.svg_precision, ..current };
+        if kept_by_undo != *settings {
+            self.drag = None;
+            self.history.push(before);
+        }
+
+        true
+    }
+
+    /// opens a drawing saved as JSON in place of the current one when
+    /// `replace` is set, or else merges it beside the current drawing and
+    /// selects it, then fits the view to the drawing. a drawing that can't
+    /// be read leaves everything as it was and becomes the import error.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{Canvas, Point};
+    /// use web::AppState;
+    ///
+    /// let mut saved = Canvas::new(400.0, 200.0);
+    /// saved.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+    ///
+    /// let mut state = AppState::new();
+    /// state.canvas_mut().add_line(&Point::new(0.0, 0.0), &Point::new(10.0, 10.0)).unwrap();
+    /// let line = state.canvas().ids().to_vec();
+    ///
+    /// assert!(state.import_json(&saved.to_json(), false));
+    /// assert_eq!(2, state.canvas().len());
+    /// assert_eq!(1, state.canvas().selection().len());
+    /// assert!(!line.contains(&state.canvas().selection()[0]));
+    ///
+    /// // undoing the merge takes away only what it added
+    /// assert!(state.undo());
+    /// assert_eq!(line, state.canvas().ids());
+    ///
+    /// assert!(state.import_json(&saved.to_json(), false));
+    /// assert!(state.import_json(&saved.to_json(), true));
+    /// assert_eq!(1, state.canvas().len());
+    /// assert_eq!(400.0, state.canvas().width());
+    ///
+    /// assert!(!state.import_json("{", true));
+    /// assert!(state.import_error().is_some());
+    /// assert_eq!(1, state.canvas().len());
+    ///
+    /// assert!(state.undo());
+    /// assert_eq!(2, state.canvas().len());
+    ///
+    /// ```
+    pub fn import_json(&mut self, json: &str, replace: bool) -> bool {
+        let loaded = match program_core::Canvas::from_json(json) {
+            Ok(loaded) => loaded,
+            Err(error) => {
+                self.import_error = Some(error.to_string());
+                return false;
+            }
+        };
+
+        if replace {
+            self.replace_canvas(loaded);
+        } else {
+            let before = self.canvas.snapshot();
+            if let Err(error) = self.canvas.merge(loaded, MergePlacement::Beside) {
+                self.import_error = Some(error.to_string());
+                return false;
+            }
+            self.history.push(before);
+        }
+        self.imported();
+
+        true
+    }
+
+    /// adds the drawables of an SVG document on top of the current ones,
+    /// then fits the view to the drawing. like `import_json` a document that
+    /// can't be read becomes the import error.
+    ///
+    /// # Examples
+    /// ```
+    /// use web::AppState;
+    ///
+    /// let mut state = AppState::new();
+    /// assert!(state.import_svg(r#"<svg xmlns="http://www.w3.org/2000/svg"><circle cx="10" cy="10" r="5" /></svg>"#));
+    /// assert_eq!(1, state.canvas().len());
+    ///
+    /// assert!(!state.import_svg("<svg><circle r=\"x\" /></svg>"));
+    /// assert_eq!(Some("invalid r of <circle>: \"x\""), state.import_error());
+    ///
+    /// // shapes that can't be drawn aren't added either
+    /// assert!(!state.import_svg(r#"<svg><circle cx="1" cy="1" /></svg>"#));
+    /// assert_eq!(Some("<circle> has no r"), state.import_error());
+    /// assert!(!state.import_svg(r#"<svg><rect width="0" height="3" /></svg>"#));
+    /// assert_eq!(Some("invalid drawable: a rect must have a width and a height"), state.import_error());
+    /// assert_eq!(1, state.canvas().len());
+    ///
+    /// state.set_import_error(None);
+    /// assert_eq!(None, state.import_error());
+    ///
+    /// ```
+    pub fn import_svg(&mut self, svg: &str) -> bool {
+        let before = self.canvas.snapshot();
+
+        match self.canvas.import_svg(svg) {
+            Ok(_) => {
+                self.history.push(before);
+                self.imported();
+                true
+            }
+            Err(error) => {
+                self.import_error = Some(error.to_string());
+                false
+            }
+        }
+    }
+
+    fn imported(&mut self) {
+        self.drag = None;
+        self.import_error = None;
+        self.canvas.fit_to_content();
+    }
+
+    /// saves the drawing with `save`, which returns whether it could,
+    /// and marks it saved when it did.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::{AppState, SaveStatus};
+    ///
+    /// let mut state = AppState::new();
+    /// assert_eq!(SaveStatus::Saved, state.save_status());
+    ///
+    /// state.set_tool(Some(web::Tool::Circle));
+    /// state.click(Point::new(10.0, 10.0));
+    /// state.click(Point::new(20.0, 10.0));
+    /// assert_eq!(SaveStatus::Saving, state.save_status());
+    ///
+    /// assert!(!state.save_with(|_| false));
+    /// assert_eq!(SaveStatus::Unsaved, state.save_status());
+    ///
+    /// let mut saved = None;
+    /// assert!(state.save_with(|canvas| { saved = Some(canvas.len()); true }));
+    /// assert_eq!(Some(1), saved);
+    /// assert_eq!(SaveStatus::Saved, state.save_status());
+    ///
+    /// state.undo();
+    /// assert_eq!(SaveStatus::Saving, state.save_status());
+    ///
+    /// ```
+    pub fn save_with(&mut self, save: impl FnOnce(&program_core::Canvas) -> bool) -> bool {
+        let saved = save(&self.canvas);
+        if saved {
+            self.canvas.mark_saved();
+        }
+        self.save_failed = !saved;
+
+        saved
+    }
+
+    /// whether the drawing is saved, waiting for the next autosave,
+    /// or couldn't be saved.
+    pub fn save_status(&self) -> SaveStatus {
+        match (self.canvas.has_unsaved_changes(), self.save_failed) {
+            (false, _) => SaveStatus::Saved,
+            (true, false) => SaveStatus::Saving,
+            (true, true) => SaveStatus::Unsaved,
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+    }
+
+    /// the texts of the interface, in its language.
+    pub fn strings(&self) -> &'static Strings {
+        self.language.strings()
+    }
+
+    /// why the last shape couldn't be drawn, like a line
+    /// ending where it started, until another one is.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::{CanvasError, Point};
+    /// use web::{AppState, Tool};
+    ///
+    /// let mut state = AppState::new();
+    /// state.set_tool(Some(Tool::Line));
+    /// state.click(Point::new(10.0, 10.0));
+    /// state.click(Point::new(10.0, 10.0));
+    ///
+    /// assert_eq!(Some(CanvasError::ZeroLength), state.draw_error());
+    /// assert!(state.canvas().is_empty());
+    /// assert!(!state.undo());
+    /// assert_eq!(Some(Tool::Line), state.tool());
+    ///
+    /// state.click(Point::new(10.0, 10.0));
+    /// state.click(Point::new(20.0, 10.0));
+    /// assert_eq!(None, state.draw_error());
+    /// assert_eq!(1, state.canvas().len());
+    ///
+    /// ```
+    pub fn draw_error(&self) -> Option<CanvasError> {
+        self.draw_error
+    }
+
+    /// why the last file couldn't be opened.
+    pub fn import_error(&self) -> Option<&str> {
+        self.import_error.as_deref()
+    }
+
+    /// shows why a file couldn't be opened, or dismisses the message with `None`.
+    pub fn set_import_error(&mut self, error: Option<String>) {
+        self.import_error = error;
+    }
+
+    /// runs the action bound to a key, returning whether it did anything.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::{shortcut_for, AppState, Modifiers, Tool};
+    ///
+    /// let mut state = AppState::new();
+    /// let id = state.canvas_mut().add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// assert!(!state.run_shortcut(shortcut_for("ArrowUp", false, false).unwrap()));
+    ///
+    /// state.press(Point::new(50.0, 50.0), Modifiers::default());
+    /// state.release();
+    /// assert!(state.run_shortcut(shortcut_for("ArrowUp", true, false).unwrap()));
+    /// assert!(state.run_shortcut(shortcut_for("d", false, true).unwrap()));
+    /// assert_eq!(2, state.canvas().len());
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"40\" r=\"5\" />"), state.canvas().svg_fragment_for(id));
+    ///
+    /// assert!(state.undo());
+    /// assert!(state.undo());
+    /// assert_eq!(Some("<circle cx=\"50\" cy=\"50\" r=\"5\" />"), state.canvas().svg_fragment_for(id));
+    ///
+    /// assert!(state.run_shortcut(shortcut_for("c", false, false).unwrap()));
+    /// assert_eq!(Some(Tool::Circle), state.tool());
+    /// assert!(state.run_shortcut(shortcut_for("v", false, false).unwrap()));
+    /// assert_eq!(None, state.tool());
+    ///
+    /// ```
+    pub fn run_shortcut(&mut self, shortcut: Shortcut) -> bool {
+        match shortcut {
+            Shortcut::Tool(tool) => {
+                self.set_tool(Some(tool));
+                true
+            }
+            Shortcut::SelectTool | Shortcut::Cancel => {
+                let armed = self.tool.is_some();
+                self.cancel_tool();
+                armed
+            }
+            // saving needs the browser's storage, the canvas does it itself
+            Shortcut::Save => false,
+            Shortcut::Delete => self.delete_selected(),
+            Shortcut::Nudge(offset) => {
+                let before = self.canvas.snapshot();
+                let nudged = self.canvas.nudge_selected(offset.x, offset.y);
+                if nudged {
+                    self.history.push(before);
+                }
+                nudged
+            }
+            Shortcut::Duplicate => {
+                let before = self.canvas.snapshot();
+                let duplicated = !self
+                    .canvas
+                    .duplicate_selected(&Point::new(DUPLICATE_OFFSET, DUPLICATE_OFFSET))
+                    .is_empty();
+                if duplicated {
+                    self.history.push(before);
+                }
+                duplicated
+            }
+        }
+    }
+
+    /// whether a drawable was copied and can be pasted.
+    pub fn can_paste(&self) -> bool {
+        self.clipboard.is_some()
+    }
+
+    /// runs an action picked from the context menu, returning whether it
+    /// did anything. actions on a drawable act on the selection, pasting
+    /// centers the copied drawable on `at`.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::{AppState, MenuAction};
+    ///
+    /// let mut state = AppState::new();
+    /// let bottom = state.canvas_mut().add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    /// let top = state.canvas_mut().add_circle(&Point::new(52.0, 50.0), 5.0).unwrap();
+    /// assert!(!state.run_menu_action(MenuAction::Copy, &Point::new(0.0, 0.0)));
+    ///
+    /// state.canvas_mut().select_drawable_at(&Point::new(50.0, 50.0));
+    /// assert!(state.run_menu_action(MenuAction::SendToBack, &Point::new(0.0, 0.0)));
+    /// assert_eq!(vec![top, bottom], state.canvas().ids());
+    ///
+    /// assert!(state.run_menu_action(MenuAction::Lock, &Point::new(0.0, 0.0)));
+    /// assert!(!state.run_menu_action(MenuAction::Delete, &Point::new(0.0, 0.0)));
+    ///
+    /// assert!(state.run_menu_action(MenuAction::Copy, &Point::new(0.0, 0.0)));
+    /// assert!(state.run_menu_action(MenuAction::Paste, &Point::new(200.0, 100.0)));
+    /// let pasted = state.canvas().selected_drawable().unwrap();
+    /// assert_eq!(Some("<circle cx=\"200\" cy=\"100\" r=\"5\" />"), state.canvas().svg_fragment_for(pasted));
+    /// assert!(!state.canvas().is_locked(pasted));
+    ///
+    /// assert!(state.undo());
+    /// assert!(state.undo());
+    /// assert!(state.undo());
+    /// assert_eq!(vec![bottom, top], state.canvas().ids());
+    ///
+    /// ```
+    pub fn run_menu_action(&mut self, action: MenuAction, at: &Point) -> bool {
+        let selected = self.canvas.selected_drawable();
+
+        match action {
+            MenuAction::Delete => self.delete_selected(),
+            MenuAction::Duplicate => self.run_shortcut(Shortcut::Duplicate),
+            MenuAction::Copy => {
+                self.clipboard = selected.and_then(|id| self.canvas.get(id)).map(|drawable| Drawable::clone(drawable));
+                self.clipboard.is_some()
+            }
+            MenuAction::Paste => {
+                let mut copy = match self.clipboard.clone() {
+                    Some(copy) => copy,
+                    None => return false,
+                };
+                let bounds = copy.bounding_box();
+                let (min, max) = (bounds.min(), bounds.max());
+                copy.translate(&Point::new(at.x - (min.x + max.x) / 2f64, at.y - (min.y + max.y) / 2f64));
+
+                let before = self.canvas.snapshot();
+                match self.canvas.add_drawables(vec![copy]) {
+                    Ok(pasted) => {
+                        self.history.push(before);
+                        self.canvas.set_selected_drawable(pasted.first().copied());
+                        true
+                    }
+                    Err(error) => {
+                        self.draw_error = Some(error);
+                        false
+                    }
+                }
+            }
+            MenuAction::BringToFront => self.change_selected(|canvas, id| canvas.bring_to_front(id)),
+            MenuAction::SendToBack => self.change_selected(|canvas, id| canvas.send_to_back(id)),
+            MenuAction::Lock => self.change_selected(|canvas, id| canvas.set_locked(id, true)),
+            MenuAction::Unlock => self.change_selected(|canvas, id| canvas.set_locked(id, false)),
+            // the properties panel follows the selection, the menu only focuses it
+            MenuAction::Properties => selected.is_some(),
+        }
+    }
+
+    /// applies `change` to the selected drawable as a single undo step.
+    fn change_selected(&mut self, change: impl FnOnce(&mut program_core::Canvas, program_core::DrawableId) -> bool) -> bool {
+        let id = match self.canvas.selected_drawable() {
+            Some(id) => id,
+            None => return false,
+        };
+        let before = self.canvas.snapshot();
+        let changed = change(&mut self.canvas, id);
+
+        if changed {
+            self.history.push(before);
+        }
+
+        changed
+    }
+
+    /// selects a drawable picked from a list, or with `add` adds it to
+    /// the selection or takes it out, like shift-clicking it.
+    pub fn select(&mut self, id: DrawableId, add: bool) {
+        if add {
+            self.canvas.toggle_selected(id);
+        } else {
+            self.canvas.set_selected_drawable(Some(id));
+        }
+    }
+
+    /// names a drawable, or takes its name away, as a single undo step.
+    pub fn rename(&mut self, id: DrawableId, name: Option<String>) -> bool {
+        self.change_drawables(|canvas| canvas.set_name(id, name))
+    }
+
+    /// hides or shows a drawable as a single undo step.
+    pub fn set_hidden(&mut self, id: DrawableId, hidden: bool) -> bool {
+        self.change_drawables(|canvas| canvas.set_hidden(id, hidden))
+    }
+
+    /// locks or unlocks a drawable as a single undo step.
+    pub fn set_locked(&mut self, id: DrawableId, locked: bool) -> bool {
+        self.change_drawables(|canvas| canvas.set_locked(id, locked))
+    }
+
+    /// deletes a drawable as a single undo step, unless it's locked,
+    /// like deleting it with the Delete key.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Point;
+    /// use web::AppState;
+    ///
+    /// let mut state = AppState::new();
+    /// let id = state.canvas_mut().add_circle(&Point::new(50.0, 50.0), 5.0).unwrap();
+    ///
+    /// assert!(state.set_locked(id, true));
+    /// assert!(!state.delete(id));
+    /// assert!(state.set_locked(id, false));
+    /// assert!(!state.set_locked(id, false));
+    ///
+    /// assert!(state.rename(id, Some(String::from("wheel"))));
+    /// assert!(state.delete(id));
+    /// assert!(state.canvas().is_empty());
+    ///
+    /// assert!(state.undo());
+    /// assert_eq!(Some("wheel"), state.canvas().name(id));
+    /// assert!(state.undo());
+    /// assert_eq!(None, state.canvas().name(id));
+    ///
+    /// ```
+    pub fn delete(&mut self, id: DrawableId) -> bool {
+        let deleted = self.change_drawables(|canvas| {
+            if !canvas.is_locked(id) {
+                canvas.delete(id);
+            }
+        });
+        if deleted {
+            self.drag = None;
+        }
+
+        deleted
+    }
+
+    /// applies `change` to the canvas, keeping an undo step only when
+    /// it changed any drawable.
+    fn change_drawables<T>(&mut self, change: impl FnOnce(&mut program_core::Canvas) -> T) -> bool {
+        let (before, revision) = (self.canvas.snapshot(), self.canvas.revision());
+        change(&mut self.canvas);
+
+        let changed = self.canvas.revision() != revision;
+        if changed {
+            self.history.push(before);
+        }
+
+        changed
+    }
+
+    fn checkpoint(&mut self) {
+        self.history.push(self.canvas.snapshot());
     }
 
 }
 
+/// the CSS cursor shown over a handle.
+fn guide_cursor(axis: GuideAxis) -> &'static str {
+    match axis {
+        GuideAxis::Horizontal => "row-resize",
+        GuideAxis::Vertical => "col-resize",
+    }
+}
 
+fn handle_cursor(kind: HandleKind) -> &'static str {
+    match kind {
+        HandleKind::Start | HandleKind::End => "move",
+        // corners count clockwise from the top left one
+        HandleKind::Corner(0 | 2) => "nwse-resize",
+        HandleKind::Corner(_) => "nesw-resize",
+        HandleKind::Radius => "ew-resize",
+        HandleKind::Rotate => "grab",
+    }
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
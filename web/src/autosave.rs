@@ -0,0 +1,65 @@
+use program_core::{Canvas, FORMAT_VERSION};
+use web_sys::Storage;
+use super::i18n::Strings;
+
+/// the local storage key of the saved drawing. it carries the format version,
+/// so a drawing saved by an incompatible version is never read back.
+pub fn storage_key() -> String {
+    format!("alrassam.drawing.v{}", FORMAT_VERSION)
+}
+
+/// local storage, `None` when the browser doesn't allow using it.
+pub(crate) fn local_storage() -> Option<Storage> {
+    gloo_utils::window().local_storage().ok().flatten()
+}
+
+/// loads the saved drawing. a missing, unreadable, or corrupted drawing
+/// is logged and ignored, it must never keep the app from starting.
+pub fn load_canvas() -> Option<Canvas> {
+    let json = local_storage()?.get_item(&storage_key()).ok().flatten()?;
+
+    let mut canvas = Canvas::from_json(&json)
+        .map_err(|error| log::warn!("ignoring the saved drawing: {}", error))
+        .ok()?;
+    canvas.mark_saved();
+
+    Some(canvas)
+}
+
+/// writes the drawing to local storage, returning whether it could.
+pub fn save_canvas(canvas: &Canvas) -> bool {
+    let saved = local_storage().map(|storage| storage.set_item(&storage_key(), &canvas.to_json()));
+
+    if !matches!(saved, Some(Ok(()))) {
+        log::warn!("the drawing couldn't be saved to local storage");
+        return false;
+    }
+
+    true
+}
+
+/// whether the drawing is safely stored, shown in the panel.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SaveStatus {
+    Saved,
+    /// changed, and about to be saved automatically.
+    Saving,
+    /// changed, and the last attempt to save it failed.
+    Unsaved,
+}
+
+impl SaveStatus {
+    pub fn label(&self, strings: &Strings) -> &'static str {
+        match self {
+            SaveStatus::Saved => strings.saved,
+            SaveStatus::Saving => strings.saving,
+            SaveStatus::Unsaved => strings.unsaved,
+        }
+    }
+}
+
+pub fn clear_saved_canvas() {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(&storage_key());
+    }
+}
@@ -1,7 +1,12 @@
 use yew::prelude::*;
-use program_core::{Drawable, Line, Point};
-use super::{AppState, app_state::Message};
-use log;
+use gloo_events::{EventListener, EventListenerOptions};
+use gloo_render::{request_animation_frame, AnimationFrame};
+use gloo_timers::callback::Timeout;
+use program_core::{Aabb, Drawable, Guide, GuideAxis, Handle, HandleKind, Point, Snap, SnapKind, GRID_COLOR, GUIDE_COLOR};
+use std::cell::RefCell;
+use wasm_bindgen::JsCast;
+use super::{AppState, Drawing, Modifiers, autosave::save_canvas, import::open_file, context_menu::{ContextMenu, OpenMenu}, pointer::{event_to_screen, event_to_world, event_to_world_from}, shortcuts::{shortcut_for, Shortcut}, text_editor::TextEditor, numeric_entry::NumericEntry};
+use super::app_state::{Tool, HANDLE_SIZE, ROTATE_HANDLE_DISTANCE};
 
 #[derive(PartialEq, Properties)]
 pub struct CanvasProps {
@@ -10,59 +15,375 @@ pub struct CanvasProps {
 
 #[function_component(Canvas)]
 pub fn canvas(props: &CanvasProps) -> Html {
+    let pan = use_mut_ref(Pan::default);
+
     let appstate = props.appstate.clone(); 
-    
+    let panning = pan.clone();
     let svg_onclick = Callback::from(move |evt: MouseEvent| {
-        let mut new_state = (*appstate).clone();
-        let click_point = Point::new(evt.offset_x().into(), evt.offset_y().into());
-        
-        if let Some(message) = new_state.current_message() {
-            match message {
-                Message::Line => { 
-                    new_state.set_message(Some(Message::FinishLine(click_point)));
-                },
-                Message::FinishLine(point) => {
-                    let line = Line::new(&point, &click_point);
-                    new_state.add(&Drawable::Line(line));
-                },
-            }
+        // a click that ends a space+drag pan isn't meant for the tool
+        if panning.borrow().space_held {
+            return;
+        }
+
+        let mut new_state = appstate.edited();
+        let click_point = event_to_world(&evt, new_state.canvas());
+
+        new_state.set_ortho_inverted(evt.shift_key());
+        new_state.click(click_point);
+
+        appstate.set(new_state);
+    });
+
+    let appstate = props.appstate.clone();
+    let svg_onwheel = Callback::from(move |evt: WheelEvent| {
+        evt.prevent_default();
+
+        let mut new_state = appstate.edited();
+        let anchor = event_to_screen(&evt, new_state.canvas());
+        new_state.canvas_mut().zoom_at(&anchor, ZOOM_STEP.powf(-evt.delta_y() / 100f64));
+
+        appstate.set(new_state);
+    });
+
+    // registered on the document so the canvas doesn't need focus, and
+    // re-registered with every state change so it never acts on a stale one.
+    // dropping the listener removes it, including when the canvas unmounts
+    use_effect_with_deps(
+        |appstate: &UseStateHandle<AppState>| {
+            let appstate = appstate.clone();
+            let document = gloo_utils::document();
+            let listener = EventListener::new(&document, "keydown", move |evt| {
+                let evt = evt.dyn_ref::<KeyboardEvent>().expect("keydown is a keyboard event");
+                let typing = gloo_utils::document()
+                    .active_element()
+                    .is_some_and(|element| matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA"));
+
+                if typing {
+                    return;
+                }
+
+                let shortcut = match shortcut_for(&evt.key(), evt.shift_key(), evt.ctrl_key() || evt.meta_key()) {
+                    Some(shortcut) => shortcut,
+                    None => return,
+                };
+
+                let mut new_state = appstate.edited();
+                if shortcut == Shortcut::Save {
+                    // the browser would offer to save the page otherwise
+                    evt.prevent_default();
+                    new_state.save_with(save_canvas);
+                    appstate.set(new_state);
+                } else if new_state.run_shortcut(shortcut) {
+                    evt.prevent_default();
+                    appstate.set(new_state);
+                }
+            });
+
+            move || drop(listener)
+        },
+        props.appstate.clone(),
+    );
+
+    // the state as of the last render, for callbacks that run later
+    // and must not bring back a state that was replaced in the meantime
+    let latest = use_mut_ref(|| props.appstate.clone());
+    *latest.borrow_mut() = props.appstate.clone();
+
+    // saves a second after the last change, a new change drops the pending save
+    use_effect_with_deps(
+        move |canvas: &program_core::Canvas| {
+            let timeout = canvas.has_unsaved_changes().then(|| {
+                Timeout::new(1_000, move || {
+                    let appstate = latest.borrow().clone();
+                    let mut new_state = appstate.edited();
+                    new_state.save_with(save_canvas);
+
+                    appstate.set(new_state);
+                })
+            });
+
+            move || drop(timeout)
+        },
+        props.appstate.canvas().clone(),
+    );
+
+    // asks before leaving the page while there are changes that aren't saved
+    use_effect_with_deps(
+        |unsaved: &bool| {
+            let listener = unsaved.then(|| {
+                let options = EventListenerOptions::enable_prevent_default();
+                EventListener::new_with_options(&gloo_utils::window(), "beforeunload", options, |evt| {
+                    evt.prevent_default();
+                    if let Some(evt) = evt.dyn_ref::<web_sys::BeforeUnloadEvent>() {
+                        evt.set_return_value("");
+                    }
+                })
+            });
+
+            move || drop(listener)
+        },
+        props.appstate.canvas().has_unsaved_changes(),
+    );
+
+    let pending_pointer = use_mut_ref(PendingPointer::default);
+
+    let appstate = props.appstate.clone();
+    let panning = pan.clone();
+    let svg_onmousedown = Callback::from(move |evt: MouseEvent| {
+        // the right button opens the context menu instead
+        if evt.button() == 2 {
+            return;
+        }
+
+        // the middle button, or any button while space is held, pans the view
+        if evt.button() == 1 || panning.borrow().space_held {
+            evt.prevent_default();
+            panning.borrow_mut().last = Some(event_to_screen(&evt, appstate.canvas()));
+            return;
+        }
+
+        let mut new_state = appstate.edited();
+        let world_point = event_to_world(&evt, new_state.canvas());
+        new_state.press(world_point, Modifiers { shift: evt.shift_key(), alt: evt.alt_key() });
+
+        appstate.set(new_state);
+    });
+
+    let appstate = props.appstate.clone();
+    let pending = pending_pointer.clone();
+    let panning = pan.clone();
+    let svg_onmousemove = Callback::from(move |evt: MouseEvent| {
+        if let Some(last) = panning.borrow().last.clone() {
+            let point = event_to_screen(&evt, appstate.canvas());
+            let mut new_state = appstate.edited();
+            new_state.canvas_mut().pan_by(&Point::new(point.x - last.x, point.y - last.y));
+            panning.borrow_mut().last = Some(point);
+
+            appstate.set(new_state);
+            return;
+        }
+
+        let mut pending_pointer = pending.borrow_mut();
+        let scheduled = pending_pointer.point.is_some();
+        pending_pointer.point = Some(event_to_world(&evt, appstate.canvas()));
+        pending_pointer.modifiers = Modifiers { shift: evt.shift_key(), alt: evt.alt_key() };
+
+        if !scheduled {
+            let (appstate, pending) = (appstate.clone(), pending.clone());
+            pending_pointer.frame = Some(request_animation_frame(move |_| {
+                let (point, modifiers) = {
+                    let mut pending_pointer = pending.borrow_mut();
+                    (pending_pointer.point.take(), pending_pointer.modifiers)
+                };
+
+                if let Some(point) = point {
+                    let mut new_state = appstate.edited();
+                    new_state.set_ortho_inverted(modifiers.shift);
+                    new_state.set_cursor(Some(point.clone()));
+                    new_state.drag_to(point, modifiers);
+
+                    appstate.set(new_state);
+                }
+            }));
+        }
+    });
+
+    let appstate = props.appstate.clone();
+    let pending = pending_pointer.clone();
+    let panning = pan.clone();
+    let svg_onmouseup = Callback::from(move |_: MouseEvent| {
+        panning.borrow_mut().last = None;
+        release_pointer(&appstate, &pending, false);
+    });
+
+    // leaving the canvas ends the drag just like releasing the button does,
+    // and a guide follows the pointer out of the view to be removed
+    let appstate = props.appstate.clone();
+    let pending = pending_pointer;
+    let panning = pan.clone();
+    let svg_onmouseleave = Callback::from(move |evt: MouseEvent| {
+        panning.borrow_mut().last = None;
+        if appstate.is_dragging_guide() {
+            pending.borrow_mut().point = Some(event_to_world(&evt, appstate.canvas()));
+        }
+        release_pointer(&appstate, &pending, true);
+    });
+
+    let menu = use_state(|| None::<OpenMenu>);
+    let svg_ref = use_node_ref();
+
+    // double-clicking a text types over it, unless another tool is selected
+    let appstate = props.appstate.clone();
+    let svg_ondblclick = Callback::from(move |evt: MouseEvent| {
+        if !matches!(appstate.tool(), None | Some(Tool::Text)) {
+            return;
         }
 
+        let mut new_state = appstate.edited();
+        let world = event_to_world(&evt, new_state.canvas());
+        if new_state.edit_text_at(&world) {
+            appstate.set(new_state);
+        }
+    });
+
+    let appstate = props.appstate.clone();
+    let open_menu = menu.clone();
+    let svg_oncontextmenu = Callback::from(move |evt: MouseEvent| {
+        evt.prevent_default();
+
+        let mut new_state = appstate.edited();
+        let world = event_to_world(&evt, new_state.canvas());
+        let target = new_state.canvas().topmost_at(&world);
+        new_state.canvas_mut().set_selected_drawable(target);
+
+        open_menu.set(Some(OpenMenu {
+            client: Point::new(evt.client_x().into(), evt.client_y().into()),
+            world,
+            on_drawable: target.is_some(),
+        }));
         appstate.set(new_state);
     });
 
+    // files dropped on the drawing are opened like ones picked with the Open button,
+    // dragging over it has to be allowed for the drop to happen at all
+    let area_ondragover = Callback::from(|evt: DragEvent| evt.prevent_default());
+
+    let appstate = props.appstate.clone();
+    let area_ondrop = Callback::from(move |evt: DragEvent| {
+        evt.prevent_default();
+
+        let file = evt.data_transfer().and_then(|data| data.files()).and_then(|files| files.get(0));
+        if let Some(file) = file {
+            open_file(file, appstate.clone());
+        }
+    });
+
+    let panning = pan.clone();
+    let svg_onkeydown = Callback::from(move |evt: KeyboardEvent| {
+        if evt.key() == " " {
+            evt.prevent_default();
+            panning.borrow_mut().space_held = true;
+        }
+    });
+
+    let panning = pan;
+    let svg_onkeyup = Callback::from(move |evt: KeyboardEvent| {
+        if evt.key() == " " {
+            panning.borrow_mut().space_held = false;
+        }
+    });
+
+    // patches the drawables' elements once yew is done with the rest
+    let drawing_ref = use_node_ref();
+    let drawing = use_mut_ref(Drawing::new);
+    {
+        let (group, appstate) = (drawing_ref.clone(), props.appstate.clone());
+        use_effect(move || {
+            if let Some(group) = group.cast::<web_sys::Element>() {
+                if let Err(error) = drawing.borrow_mut().draw(&group, &appstate) {
+                    log::error!("drawing the canvas failed: {:?}", error);
+                }
+            }
+
+            || ()
+        });
+    }
+
     let appstate = props.appstate.clone();
+    let canvas = (*appstate).canvas();
+    let visible = canvas.viewport().visible(canvas.width(), canvas.height());
+    let svg_style = match canvas.background() {
+        Some(background) => format!("border: 5px solid red; background: {}; cursor: {};", background, appstate.pointer_cursor()),
+        None => format!("border: 5px solid red; cursor: {};", appstate.pointer_cursor()),
+    };
+    let hover_bounds = appstate
+        .hover()
+        .filter(|&id| !canvas.is_selected(id))
+        .and_then(|id| canvas.get(id))
+        .map(|drawable| drawable.bounding_box().expand(SELECTION_MARGIN / canvas.viewport().zoom()));
+    let view_box = format!(
+        "{} {} {} {}",
+        visible.min().x(),
+        visible.min().y(),
+        visible.max().x() - visible.min().x(),
+        visible.max().y() - visible.min().y(),
+    );
 
     html! {
         <>
-            <svg width="1200" height="800" style="border: 5px solid red;" onclick={svg_onclick}>
-                {
-                    (*appstate).drawables().iter().map(|drawable: &Drawable| match drawable {
-                        Drawable::Line(line) => 
-                            html! { <line 
-                                x1={line.start().x().to_string()}
-                                y1={line.start().y().to_string()}
-                                x2={line.end().x().to_string()}
-                                y2={line.end().y().to_string()}
-                                style="stroke:rgb(255, 0, 0)"
-                            /> },
-                        _ => html! { "" }
+            <div style="display: inline-grid; grid-template-columns: auto auto;">
+            <div />
+            <Ruler appstate={props.appstate.clone()} axis={GuideAxis::Horizontal} svg={svg_ref.clone()} />
+            <Ruler appstate={props.appstate.clone()} axis={GuideAxis::Vertical} svg={svg_ref.clone()} />
+            // positions the text editor over the drawing
+            <div style="position: relative; display: inline-block;" ondragover={area_ondragover} ondrop={area_ondrop}>
+                <svg ref={svg_ref.clone()} width={canvas.width().to_string()} height={canvas.height().to_string()} viewBox={view_box} style={svg_style} tabindex="0"
+                    onclick={svg_onclick}
+                    onmousedown={svg_onmousedown}
+                    onmousemove={svg_onmousemove}
+                    onmouseup={svg_onmouseup}
+                    onmouseleave={svg_onmouseleave}
+                    onwheel={svg_onwheel}
+                    oncontextmenu={svg_oncontextmenu}
+                    ondblclick={svg_ondblclick}
+                    onkeydown={svg_onkeydown}
+                    onkeyup={svg_onkeyup}>
+                    if let Some(spacing) = canvas.grid_spacing() {
+                        <Grid spacing={spacing} zoom={canvas.viewport().zoom()} visible={visible.clone()} />
+                    }
+                    // filled by `Drawing` after each render, not by yew
+                    <g ref={drawing_ref}></g>
+                    <Guides guides={canvas.guides().to_vec()} visible={visible.clone()} />
+
+                    // drawn over the drawing but not part of it,
+                    // and invisible to the mouse so it never gets in the way of a click
+                    <g pointer-events="none">
+                        if let Some(bounds) = hover_bounds {
+                            <SelectionOutline bounds={bounds} hover=true />
+                        }
+                        if let Some(bounds) = canvas.selection_bounds() {
+                            <SelectionOutline bounds={bounds.expand(SELECTION_MARGIN / canvas.viewport().zoom())} />
+                        }
+                        {
+                            canvas.selected_handles(ROTATE_HANDLE_DISTANCE / canvas.viewport().zoom()).into_iter().map(|handle| html! {
+                                <HandleMarker handle={handle} zoom={canvas.viewport().zoom()} />
+                            }).collect::<Html>()
+                        }
+                        if let Some((bounds, contained)) = appstate.marquee() {
+                            <Marquee bounds={bounds} contained={contained} />
+                        }
+                        if let Some(preview) = appstate.tool_preview() {
+                            <ToolPreview drawable={preview} />
+                        }
+                        {
+                            appstate.pending_points().into_iter().map(|point| html! {
+                                <circle
+                                    cx={point.x().to_string()}
+                                    cy={point.y().to_string()}
+                                    r={(PENDING_POINT_RADIUS / canvas.viewport().zoom()).to_string()}
+                                    style="fill:rgb(0, 120, 215)"
+                                />
+                            }).collect::<Html>()
+                        }
+                        if let Some(snap) = appstate.snap() {
+                            <SnapMarker snap={snap.clone()} zoom={canvas.viewport().zoom()} />
+                        }
+                    </g>
+
+                </svg>
+                <TextEditor appstate={props.appstate.clone()} svg={svg_ref} />
+                <NumericEntry appstate={props.appstate.clone()} />
+            </div>
+            </div>
+            <ContextMenu appstate={props.appstate.clone()} menu={menu} />
 
-                    }).collect::<Html>()
-                }
-              
-            </svg>
-            
             <hr />
 
-            <h1>{ format!("appstate.drawables().len() = {}", (*appstate).drawables().len()) }</h1>
+            <h1>{ format!("appstate.canvas().len() = {}", canvas.len()) }</h1>
             <p>
                 {
-                    (*appstate).drawables().iter().map(|drawable: &Drawable| match drawable {
-                        Drawable::Line(line) => html! { 
-                            format!("{:?}\n", line)
-                        },
-                        _ => html! {}
+                    canvas.drawables().map(|(_, drawable)| html! { 
+                        format!("{:?}\n", drawable)
                     }).collect::<Html>()
                 }
             </p>
@@ -70,4 +391,438 @@ pub fn canvas(props: &CanvasProps) -> Html {
     }
 }
 
+/// how much one notch of the mouse wheel zooms.
+const ZOOM_STEP: f64 = 1.1;
+
+/// the gap, in screen pixels, between a drawable and its selection outline.
+const SELECTION_MARGIN: f64 = 4.0;
+
+/// the radius, in screen pixels, of the marker on the first point of a shape being drawn.
+const PENDING_POINT_RADIUS: f64 = 3.0;
+
+#[derive(PartialEq, Properties)]
+struct SelectionOutlineProps {
+    bounds: Aabb,
+    #[prop_or_default]
+    hover: bool,
+}
+
+/// a dashed box around the selected drawables, or with `hover` a faint
+/// solid one around the drawable under the pointer.
+/// its stroke keeps the same width at any zoom.
+#[function_component(SelectionOutline)]
+fn selection_outline(props: &SelectionOutlineProps) -> Html {
+    let (min, max) = (props.bounds.min(), props.bounds.max());
+    let style = if props.hover {
+        "stroke:rgba(0, 120, 215, 0.5); fill:none"
+    } else {
+        "stroke:rgb(0, 120, 215); stroke-dasharray:4 3; fill:none"
+    };
 
+    html! {
+        <rect
+            x={min.x().to_string()}
+            y={min.y().to_string()}
+            width={(max.x() - min.x()).to_string()}
+            height={(max.y() - min.y()).to_string()}
+            style={style}
+            vector-effect="non-scaling-stroke"
+        />
+    }
+}
+
+#[derive(PartialEq, Properties)]
+struct MarqueeProps {
+    bounds: Aabb,
+    contained: bool,
+}
+
+/// the box being dragged out to select drawables. it's filled when
+/// it only selects the drawables inside it and dashed otherwise.
+#[function_component(Marquee)]
+fn marquee(props: &MarqueeProps) -> Html {
+    let (min, max) = (props.bounds.min(), props.bounds.max());
+    let style = if props.contained {
+        "stroke:rgb(0, 120, 215); fill:rgba(0, 120, 215, 0.15)"
+    } else {
+        "stroke:rgb(0, 120, 215); stroke-dasharray:4 3; fill:rgba(0, 120, 215, 0.05)"
+    };
+
+    html! {
+        <rect
+            x={min.x().to_string()}
+            y={min.y().to_string()}
+            width={(max.x() - min.x()).to_string()}
+            height={(max.y() - min.y()).to_string()}
+            style={style}
+            vector-effect="non-scaling-stroke"
+        />
+    }
+}
+
+#[derive(PartialEq, Properties)]
+struct ToolPreviewProps {
+    drawable: Drawable,
+}
+
+/// the dashed outline of the shape the next click draws.
+#[function_component(ToolPreview)]
+fn tool_preview(props: &ToolPreviewProps) -> Html {
+    let style = "stroke:rgb(0, 120, 215); stroke-dasharray:4 3; fill:none";
+
+    match &props.drawable {
+        Drawable::Line(line) => html! {
+            <line
+                x1={line.start().x().to_string()}
+                y1={line.start().y().to_string()}
+                x2={line.end().x().to_string()}
+                y2={line.end().y().to_string()}
+                style={style}
+                vector-effect="non-scaling-stroke"
+            />
+        },
+        Drawable::Circle(circle) => html! {
+            <circle
+                cx={circle.center().x().to_string()}
+                cy={circle.center().y().to_string()}
+                r={circle.radius().to_string()}
+                style={style}
+                vector-effect="non-scaling-stroke"
+            />
+        },
+        Drawable::Rect(rect) => html! {
+            <rect
+                x={rect.corner().x().to_string()}
+                y={rect.corner().y().to_string()}
+                width={rect.width().to_string()}
+                height={rect.height().to_string()}
+                style={style}
+                vector-effect="non-scaling-stroke"
+            />
+        },
+        Drawable::Arc(arc) => html! {
+            <path d={arc.path_data()} style={style} vector-effect="non-scaling-stroke" />
+        },
+        Drawable::Point(_) | Drawable::Text(_) => html! {},
+    }
+}
+
+#[derive(PartialEq, Properties)]
+struct GuidesProps {
+    guides: Vec<Guide>,
+    visible: Aabb,
+}
+
+/// the ruler guides, across the whole view whatever the size of the drawing.
+#[function_component(Guides)]
+fn guides(props: &GuidesProps) -> Html {
+    let (min, max) = (props.visible.min(), props.visible.max());
+
+    html! {
+        <g pointer-events="none">
+            { for props.guides.iter().map(|guide| {
+                let ((x1, y1), (x2, y2)) = match guide.axis {
+                    GuideAxis::Horizontal => ((min.x(), guide.position), (max.x(), guide.position)),
+                    GuideAxis::Vertical => ((guide.position, min.y()), (guide.position, max.y())),
+                };
+                html! {
+                    <line key={guide.id.to_string()}
+                        x1={x1.to_string()} y1={y1.to_string()} x2={x2.to_string()} y2={y2.to_string()}
+                        stroke={GUIDE_COLOR} vector-effect="non-scaling-stroke"
+                    />
+                }
+            }) }
+        </g>
+    }
+}
+
+/// the smallest distance between ruler ticks, in screen pixels.
+const RULER_TICK_SPACING: f64 = 50.0;
+
+/// the step between ruler ticks in drawing coordinates, a 1, 2 or 5
+/// times a power of ten at least `RULER_TICK_SPACING` pixels apart on screen.
+fn ruler_step(zoom: f64) -> f64 {
+    let least = RULER_TICK_SPACING / zoom;
+    let power = 10f64.powf(least.log10().floor());
+
+    [1f64, 2f64, 5f64, 10f64].into_iter().map(|factor| factor * power).find(|step| *step >= least).unwrap_or(10f64 * power)
+}
+
+/// the width, in screen pixels, of the rulers along the top and left edges.
+const RULER_SIZE: f64 = 16.0;
+
+/// the width, in pixels, of the canvas' border, which the rulers line up inside of.
+const CANVAS_BORDER: f64 = 5.0;
+
+#[derive(PartialEq, Properties)]
+struct RulerProps {
+    appstate: UseStateHandle<AppState>,
+    /// the guides dragged out of it, horizontal ones for the top ruler.
+    axis: GuideAxis,
+    /// the SVG element of the drawing, which pointer positions are measured from.
+    svg: NodeRef,
+}
+
+/// a strip along the top or left edge of the drawing, marked in drawing
+/// coordinates, that guides are dragged out of and back onto to remove them.
+#[function_component(Ruler)]
+fn ruler(props: &RulerProps) -> Html {
+    let (appstate, axis) = (&props.appstate, props.axis);
+    let world = {
+        let svg = props.svg.clone();
+        move |evt: &MouseEvent, appstate: &AppState| {
+            svg.cast::<web_sys::Element>().map(|svg| event_to_world_from(evt, &svg, appstate.canvas()))
+        }
+    };
+
+    let onmousedown = {
+        let (appstate, world) = (appstate.clone(), world.clone());
+        Callback::from(move |evt: MouseEvent| {
+            if evt.button() != 0 {
+                return;
+            }
+            evt.prevent_default();
+
+            let mut new_state = appstate.edited();
+            if let Some(point) = world(&evt, &new_state) {
+                new_state.drag_out_guide(axis, &point);
+                appstate.set(new_state);
+            }
+        })
+    };
+    // over the ruler the guide is outside the view, where letting go removes it
+    let onmousemove = {
+        let (appstate, world) = (appstate.clone(), world.clone());
+        Callback::from(move |evt: MouseEvent| {
+            let mut new_state = appstate.edited();
+            if let (true, Some(point)) = (new_state.is_dragging_guide(), world(&evt, &new_state)) {
+                new_state.drag_to(point, Modifiers::default());
+                appstate.set(new_state);
+            }
+        })
+    };
+    let onmouseup = {
+        let appstate = appstate.clone();
+        Callback::from(move |_: MouseEvent| {
+            if appstate.is_dragging_guide() {
+                let mut new_state = appstate.edited();
+                new_state.release();
+                appstate.set(new_state);
+            }
+        })
+    };
+    // the button was let go somewhere else than the drawing
+    let onmouseleave = {
+        let appstate = appstate.clone();
+        Callback::from(move |evt: MouseEvent| {
+            if appstate.is_dragging_guide() && evt.buttons() == 0 {
+                let mut new_state = appstate.edited();
+                new_state.release();
+                appstate.set(new_state);
+            }
+        })
+    };
+
+    let canvas = appstate.canvas();
+    let viewport = canvas.viewport();
+    let visible = viewport.visible(canvas.width(), canvas.height());
+    let step = ruler_step(viewport.zoom());
+    // as many decimals as the step has, so labels don't show rounding errors
+    let decimals = (-step.log10().floor()).max(0f64) as usize;
+    let (from, to, length) = match axis {
+        GuideAxis::Horizontal => (visible.min().x(), visible.max().x(), canvas.width()),
+        GuideAxis::Vertical => (visible.min().y(), visible.max().y(), canvas.height()),
+    };
+    let ticks = ((from / step).ceil() as i64..=(to / step).floor() as i64).map(|tick| {
+        let value = tick as f64 * step;
+        let screen = viewport.world_to_screen(&Point::new(value, value));
+        let label = format!("{:.*}", decimals, value);
+
+        match axis {
+            GuideAxis::Horizontal => html! {
+                <>
+                    <line x1={screen.x().to_string()} y1={(RULER_SIZE / 2f64).to_string()} x2={screen.x().to_string()} y2={RULER_SIZE.to_string()} stroke="#808080" />
+                    <text x={(screen.x() + 2f64).to_string()} y={(RULER_SIZE * 0.6).to_string()}>{ label }</text>
+                </>
+            },
+            GuideAxis::Vertical => html! {
+                <>
+                    <line x1={(RULER_SIZE / 2f64).to_string()} y1={screen.y().to_string()} x2={RULER_SIZE.to_string()} y2={screen.y().to_string()} stroke="#808080" />
+                    <text x="1" y={(screen.y() - 2f64).to_string()}>{ label }</text>
+                </>
+            },
+        }
+    });
+
+    let (width, height, style) = match axis {
+        GuideAxis::Horizontal => (length, RULER_SIZE, format!("margin: 0 {0}px; cursor: row-resize;", CANVAS_BORDER)),
+        GuideAxis::Vertical => (RULER_SIZE, length, format!("margin: {0}px 0; cursor: col-resize;", CANVAS_BORDER)),
+    };
+
+    html! {
+        <svg width={width.to_string()} height={height.to_string()}
+            style={format!("display: block; background: #f4f4f4; font: {}px sans-serif; fill: #606060; {}", RULER_SIZE * 0.55, style)}
+            onmousedown={onmousedown} onmousemove={onmousemove} onmouseup={onmouseup} onmouseleave={onmouseleave}
+        >
+            <title>{ appstate.strings().ruler_hint }</title>
+            { for ticks }
+        </svg>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+struct GridProps {
+    spacing: f64,
+    zoom: f64,
+    visible: Aabb,
+}
+
+/// grid lines behind the drawing, laid out in drawing coordinates
+/// so they stay on the points clicks snap to at any zoom and pan.
+/// its lines keep the same width on screen at any zoom.
+#[function_component(Grid)]
+fn grid(props: &GridProps) -> Html {
+    let (min, max) = (props.visible.min(), props.visible.max());
+
+    html! {
+        <g pointer-events="none">
+            <defs>
+                <pattern
+                    id="grid"
+                    width={props.spacing.to_string()}
+                    height={props.spacing.to_string()}
+                    patternUnits="userSpaceOnUse"
+                >
+                    <path
+                        d={format!("M {0} 0 L 0 0 0 {0}", props.spacing)}
+                        fill="none"
+                        stroke={GRID_COLOR}
+                        stroke-width={(1f64 / props.zoom).to_string()}
+                    />
+                </pattern>
+            </defs>
+            <rect
+                x={min.x().to_string()}
+                y={min.y().to_string()}
+                width={(max.x() - min.x()).to_string()}
+                height={(max.y() - min.y()).to_string()}
+                fill="url(#grid)"
+            />
+        </g>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+struct HandleMarkerProps {
+    handle: Handle,
+    zoom: f64,
+}
+
+/// a square to drag a corner, endpoint or rim by,
+/// or a circle to rotate by. it keeps the same size at any zoom.
+#[function_component(HandleMarker)]
+fn handle_marker(props: &HandleMarkerProps) -> Html {
+    let (pos, size) = (&props.handle.pos, HANDLE_SIZE / props.zoom);
+    let style = "stroke:rgb(0, 120, 215); fill:white";
+
+    match props.handle.kind {
+        HandleKind::Rotate => html! {
+            <circle
+                cx={pos.x().to_string()}
+                cy={pos.y().to_string()}
+                r={(size / 2f64).to_string()}
+                style={style}
+                vector-effect="non-scaling-stroke"
+            />
+        },
+        _ => html! {
+            <rect
+                x={(pos.x() - size / 2f64).to_string()}
+                y={(pos.y() - size / 2f64).to_string()}
+                width={size.to_string()}
+                height={size.to_string()}
+                style={style}
+                vector-effect="non-scaling-stroke"
+            />
+        },
+    }
+}
+
+/// the side, in screen pixels, of the marker on the point the pointer snapped to.
+const SNAP_MARKER_SIZE: f64 = 10.0;
+
+#[derive(PartialEq, Properties)]
+struct SnapMarkerProps {
+    snap: Snap,
+    zoom: f64,
+}
+
+/// marks the point the pointer snapped to with a square for an endpoint,
+/// a triangle for a midpoint, a circle for a center, an X for an intersection,
+/// a plus for the grid and a diamond for a guide. it keeps the same size at any zoom.
+#[function_component(SnapMarker)]
+fn snap_marker(props: &SnapMarkerProps) -> Html {
+    let (x, y, half) = (props.snap.point.x(), props.snap.point.y(), SNAP_MARKER_SIZE / props.zoom / 2f64);
+    let style = "stroke:rgb(230, 120, 0); stroke-width:2; fill:none";
+
+    let d = match props.snap.kind {
+        Some(SnapKind::Endpoint) => format!("M {} {} h {} v {} h {} Z", x - half, y - half, 2f64 * half, 2f64 * half, -2f64 * half),
+        Some(SnapKind::Midpoint) => format!("M {} {} L {} {} L {} {} Z", x, y - half, x + half, y + half, x - half, y + half),
+        Some(SnapKind::Center) => return html! {
+            <circle cx={x.to_string()} cy={y.to_string()} r={half.to_string()} style={style} vector-effect="non-scaling-stroke" />
+        },
+        Some(SnapKind::Intersection) => format!("M {} {} L {} {} M {} {} L {} {}", x - half, y - half, x + half, y + half, x - half, y + half, x + half, y - half),
+        Some(SnapKind::Grid) => format!("M {} {} H {} M {} {} V {}", x - half, y, x + half, x, y - half, y + half),
+        Some(SnapKind::Guide) => format!("M {} {} L {} {} L {} {} L {} {} Z", x, y - half, x + half, y, x, y + half, x - half, y),
+        None => return Html::default(),
+    };
+
+    html! {
+        <path d={d} style={style} vector-effect="non-scaling-stroke" />
+    }
+}
+
+/// the state of panning the view, which isn't part of the drawing.
+#[derive(Default)]
+struct Pan {
+    /// the last pointer position while panning.
+    last: Option<Point>,
+    space_held: bool,
+}
+
+/// the latest pointer position, applied on the next animation frame
+/// so dragging and tracking the cursor update the state at most once per frame.
+#[derive(Default)]
+struct PendingPointer {
+    point: Option<Point>,
+    /// the keys held, shift constrains dragging handles
+    /// and alt switches what the marquee selects.
+    modifiers: Modifiers,
+    frame: Option<AnimationFrame>,
+}
+
+/// applies the pending pointer position right away and ends the drag,
+/// forgetting the cursor when it `left` the canvas.
+fn release_pointer(appstate: &UseStateHandle<AppState>, pending: &RefCell<PendingPointer>, left: bool) {
+    let (point, modifiers) = {
+        let mut pending_pointer = pending.borrow_mut();
+        pending_pointer.frame = None;
+        (pending_pointer.point.take(), pending_pointer.modifiers)
+    };
+
+    if point.is_none() && !left && !appstate.is_dragging() {
+        return;
+    }
+
+    let mut new_state = appstate.edited();
+    if let Some(point) = point {
+        new_state.set_cursor(Some(point.clone()));
+        new_state.drag_to(point, modifiers);
+    }
+    if left {
+        new_state.set_cursor(None);
+    }
+    new_state.release();
+
+    appstate.set(new_state);
+}
@@ -0,0 +1,199 @@
+use yew::prelude::*;
+use gloo_events::EventListener;
+use program_core::Point;
+use wasm_bindgen::JsCast;
+use super::AppState;
+use super::i18n::Strings;
+
+/// an action picked from the context menu.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MenuAction {
+    Delete,
+    Duplicate,
+    Copy,
+    /// places the copied drawable under the cursor.
+    Paste,
+    BringToFront,
+    SendToBack,
+    Lock,
+    Unlock,
+    /// moves the focus to the properties panel.
+    Properties,
+}
+
+impl MenuAction {
+    pub fn label(&self, strings: &Strings) -> &'static str {
+        match self {
+            MenuAction::Delete => strings.delete,
+            MenuAction::Duplicate => strings.duplicate,
+            MenuAction::Copy => strings.copy,
+            MenuAction::Paste => strings.paste,
+            MenuAction::BringToFront => strings.bring_to_front,
+            MenuAction::SendToBack => strings.send_to_back,
+            MenuAction::Lock => strings.lock,
+            MenuAction::Unlock => strings.unlock,
+            MenuAction::Properties => strings.properties,
+        }
+    }
+}
+
+/// the actions offered by the menu, for a drawable when `locked` is set
+/// and for empty space otherwise.
+///
+/// # Examples
+/// ```
+/// use web::{menu_actions, MenuAction};
+///
+/// assert_eq!(vec![MenuAction::Paste], menu_actions(None));
+/// assert!(menu_actions(Some(false)).contains(&MenuAction::Lock));
+/// assert!(menu_actions(Some(true)).contains(&MenuAction::Unlock));
+///
+/// ```
+pub fn menu_actions(locked: Option<bool>) -> Vec<MenuAction> {
+    match locked {
+        Some(locked) => vec![
+            MenuAction::Delete,
+            MenuAction::Duplicate,
+            MenuAction::Copy,
+            MenuAction::BringToFront,
+            MenuAction::SendToBack,
+            if locked { MenuAction::Unlock } else { MenuAction::Lock },
+            MenuAction::Properties,
+        ],
+        None => vec![MenuAction::Paste],
+    }
+}
+
+/// the width of the menu in pixels.
+const MENU_WIDTH: f64 = 160.0;
+/// the height of one menu item in pixels.
+const ITEM_HEIGHT: f64 = 26.0;
+
+/// where to put the top left corner of a menu of the given size opened
+/// at `at`, moved back inside the window when it would stick out of it.
+///
+/// # Examples
+/// ```
+/// use program_core::Point;
+/// use web::menu_position;
+///
+/// assert_eq!(Point::new(100.0, 50.0), menu_position(&Point::new(100.0, 50.0), (160.0, 100.0), (800.0, 600.0)));
+/// assert_eq!(Point::new(640.0, 500.0), menu_position(&Point::new(790.0, 590.0), (160.0, 100.0), (800.0, 600.0)));
+/// assert_eq!(Point::new(0.0, 0.0), menu_position(&Point::new(50.0, 50.0), (160.0, 100.0), (100.0, 80.0)));
+///
+/// ```
+pub fn menu_position(at: &Point, (width, height): (f64, f64), (window_width, window_height): (f64, f64)) -> Point {
+    Point::new(
+        at.x().min(window_width - width).max(0f64),
+        at.y().min(window_height - height).max(0f64),
+    )
+}
+
+/// a context menu that's open, in client coordinates and drawing coordinates.
+#[derive(Clone, PartialEq, Debug)]
+pub struct OpenMenu {
+    pub client: Point,
+    pub world: Point,
+    /// whether it was opened over a drawable.
+    pub on_drawable: bool,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ContextMenuProps {
+    pub appstate: UseStateHandle<AppState>,
+    pub menu: UseStateHandle<Option<OpenMenu>>,
+}
+
+/// the menu opened by right-clicking the canvas. picking an action,
+/// pressing Escape or clicking anywhere else closes it.
+#[function_component(ContextMenu)]
+pub fn context_menu(props: &ContextMenuProps) -> Html {
+    // registered on the document while the menu is open,
+    // a press inside the menu is stopped before it gets there
+    use_effect_with_deps(
+        |menu: &UseStateHandle<Option<OpenMenu>>| {
+            let listeners = menu.as_ref().map(|_| {
+                let document = gloo_utils::document();
+                let (on_press, on_key) = (menu.clone(), menu.clone());
+
+                (
+                    EventListener::new(&document, "mousedown", move |_| on_press.set(None)),
+                    EventListener::new(&document, "keydown", move |evt| {
+                        let evt = evt.dyn_ref::<KeyboardEvent>().expect("keydown is a keyboard event");
+                        if evt.key() == "Escape" {
+                            on_key.set(None);
+                        }
+                    }),
+                )
+            });
+
+            move || drop(listeners)
+        },
+        props.menu.clone(),
+    );
+
+    let menu = match &*props.menu {
+        Some(menu) => menu.clone(),
+        None => return html! {},
+    };
+    let locked = menu.on_drawable.then(|| {
+        let canvas = props.appstate.canvas();
+        canvas.selected_drawable().is_some_and(|id| canvas.is_locked(id))
+    });
+    let actions = menu_actions(locked);
+
+    let window = gloo_utils::window();
+    let window_size = (
+        window.inner_width().ok().and_then(|width| width.as_f64()).unwrap_or(f64::INFINITY),
+        window.inner_height().ok().and_then(|height| height.as_f64()).unwrap_or(f64::INFINITY),
+    );
+    let position = menu_position(&menu.client, (MENU_WIDTH, ITEM_HEIGHT * actions.len() as f64), window_size);
+    let style = format!(
+        "position: fixed; left: {}px; top: {}px; width: {}px; display: flex; flex-direction: column; \
+         background: white; border: 1px solid gray; box-shadow: 2px 2px 6px rgba(0, 0, 0, 0.3); z-index: 10;",
+        position.x(), position.y(), MENU_WIDTH,
+    );
+
+    let items = actions.into_iter().map(|action| {
+        let (appstate, menu_handle, at) = (props.appstate.clone(), props.menu.clone(), menu.world.clone());
+        let disabled = action == MenuAction::Paste && !props.appstate.can_paste();
+        let onclick = Callback::from(move |_: MouseEvent| {
+            let mut new_state = appstate.edited();
+            if new_state.run_menu_action(action, &at) {
+                appstate.set(new_state);
+            }
+            if action == MenuAction::Properties {
+                focus_properties();
+            }
+            menu_handle.set(None);
+        });
+
+        html! {
+            <button onclick={onclick} disabled={disabled} style={format!("height: {}px; text-align: start;", ITEM_HEIGHT)}>
+                { action.label(props.appstate.strings()) }
+            </button>
+        }
+    }).collect::<Html>();
+
+    html! {
+        <div role="menu" style={style} onmousedown={Callback::from(|evt: MouseEvent| evt.stop_propagation())}>
+            { items }
+        </div>
+    }
+}
+
+/// focuses the first field of the properties panel, once it's rendered.
+fn focus_properties() {
+    gloo_timers::callback::Timeout::new(0, || {
+        let field = gloo_utils::document()
+            .query_selector("#properties input")
+            .ok()
+            .flatten()
+            .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+
+        if let Some(field) = field {
+            let _ = field.focus();
+        }
+    })
+    .forget();
+}
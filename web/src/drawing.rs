@@ -0,0 +1,194 @@
+use program_core::{Drawable, DrawableId, Style};
+use std::collections::BTreeMap;
+use wasm_bindgen::JsValue;
+use web_sys::{Document, Element};
+use super::AppState;
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// how many drawables may be dirty for a draw to patch their elements,
+/// past it drawing everything again is about as quick.
+pub const FULL_REDRAW_THRESHOLD: usize = 256;
+
+/// # Drawing
+/// the elements the drawables of a canvas are drawn as, inside a group
+/// yew leaves empty. once drawn, only the elements of the drawables
+/// marked dirty are replaced, so dragging a shape among thousands leaves
+/// every other element as it is.
+#[derive(Default)]
+pub struct Drawing {
+    /// the group last drawn into.
+    group: Option<Element>,
+    /// the revision of the canvas last drawn.
+    revision: Option<u64>,
+    /// the element of every drawn drawable, points and hidden ones have none.
+    elements: BTreeMap<DrawableId, Element>,
+}
+
+impl Drawing {
+    pub fn new() -> Drawing {
+        Drawing::default()
+    }
+
+    /// draws the canvas of `state` into `group`. when the last draw was of
+    /// the revision its dirty ids start from, only their elements are
+    /// replaced, otherwise every element is.
+    pub fn draw(&mut self, group: &Element, state: &AppState) -> Result<(), JsValue> {
+        let canvas = state.canvas();
+        let patch = self.group.as_ref() == Some(group)
+            && self.revision.is_some()
+            && self.revision == state.dirty_from()
+            && canvas.dirty_ids().len() <= FULL_REDRAW_THRESHOLD;
+
+        if patch {
+            self.patch(group, state)?;
+        } else {
+            self.redraw(group, state)?;
+        }
+
+        self.group = Some(group.clone());
+        self.revision = Some(canvas.revision());
+
+        Ok(())
+    }
+
+    /// the element `id` is drawn as, `None` if it isn't drawn.
+    pub fn element(&self, id: DrawableId) -> Option<&Element> {
+        self.elements.get(&id)
+    }
+
+    fn redraw(&mut self, group: &Element, state: &AppState) -> Result<(), JsValue> {
+        let canvas = state.canvas();
+        let document = gloo_utils::document();
+
+        // yew keeps a placeholder of its own in the group, so only the
+        // elements drawn here are taken out
+        for element in std::mem::take(&mut self.elements).into_values() {
+            element.remove();
+        }
+
+        for (id, drawable) in canvas.drawables().filter(|(id, _)| !canvas.is_hidden(*id)) {
+            if let Some(element) = drawable_element(&document, id, drawable)? {
+                group.append_child(&element)?;
+                self.elements.insert(id, element);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn patch(&mut self, group: &Element, state: &AppState) -> Result<(), JsValue> {
+        let canvas = state.canvas();
+        let document = gloo_utils::document();
+        let dirty: Vec<DrawableId> = canvas.dirty_ids().collect();
+
+        // every element left is where it belongs, so each dirty drawable
+        // still drawn goes right below the first one above it that's left
+        for id in &dirty {
+            if let Some(element) = self.elements.remove(id) {
+                element.remove();
+            }
+        }
+
+        for id in dirty {
+            let drawable = match canvas.get(id) {
+                Some(drawable) if !canvas.is_hidden(id) => drawable,
+                _ => continue,
+            };
+            let element = match drawable_element(&document, id, drawable)? {
+                Some(element) => element,
+                None => continue,
+            };
+
+            let mut above = canvas.stacked_above(id);
+            while let Some(other) = above.filter(|other| !self.elements.contains_key(other)) {
+                above = canvas.stacked_above(other);
+            }
+
+            group.insert_before(&element, above.and_then(|above| self.elements.get(&above)).map(|above| above.as_ref()))?;
+            self.elements.insert(id, element);
+        }
+
+        Ok(())
+    }
+}
+
+/// the `stroke-dasharray` of a dashed or dotted stroke, like the one exported.
+fn dash_array(style: &Style) -> Option<String> {
+    let dashes = style.stroke_style.dash_array(style.stroke_width.unwrap_or(1f64))?;
+
+    Some(dashes.iter().map(f64::to_string).collect::<Vec<String>>().join(" "))
+}
+
+/// the element a drawable is drawn as, tagged with its id. points have none.
+fn drawable_element(document: &Document, id: DrawableId, drawable: &Drawable) -> Result<Option<Element>, JsValue> {
+    let outline = "stroke:rgb(255, 0, 0); fill:none";
+    let (name, attributes): (&str, Vec<(&str, Option<String>)>) = match drawable {
+        Drawable::Line(line) => (
+            "line",
+            vec![
+                ("x1", Some(line.start().x().to_string())),
+                ("y1", Some(line.start().y().to_string())),
+                ("x2", Some(line.end().x().to_string())),
+                ("y2", Some(line.end().y().to_string())),
+                ("stroke-dasharray", dash_array(line.style())),
+                ("style", Some(String::from("stroke:rgb(255, 0, 0)"))),
+            ],
+        ),
+        Drawable::Circle(circle) => (
+            "circle",
+            vec![
+                ("cx", Some(circle.center().x().to_string())),
+                ("cy", Some(circle.center().y().to_string())),
+                ("r", Some(circle.radius().to_string())),
+                ("style", Some(String::from(outline))),
+            ],
+        ),
+        Drawable::Rect(rect) => (
+            "rect",
+            vec![
+                ("x", Some(rect.corner().x().to_string())),
+                ("y", Some(rect.corner().y().to_string())),
+                ("width", Some(rect.width().to_string())),
+                ("height", Some(rect.height().to_string())),
+                (
+                    "transform",
+                    (rect.rotation() != 0f64)
+                        .then(|| format!("rotate({} {} {})", rect.rotation().to_degrees(), rect.center().x(), rect.center().y())),
+                ),
+                ("stroke-dasharray", dash_array(rect.style())),
+                ("style", Some(String::from(outline))),
+            ],
+        ),
+        Drawable::Text(text) => (
+            "text",
+            vec![
+                ("x", Some(text.pos().x().to_string())),
+                ("y", Some(text.pos().y().to_string())),
+                ("font-size", Some(text.font_size().to_string())),
+            ],
+        ),
+        Drawable::Arc(arc) => (
+            "path",
+            vec![
+                ("d", Some(arc.path_data())),
+                ("stroke-dasharray", dash_array(arc.style())),
+                ("style", Some(String::from(outline))),
+            ],
+        ),
+        Drawable::Point(_) => return Ok(None),
+    };
+
+    let element = document.create_element_ns(Some(SVG_NAMESPACE), name)?;
+    element.set_attribute("data-id", &id.to_string())?;
+    for (attribute, value) in attributes {
+        if let Some(value) = value {
+            element.set_attribute(attribute, &value)?;
+        }
+    }
+    if let Drawable::Text(text) = drawable {
+        element.set_text_content(Some(text.text()));
+    }
+
+    Ok(Some(element))
+}
@@ -0,0 +1,108 @@
+use program_core::Canvas;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, BlobPropertyBag, CanvasRenderingContext2d, HtmlAnchorElement, HtmlCanvasElement, HtmlImageElement, Url};
+
+/// the name of the downloaded file, derived from the document title.
+/// characters that aren't safe in file names are replaced with dashes.
+///
+/// # Examples
+/// ```
+/// use web::file_name;
+///
+/// assert_eq!("drawing.svg", file_name(None, "svg"));
+/// assert_eq!("drawing.svg", file_name(Some(" /?* "), "svg"));
+/// assert_eq!("floor-plan_v2.svg", file_name(Some("Floor plan_v2"), "svg"));
+/// assert_eq!("a-b.png", file_name(Some("a/../b"), "png"));
+///
+/// ```
+pub fn file_name(title: Option<&str>, extension: &str) -> String {
+    let mut name = String::new();
+
+    for c in title.unwrap_or_default().chars() {
+        if c.is_alphanumeric() || c == '_' {
+            name.extend(c.to_lowercase());
+        } else if !name.is_empty() && !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+
+    let name = name.trim_end_matches('-');
+    format!("{}.{}", if name.is_empty() { "drawing" } else { name }, extension)
+}
+
+fn svg_blob(canvas: &Canvas) -> Result<Blob, JsValue> {
+    let parts = js_sys::Array::of1(&JsValue::from_str(&canvas.to_svg()));
+    let options = BlobPropertyBag::new();
+    options.set_type("image/svg+xml");
+
+    Blob::new_with_str_sequence_and_options(&parts, &options)
+}
+
+/// downloads `blob` as `name` through a temporary object URL.
+fn download_blob(blob: &Blob, name: &str) -> Result<(), JsValue> {
+    let url = Url::create_object_url_with_blob(blob)?;
+
+    let anchor: HtmlAnchorElement = gloo_utils::document().create_element("a")?.unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(name);
+    anchor.click();
+
+    Url::revoke_object_url(&url)
+}
+
+/// downloads the SVG document of `canvas`.
+pub fn download_svg(canvas: &Canvas) -> Result<(), JsValue> {
+    download_blob(&svg_blob(canvas)?, &file_name(canvas.title().map(String::as_str), "svg"))
+}
+
+/// downloads a PNG image of `canvas`, `scale` pixels to a canvas unit. the
+/// browser draws the SVG document onto an offscreen canvas element, so the
+/// image looks like the SVG does, text included.
+pub async fn download_png(canvas: &Canvas, scale: f64) -> Result<(), JsValue> {
+    let url = Url::create_object_url_with_blob(&svg_blob(canvas)?)?;
+    let image = HtmlImageElement::new()?;
+    image.set_src(&url);
+    let loaded = JsFuture::from(image.decode()).await;
+    Url::revoke_object_url(&url)?;
+    loaded?;
+
+    let element: HtmlCanvasElement = gloo_utils::document().create_element("canvas")?.unchecked_into();
+    let (width, height) = ((canvas.width() * scale).ceil(), (canvas.height() * scale).ceil());
+    element.set_width(width as u32);
+    element.set_height(height as u32);
+
+    let context: CanvasRenderingContext2d = element
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("the canvas element has no 2d context"))?
+        .unchecked_into();
+    context.draw_image_with_html_image_element_and_dw_and_dh(&image, 0f64, 0f64, width, height)?;
+
+    let blob = JsFuture::from(js_sys::Promise::new(&mut |resolve, reject| {
+        if let Err(error) = element.to_blob(&resolve) {
+            let _ = reject.call1(&JsValue::NULL, &error);
+        }
+    }))
+    .await?;
+    // the canvas can't always be encoded, like when it's too large
+    if blob.is_null() {
+        return Err(JsValue::from_str("the image couldn't be encoded"));
+    }
+
+    download_blob(blob.unchecked_ref(), &file_name(canvas.title().map(String::as_str), "png"))
+}
+
+/// copies the SVG document of `canvas` to the clipboard.
+/// fails when the page isn't allowed to use the clipboard.
+pub async fn copy_svg(canvas: &Canvas) -> Result<(), JsValue> {
+    let navigator = gloo_utils::window().navigator();
+
+    // the clipboard only exists in secure contexts
+    if js_sys::Reflect::get(&navigator, &JsValue::from_str("clipboard"))?.is_undefined() {
+        return Err(JsValue::from_str("the clipboard isn't available"));
+    }
+
+    JsFuture::from(navigator.clipboard().write_text(&canvas.to_svg())).await?;
+
+    Ok(())
+}
@@ -0,0 +1,679 @@
+use std::fmt;
+use super::autosave::local_storage;
+
+/// declares `Strings` with one field per key, and a way to list them,
+/// so a table missing a key doesn't compile.
+macro_rules! strings {
+    ($($(#[$attr:meta])* $key:ident,)*) => {
+        /// every text the interface shows, in one language. texts with
+        /// `{}` in them are filled in with `fill`.
+        #[derive(PartialEq, Debug)]
+        pub struct Strings {
+            $($(#[$attr])* pub $key: &'static str,)*
+        }
+
+        impl Strings {
+            /// every text with its key, in the order they're declared.
+            pub fn entries(&self) -> Vec<(&'static str, &'static str)> {
+                vec![$((stringify!($key), self.$key),)*]
+            }
+        }
+    };
+}
+
+strings! {
+    title,
+    /// the name of the language, on the button switching to it.
+    language_name,
+
+    tool_select,
+    tool_line,
+    tool_circle,
+    tool_circle_three_points,
+    tool_rect,
+    tool_text,
+    add_line,
+    add_circle,
+    add_circle_three_points,
+    add_rect,
+    add_text,
+    select_hint,
+    /// the tool's name and its key.
+    tool_hint,
+    font_size,
+    delete,
+    delete_hint,
+    undo,
+    stroke_align,
+    rotation_pivot,
+    pivot_center,
+    pivot_start,
+    align_center,
+    align_inside,
+    align_outside,
+    stroke_style,
+    style_solid,
+    style_dashed,
+    style_dotted,
+    dash_lengths,
+    line_cap,
+    cap_butt,
+    cap_round,
+    cap_square,
+    line_join,
+    join_miter,
+    join_round,
+    join_bevel,
+    round_coordinates,
+    /// the number of decimals.
+    round_coordinates_hint,
+    fit,
+    new_drawing,
+    document_settings,
+    restore,
+    open,
+    open_hint,
+    export_svg,
+    download_png,
+    copy_svg,
+    export_failed,
+    copied,
+    copy_failed,
+    keep_tool_selected,
+    grid,
+    grid_spacing,
+    export_grid,
+    keep_on_canvas,
+    snap,
+    snap_endpoint,
+    snap_midpoint,
+    snap_center,
+    snap_intersection,
+    snap_guide,
+    ruler_hint,
+    /// the kind of point the pointer snapped to.
+    snapped_to,
+    ortho,
+    ortho_hint,
+    ortho_step,
+    numeric_entry_hint,
+    not_finite,
+    /// the radius that was given.
+    non_positive_radius,
+    zero_length_line,
+    zero_area_rect,
+    zero_sweep_arc,
+    collinear_points,
+    saved,
+    saving,
+    unsaved,
+    save_hint,
+
+    replace_drawing,
+    /// why the file couldn't be opened.
+    open_failed,
+    /// the file name.
+    not_a_drawing,
+    /// the file name and the error.
+    unreadable,
+    dismiss,
+
+    /// the unit.
+    width_in,
+    /// the unit.
+    height_in,
+    when_resizing,
+    keep_positions,
+    keep_centered,
+    scale_drawing,
+    background,
+    grid_spacing_px,
+    no_grid,
+    svg_precision,
+    full_precision,
+    unit,
+    create,
+    cancel,
+    close,
+
+    /// the x and y of the point.
+    point_summary,
+    /// the length.
+    line_summary,
+    /// the radius.
+    circle_summary,
+    /// the width and the height.
+    rect_summary,
+    /// the radius and the degrees the arc turns.
+    arc_summary,
+    /// the number of characters.
+    text_summary,
+    /// the number of shapes.
+    shapes,
+    /// the number of shapes.
+    shapes_selected,
+
+    start,
+    end,
+    center,
+    radius,
+    start_angle,
+    end_angle,
+    corner,
+    width,
+    height,
+    rotation,
+    content,
+    position,
+    apply,
+    not_a_number,
+    non_positive_length,
+    invalid_angle,
+    invalid_dashes,
+    /// the id of the shape and its place in the stacking order.
+    props_header,
+    shape_missing,
+    shape_locked,
+    wrong_kind,
+
+    shapes_panel,
+    filter_shapes,
+    point,
+    arc,
+    rename_hint,
+    hide,
+    show,
+    /// the length.
+    length_detail,
+    /// the radius.
+    radius_detail,
+    /// the width and the height.
+    size_detail,
+    /// the number of characters.
+    chars_detail,
+    /// the x and y of the point.
+    position_detail,
+
+    duplicate,
+    copy,
+    paste,
+    bring_to_front,
+    send_to_back,
+    lock,
+    unlock,
+    properties,
+
+    print,
+    page_size,
+    portrait,
+    landscape,
+    fit_to_page,
+    print_scale,
+    crop_to_drawing,
+    title_block,
+    untitled,
+    print_failed,
+
+    stamps,
+    tool_stamp,
+    save_as_stamp,
+    stamp_name,
+    /// the number of the stamp.
+    default_stamp_name,
+    stamp_hint,
+}
+
+pub static ENGLISH: Strings = Strings {
+    title: "Alrassam - Prototype",
+    language_name: "English",
+
+    tool_select: "Select",
+    tool_line: "Line",
+    tool_circle: "Circle",
+    tool_circle_three_points: "Circle (3 points)",
+    tool_rect: "Rect",
+    tool_text: "Text",
+    add_line: "Add Line",
+    add_circle: "Add Circle",
+    add_circle_three_points: "Add Circle (3 points)",
+    add_rect: "Add Rect",
+    add_text: "Add Text",
+    select_hint: "Select and move (V)",
+    tool_hint: "{} ({}), double-click to keep it selected",
+    font_size: "Font size",
+    delete: "Delete",
+    delete_hint: "Delete the selection (Delete, Backspace)",
+    undo: "Undo",
+    stroke_align: "Stroke",
+    rotation_pivot: "Rotate about",
+    pivot_center: "Center",
+    pivot_start: "First corner",
+    align_center: "Centered",
+    align_inside: "Inside",
+    align_outside: "Outside",
+    stroke_style: "Line style",
+    style_solid: "Solid",
+    style_dashed: "Dashed",
+    style_dotted: "Dotted",
+    dash_lengths: "Dashes",
+    line_cap: "Ends",
+    cap_butt: "Flat",
+    cap_round: "Round",
+    cap_square: "Square",
+    line_join: "Corners",
+    join_miter: "Sharp",
+    join_round: "Round",
+    join_bevel: "Beveled",
+    round_coordinates: "Round coordinates",
+    round_coordinates_hint: "Round every coordinate to {} decimals",
+    fit: "Fit",
+    new_drawing: "New drawing",
+    document_settings: "Document settings",
+    restore: "Restore",
+    open: "Open",
+    open_hint: "Open a saved drawing or an SVG document",
+    export_svg: "Export SVG",
+    download_png: "Download PNG",
+    copy_svg: "Copy SVG",
+    export_failed: "Export failed",
+    copied: "Copied",
+    copy_failed: "Copy failed",
+    keep_tool_selected: "Keep tool selected",
+    grid: "Grid",
+    grid_spacing: "Grid spacing",
+    export_grid: "Include grid in export",
+    keep_on_canvas: "Keep shapes on the canvas",
+    snap: "Snap",
+    snap_endpoint: "Endpoint",
+    snap_midpoint: "Midpoint",
+    snap_center: "Center",
+    snap_intersection: "Intersection",
+    snap_guide: "Guides",
+    ruler_hint: "Drag a guide out of the ruler, and back onto it to remove it",
+    snapped_to: "Snap: {}",
+    ortho: "Ortho",
+    ortho_hint: "Draw lines and rectangles at fixed angles, hold Shift to switch for one shape",
+    ortho_step: "Ortho angle",
+    numeric_entry_hint: "x,y for a point, @dx,dy for an offset, @length<angle, or a length",
+    not_finite: "The shape is too far away to draw",
+    non_positive_radius: "A circle or an arc needs a radius above 0, not {}",
+    zero_length_line: "A line can't end where it starts",
+    zero_area_rect: "A rectangle needs a width and a height",
+    zero_sweep_arc: "An arc can't end where it starts",
+    collinear_points: "A circle can't run through three points on one line",
+    saved: "Saved",
+    saving: "Saving\u{2026}",
+    unsaved: "Unsaved changes",
+    save_hint: "Save now (Ctrl+S)",
+
+    replace_drawing: "Replace the current drawing? Cancel adds the file to it instead.",
+    open_failed: "The file couldn't be opened: {}",
+    not_a_drawing: "{} isn't a drawing or an SVG document",
+    unreadable: "{} couldn't be read: {}",
+    dismiss: "Dismiss",
+
+    width_in: "Width ({})",
+    height_in: "Height ({})",
+    when_resizing: "When resizing",
+    keep_positions: "Keep positions",
+    keep_centered: "Keep centered",
+    scale_drawing: "Scale drawing",
+    background: "Background",
+    grid_spacing_px: "Grid spacing (px)",
+    no_grid: "no grid",
+    svg_precision: "Decimals in exported SVG",
+    full_precision: "all",
+    unit: "Unit",
+    create: "Create",
+    cancel: "Cancel",
+    close: "Close",
+
+    point_summary: "Point, {}, {}",
+    line_summary: "Line, len {}",
+    circle_summary: "Circle, r {}",
+    rect_summary: "Rect, {} \u{d7} {}",
+    arc_summary: "Arc, r {}, {}\u{b0}",
+    text_summary: "Text, {} chars",
+    shapes: "{} shapes",
+    shapes_selected: "{} shapes selected",
+
+    start: "Start",
+    end: "End",
+    center: "Center",
+    radius: "Radius",
+    start_angle: "Start angle (\u{b0})",
+    end_angle: "End angle (\u{b0})",
+    corner: "Corner",
+    width: "Width",
+    height: "Height",
+    rotation: "Rotation (\u{b0})",
+    content: "Content",
+    position: "Position",
+    apply: "Apply",
+    not_a_number: "expected a number",
+    non_positive_length: "expected a number greater than zero",
+    invalid_angle: "expected a number of degrees",
+    invalid_dashes: "expected lengths that aren't negative, apart by spaces",
+    props_header: "#{} \u{b7} order {}",
+    shape_missing: "This shape no longer exists",
+    shape_locked: "This shape is locked",
+    wrong_kind: "These properties are for another kind of shape",
+
+    shapes_panel: "Shapes",
+    filter_shapes: "Filter shapes",
+    point: "Point",
+    arc: "Arc",
+    rename_hint: "Double-click to rename",
+    hide: "Hide",
+    show: "Show",
+    length_detail: "len {}",
+    radius_detail: "r {}",
+    size_detail: "{} \u{d7} {}",
+    chars_detail: "{} chars",
+    position_detail: "{}, {}",
+
+    duplicate: "Duplicate",
+    copy: "Copy",
+    paste: "Paste",
+    bring_to_front: "Bring to Front",
+    send_to_back: "Send to Back",
+    lock: "Lock",
+    unlock: "Unlock",
+    properties: "Properties",
+
+    print: "Print",
+    page_size: "Page size",
+    portrait: "Portrait",
+    landscape: "Landscape",
+    fit_to_page: "Fit to page",
+    print_scale: "Scale (%)",
+    crop_to_drawing: "Crop to the drawing",
+    title_block: "Title block",
+    untitled: "Untitled",
+    print_failed: "Printing failed",
+
+    stamps: "Stamps",
+    tool_stamp: "Stamp",
+    save_as_stamp: "Save as stamp",
+    stamp_name: "Stamp name",
+    default_stamp_name: "Stamp {}",
+    stamp_hint: "Click to place it on the canvas, double-click to rename",
+};
+
+pub static ARABIC: Strings = Strings {
+    title: "الرسام - نموذج اولي",
+    language_name: "العربية",
+
+    tool_select: "تحديد",
+    tool_line: "خط",
+    tool_circle: "دائرة",
+    tool_circle_three_points: "دائرة (3 نقاط)",
+    tool_rect: "مستطيل",
+    tool_text: "نص",
+    add_line: "إضافة خط",
+    add_circle: "إضافة دائرة",
+    add_circle_three_points: "إضافة دائرة (3 نقاط)",
+    add_rect: "إضافة مستطيل",
+    add_text: "إضافة نص",
+    select_hint: "تحديد وتحريك (V)",
+    tool_hint: "{} ({})، انقر مرتين لإبقائها محددة",
+    font_size: "حجم الخط",
+    delete: "حذف",
+    delete_hint: "حذف المحدد (Delete، Backspace)",
+    undo: "تراجع",
+    stroke_align: "الحد",
+    rotation_pivot: "التدوير حول",
+    pivot_center: "المركز",
+    pivot_start: "الركن الأول",
+    align_center: "في المنتصف",
+    align_inside: "للداخل",
+    align_outside: "للخارج",
+    stroke_style: "نمط الخط",
+    style_solid: "متصل",
+    style_dashed: "متقطع",
+    style_dotted: "منقط",
+    dash_lengths: "الشرطات",
+    line_cap: "الأطراف",
+    cap_butt: "مسطحة",
+    cap_round: "مستديرة",
+    cap_square: "مربعة",
+    line_join: "الزوايا",
+    join_miter: "حادة",
+    join_round: "مستديرة",
+    join_bevel: "مشطوفة",
+    round_coordinates: "تقريب الإحداثيات",
+    round_coordinates_hint: "تقريب كل الإحداثيات إلى {} منازل عشرية",
+    fit: "ملاءمة",
+    new_drawing: "رسم جديد",
+    document_settings: "إعدادات المستند",
+    restore: "استعادة",
+    open: "فتح",
+    open_hint: "فتح رسم محفوظ أو مستند SVG",
+    export_svg: "تصدير SVG",
+    download_png: "تنزيل PNG",
+    copy_svg: "نسخ SVG",
+    export_failed: "فشل التصدير",
+    copied: "تم النسخ",
+    copy_failed: "فشل النسخ",
+    keep_tool_selected: "إبقاء الأداة محددة",
+    grid: "الشبكة",
+    grid_spacing: "تباعد الشبكة",
+    export_grid: "تضمين الشبكة في التصدير",
+    keep_on_canvas: "إبقاء الأشكال داخل اللوحة",
+    snap: "الالتقاط",
+    snap_endpoint: "نقطة طرفية",
+    snap_midpoint: "نقطة المنتصف",
+    snap_center: "المركز",
+    snap_intersection: "التقاطع",
+    snap_guide: "الأدلة",
+    ruler_hint: "اسحب دليلًا من المسطرة، وأعده إليها لإزالته",
+    snapped_to: "التقاط: {}",
+    ortho: "تعامد",
+    ortho_hint: "ارسم الخطوط والمستطيلات بزوايا ثابتة، واضغط Shift للتبديل لشكل واحد",
+    ortho_step: "زاوية التعامد",
+    numeric_entry_hint: "x,y لنقطة، \u{200e}@dx,dy\u{200e} لإزاحة، \u{200e}@الطول<الزاوية\u{200e}، أو طول",
+    not_finite: "الشكل أبعد من أن يُرسم",
+    non_positive_radius: "تحتاج الدائرة أو القوس إلى نصف قطر أكبر من 0، وليس {}",
+    zero_length_line: "لا يمكن أن ينتهي الخط حيث يبدأ",
+    zero_area_rect: "يحتاج المستطيل إلى عرض وارتفاع",
+    zero_sweep_arc: "لا يمكن أن ينتهي القوس حيث يبدأ",
+    collinear_points: "لا يمكن أن تمر دائرة بثلاث نقاط على خط واحد",
+    saved: "تم الحفظ",
+    saving: "جارٍ الحفظ\u{2026}",
+    unsaved: "تغييرات غير محفوظة",
+    save_hint: "احفظ الآن (Ctrl+S)",
+
+    replace_drawing: "استبدال الرسم الحالي؟ الإلغاء يضيف الملف إليه بدلًا من ذلك.",
+    open_failed: "تعذر فتح الملف: {}",
+    not_a_drawing: "{} ليس رسمًا ولا مستند SVG",
+    unreadable: "تعذرت قراءة {}: {}",
+    dismiss: "إغلاق",
+
+    width_in: "العرض ({})",
+    height_in: "الارتفاع ({})",
+    when_resizing: "عند تغيير الحجم",
+    keep_positions: "إبقاء المواضع",
+    keep_centered: "إبقاء التوسيط",
+    scale_drawing: "تحجيم الرسم",
+    background: "الخلفية",
+    grid_spacing_px: "تباعد الشبكة (px)",
+    no_grid: "بلا شبكة",
+    svg_precision: "المنازل العشرية في SVG المُصدَّر",
+    full_precision: "الكل",
+    unit: "الوحدة",
+    create: "إنشاء",
+    cancel: "إلغاء",
+    close: "إغلاق",
+
+    point_summary: "نقطة، {}، {}",
+    line_summary: "خط، الطول {}",
+    circle_summary: "دائرة، نصف القطر {}",
+    rect_summary: "مستطيل، {} \u{d7} {}",
+    arc_summary: "قوس، نصف القطر {}، {}\u{b0}",
+    text_summary: "نص، {} حرف",
+    shapes: "{} أشكال",
+    shapes_selected: "{} أشكال محددة",
+
+    start: "البداية",
+    end: "النهاية",
+    center: "المركز",
+    radius: "نصف القطر",
+    start_angle: "زاوية البداية (\u{b0})",
+    end_angle: "زاوية النهاية (\u{b0})",
+    corner: "الركن",
+    width: "العرض",
+    height: "الارتفاع",
+    rotation: "الدوران (\u{b0})",
+    content: "المحتوى",
+    position: "الموضع",
+    apply: "تطبيق",
+    not_a_number: "يجب أن يكون رقمًا",
+    non_positive_length: "يجب أن يكون رقمًا أكبر من الصفر",
+    invalid_angle: "يجب أن يكون عددًا من الدرجات",
+    invalid_dashes: "يجب أن تكون أطوالًا غير سالبة تفصل بينها مسافات",
+    props_header: "#{} \u{b7} الترتيب {}",
+    shape_missing: "لم يعد هذا الشكل موجودًا",
+    shape_locked: "هذا الشكل مقفل",
+    wrong_kind: "هذه الخصائص لنوع آخر من الأشكال",
+
+    shapes_panel: "الأشكال",
+    filter_shapes: "تصفية الأشكال",
+    point: "نقطة",
+    arc: "قوس",
+    rename_hint: "انقر مرتين لإعادة التسمية",
+    hide: "إخفاء",
+    show: "إظهار",
+    length_detail: "الطول {}",
+    radius_detail: "نصف القطر {}",
+    size_detail: "{} \u{d7} {}",
+    chars_detail: "{} حرف",
+    position_detail: "{}، {}",
+
+    duplicate: "تكرار",
+    copy: "نسخ",
+    paste: "لصق",
+    bring_to_front: "إحضار إلى الأمام",
+    send_to_back: "إرسال إلى الخلف",
+    lock: "قفل",
+    unlock: "إلغاء القفل",
+    properties: "الخصائص",
+
+    print: "طباعة",
+    page_size: "حجم الصفحة",
+    portrait: "عمودي",
+    landscape: "أفقي",
+    fit_to_page: "ملاءمة الصفحة",
+    print_scale: "المقياس (%)",
+    crop_to_drawing: "القص على حدود الرسم",
+    title_block: "كتلة العنوان",
+    untitled: "بلا عنوان",
+    print_failed: "فشلت الطباعة",
+
+    stamps: "الأختام",
+    tool_stamp: "ختم",
+    save_as_stamp: "حفظ كختم",
+    stamp_name: "اسم الختم",
+    default_stamp_name: "ختم {}",
+    stamp_hint: "انقر لوضعه على اللوحة، وانقر مرتين لإعادة التسمية",
+};
+
+/// replaces each `{}` in `template` with the next of `values`.
+///
+/// # Examples
+/// ```
+/// use web::{fill, ARABIC, ENGLISH};
+///
+/// assert_eq!("Rect, 3.0 \u{d7} 4.5", fill(ENGLISH.rect_summary, &[&"3.0", &4.5]));
+///
+/// // every text is in both tables, with as many blanks to fill in
+/// let blanks = |text: &str| text.matches("{}").count();
+/// for ((key, english), (_, arabic)) in ENGLISH.entries().into_iter().zip(ARABIC.entries()) {
+///     assert!(!english.is_empty() && !arabic.is_empty(), "{} is empty", key);
+///     assert_eq!(blanks(english), blanks(arabic), "{} has different blanks", key);
+/// }
+///
+/// ```
+pub fn fill(template: &str, values: &[&dyn fmt::Display]) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut values = values.iter();
+    let mut parts = template.split("{}");
+
+    filled.push_str(parts.next().unwrap_or_default());
+    for part in parts {
+        if let Some(value) = values.next() {
+            filled.push_str(&value.to_string());
+        }
+        filled.push_str(part);
+    }
+
+    filled
+}
+
+/// a language the interface can be shown in.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum Language {
+    #[default]
+    Arabic,
+    English,
+}
+
+impl Language {
+    pub fn strings(&self) -> &'static Strings {
+        match self {
+            Language::Arabic => &ARABIC,
+            Language::English => &ENGLISH,
+        }
+    }
+
+    /// the language tag, for the `lang` attribute and for storing it.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::Arabic => "ar",
+            Language::English => "en",
+        }
+    }
+
+    /// the direction the language is written in, for the `dir` attribute.
+    pub fn dir(&self) -> &'static str {
+        match self {
+            Language::Arabic => "rtl",
+            Language::English => "ltr",
+        }
+    }
+
+    /// the language the toggle switches to.
+    pub fn other(&self) -> Language {
+        match self {
+            Language::Arabic => Language::English,
+            Language::English => Language::Arabic,
+        }
+    }
+
+    /// # Examples
+    /// ```
+    /// use web::Language;
+    ///
+    /// assert_eq!(Some(Language::English), Language::from_code("en"));
+    /// assert_eq!(None, Language::from_code("fr"));
+    ///
+    /// ```
+    pub fn from_code(code: &str) -> Option<Language> {
+        [Language::Arabic, Language::English].into_iter().find(|language| language.code() == code)
+    }
+}
+
+const LANGUAGE_KEY: &str = "alrassam.language";
+
+/// the language picked last time, or the default one.
+pub fn load_language() -> Language {
+    local_storage()
+        .and_then(|storage| storage.get_item(LANGUAGE_KEY).ok().flatten())
+        .and_then(|code| Language::from_code(&code))
+        .unwrap_or_default()
+}
+
+pub fn save_language(language: Language) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(LANGUAGE_KEY, language.code());
+    }
+}
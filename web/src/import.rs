@@ -0,0 +1,108 @@
+use yew::prelude::*;
+use super::AppState;
+use super::i18n::fill;
+
+/// the kinds of files that can be opened.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ImportFormat {
+    /// a drawing saved as JSON.
+    Json,
+    /// an SVG document, like an exported drawing.
+    Svg,
+}
+
+/// the format of a file, told by its name or failing that by its MIME type.
+///
+/// # Examples
+/// ```
+/// use web::{import_format, ImportFormat};
+///
+/// assert_eq!(Some(ImportFormat::Json), import_format("drawing.JSON", ""));
+/// assert_eq!(Some(ImportFormat::Svg), import_format("floor-plan.svg", "image/svg+xml"));
+/// assert_eq!(Some(ImportFormat::Svg), import_format("download", "image/svg+xml"));
+/// assert_eq!(None, import_format("photo.png", "image/png"));
+///
+/// ```
+pub fn import_format(name: &str, mime_type: &str) -> Option<ImportFormat> {
+    let extension = name.rsplit_once('.').map(|(_, extension)| extension.to_ascii_lowercase());
+
+    match (extension.as_deref(), mime_type) {
+        (Some("json"), _) | (_, "application/json") => Some(ImportFormat::Json),
+        (Some("svg"), _) | (_, "image/svg+xml") => Some(ImportFormat::Svg),
+        _ => None,
+    }
+}
+
+/// the file types offered by the file picker.
+pub const ACCEPTED_FILES: &str = ".json,.svg,application/json,image/svg+xml";
+
+/// reads `file` without blocking the page and opens it. a JSON drawing
+/// replaces the current one or is added to it, as the user picks.
+/// an SVG document is always added to the current drawing.
+pub fn open_file(file: web_sys::File, appstate: UseStateHandle<AppState>) {
+    let file = gloo_file::File::from(file);
+    let format = match import_format(&file.name(), &file.raw_mime_type()) {
+        Some(format) => format,
+        None => {
+            let mut new_state = appstate.edited();
+            new_state.set_import_error(Some(fill(new_state.strings().not_a_drawing, &[&file.name()])));
+            return appstate.set(new_state);
+        }
+    };
+
+    wasm_bindgen_futures::spawn_local(async move {
+        let contents = match gloo_file::futures::read_as_text(&file).await {
+            Ok(contents) => contents,
+            Err(error) => {
+                let mut new_state = appstate.edited();
+                new_state.set_import_error(Some(fill(new_state.strings().unreadable, &[&file.name(), &error])));
+                return appstate.set(new_state);
+            }
+        };
+
+        let mut new_state = appstate.edited();
+        match format {
+            ImportFormat::Json => {
+                let replace = gloo_utils::window()
+                    .confirm_with_message(new_state.strings().replace_drawing)
+                    .unwrap_or(false);
+                new_state.import_json(&contents, replace);
+            }
+            ImportFormat::Svg => {
+                new_state.import_svg(&contents);
+            }
+        }
+
+        appstate.set(new_state);
+    });
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ImportErrorBannerProps {
+    pub appstate: UseStateHandle<AppState>,
+}
+
+/// tells why a file couldn't be opened, until it's dismissed.
+#[function_component(ImportErrorBanner)]
+pub fn import_error_banner(props: &ImportErrorBannerProps) -> Html {
+    let error = match props.appstate.import_error() {
+        Some(error) => error.to_string(),
+        None => return html! {},
+    };
+
+    let strings = props.appstate.strings();
+    let appstate = props.appstate.clone();
+    let dismiss_onclick = Callback::from(move |_| {
+        let mut new_state = appstate.edited();
+        new_state.set_import_error(None);
+
+        appstate.set(new_state);
+    });
+
+    html! {
+        <div role="alert" style="display: flex; gap: 1em; align-items: center; padding: 0.5em; background: #fde8e8; border: 1px solid #e0a0a0;">
+            <span>{ fill(strings.open_failed, &[&error]) }</span>
+            <button onclick={dismiss_onclick} title={strings.dismiss}>{ "\u{d7}" }</button>
+        </div>
+    }
+}
@@ -1,7 +1,42 @@
+// yew 0.19's html! macro expands props into statements clippy flags as no-ops
+#![allow(clippy::unnecessary_operation)]
+
 mod panel;
 mod canvas;
 mod app_state;
+mod autosave;
+mod context_menu;
+mod drawing;
+mod export;
+mod i18n;
+mod import;
+mod numeric_entry;
+mod outliner;
+mod properties;
+mod pointer;
+mod print;
+mod settings;
+mod shortcuts;
+mod stamps;
+mod status_bar;
+mod text_editor;
 
 pub use panel::Panel;
 pub use canvas::Canvas;
-pub use app_state::AppState;
+pub use drawing::{Drawing, FULL_REDRAW_THRESHOLD};
+pub use pointer::{client_to_screen, client_to_world, event_to_screen, event_to_world, event_to_world_from, screen_to_client};
+pub use shortcuts::{shortcut_for, Shortcut};
+pub use status_bar::{selection_summary, StatusBar};
+pub use text_editor::TextEditor;
+pub use numeric_entry::{parse_tool_input, NumericEntry};
+pub use context_menu::{menu_actions, menu_position, ContextMenu, MenuAction, OpenMenu};
+pub use autosave::{clear_saved_canvas, load_canvas, save_canvas, storage_key, SaveStatus};
+pub use export::{copy_svg, download_png, download_svg, file_name};
+pub use print::{print, print_document, print_export_options, PageSize, PrintDialog, PrintOptions, PrintScale, PAGE_MARGIN_MM, TITLE_BLOCK_MM};
+pub use i18n::{fill, load_language, save_language, Language, Strings, ARABIC, ENGLISH};
+pub use outliner::{filter_rows, outline_rows, OutlineRow, Outliner};
+pub use stamps::{load_stamps, save_stamps, stamps_from_json, stamps_to_json, Stamp, StampsPanel};
+pub use import::{import_format, open_file, ImportErrorBanner, ImportFormat, ACCEPTED_FILES};
+pub use properties::{field_values, invalid_field, parse_props, FieldErrors, FieldValues, PropertiesPanel};
+pub use app_state::{AppState, Modifiers, TextEdit, Tool, ToolInput, ToolProgress, DEFAULT_CANVAS_SIZE};
+pub use settings::{parse_size, DocumentSettings, SettingsDialog, SettingsMode};
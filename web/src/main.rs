@@ -1,30 +1,35 @@
-use std::str;
+// yew 0.19's html! macro expands props into statements clippy flags as no-ops
+#![allow(clippy::unnecessary_operation)]
+
 use yew::prelude::*;
-use web::{Panel, Canvas, AppState};
-use wasm_logger;
+use web::{Panel, Canvas, AppState, ImportErrorBanner, Outliner, PropertiesPanel, StampsPanel, StatusBar, load_canvas, load_language};
 
 #[function_component(App)]
 fn app() -> Html {
-    let appstate = use_state(|| AppState::new());
+    let appstate = use_state(|| {
+        let mut appstate = load_canvas().map(AppState::with_canvas).unwrap_or_default();
+        appstate.set_language(load_language());
+
+        appstate
+    });
+    let language = appstate.language();
 
     html! {
-        <>
-            <div dir="rtl">
-            <h1>
-                {
-                    str::from_utf8(&[
-                        0xd8, 0xa7, 0xd9, 0x84, 0xd8, 0xb1, 0xd8, 0xb3, 0xd8, 0xa7, 0xd9, 0x85, 0x20, 0x2d, 0x20, 0xd9, 0x86, 0xd9, 0x85, 0xd9, 0x88, 0xd8, 0xb0, 0xd8, 0xac, 0x20, 0xd8, 0xa7, 0xd9, 0x88, 0xd9, 0x84, 0xd9, 0x8a
-                    ]).unwrap()
-                }
-            </h1>
-            </div>
+        <div dir={language.dir()} lang={language.code()}>
+            <h1>{ appstate.strings().title }</h1>
 
             <br />
 
             <Panel appstate={appstate.clone()} />
+            <ImportErrorBanner appstate={appstate.clone()} />
             <hr />
             <Canvas appstate={appstate.clone()} />
-        </>
+            <StatusBar appstate={appstate.clone()} />
+            <hr />
+            <Outliner appstate={appstate.clone()} />
+            <StampsPanel appstate={appstate.clone()} />
+            <PropertiesPanel appstate={appstate.clone()} />
+        </div>
     }
 }
 
@@ -0,0 +1,162 @@
+use yew::prelude::*;
+use gloo_events::EventListener;
+use program_core::Point;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use super::app_state::{AppState, ToolInput};
+
+/// reads what's typed in the numeric entry, angles being in degrees:
+/// `@100<30` is 100 long at 30°, `@40,25` an offset from the first point,
+/// `50,25` a point, `100` a length alone and `<30` an angle alone.
+///
+/// # Examples
+/// ```
+/// use program_core::Point;
+/// use web::{parse_tool_input, ToolInput};
+///
+/// assert_eq!(Some(vec![ToolInput::Length(100.0), ToolInput::Angle(30f64.to_radians())]), parse_tool_input("@100<30"));
+/// assert_eq!(Some(vec![ToolInput::Relative(Point::new(40.0, -25.0))]), parse_tool_input(" @40, -25 "));
+/// assert_eq!(Some(vec![ToolInput::Point(Point::new(50.0, 25.0))]), parse_tool_input("50,25"));
+/// assert_eq!(Some(vec![ToolInput::Length(12.5)]), parse_tool_input("12.5"));
+/// assert_eq!(Some(vec![ToolInput::Angle(-45f64.to_radians())]), parse_tool_input("<-45"));
+///
+/// assert_eq!(None, parse_tool_input("@"));
+/// assert_eq!(None, parse_tool_input("@100<"));
+/// assert_eq!(None, parse_tool_input("1,2,3"));
+/// assert_eq!(None, parse_tool_input("inf"));
+///
+/// ```
+pub fn parse_tool_input(entry: &str) -> Option<Vec<ToolInput>> {
+    let number = |text: &str| text.trim().parse::<f64>().ok().filter(|value| value.is_finite());
+    let entry = entry.trim();
+
+    if let Some(angle) = entry.strip_prefix('<') {
+        return Some(vec![ToolInput::Angle(number(angle)?.to_radians())]);
+    }
+
+    match entry.strip_prefix('@') {
+        Some(relative) => match relative.split_once('<') {
+            Some((length, angle)) => Some(vec![ToolInput::Length(number(length)?), ToolInput::Angle(number(angle)?.to_radians())]),
+            None => Some(vec![ToolInput::Relative(relative.parse::<Point>().ok()?)]),
+        },
+        None => match number(entry) {
+            Some(length) => Some(vec![ToolInput::Length(length)]),
+            None => Some(vec![ToolInput::Point(entry.parse::<Point>().ok()?)]),
+        },
+    }
+}
+
+/// whether `key` starts a numeric entry.
+fn starts_entry(key: &str) -> bool {
+    let mut chars = key.chars();
+
+    matches!((chars.next(), chars.next()), (Some(c), None) if c.is_ascii_digit() || "@<.-".contains(c))
+}
+
+#[derive(PartialEq, Properties)]
+pub struct NumericEntryProps {
+    pub appstate: UseStateHandle<AppState>,
+}
+
+/// a box to type exact lengths, angles and points into, opened by starting to
+/// type while a tool is selected. Enter feeds the entry to the tool and Escape closes it.
+#[function_component(NumericEntry)]
+pub fn numeric_entry(props: &NumericEntryProps) -> Html {
+    let entry = use_state(|| None::<String>);
+    let invalid = use_state(|| false);
+    let input = use_node_ref();
+
+    let armed = props.appstate.tool().is_some() && props.appstate.text_edit().is_none();
+
+    // listens on the document so the canvas doesn't need focus,
+    // only while the entry is closed so typing in it isn't caught
+    use_effect_with_deps(
+        {
+            let entry = entry.clone();
+            move |(armed, open): &(bool, bool)| {
+                let listener = (*armed && !*open).then(|| {
+                    EventListener::new(&gloo_utils::document(), "keydown", move |evt| {
+                        let evt = evt.dyn_ref::<KeyboardEvent>().expect("keydown is a keyboard event");
+                        let typing = gloo_utils::document()
+                            .active_element()
+                            .is_some_and(|element| matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA"));
+
+                        if typing || evt.ctrl_key() || evt.meta_key() || evt.alt_key() || !starts_entry(&evt.key()) {
+                            return;
+                        }
+
+                        evt.prevent_default();
+                        entry.set(Some(evt.key()));
+                    })
+                });
+
+                move || drop(listener)
+            }
+        },
+        (armed, entry.is_some()),
+    );
+
+    use_effect_with_deps(
+        |(input, _): &(NodeRef, bool)| {
+            if let Some(input) = input.cast::<HtmlInputElement>() {
+                let _ = input.focus();
+            }
+
+            || ()
+        },
+        (input.clone(), entry.is_some()),
+    );
+
+    let value = match (&*entry, armed) {
+        (Some(value), true) => value.clone(),
+        _ => return html! {},
+    };
+    let strings = props.appstate.strings();
+
+    let oninput = {
+        let (entry, invalid) = (entry.clone(), invalid.clone());
+        Callback::from(move |evt: InputEvent| {
+            entry.set(Some(evt.target_unchecked_into::<HtmlInputElement>().value()));
+            invalid.set(false);
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let onkeydown = {
+        let (entry, invalid) = (entry.clone(), invalid.clone());
+        Callback::from(move |evt: KeyboardEvent| {
+            match evt.key().as_str() {
+                "Enter" => match parse_tool_input(&evt.target_unchecked_into::<HtmlInputElement>().value()) {
+                    Some(inputs) => {
+                        let mut new_state = appstate.edited();
+                        for input in inputs {
+                            new_state.tool_numeric_input(input);
+                        }
+                        appstate.set(new_state);
+                        entry.set(None);
+                    }
+                    None => invalid.set(true),
+                },
+                "Escape" => entry.set(None),
+                _ => return,
+            }
+
+            evt.prevent_default();
+        })
+    };
+
+    let border = if *invalid { "red" } else { "rgb(0, 120, 215)" };
+
+    html! {
+        <input
+            ref={input}
+            type="text"
+            value={value}
+            title={strings.numeric_entry_hint}
+            aria-invalid={invalid.to_string()}
+            style={format!("position: absolute; left: 8px; bottom: 8px; font-family: monospace; border: 1px solid {};", border)}
+            oninput={oninput}
+            onkeydown={onkeydown}
+        />
+    }
+}
@@ -0,0 +1,267 @@
+use yew::prelude::*;
+use program_core::{Canvas, Drawable, DrawableId, Props, Unit};
+use std::rc::Rc;
+use web_sys::HtmlInputElement;
+use super::AppState;
+use super::i18n::{fill, Language, Strings};
+
+/// a drawable as listed in the outliner.
+#[derive(Clone, PartialEq, Debug)]
+pub struct OutlineRow {
+    pub id: DrawableId,
+    /// a symbol for the kind of drawable.
+    pub icon: &'static str,
+    /// the name of the drawable, or its kind and number, like "Line 3".
+    pub label: String,
+    pub named: bool,
+    /// its key dimension, in the unit of the drawing.
+    pub detail: String,
+    pub hidden: bool,
+    pub locked: bool,
+}
+
+/// the rows of the outliner, topmost drawable first. drawables without
+/// a name are numbered by kind from the bottom of the drawing up.
+///
+/// # Examples
+/// ```
+/// use program_core::{Canvas, Point, Unit};
+/// use web::{filter_rows, outline_rows, ENGLISH};
+///
+/// let mut canvas = Canvas::new(200.0, 200.0);
+/// canvas.add_line(&Point::new(0.0, 0.0), &Point::new(30.0, 40.0)).unwrap();
+/// let wheel = canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+/// canvas.add_line(&Point::new(0.0, 0.0), &Point::new(10.0, 0.0)).unwrap();
+/// canvas.set_name(wheel, Some(String::from("Front wheel")));
+/// canvas.set_hidden(wheel, true);
+///
+/// let rows = outline_rows(&canvas, &ENGLISH);
+/// let labels: Vec<&str> = rows.iter().map(|row| row.label.as_str()).collect();
+/// assert_eq!(vec!["Line 2", "Front wheel", "Line 1"], labels);
+/// assert_eq!("len 50.0", rows[2].detail);
+/// assert!(rows[1].named && rows[1].hidden);
+///
+/// canvas.set_unit(Unit::Cm);
+/// assert_eq!(format!("r {:.1}", 10.0 / Unit::Cm.px_per_unit()), outline_rows(&canvas, &ENGLISH)[1].detail);
+///
+/// assert_eq!(2, filter_rows(&rows, " line ").len());
+/// assert_eq!(vec![wheel], filter_rows(&rows, "WHEEL").iter().map(|row| row.id).collect::<Vec<_>>());
+/// assert_eq!(3, filter_rows(&rows, "").len());
+///
+/// ```
+pub fn outline_rows(canvas: &Canvas, strings: &Strings) -> Vec<OutlineRow> {
+    let unit = canvas.unit();
+    let mut counts = [0usize; 6];
+
+    let mut rows: Vec<OutlineRow> = canvas
+        .drawables()
+        .map(|(id, drawable)| {
+            let (kind, icon, name) = kind_of(drawable, strings);
+            counts[kind] += 1;
+            let label = match canvas.name(id) {
+                Some(name) => name.to_string(),
+                None => format!("{} {}", name, counts[kind]),
+            };
+
+            OutlineRow {
+                id,
+                icon,
+                label,
+                named: canvas.name(id).is_some(),
+                detail: detail(drawable, unit, strings),
+                hidden: canvas.is_hidden(id),
+                locked: canvas.is_locked(id),
+            }
+        })
+        .collect();
+    rows.reverse();
+
+    rows
+}
+
+/// the rows whose label contains `filter`, ignoring case.
+pub fn filter_rows<'a>(rows: &'a [OutlineRow], filter: &str) -> Vec<&'a OutlineRow> {
+    let filter = filter.trim().to_lowercase();
+
+    rows.iter().filter(|row| row.label.to_lowercase().contains(&filter)).collect()
+}
+
+/// the index used to number the drawables of each kind, its icon and name.
+fn kind_of(drawable: &Drawable, strings: &Strings) -> (usize, &'static str, &'static str) {
+    match drawable {
+        Drawable::Point(_) => (0, "\u{2022}", strings.point),
+        Drawable::Line(_) => (1, "\u{2571}", strings.tool_line),
+        Drawable::Circle(_) => (2, "\u{25cb}", strings.tool_circle),
+        Drawable::Rect(_) => (3, "\u{25ad}", strings.tool_rect),
+        Drawable::Text(_) => (4, "T", strings.tool_text),
+        Drawable::Arc(_) => (5, "\u{25e0}", strings.arc),
+    }
+}
+
+fn detail(drawable: &Drawable, unit: Unit, strings: &Strings) -> String {
+    let length = |length: f64| format!("{:.1}", length);
+
+    match Props::from(drawable).scaled(1f64 / unit.px_per_unit()) {
+        Props::Point(point) => fill(strings.position_detail, &[&length(point.pos.x), &length(point.pos.y)]),
+        Props::Line(line) => {
+            let len = ((line.end.x - line.start.x).powi(2) + (line.end.y - line.start.y).powi(2)).sqrt();
+            fill(strings.length_detail, &[&length(len)])
+        }
+        Props::Circle(circle) => fill(strings.radius_detail, &[&length(circle.radius)]),
+        Props::Rect(rect) => fill(strings.size_detail, &[&length(rect.width), &length(rect.height)]),
+        Props::Text(text) => fill(strings.chars_detail, &[&text.content.chars().count()]),
+        Props::Arc(arc) => fill(strings.radius_detail, &[&length(arc.radius)]),
+    }
+}
+
+/// what the cached rows were built from.
+type RowsKey = (u64, Unit, Language);
+
+#[derive(PartialEq, Properties)]
+pub struct OutlinerProps {
+    pub appstate: UseStateHandle<AppState>,
+}
+
+/// lists every drawable, to find, select, rename, hide, lock or delete it.
+/// the rows are only rebuilt when the canvas reports a change to its drawables.
+#[function_component(Outliner)]
+pub fn outliner(props: &OutlinerProps) -> Html {
+    let open = use_state(|| true);
+    let filter = use_state(String::new);
+    let renaming = use_state(|| None::<DrawableId>);
+    let cache = use_mut_ref(|| (None::<RowsKey>, Rc::new(Vec::<OutlineRow>::new())));
+
+    let appstate = &props.appstate;
+    let canvas = appstate.canvas();
+    let strings = appstate.strings();
+
+    let key = (canvas.revision(), canvas.unit(), appstate.language());
+    let rows = {
+        let mut cache = cache.borrow_mut();
+        if cache.0 != Some(key) {
+            *cache = (Some(key), Rc::new(outline_rows(canvas, strings)));
+        }
+        cache.1.clone()
+    };
+
+    let toggle_onclick = {
+        let open = open.clone();
+        Callback::from(move |_| open.set(!*open))
+    };
+
+    let filter_oninput = {
+        let filter = filter.clone();
+        Callback::from(move |evt: InputEvent| filter.set(evt.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+
+    // every change to a row goes through the app state, a new state only when it changed
+    let change = |id: DrawableId, change: fn(&mut AppState, DrawableId) -> bool| {
+        let appstate = props.appstate.clone();
+
+        Callback::from(move |evt: MouseEvent| {
+            evt.stop_propagation();
+            let mut new_state = appstate.edited();
+            if change(&mut new_state, id) {
+                appstate.set(new_state);
+            }
+        })
+    };
+
+    let row_html = |row: &OutlineRow| {
+        let id = row.id;
+
+        let onclick = {
+            let appstate = props.appstate.clone();
+            Callback::from(move |evt: MouseEvent| {
+                let mut new_state = appstate.edited();
+                new_state.select(id, evt.shift_key());
+                appstate.set(new_state);
+            })
+        };
+        let ondblclick = {
+            let renaming = renaming.clone();
+            Callback::from(move |_| renaming.set(Some(id)))
+        };
+
+        let label = if *renaming == Some(id) {
+            let onchange = {
+                let (appstate, renaming) = (props.appstate.clone(), renaming.clone());
+                Callback::from(move |evt: Event| {
+                    let name = evt.target_unchecked_into::<HtmlInputElement>().value();
+                    let mut new_state = appstate.edited();
+                    if new_state.rename(id, Some(name)) {
+                        appstate.set(new_state);
+                    }
+                    renaming.set(None);
+                })
+            };
+            let onkeydown = {
+                let renaming = renaming.clone();
+                Callback::from(move |evt: KeyboardEvent| {
+                    if evt.key() == "Escape" {
+                        renaming.set(None);
+                    }
+                })
+            };
+            // a name that was changed is saved by onchange first
+            let onblur = {
+                let renaming = renaming.clone();
+                Callback::from(move |_| renaming.set(None))
+            };
+
+            html! {
+                <input type="text" value={canvas.name(id).unwrap_or_default().to_string()} placeholder={row.label.clone()}
+                    onchange={onchange} onkeydown={onkeydown} onblur={onblur} onclick={Callback::from(|evt: MouseEvent| evt.stop_propagation())} />
+            }
+        } else {
+            html! {
+                <span title={strings.rename_hint} style={if row.named { "" } else { "font-style: italic;" }}>{ row.label.clone() }</span>
+            }
+        };
+
+        let (hidden, locked) = (row.hidden, row.locked);
+        let visibility_onclick = if hidden {
+            change(id, |state, id| state.set_hidden(id, false))
+        } else {
+            change(id, |state, id| state.set_hidden(id, true))
+        };
+        let lock_onclick = if locked {
+            change(id, |state, id| state.set_locked(id, false))
+        } else {
+            change(id, |state, id| state.set_locked(id, true))
+        };
+        let delete_onclick = change(id, AppState::delete);
+
+        let selected = canvas.is_selected(id);
+        let style = format!(
+            "display: flex; gap: 0.5em; align-items: center; padding: 0.1em 0.3em; cursor: default;{}{}",
+            if selected { " background: rgba(0, 120, 215, 0.2);" } else { "" },
+            if hidden { " opacity: 0.5;" } else { "" },
+        );
+
+        html! {
+            <li key={id.to_string()} role="option" aria-selected={selected.to_string()} style={style} onclick={onclick} ondblclick={ondblclick}>
+                <span aria-hidden="true" style="width: 1em; text-align: center;">{ row.icon }</span>
+                { label }
+                <span style="color: gray; margin-inline-start: auto;">{ row.detail.clone() }</span>
+                <button onclick={visibility_onclick} aria-pressed={hidden.to_string()}>{ if hidden { strings.show } else { strings.hide } }</button>
+                <button onclick={lock_onclick} aria-pressed={locked.to_string()}>{ if locked { strings.unlock } else { strings.lock } }</button>
+                <button onclick={delete_onclick} title={strings.delete} disabled={locked}>{ "\u{d7}" }</button>
+            </li>
+        }
+    };
+
+    html! {
+        <div id="outliner">
+            <button onclick={toggle_onclick} aria-expanded={open.to_string()}>
+                { format!("{} {} ({})", if *open { "\u{25be}" } else { "\u{25b8}" }, strings.shapes_panel, rows.len()) }
+            </button>
+            if *open {
+                <input type="search" placeholder={strings.filter_shapes} value={(*filter).clone()} oninput={filter_oninput} />
+                <ul role="listbox" aria-multiselectable="true" style="list-style: none; margin: 0; padding: 0; max-height: 20em; overflow-y: auto;">
+                    { filter_rows(&rows, &filter).into_iter().map(row_html).collect::<Html>() }
+                </ul>
+            }
+        </div>
+    }
+}
@@ -1,6 +1,21 @@
 use yew::prelude::*;
-use program_core::{Drawable, Line, Point};
-use super::app_state::{AppState, Message};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use program_core::SnapKind;
+use std::f64::consts::FRAC_PI_2;
+use super::app_state::{AppState, Tool};
+use super::autosave::load_canvas;
+use super::export::{copy_svg, download_png, download_svg};
+use super::i18n::{fill, save_language};
+use super::import::{open_file, ACCEPTED_FILES};
+use super::print::PrintDialog;
+use super::settings::{SettingsDialog, SettingsMode};
+use super::status_bar::{snap_kind_name, tool_name};
+
+/// the pixels to a canvas unit of downloaded PNG images, sharp on high density screens.
+const PNG_SCALE: f64 = 2.0;
+
+/// the decimals "Round coordinates" leaves.
+const ROUND_DECIMALS: u32 = 2;
 
 #[derive(PartialEq, Properties)]
 pub struct PanelProps {
@@ -9,26 +24,456 @@ pub struct PanelProps {
 
 #[function_component(Panel)]
 pub fn panel(props: &PanelProps) -> Html {
-    let appstate = props.appstate.clone(); 
+    let strings = props.appstate.strings();
+
+    // the tool buttons and the canvas share the tool through the app state,
+    // so a tool selected here is the one the next canvas click uses
+    let select_tool = |tool: Option<Tool>| {
+        let appstate = props.appstate.clone();
+
+        Callback::from(move |_| {
+            let mut new_state = appstate.edited();
+            new_state.set_tool(tool);
+
+            appstate.set(new_state);
+        })
+    };
+
+    // double-clicking a tool keeps it selected, like the checkbox does
+    let lock_tool = |tool: Option<Tool>| {
+        let appstate = props.appstate.clone();
+
+        Callback::from(move |_| {
+            if let Some(tool) = tool {
+                let mut new_state = appstate.edited();
+                new_state.lock_tool(tool);
+
+                appstate.set(new_state);
+            }
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let font_size_onchange = {
+        Callback::from(move |evt: Event| {
+            let input: HtmlInputElement = evt.target_unchecked_into();
+            let mut new_state = appstate.edited();
+
+            match input.value().trim().parse::<f64>() {
+                Ok(font_size) => new_state.set_font_size(font_size),
+                Err(_) => input.set_value(&new_state.font_size().to_string()),
+            }
+
+            appstate.set(new_state);
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let delete_onclick = {
+        Callback::from(move |_| {
+           let mut new_state = appstate.edited();
+
+           new_state.delete_selected();
+
+           appstate.set(new_state);
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let undo_onclick = {
+        Callback::from(move |_| {
+           let mut new_state = appstate.edited();
+
+           new_state.undo();
+
+           appstate.set(new_state);
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let round_onclick = {
+        Callback::from(move |_| {
+           let mut new_state = appstate.edited();
+
+           if new_state.round_all_coordinates(ROUND_DECIMALS) > 0 {
+               appstate.set(new_state);
+           }
+        })
+    };
+
+    let export_status = use_state(|| None::<&'static str>);
+
+    let appstate = props.appstate.clone();
+    let export_onclick = {
+        let export_status = export_status.clone();
+
+        Callback::from(move |_| {
+            match download_svg(appstate.canvas()) {
+                Ok(()) => export_status.set(None),
+                Err(error) => {
+                    log::error!("exporting the SVG failed: {:?}", error);
+                    export_status.set(Some(strings.export_failed));
+                }
+            }
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let png_onclick = {
+        let export_status = export_status.clone();
+
+        Callback::from(move |_| {
+            let (canvas, export_status) = (appstate.canvas().clone(), export_status.clone());
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match download_png(&canvas, PNG_SCALE).await {
+                    Ok(()) => export_status.set(None),
+                    Err(error) => {
+                        log::error!("exporting the PNG failed: {:?}", error);
+                        export_status.set(Some(strings.export_failed));
+                    }
+                }
+            });
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let copy_onclick = {
+        let export_status = export_status.clone();
+
+        Callback::from(move |_| {
+            let (canvas, export_status) = (appstate.canvas().clone(), export_status.clone());
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match copy_svg(&canvas).await {
+                    Ok(()) => export_status.set(Some(strings.copied)),
+                    Err(error) => {
+                        log::error!("copying the SVG failed: {:?}", error);
+                        export_status.set(Some(strings.copy_failed));
+                    }
+                }
+            });
+        })
+    };
+
+    let settings_mode = use_state(|| None::<SettingsMode>);
+
+    let open_settings = |mode: SettingsMode| {
+        let settings_mode = settings_mode.clone();
+
+        Callback::from(move |_| settings_mode.set(Some(mode)))
+    };
+
+    let print_open = use_state(|| false);
+    let print_onclick = {
+        let print_open = print_open.clone();
+        Callback::from(move |_| print_open.set(true))
+    };
+
+    let appstate = props.appstate.clone();
+    let restore_onclick = {
+        Callback::from(move |_| {
+            match load_canvas() {
+                Some(canvas) => {
+                    let mut new_state = appstate.edited();
+                    new_state.replace_canvas(canvas);
+
+                    appstate.set(new_state);
+                }
+                None => log::warn!("there's no saved drawing to restore"),
+            }
+        })
+    };
+
+    let file_input = use_node_ref();
+
+    let open_onclick = {
+        let file_input = file_input.clone();
+
+        Callback::from(move |_| {
+            if let Some(input) = file_input.cast::<HtmlInputElement>() {
+                input.click();
+            }
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let file_onchange = {
+        Callback::from(move |evt: Event| {
+            let input: HtmlInputElement = evt.target_unchecked_into();
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                open_file(file, appstate.clone());
+            }
+
+            // picking the same file again should open it again
+            input.set_value("");
+        })
+    };
+
+    let zoom_onclick = |factor: f64| {
+        let appstate = props.appstate.clone();
+
+        Callback::from(move |_| {
+            let mut new_state = appstate.edited();
+            let canvas = new_state.canvas_mut();
+            let center = program_core::Point::new(canvas.width() / 2f64, canvas.height() / 2f64);
+            canvas.zoom_at(&center, factor);
+
+            appstate.set(new_state);
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let fit_onclick = {
+        Callback::from(move |_| {
+            let mut new_state = appstate.edited();
+
+            new_state.canvas_mut().fit_to_content();
+
+            appstate.set(new_state);
+        })
+    };
 
-    let add_line_onclick = {
+    let appstate = props.appstate.clone();
+    let keep_tool_armed_onclick = {
         Callback::from(move |_| {
-           let mut new_state = (*appstate).clone();
-           
-           new_state.set_message(Some(Message::Line));
+           let mut new_state = appstate.edited();
+
+           new_state.set_keep_tool_armed(!new_state.keep_tool_armed());
 
            appstate.set(new_state);
         })
     };
 
+    let grid_spacing = use_state(|| props.appstate.canvas().grid_spacing().unwrap_or(DEFAULT_GRID_SPACING));
+
+    let appstate = props.appstate.clone();
+    let grid_onclick = {
+        let grid_spacing = grid_spacing.clone();
+
+        Callback::from(move |_| {
+            let mut new_state = appstate.edited();
+            let canvas = new_state.canvas_mut();
+            let spacing = if canvas.grid_spacing().is_some() { None } else { Some(*grid_spacing) };
+            canvas.set_grid_spacing(spacing);
+
+            appstate.set(new_state);
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let grid_spacing_onchange = {
+        let grid_spacing = grid_spacing.clone();
+
+        Callback::from(move |evt: Event| {
+            let input: HtmlInputElement = evt.target_unchecked_into();
+            let spacing = match input.value().trim().parse::<f64>() {
+                Ok(spacing) if spacing > 0f64 && spacing.is_finite() => spacing,
+                _ => {
+                    input.set_value(&grid_spacing.to_string());
+                    return;
+                }
+            };
+            grid_spacing.set(spacing);
+
+            if appstate.canvas().grid_spacing().is_some() {
+                let mut new_state = appstate.edited();
+                new_state.canvas_mut().set_grid_spacing(Some(spacing));
+
+                appstate.set(new_state);
+            }
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let export_grid_onclick = {
+        Callback::from(move |_| {
+            let mut new_state = appstate.edited();
+            let canvas = new_state.canvas_mut();
+            canvas.set_export_grid(!canvas.export_grid());
+
+            appstate.set(new_state);
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let keep_on_canvas_onclick = {
+        Callback::from(move |_| {
+            let mut new_state = appstate.edited();
+            let canvas = new_state.canvas_mut();
+            canvas.set_clamp_to_bounds(!canvas.clamp_to_bounds());
+
+            appstate.set(new_state);
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let snapping_onclick = {
+        Callback::from(move |_| {
+            let mut new_state = appstate.edited();
+            new_state.set_snapping(!new_state.snapping());
+
+            appstate.set(new_state);
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let ortho_onclick = {
+        Callback::from(move |_| {
+            let mut new_state = appstate.edited();
+            let step = match new_state.canvas().ortho_mode() {
+                Some(_) => None,
+                None => Some(FRAC_PI_2),
+            };
+            new_state.canvas_mut().set_ortho_mode(step);
+
+            appstate.set(new_state);
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let ortho_step_onchange = {
+        Callback::from(move |evt: Event| {
+            let degrees = evt.target_unchecked_into::<HtmlSelectElement>().value().parse::<f64>();
+            if let Ok(degrees) = degrees {
+                let mut new_state = appstate.edited();
+                new_state.canvas_mut().set_ortho_mode(Some(degrees.to_radians()));
+
+                appstate.set(new_state);
+            }
+        })
+    };
+    let ortho_degrees = props.appstate.canvas().ortho_mode().map(|step| step.to_degrees().round() as u32);
+
+    let snap_kind_onclick = |kind: SnapKind| {
+        let appstate = props.appstate.clone();
+        Callback::from(move |_| {
+            let mut new_state = appstate.edited();
+            new_state.set_snap_kind(kind, !new_state.snap_kinds().contains(kind));
+
+            appstate.set(new_state);
+        })
+    };
+
+    let appstate = props.appstate.clone();
+    let language_onclick = {
+        Callback::from(move |_| {
+            let mut new_state = appstate.edited();
+            let language = new_state.language().other();
+            new_state.set_language(language);
+            save_language(language);
+
+            appstate.set(new_state);
+        })
+    };
+
+    let tool = props.appstate.tool();
+    let tool_title = |tool: Tool, key: &str| fill(strings.tool_hint, &[&tool_name(Some(tool), strings), &key]);
+
     html! {
         <>
             <div>
-                <button onclick={add_line_onclick}>{ "Add Line" }</button>
+                {
+                    [
+                        (strings.tool_select, String::from(strings.select_hint), None),
+                        (strings.add_line, tool_title(Tool::Line, "L"), Some(Tool::Line)),
+                        (strings.add_circle, tool_title(Tool::Circle, "C"), Some(Tool::Circle)),
+                        (strings.add_circle_three_points, tool_title(Tool::CircleThreePoints, "3"), Some(Tool::CircleThreePoints)),
+                        (strings.add_rect, tool_title(Tool::Rect, "R"), Some(Tool::Rect)),
+                        (strings.add_text, tool_title(Tool::Text, "T"), Some(Tool::Text)),
+                    ].into_iter().map(|(label, title, button_tool)| {
+                        tool_button(label, title, button_tool == tool, select_tool(button_tool), lock_tool(button_tool))
+                    }).collect::<Html>()
+                }
+                <input type="number" min="1" step="1" title={strings.font_size} value={props.appstate.font_size().to_string()} onchange={font_size_onchange} />
+                <button onclick={delete_onclick} title={strings.delete_hint} disabled={props.appstate.canvas().selection().is_empty()}>{ strings.delete }</button>
+                <button onclick={undo_onclick}>{ strings.undo }</button>
+                <button onclick={round_onclick} title={fill(strings.round_coordinates_hint, &[&ROUND_DECIMALS])}>{ strings.round_coordinates }</button>
+                <button onclick={zoom_onclick(1.25)}>{ "+" }</button>
+                <button onclick={zoom_onclick(0.8)}>{ "\u{2212}" }</button>
+                <button onclick={fit_onclick}>{ strings.fit }</button>
+                <button onclick={open_settings(SettingsMode::NewDocument)}>{ strings.new_drawing }</button>
+                <button onclick={open_settings(SettingsMode::Edit)}>{ strings.document_settings }</button>
+                <button onclick={restore_onclick}>{ strings.restore }</button>
+                <button onclick={open_onclick} title={strings.open_hint}>{ strings.open }</button>
+                <input type="file" accept={ACCEPTED_FILES} ref={file_input} onchange={file_onchange} style="display: none;" />
+                <button onclick={export_onclick}>{ strings.export_svg }</button>
+                <button onclick={png_onclick}>{ strings.download_png }</button>
+                <button onclick={copy_onclick}>{ strings.copy_svg }</button>
+                <button onclick={print_onclick}>{ strings.print }</button>
+                if let Some(status) = *export_status {
+                    <span>{ status }</span>
+                }
+                <label>
+                    <input type="checkbox" checked={props.appstate.keep_tool_armed()} onclick={keep_tool_armed_onclick} />
+                    { strings.keep_tool_selected }
+                </label>
+                <label>
+                    <input type="checkbox" checked={props.appstate.canvas().grid_spacing().is_some()} onclick={grid_onclick} />
+                    { strings.grid }
+                </label>
+                <input type="number" min="1" step="1" title={strings.grid_spacing} value={grid_spacing.to_string()} onchange={grid_spacing_onchange} />
+                <label>
+                    <input type="checkbox" checked={props.appstate.canvas().export_grid()} onclick={export_grid_onclick} disabled={props.appstate.canvas().grid_spacing().is_none()} />
+                    { strings.export_grid }
+                </label>
+                <label>
+                    <input type="checkbox" checked={props.appstate.canvas().clamp_to_bounds()} onclick={keep_on_canvas_onclick} />
+                    { strings.keep_on_canvas }
+                </label>
+                <label>
+                    <input type="checkbox" checked={props.appstate.snapping()} onclick={snapping_onclick} />
+                    { strings.snap }
+                </label>
+                <label title={strings.ortho_hint}>
+                    <input type="checkbox" checked={ortho_degrees.is_some()} onclick={ortho_onclick} />
+                    { strings.ortho }
+                </label>
+                <select title={strings.ortho_step} onchange={ortho_step_onchange} disabled={ortho_degrees.is_none()}>
+                    {
+                        ORTHO_STEPS.into_iter().map(|degrees| html! {
+                            <option value={degrees.to_string()} selected={ortho_degrees == Some(degrees)}>{ format!("{}\u{b0}", degrees) }</option>
+                        }).collect::<Html>()
+                    }
+                </select>
+                {
+                    SnapKind::ALL.into_iter().map(|kind| html! {
+                        <label>
+                            <input type="checkbox" checked={props.appstate.snap_kinds().contains(kind)} onclick={snap_kind_onclick(kind)} disabled={!props.appstate.snapping()} />
+                            { snap_kind_name(kind, strings) }
+                        </label>
+                    }).collect::<Html>()
+                }
+                <span role="status" title={strings.save_hint}>{ props.appstate.save_status().label(strings) }</span>
+                <button onclick={language_onclick} lang={props.appstate.language().other().code()}>
+                    { props.appstate.language().other().strings().language_name }
+                </button>
+                <SettingsDialog appstate={props.appstate.clone()} mode={settings_mode} />
+                <PrintDialog appstate={props.appstate.clone()} open={print_open} />
 
             </div>
         </>
     }
 }
 
+/// the grid spacing, in drawing units, until another one is entered.
+const DEFAULT_GRID_SPACING: f64 = 20.0;
 
+/// the steps, in degrees, ortho mode can keep shapes at.
+const ORTHO_STEPS: [u32; 3] = [90, 45, 15];
+
+/// a button selecting a tool, shown pressed and given the `active` class
+/// while its tool is selected. `title` names the key that selects the tool too.
+fn tool_button(label: &'static str, title: String, active: bool, onclick: Callback<MouseEvent>, ondblclick: Callback<MouseEvent>) -> Html {
+    html! {
+        <button
+            class={classes!("tool", active.then_some("active"))}
+            onclick={onclick}
+            ondblclick={ondblclick}
+            title={title}
+            aria-pressed={active.to_string()}
+        >
+            { label }
+        </button>
+    }
+}
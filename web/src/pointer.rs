@@ -0,0 +1,119 @@
+use program_core::{Aabb, Canvas, Point};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, MouseEvent};
+
+/// maps a position in client coordinates to the canvas' screen coordinates,
+/// the ones the viewport works in. `target` is the content box of the SVG
+/// element in client coordinates, so scrolling, borders and CSS sizes
+/// other than the SVG's own all cancel out. client coordinates are CSS
+/// pixels on every display, so the device pixel ratio doesn't matter.
+///
+/// # Examples
+/// ```
+/// use program_core::{Aabb, Point};
+/// use web::client_to_screen;
+///
+/// // a page scrolled so the canvas starts left of and above the window
+/// let scrolled = Aabb::new(&Point::new(-100.0, -50.0), &Point::new(1100.0, 750.0));
+/// assert_eq!(Point::new(300.0, 250.0), client_to_screen(&Point::new(200.0, 200.0), &scrolled, 1200.0, 800.0));
+///
+/// // a canvas styled to half its width and height
+/// let scaled = Aabb::new(&Point::new(10.0, 20.0), &Point::new(610.0, 420.0));
+/// assert_eq!(Point::new(200.0, 100.0), client_to_screen(&Point::new(110.0, 70.0), &scaled, 1200.0, 800.0));
+///
+/// ```
+pub fn client_to_screen(client: &Point, target: &Aabb, width: f64, height: f64) -> Point {
+    let (min, max) = (target.min(), target.max());
+    let (css_width, css_height) = (max.x() - min.x(), max.y() - min.y());
+
+    // an element that isn't laid out has no size, skip the scaling
+    let scale_x = if css_width > 0f64 { width / css_width } else { 1f64 };
+    let scale_y = if css_height > 0f64 { height / css_height } else { 1f64 };
+
+    Point::new((client.x() - min.x()) * scale_x, (client.y() - min.y()) * scale_y)
+}
+
+/// the inverse of [`client_to_screen`], used to place HTML over the canvas.
+///
+/// # Examples
+/// ```
+/// use program_core::{Aabb, Point};
+/// use web::{client_to_screen, screen_to_client};
+///
+/// let scaled = Aabb::new(&Point::new(10.0, 20.0), &Point::new(610.0, 420.0));
+/// let screen = client_to_screen(&Point::new(110.0, 70.0), &scaled, 1200.0, 800.0);
+///
+/// assert_eq!(Point::new(110.0, 70.0), screen_to_client(&screen, &scaled, 1200.0, 800.0));
+///
+/// ```
+pub fn screen_to_client(screen: &Point, target: &Aabb, width: f64, height: f64) -> Point {
+    let (min, max) = (target.min(), target.max());
+    let scale_x = if width > 0f64 { (max.x() - min.x()) / width } else { 1f64 };
+    let scale_y = if height > 0f64 { (max.y() - min.y()) / height } else { 1f64 };
+
+    Point::new(min.x() + screen.x() * scale_x, min.y() + screen.y() * scale_y)
+}
+
+/// maps a position in client coordinates to drawing coordinates,
+/// going through the SVG element's box and then the view of `canvas`.
+///
+/// # Examples
+/// ```
+/// use program_core::{Aabb, Canvas, Point};
+/// use web::client_to_world;
+///
+/// let mut canvas = Canvas::new(1200.0, 800.0);
+/// canvas.zoom_at(&Point::new(0.0, 0.0), 2.0);
+/// canvas.pan_by(&Point::new(100.0, 0.0));
+///
+/// // scrolled by 40 pixels and styled to half the canvas' size
+/// let target = Aabb::new(&Point::new(0.0, -40.0), &Point::new(600.0, 360.0));
+///
+/// assert_eq!(Point::new(50.0, 40.0), client_to_world(&Point::new(100.0, 0.0), &target, &canvas));
+///
+/// ```
+pub fn client_to_world(client: &Point, target: &Aabb, canvas: &Canvas) -> Point {
+    canvas.screen_to_world(&client_to_screen(client, target, canvas.width(), canvas.height()))
+}
+
+/// the position of a mouse event in the canvas' screen coordinates.
+/// the event must come from a handler on the SVG element.
+pub fn event_to_screen(evt: &MouseEvent, canvas: &Canvas) -> Point {
+    client_to_screen(&client_point(evt), &content_box(evt), canvas.width(), canvas.height())
+}
+
+/// the position of a mouse event in drawing coordinates.
+/// the event must come from a handler on the SVG element.
+pub fn event_to_world(evt: &MouseEvent, canvas: &Canvas) -> Point {
+    client_to_world(&client_point(evt), &content_box(evt), canvas)
+}
+
+/// the position of a mouse event in drawing coordinates, measured from
+/// `svg` rather than from the element handling it, like for events on the rulers.
+pub fn event_to_world_from(evt: &MouseEvent, svg: &Element, canvas: &Canvas) -> Point {
+    client_to_world(&client_point(evt), &element_content_box(svg), canvas)
+}
+
+fn client_point(evt: &MouseEvent) -> Point {
+    Point::new(evt.client_x().into(), evt.client_y().into())
+}
+
+/// the box inside the border of the element handling `evt`, in client coordinates.
+fn content_box(evt: &MouseEvent) -> Aabb {
+    let element = evt
+        .current_target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .expect("pointer events are handled on the canvas element");
+
+    element_content_box(&element)
+}
+
+fn element_content_box(element: &Element) -> Aabb {
+    let rect = element.get_bounding_client_rect();
+    let min = Point::new(rect.left() + f64::from(element.client_left()), rect.top() + f64::from(element.client_top()));
+
+    Aabb::new(
+        &min,
+        &Point::new(min.x() + f64::from(element.client_width()), min.y() + f64::from(element.client_height())),
+    )
+}
@@ -0,0 +1,343 @@
+use yew::prelude::*;
+use gloo_events::EventListener;
+use program_core::{Canvas, ExportOptions, Unit};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{HtmlIFrameElement, HtmlInputElement, HtmlSelectElement};
+use super::app_state::AppState;
+use super::i18n::Language;
+
+/// a sheet of paper to print on.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum PageSize {
+    #[default]
+    A4,
+    Letter,
+}
+
+impl PageSize {
+    pub const ALL: [PageSize; 2] = [PageSize::A4, PageSize::Letter];
+
+    /// the width and height of the page standing up, in millimeters.
+    pub fn portrait_mm(&self) -> (f64, f64) {
+        match self {
+            PageSize::A4 => (210f64, 297f64),
+            PageSize::Letter => (215.9, 279.4),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PageSize::A4 => "A4",
+            PageSize::Letter => "Letter",
+        }
+    }
+}
+
+/// how big the drawing is printed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PrintScale {
+    /// as big as fits on the page.
+    Fit,
+    /// its real size, a pixel being a CSS pixel, times the factor.
+    Factor(f64),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PrintOptions {
+    pub page: PageSize,
+    pub landscape: bool,
+    pub scale: PrintScale,
+    /// prints only the drawables instead of the whole canvas.
+    pub crop_to_drawing: bool,
+    /// adds the title of the drawing and the date under it.
+    pub title_block: bool,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions { page: PageSize::A4, landscape: false, scale: PrintScale::Fit, crop_to_drawing: true, title_block: true }
+    }
+}
+
+impl PrintOptions {
+    /// the width and height of the page as it's turned, in millimeters.
+    pub fn page_mm(&self) -> (f64, f64) {
+        let (width, height) = self.page.portrait_mm();
+        if self.landscape { (height, width) } else { (width, height) }
+    }
+}
+
+/// the blank edge of the page, in millimeters.
+pub const PAGE_MARGIN_MM: f64 = 10.0;
+/// the height of the title block, in millimeters.
+pub const TITLE_BLOCK_MM: f64 = 12.0;
+/// the space left around the drawables when cropping to them, in pixels.
+const CROP_MARGIN: f64 = 10.0;
+
+/// how the drawing is exported for printing with `options`, at the size in
+/// millimeters its scale gives it.
+///
+/// # Examples
+/// ```
+/// use program_core::{Canvas, Point, Unit};
+/// use web::{print_export_options, PageSize, PrintOptions, PrintScale};
+///
+/// let mut canvas = Canvas::new(1000.0, 1000.0);
+/// canvas.add_rect(&Point::new(100.0, 100.0), &Point::new(480.0, 290.0)).unwrap();
+///
+/// // 400 by 210 pixels with the margin, made as wide as an A4 page fits
+/// let options = PrintOptions::default();
+/// let export = print_export_options(&canvas, &options);
+/// assert!(export.fit_to_content);
+/// let (width, height) = export.size_mm.unwrap();
+/// assert!((width - 190.0).abs() < 1e-9 && (height - 99.75).abs() < 1e-9);
+///
+/// // at half its real size, whatever the page
+/// let options = PrintOptions { page: PageSize::Letter, landscape: true, scale: PrintScale::Factor(0.5), ..options };
+/// let (width, _) = print_export_options(&canvas, &options).size_mm.unwrap();
+/// assert!((width - 200.0 / Unit::Mm.px_per_unit()).abs() < 1e-9);
+///
+/// // the whole canvas, as tall as the page fits under the title block
+/// let options = PrintOptions { crop_to_drawing: false, ..PrintOptions::default() };
+/// let (width, height) = print_export_options(&canvas, &options).size_mm.unwrap();
+/// assert!((width - 190.0).abs() < 1e-9 && (height - 190.0).abs() < 1e-9);
+/// let options = PrintOptions { landscape: true, ..options };
+/// let (width, height) = print_export_options(&canvas, &options).size_mm.unwrap();
+/// assert!((width - 178.0).abs() < 1e-9 && (height - 178.0).abs() < 1e-9);
+///
+/// ```
+pub fn print_export_options(canvas: &Canvas, options: &PrintOptions) -> ExportOptions {
+    let (width, height) = match canvas.content_bounds().filter(|_| options.crop_to_drawing) {
+        Some(bounds) => {
+            let bounds = bounds.expand(CROP_MARGIN);
+            (bounds.max().x - bounds.min().x, bounds.max().y - bounds.min().y)
+        }
+        None => (canvas.width(), canvas.height()),
+    };
+    let (width_mm, height_mm) = (width / Unit::Mm.px_per_unit(), height / Unit::Mm.px_per_unit());
+
+    let factor = match options.scale {
+        PrintScale::Factor(factor) => factor,
+        PrintScale::Fit => {
+            let (page_width, page_height) = options.page_mm();
+            let title = if options.title_block { TITLE_BLOCK_MM } else { 0f64 };
+            let (room_width, room_height) = (page_width - 2f64 * PAGE_MARGIN_MM, page_height - 2f64 * PAGE_MARGIN_MM - title);
+
+            (room_width / width_mm).min(room_height / height_mm)
+        }
+    };
+
+    ExportOptions {
+        fit_to_content: options.crop_to_drawing,
+        margin: CROP_MARGIN,
+        size_mm: Some((width_mm * factor, height_mm * factor)),
+        precision: None,
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// the page that is printed: the drawing exported with `print_export_options`,
+/// and with the title block its title and `date` under it. nothing of the
+/// view, like the zoom or the selection, is part of it.
+///
+/// # Examples
+/// ```
+/// use program_core::{Canvas, Point};
+/// use web::{print_document, Language, PrintOptions};
+///
+/// let mut canvas = Canvas::new(400.0, 300.0);
+/// canvas.set_title(String::from("Plans <draft>"));
+/// canvas.add_circle(&Point::new(50.0, 50.0), 10.0).unwrap();
+/// canvas.set_selected_drawable(canvas.topmost_at(&Point::new(50.0, 50.0)));
+///
+/// let options = PrintOptions { landscape: true, ..PrintOptions::default() };
+/// let page = print_document(&canvas, &options, Language::English, "1/2/2026");
+/// assert!(page.contains("@page { size: 297mm 210mm; margin: 10mm; }"));
+/// assert!(page.contains("<span>Plans &lt;draft&gt;</span><span>1/2/2026</span>"));
+/// assert!(page.contains("<circle cx=\"50\" cy=\"50\" r=\"10\" />"));
+/// assert!(page.contains("<html lang=\"en\" dir=\"ltr\">"));
+///
+/// let options = PrintOptions { title_block: false, ..options };
+/// assert!(!print_document(&canvas, &options, Language::English, "1/2/2026").contains("1/2/2026"));
+///
+/// ```
+pub fn print_document(canvas: &Canvas, options: &PrintOptions, language: Language, date: &str) -> String {
+    let (page_width, page_height) = options.page_mm();
+    let svg = canvas.to_svg_with(&print_export_options(canvas, options));
+    let title_block = if options.title_block {
+        let title = canvas.title().map(String::as_str).unwrap_or(language.strings().untitled);
+        format!(
+            "<div style=\"display: flex; justify-content: space-between; align-items: center; height: {}mm; border-top: 1px solid black;\">\
+             <span>{}</span><span>{}</span></div>",
+            TITLE_BLOCK_MM, escape_html(title), escape_html(date),
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        "<!DOCTYPE html><html lang=\"{}\" dir=\"{}\"><head><meta charset=\"utf-8\"><title>{}</title>\
+         <style>@page {{ size: {}mm {}mm; margin: {}mm; }} body {{ margin: 0; font-family: sans-serif; }} svg {{ display: block; margin: 0 auto; }}</style>\
+         </head><body>{}{}</body></html>",
+        language.code(),
+        language.dir(),
+        escape_html(canvas.title().map(String::as_str).unwrap_or_default()),
+        page_width,
+        page_height,
+        PAGE_MARGIN_MM,
+        svg,
+        title_block,
+    )
+}
+
+/// prints the drawing from a hidden frame holding the `print_document`,
+/// which is removed once the browser is done printing.
+pub fn print(canvas: &Canvas, options: &PrintOptions, language: Language) -> Result<(), JsValue> {
+    let date = String::from(js_sys::Date::new_0().to_locale_date_string(language.code(), &JsValue::UNDEFINED));
+    let document = gloo_utils::document();
+    let body = document.body().ok_or_else(|| JsValue::from_str("the page has no body"))?;
+
+    let frame: HtmlIFrameElement = document.create_element("iframe")?.unchecked_into();
+    frame.set_attribute("style", "position: fixed; width: 0; height: 0; border: 0; visibility: hidden;")?;
+    frame.set_srcdoc(&print_document(canvas, options, language, &date));
+
+    let loaded = frame.clone();
+    EventListener::once(&frame, "load", move |_| {
+        let window = match loaded.content_window() {
+            Some(window) => window,
+            None => return loaded.remove(),
+        };
+
+        let printed = loaded.clone();
+        EventListener::once(&window, "afterprint", move |_| printed.remove()).forget();
+        if window.print().is_err() {
+            loaded.remove();
+        }
+    })
+    .forget();
+
+    body.append_child(&frame)?;
+
+    Ok(())
+}
+
+#[derive(PartialEq, Properties)]
+pub struct PrintDialogProps {
+    pub appstate: UseStateHandle<AppState>,
+    pub open: UseStateHandle<bool>,
+}
+
+/// picks the page, the scale and the title block, then prints the drawing.
+#[function_component(PrintDialog)]
+pub fn print_dialog(props: &PrintDialogProps) -> Html {
+    let options = use_state(PrintOptions::default);
+    let failed = use_state(|| false);
+
+    if !*props.open {
+        return html! {};
+    }
+    let strings = props.appstate.strings();
+
+    let change = |update: fn(&mut PrintOptions, &web_sys::Event)| {
+        let options = options.clone();
+        Callback::from(move |evt: Event| {
+            let mut changed = (*options).clone();
+            update(&mut changed, &evt);
+            options.set(changed);
+        })
+    };
+
+    let page_onchange = change(|options, evt| {
+        let select: HtmlSelectElement = evt.target_unchecked_into();
+        if let Some(&page) = PageSize::ALL.get(select.selected_index().max(0) as usize) {
+            options.page = page;
+        }
+    });
+    let orientation_onchange = change(|options, evt| {
+        options.landscape = evt.target_unchecked_into::<HtmlSelectElement>().selected_index() == 1;
+    });
+    let fit_onchange = change(|options, evt| {
+        options.scale = if evt.target_unchecked_into::<HtmlInputElement>().checked() { PrintScale::Fit } else { PrintScale::Factor(1f64) };
+    });
+    let scale_onchange = change(|options, evt| {
+        let input: HtmlInputElement = evt.target_unchecked_into();
+        match input.value().trim().parse::<f64>() {
+            Ok(percent) if percent > 0f64 && percent.is_finite() => options.scale = PrintScale::Factor(percent / 100f64),
+            _ => input.set_value(&match options.scale {
+                PrintScale::Factor(factor) => (factor * 100f64).to_string(),
+                PrintScale::Fit => String::from("100"),
+            }),
+        }
+    });
+    let crop_onchange = change(|options, evt| options.crop_to_drawing = evt.target_unchecked_into::<HtmlInputElement>().checked());
+    let title_block_onchange = change(|options, evt| options.title_block = evt.target_unchecked_into::<HtmlInputElement>().checked());
+
+    let print_onclick = {
+        let (appstate, options, failed, open) = (props.appstate.clone(), options.clone(), failed.clone(), props.open.clone());
+        Callback::from(move |_| match print(appstate.canvas(), &options, appstate.language()) {
+            Ok(()) => {
+                failed.set(false);
+                open.set(false);
+            }
+            Err(_) => failed.set(true),
+        })
+    };
+
+    let close = props.open.clone();
+    let close_onclick = Callback::from(move |_| close.set(false));
+
+    let percent = match options.scale {
+        PrintScale::Factor(factor) => (factor * 100f64).to_string(),
+        PrintScale::Fit => String::from("100"),
+    };
+
+    html! {
+        <div role="dialog" aria-label={strings.print}
+            style="position: fixed; inset: 0; display: flex; align-items: center; justify-content: center; background: rgba(0, 0, 0, 0.3); z-index: 20;"
+        >
+            <div style="display: flex; flex-direction: column; gap: 0.5em; padding: 1em; background: white; border: 1px solid gray;">
+                <h2>{ strings.print }</h2>
+                <label>
+                    { strings.page_size }{ " " }
+                    <select onchange={page_onchange}>
+                        {
+                            PageSize::ALL.iter().map(|&page| html! {
+                                <option selected={page == options.page}>{ page.name() }</option>
+                            }).collect::<Html>()
+                        }
+                    </select>
+                    <select onchange={orientation_onchange}>
+                        <option selected={!options.landscape}>{ strings.portrait }</option>
+                        <option selected={options.landscape}>{ strings.landscape }</option>
+                    </select>
+                </label>
+                <label>
+                    <input type="checkbox" checked={options.scale == PrintScale::Fit} onchange={fit_onchange} />
+                    { strings.fit_to_page }
+                </label>
+                <label>
+                    { strings.print_scale }{ " " }
+                    <input type="number" min="1" value={percent} onchange={scale_onchange} disabled={options.scale == PrintScale::Fit} />
+                </label>
+                <label>
+                    <input type="checkbox" checked={options.crop_to_drawing} onchange={crop_onchange} />
+                    { strings.crop_to_drawing }
+                </label>
+                <label>
+                    <input type="checkbox" checked={options.title_block} onchange={title_block_onchange} />
+                    { strings.title_block }
+                </label>
+                if *failed {
+                    <span role="alert">{ strings.print_failed }</span>
+                }
+                <div>
+                    <button onclick={print_onclick}>{ strings.print }</button>
+                    <button onclick={close_onclick}>{ strings.close }</button>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,609 @@
+use yew::prelude::*;
+use program_core::{
+    Angle, ApplyPropsError, ArcProps, CanvasError, CircleProps, DrawableProps, LineCap, LineJoin, LineProps, PivotSpec, Point, PointProps, Props, RectProps, StrokeAlign,
+    StrokeStyle, TextProps,
+};
+use std::collections::BTreeMap;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use super::AppState;
+use super::i18n::{fill, Strings};
+use super::status_bar::canvas_error_message;
+
+/// the text of each form field, keyed by field name.
+pub type FieldValues = BTreeMap<&'static str, String>;
+/// the parse error of each invalid field, keyed by field name.
+pub type FieldErrors = BTreeMap<&'static str, String>;
+
+/// the label and name of every field in the form for `props`, in display order.
+fn fields(props: &Props, strings: &Strings) -> Vec<(&'static str, &'static str)> {
+    match props {
+        Props::Point(_) => vec![(strings.position, "pos")],
+        Props::Line(_) => vec![
+            (strings.start, "start"),
+            (strings.end, "end"),
+            (strings.stroke_style, "stroke_style"),
+            (strings.dash_lengths, "dashes"),
+            (strings.line_cap, "line_cap"),
+            (strings.line_join, "line_join"),
+        ],
+        Props::Circle(_) => vec![(strings.center, "center"), (strings.radius, "radius"), (strings.stroke_align, "stroke_align")],
+        Props::Rect(_) => vec![
+            (strings.corner, "corner"),
+            (strings.width, "width"),
+            (strings.height, "height"),
+            (strings.rotation, "rotation"),
+            (strings.rotation_pivot, "rotation_pivot"),
+            (strings.stroke_align, "stroke_align"),
+            (strings.stroke_style, "stroke_style"),
+            (strings.dash_lengths, "dashes"),
+        ],
+        Props::Text(_) => vec![(strings.content, "content"), (strings.position, "pos"), (strings.font_size, "font_size")],
+        Props::Arc(_) => vec![
+            (strings.center, "center"),
+            (strings.radius, "radius"),
+            (strings.start_angle, "start_angle"),
+            (strings.end_angle, "end_angle"),
+            (strings.stroke_style, "stroke_style"),
+            (strings.dash_lengths, "dashes"),
+            (strings.line_cap, "line_cap"),
+        ],
+    }
+}
+
+/// the stroke alignments, with the value of their option.
+const STROKE_ALIGNS: [(StrokeAlign, &str); 3] =
+    [(StrokeAlign::Center, "center"), (StrokeAlign::Inside, "inside"), (StrokeAlign::Outside, "outside")];
+
+fn stroke_align_value(align: StrokeAlign) -> String {
+    STROKE_ALIGNS.iter().find(|(other, _)| *other == align).map(|(_, value)| value.to_string()).unwrap_or_default()
+}
+
+/// the values of the options of the stroke styles, the dashes
+/// themselves are typed in their own field.
+const STROKE_STYLES: [&str; 3] = ["solid", "dashed", "dotted"];
+
+fn stroke_style_value(stroke_style: &StrokeStyle) -> String {
+    let value = match stroke_style {
+        StrokeStyle::Solid => STROKE_STYLES[0],
+        StrokeStyle::Dashed(_) => STROKE_STYLES[1],
+        StrokeStyle::Dotted => STROKE_STYLES[2],
+    };
+
+    value.to_string()
+}
+
+/// the dashes of a dashed stroke as they're typed, apart by spaces.
+fn dashes_value(stroke_style: &StrokeStyle) -> String {
+    match stroke_style {
+        StrokeStyle::Dashed(dashes) => dashes.iter().map(f64::to_string).collect::<Vec<String>>().join(" "),
+        _ => String::new(),
+    }
+}
+
+/// the pivots a rotation can be typed about, with the value of their option.
+/// a pivot at a given point is for edits made by code, not picked in the form.
+const PIVOTS: [(PivotSpec, &str); 2] = [(PivotSpec::Center, "center"), (PivotSpec::Start, "start")];
+
+fn pivot_value(pivot: &PivotSpec) -> String {
+    PIVOTS.iter().find(|(other, _)| other == pivot).map(|(_, value)| value.to_string()).unwrap_or_default()
+}
+
+/// the options of the fields picked from a list, as values and labels.
+fn choices(name: &str, strings: &Strings) -> Vec<(&'static str, &'static str)> {
+    match name {
+        "stroke_align" => {
+            let labels = [strings.align_center, strings.align_inside, strings.align_outside];
+            STROKE_ALIGNS.iter().zip(labels).map(|((_, value), label)| (*value, label)).collect()
+        }
+        "stroke_style" => STROKE_STYLES.into_iter().zip([strings.style_solid, strings.style_dashed, strings.style_dotted]).collect(),
+        "line_cap" => LineCap::ALL.iter().map(LineCap::name).zip([strings.cap_butt, strings.cap_round, strings.cap_square]).collect(),
+        "line_join" => LineJoin::ALL.iter().map(LineJoin::name).zip([strings.join_miter, strings.join_round, strings.join_bevel]).collect(),
+        "rotation_pivot" => PIVOTS.iter().zip([strings.pivot_center, strings.pivot_start]).map(|((_, value), label)| (*value, label)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn title(props: &Props, strings: &Strings) -> &'static str {
+    match props {
+        Props::Point(_) => strings.point,
+        Props::Line(_) => strings.tool_line,
+        Props::Circle(_) => strings.tool_circle,
+        Props::Rect(_) => strings.tool_rect,
+        Props::Text(_) => strings.tool_text,
+        Props::Arc(_) => strings.arc,
+    }
+}
+
+/// the text shown in the form fields for `props`.
+pub fn field_values(props: &Props) -> FieldValues {
+    let point = |point: &Point| point.to_string();
+
+    match props {
+        Props::Point(point_props) => FieldValues::from([("pos", point(&point_props.pos))]),
+        Props::Line(line) => FieldValues::from([
+            ("start", point(&line.start)),
+            ("end", point(&line.end)),
+            ("stroke_style", stroke_style_value(&line.stroke_style)),
+            ("dashes", dashes_value(&line.stroke_style)),
+            ("line_cap", line.line_cap.to_string()),
+            ("line_join", line.line_join.to_string()),
+        ]),
+        Props::Circle(circle) => FieldValues::from([
+            ("center", point(&circle.center)),
+            ("radius", circle.radius.to_string()),
+            ("stroke_align", stroke_align_value(circle.stroke_align)),
+        ]),
+        Props::Rect(rect) => FieldValues::from([
+            ("corner", point(&rect.corner)),
+            ("width", rect.width.to_string()),
+            ("height", rect.height.to_string()),
+            ("rotation", rect.rotation.to_degrees().to_string()),
+            ("rotation_pivot", pivot_value(&rect.rotation_pivot)),
+            ("stroke_align", stroke_align_value(rect.stroke_align)),
+            ("stroke_style", stroke_style_value(&rect.stroke_style)),
+            ("dashes", dashes_value(&rect.stroke_style)),
+        ]),
+        Props::Text(text) => FieldValues::from([
+            ("content", text.content.clone()),
+            ("pos", point(&text.pos)),
+            ("font_size", text.font_size.to_string()),
+        ]),
+        Props::Arc(arc) => FieldValues::from([
+            ("center", point(&arc.center)),
+            ("radius", arc.radius.to_string()),
+            ("start_angle", arc.start_angle.to_degrees().to_string()),
+            ("end_angle", arc.end_angle.to_degrees().to_string()),
+            ("stroke_style", stroke_style_value(&arc.stroke_style)),
+            ("dashes", dashes_value(&arc.stroke_style)),
+            ("line_cap", arc.line_cap.to_string()),
+        ]),
+    }
+}
+
+/// the field to show `error` next to, when the canvas refused to apply
+/// `props`, or `None` when it isn't about any one field.
+///
+/// # Examples
+/// ```
+/// use program_core::{Angle, CanvasError, LineCap, LineJoin, LineProps, PivotSpec, Point, Props, RectProps, StrokeAlign, StrokeStyle};
+/// use web::invalid_field;
+///
+/// let rect = |width: f64, height: f64| Props::Rect(RectProps {
+///     corner: Point::new(0.0, 0.0), width, height, rotation: Angle::default(), rotation_pivot: PivotSpec::Center,
+///     stroke_align: StrokeAlign::Center, stroke_style: StrokeStyle::Solid, stroke: None, fill: None,
+/// });
+/// assert_eq!(Some("width"), invalid_field(&rect(0.0, 5.0), CanvasError::ZeroArea));
+/// assert_eq!(Some("height"), invalid_field(&rect(5.0, 0.0), CanvasError::ZeroArea));
+///
+/// let line = Props::Line(LineProps {
+///     start: Point::new(1.0, 1.0), end: Point::new(1.0, 1.0), stroke_style: StrokeStyle::Solid, line_cap: LineCap::Butt, line_join: LineJoin::Miter, stroke: None,
+/// });
+/// assert_eq!(Some("end"), invalid_field(&line, CanvasError::ZeroLength));
+/// assert_eq!(None, invalid_field(&line, CanvasError::NotFinite));
+///
+/// ```
+pub fn invalid_field(props: &Props, error: CanvasError) -> Option<&'static str> {
+    match (props, error) {
+        (Props::Line(_), CanvasError::ZeroLength) => Some("end"),
+        (Props::Circle(_) | Props::Arc(_), CanvasError::NonPositiveRadius(_)) => Some("radius"),
+        (Props::Rect(rect), CanvasError::ZeroArea) if rect.width <= f64::EPSILON => Some("width"),
+        (Props::Rect(_), CanvasError::ZeroArea) => Some("height"),
+        (Props::Arc(_), CanvasError::ZeroSweep) => Some("end_angle"),
+        _ => None,
+    }
+}
+
+/// parses the form fields back into props of the same kind as `props`,
+/// with the errors in the language of `strings`. paints, and whether a circle
+/// is filled, have no fields yet, so they're kept as they are in `props`.
+///
+/// # Examples
+/// ```
+/// use program_core::{
+///     Angle, ArcProps, CircleProps, Color, LineCap, LineJoin, LineProps, Paint, PivotSpec, Point, PointProps, Props, RectProps, StrokeAlign, StrokeStyle, TextProps,
+/// };
+/// use web::{field_values, parse_props, ENGLISH};
+///
+/// let every_kind = [
+///     Props::Point(PointProps { pos: Point::new(-1.5, 0.0) }),
+///     Props::Line(LineProps {
+///         start: Point::new(1.0, 2.0), end: Point::new(3.0, 4.5), stroke_style: StrokeStyle::Dashed(vec![4.0, 1.5]), line_cap: LineCap::Square, line_join: LineJoin::Bevel,
+///         stroke: Some(Paint::Solid(Color::RED)),
+///     }),
+///     Props::Circle(CircleProps { center: Point::new(1.0, 2.0), radius: 5.0, stroke_align: StrokeAlign::Inside, stroke: None, fill: None, filled: true }),
+///     Props::Rect(RectProps {
+///         corner: Point::new(-1.0, 2.0), width: 3.0, height: 0.5, rotation: Angle::default(), rotation_pivot: PivotSpec::Start, stroke_align: StrokeAlign::Outside,
+///         stroke_style: StrokeStyle::Dotted, stroke: None, fill: Some(Paint::LinearGradient { stops: vec![(0.0, Color::WHITE), (1.0, Color::BLACK)], angle: Angle::default() }),
+///     }),
+///     Props::Text(TextProps { content: String::from("a, b"), pos: Point::new(1.0, 2.0), font_size: 12.0, fill: Some(Paint::Solid(Color::BLUE)) }),
+///     Props::Arc(ArcProps {
+///         center: Point::new(1.0, 2.0), radius: 5.0, start_angle: Angle::degrees(-90.0), end_angle: Angle::degrees(90.0), stroke_style: StrokeStyle::Dotted,
+///         line_cap: LineCap::Round, stroke: None,
+///     }),
+/// ];
+/// for props in &every_kind {
+///     assert_eq!(Ok(props.clone()), parse_props(props, &field_values(props), &ENGLISH));
+/// }
+///
+/// let circle = &every_kind[2];
+/// let mut values = field_values(circle);
+/// values.insert("radius", String::from("-3"));
+/// values.insert("center", String::from("1, two"));
+///
+/// let errors = parse_props(circle, &values, &ENGLISH).unwrap_err();
+/// assert_eq!(vec!["center", "radius"], errors.keys().copied().collect::<Vec<_>>());
+///
+/// // dashes are only read when the stroke is dashed
+/// let line = &every_kind[1];
+/// let mut values = field_values(line);
+/// values.insert("dashes", String::from("2, -1"));
+/// assert_eq!(vec!["dashes"], parse_props(line, &values, &ENGLISH).unwrap_err().keys().copied().collect::<Vec<_>>());
+/// values.insert("stroke_style", String::from("solid"));
+/// assert!(matches!(parse_props(line, &values, &ENGLISH), Ok(Props::Line(LineProps { stroke_style: StrokeStyle::Solid, .. }))));
+///
+/// let mut values = field_values(&every_kind[3]);
+/// values.insert("width", String::from("NaN"));
+/// assert_eq!(vec!["width"], parse_props(&every_kind[3], &values, &ENGLISH).unwrap_err().keys().copied().collect::<Vec<_>>());
+///
+/// // sizes have to be more than zero, and what isn't a number says so
+/// let size_error = |props: &Props, name: &'static str, value: &str| {
+///     let mut values = field_values(props);
+///     values.insert(name, String::from(value));
+///     parse_props(props, &values, &ENGLISH).unwrap_err().remove(name)
+/// };
+/// assert_eq!(Some(String::from(ENGLISH.non_positive_length)), size_error(circle, "radius", "0"));
+/// assert_eq!(Some(String::from(ENGLISH.non_positive_length)), size_error(&every_kind[3], "height", "-2"));
+/// assert_eq!(Some(String::from(ENGLISH.non_positive_length)), size_error(&every_kind[4], "font_size", "0"));
+/// assert_eq!(Some(String::from(ENGLISH.not_a_number)), size_error(circle, "radius", "five"));
+/// assert_eq!(Some(String::from(ENGLISH.not_a_number)), size_error(&every_kind[3], "width", "NaN"));
+///
+/// // a dash can be zero long
+/// let mut values = field_values(line);
+/// values.insert("dashes", String::from("0 2"));
+/// assert!(matches!(parse_props(line, &values, &ENGLISH), Ok(Props::Line(LineProps { stroke_style: StrokeStyle::Dashed(_), .. }))));
+///
+/// ```
+pub fn parse_props(props: &Props, values: &FieldValues, strings: &Strings) -> Result<Props, FieldErrors> {
+    let mut errors = FieldErrors::new();
+    let mut point = |name| parse_point(&mut errors, values, name);
+
+    let parsed = match props {
+        Props::Point(_) => point("pos").map(|pos| Props::Point(PointProps { pos })),
+        Props::Line(line) => {
+            let (start, end) = (point("start"), point("end"));
+            let stroke_style = parse_stroke_style(&mut errors, values, strings);
+            let line_cap = values.get("line_cap").and_then(|value| value.parse().ok()).unwrap_or_default();
+            let line_join = values.get("line_join").and_then(|value| value.parse().ok()).unwrap_or_default();
+            match (start, end, stroke_style) {
+                (Some(start), Some(end), Some(stroke_style)) => {
+                    Some(Props::Line(LineProps { start, end, stroke_style, line_cap, line_join, stroke: line.stroke.clone() }))
+                }
+                _ => None,
+            }
+        }
+        Props::Circle(circle) => {
+            let center = point("center");
+            let radius = parse_length(&mut errors, values, strings, "radius");
+            let stroke_align = parse_stroke_align(values);
+            center.zip(radius).map(|(center, radius)| {
+                let (stroke, fill, filled) = (circle.stroke.clone(), circle.fill.clone(), circle.filled);
+                Props::Circle(CircleProps { center, radius, stroke_align, stroke, fill, filled })
+            })
+        }
+        Props::Rect(rect) => {
+            let corner = point("corner");
+            let width = parse_length(&mut errors, values, strings, "width");
+            let height = parse_length(&mut errors, values, strings, "height");
+            let rotation = parse_angle(&mut errors, values, strings, "rotation");
+            let rotation_pivot = parse_pivot(values);
+            let stroke_align = parse_stroke_align(values);
+            let stroke_style = parse_stroke_style(&mut errors, values, strings);
+            match (corner, width, height, rotation, stroke_style) {
+                (Some(corner), Some(width), Some(height), Some(rotation), Some(stroke_style)) => {
+                    let (stroke, fill) = (rect.stroke.clone(), rect.fill.clone());
+                    Some(Props::Rect(RectProps { corner, width, height, rotation, rotation_pivot, stroke_align, stroke_style, stroke, fill }))
+                }
+                _ => None,
+            }
+        }
+        Props::Text(text) => {
+            let content = values.get("content").cloned().unwrap_or_default();
+            let pos = point("pos");
+            let font_size = parse_length(&mut errors, values, strings, "font_size");
+            pos.zip(font_size).map(|(pos, font_size)| Props::Text(TextProps { content, pos, font_size, fill: text.fill.clone() }))
+        }
+        Props::Arc(arc) => {
+            let center = point("center");
+            let radius = parse_length(&mut errors, values, strings, "radius");
+            let start_angle = parse_angle(&mut errors, values, strings, "start_angle");
+            let end_angle = parse_angle(&mut errors, values, strings, "end_angle");
+            let stroke_style = parse_stroke_style(&mut errors, values, strings);
+            let line_cap = values.get("line_cap").and_then(|value| value.parse().ok()).unwrap_or_default();
+            match (center, radius, start_angle, end_angle, stroke_style) {
+                (Some(center), Some(radius), Some(start_angle), Some(end_angle), Some(stroke_style)) => {
+                    Some(Props::Arc(ArcProps { center, radius, start_angle, end_angle, stroke_style, line_cap, stroke: arc.stroke.clone() }))
+                }
+                _ => None,
+            }
+        }
+    };
+
+    parsed.ok_or(errors)
+}
+
+fn parse_point(errors: &mut FieldErrors, values: &FieldValues, name: &'static str) -> Option<Point> {
+    let value = values.get(name).map(String::as_str).unwrap_or_default();
+
+    value
+        .parse()
+        .map_err(|error: program_core::ParsePointError| errors.insert(name, error.to_string()))
+        .ok()
+}
+
+/// parses a size, like a radius or a font size, which has to be more than zero
+/// for the shape to be drawn. dashes can be zero long, they're parsed on their own.
+fn parse_length(errors: &mut FieldErrors, values: &FieldValues, strings: &Strings, name: &'static str) -> Option<f64> {
+    let value = values.get(name).map(String::as_str).unwrap_or_default();
+
+    let error = match value.trim().parse::<f64>() {
+        Ok(length) if length.is_finite() && length > 0f64 => return Some(length),
+        Ok(length) if length.is_finite() => strings.non_positive_length,
+        _ => strings.not_a_number,
+    };
+    errors.insert(name, String::from(error));
+
+    None
+}
+
+/// the stroke alignment picked, only ever one of `STROKE_ALIGNS` since it's picked from a list.
+fn parse_stroke_align(values: &FieldValues) -> StrokeAlign {
+    let value = values.get("stroke_align").map(String::as_str).unwrap_or_default();
+
+    STROKE_ALIGNS.iter().find(|(_, other)| *other == value).map(|(align, _)| *align).unwrap_or_default()
+}
+
+/// the stroke style picked, with the dashes typed when it's dashed.
+/// they're lengths apart by spaces or commas, at least one of them.
+fn parse_stroke_style(errors: &mut FieldErrors, values: &FieldValues, strings: &Strings) -> Option<StrokeStyle> {
+    match values.get("stroke_style").map(String::as_str).unwrap_or_default() {
+        "dashed" => {
+            let value = values.get("dashes").map(String::as_str).unwrap_or_default();
+            let dashes = value
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|dash| !dash.is_empty())
+                .map(|dash| dash.parse::<f64>().ok().filter(|dash| dash.is_finite() && *dash >= 0f64))
+                .collect::<Option<Vec<f64>>>()
+                .filter(|dashes| !dashes.is_empty());
+
+            if dashes.is_none() {
+                errors.insert("dashes", String::from(strings.invalid_dashes));
+            }
+            dashes.map(StrokeStyle::Dashed)
+        }
+        "dotted" => Some(StrokeStyle::Dotted),
+        _ => Some(StrokeStyle::Solid),
+    }
+}
+
+/// the pivot picked, only ever one of `PIVOTS` since it's picked from a list.
+fn parse_pivot(values: &FieldValues) -> PivotSpec {
+    let value = values.get("rotation_pivot").map(String::as_str).unwrap_or_default();
+
+    PIVOTS.iter().find(|(_, other)| *other == value).map(|(pivot, _)| pivot.clone()).unwrap_or_default()
+}
+
+/// parses an angle typed in degrees.
+fn parse_angle(errors: &mut FieldErrors, values: &FieldValues, strings: &Strings, name: &'static str) -> Option<Angle> {
+    let value = values.get(name).map(String::as_str).unwrap_or_default();
+
+    match value.trim().parse::<f64>() {
+        Ok(degrees) if degrees.is_finite() => Some(Angle::degrees(degrees)),
+        _ => {
+            errors.insert(name, String::from(strings.invalid_angle));
+            None
+        }
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct PropertiesPanelProps {
+    pub appstate: UseStateHandle<AppState>,
+}
+
+/// shows the properties of the selected drawable and writes edits back to it.
+#[function_component(PropertiesPanel)]
+pub fn properties_panel(props: &PropertiesPanelProps) -> Html {
+    // the form shows lengths in the unit of the drawing
+    let px_per_unit = props.appstate.canvas().unit().px_per_unit();
+
+    // the last edit that couldn't be applied and why, shown with the form of its drawable
+    let apply_error = use_state(|| None::<(DrawableProps, ApplyPropsError)>);
+
+    let appstate = props.appstate.clone();
+    let on_apply = {
+        let apply_error = apply_error.clone();
+        Callback::from(move |props: DrawableProps| {
+            let mut new_state = appstate.edited();
+            match new_state.apply_props(&props.scaled(px_per_unit)) {
+                Ok(()) => {
+                    apply_error.set(None);
+                    appstate.set(new_state);
+                }
+                Err(error) => apply_error.set(Some((props, error))),
+            }
+        })
+    };
+
+    let canvas = props.appstate.canvas();
+    let strings = props.appstate.strings();
+
+    if canvas.selection().len() > 1 {
+        return html! { <div id="properties">{ fill(strings.shapes_selected, &[&canvas.selection().len()]) }</div> };
+    }
+
+    // keyed by the selected drawable so selecting another one starts a fresh form
+    match canvas.selected_props() {
+        Some(selected) => {
+            // a shape the canvas refused is shown as the error of the field that made it
+            let (field_errors, error) = match (*apply_error).clone().filter(|(applied, _)| applied.id == selected.id) {
+                Some((applied, ApplyPropsError::Invalid(error))) => match invalid_field(&applied.props, error) {
+                    Some(name) => (FieldErrors::from([(name, canvas_error_message(error, strings))]), None),
+                    None => (FieldErrors::new(), Some(canvas_error_message(error, strings))),
+                },
+                Some((_, error)) => (FieldErrors::new(), Some(apply_error_message(error, strings))),
+                None => (FieldErrors::new(), None),
+            };
+            html! {
+                <PropsForm
+                    key={selected.id.to_string()}
+                    props={selected.scaled(1f64 / px_per_unit)}
+                    strings={strings}
+                    on_apply={on_apply}
+                    field_errors={field_errors}
+                    error={error}
+                />
+            }
+        }
+        None => html! {},
+    }
+}
+
+fn apply_error_message(error: ApplyPropsError, strings: &Strings) -> String {
+    match error {
+        ApplyPropsError::Missing(_) => strings.shape_missing.to_string(),
+        ApplyPropsError::Locked(_) => strings.shape_locked.to_string(),
+        ApplyPropsError::WrongKind(_) => strings.wrong_kind.to_string(),
+        ApplyPropsError::Invalid(error) => canvas_error_message(error, strings),
+    }
+}
+
+#[derive(PartialEq, Properties)]
+struct PropsFormProps {
+    props: DrawableProps,
+    strings: &'static Strings,
+    on_apply: Callback<DrawableProps>,
+    /// the fields that made the canvas refuse the last edit.
+    field_errors: FieldErrors,
+    error: Option<String>,
+}
+
+/// a form with a field for every property of a drawable.
+#[function_component(PropsForm)]
+fn props_form(props: &PropsFormProps) -> Html {
+    let values = use_state(|| field_values(&props.props.props));
+    let errors = use_state(FieldErrors::new);
+
+    // the drawable was changed elsewhere, like by dragging it
+    {
+        let (values, errors) = (values.clone(), errors.clone());
+        use_effect_with_deps(
+            move |props: &Props| {
+                values.set(field_values(props));
+                errors.set(FieldErrors::new());
+                || ()
+            },
+            props.props.props.clone(),
+        );
+    }
+
+    let on_submit = {
+        let (values, errors) = (values.clone(), errors.clone());
+        let (selected, strings, on_apply) = (props.props.clone(), props.strings, props.on_apply.clone());
+
+        Callback::from(move |evt: FocusEvent| {
+            evt.prevent_default();
+
+            match parse_props(&selected.props, &values, strings) {
+                Ok(props) => {
+                    errors.set(FieldErrors::new());
+                    on_apply.emit(DrawableProps { props, ..selected.clone() });
+                }
+                Err(field_errors) => errors.set(field_errors),
+            }
+        })
+    };
+
+    html! {
+        <form id="properties" onsubmit={on_submit}>
+            <h2>{ title(&props.props.props, props.strings) }</h2>
+            <p style="color: gray;">
+                { fill(props.strings.props_header, &[&props.props.id, &props.props.z_index]) }
+                if props.props.locked {
+                    { format!(" \u{b7} {}", props.strings.lock) }
+                }
+            </p>
+            {
+                fields(&props.props.props, props.strings).into_iter().map(|(label, name)| {
+                    let value = values.get(name).cloned().unwrap_or_default();
+                    let values = values.clone();
+                    let oninput = Callback::from(move |value: String| {
+                        let mut new_values = (*values).clone();
+                        new_values.insert(name, value);
+
+                        values.set(new_values);
+                    });
+
+                    html! {
+                        <Field
+                            label={label}
+                            value={value}
+                            choices={choices(name, props.strings)}
+                            error={errors.get(name).or_else(|| props.field_errors.get(name)).cloned()}
+                            oninput={oninput}
+                        />
+                    }
+                }).collect::<Html>()
+            }
+            if let Some(error) = &props.error {
+                <span role="alert" style="color: red;">{ error }</span>
+            }
+            <button type="submit" disabled={props.props.locked}>{ props.strings.apply }</button>
+        </form>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+struct FieldProps {
+    label: &'static str,
+    value: String,
+    /// the values and labels to pick from, a text input when there are none.
+    choices: Vec<(&'static str, &'static str)>,
+    error: Option<String>,
+    oninput: Callback<String>,
+}
+
+/// a labelled text input, or a list to pick from, with its parse error next to it.
+#[function_component(Field)]
+fn field(props: &FieldProps) -> Html {
+    let input = if props.choices.is_empty() {
+        let oninput = props
+            .oninput
+            .reform(|evt: InputEvent| evt.target_unchecked_into::<HtmlInputElement>().value());
+
+        html! { <input type="text" value={props.value.clone()} oninput={oninput} /> }
+    } else {
+        let onchange = props
+            .oninput
+            .reform(|evt: Event| evt.target_unchecked_into::<HtmlSelectElement>().value());
+
+        html! {
+            <select onchange={onchange}>
+                { for props.choices.iter().map(|(value, label)| html! {
+                    <option value={*value} selected={props.value == *value}>{ *label }</option>
+                }) }
+            </select>
+        }
+    };
+
+    html! {
+        <div>
+            <label>
+                { props.label }
+                { input }
+            </label>
+            if let Some(error) = &props.error {
+                <span style="color: red;">{ error }</span>
+            }
+        </div>
+    }
+}
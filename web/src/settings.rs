@@ -0,0 +1,345 @@
+use yew::prelude::*;
+use program_core::{Canvas, ResizeMode, Unit};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use super::app_state::{AppState, DEFAULT_CANVAS_SIZE};
+use super::autosave::clear_saved_canvas;
+use super::i18n::{fill, Strings};
+
+/// the settings of a drawing, as edited in the settings dialog.
+/// the size is in pixels whatever the unit.
+#[derive(Clone, PartialEq, Debug)]
+pub struct DocumentSettings {
+    pub width: f64,
+    pub height: f64,
+    pub background: Option<String>,
+    pub grid_spacing: Option<f64>,
+    pub unit: Unit,
+    /// the decimals of the numbers in exported SVG documents.
+    pub svg_precision: Option<u32>,
+}
+
+impl DocumentSettings {
+    /// the settings `canvas` currently has.
+    pub fn of(canvas: &Canvas) -> DocumentSettings {
+        DocumentSettings {
+            width: canvas.width(),
+            height: canvas.height(),
+            background: canvas.background().map(String::from),
+            grid_spacing: canvas.grid_spacing(),
+            unit: canvas.unit(),
+            svg_precision: canvas.svg_precision(),
+        }
+    }
+
+    /// an empty drawing with these settings.
+    ///
+    /// # Examples
+    /// ```
+    /// use program_core::Unit;
+    /// use web::DocumentSettings;
+    ///
+    /// let settings = DocumentSettings {
+    ///     width: 300.0,
+    ///     height: 200.0,
+    ///     background: None,
+    ///     grid_spacing: Some(10.0),
+    ///     unit: Unit::Cm,
+    ///     svg_precision: Some(2),
+    /// };
+    /// assert_eq!(settings, DocumentSettings::of(&settings.new_canvas()));
+    ///
+    /// ```
+    pub fn new_canvas(&self) -> Canvas {
+        let mut canvas = Canvas::new(self.width, self.height);
+        canvas.set_background(self.background.clone());
+        canvas.set_grid_spacing(self.grid_spacing);
+        canvas.set_unit(self.unit);
+        canvas.set_svg_precision(self.svg_precision);
+
+        canvas
+    }
+}
+
+impl Default for DocumentSettings {
+    fn default() -> Self {
+        DocumentSettings::of(&Canvas::new(DEFAULT_CANVAS_SIZE.0, DEFAULT_CANVAS_SIZE.1))
+    }
+}
+
+/// what the settings dialog is open for.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettingsMode {
+    /// changing the current drawing, changes apply right away.
+    Edit,
+    /// picking the settings of a new drawing, which replaces the current one.
+    NewDocument,
+}
+
+/// the color shown in the picker when there's no background, or one
+/// the picker can't show because it isn't written as `#rrggbb`.
+const DEFAULT_BACKGROUND: &str = "#ffffff";
+
+fn resize_mode_name(mode: ResizeMode, strings: &Strings) -> &'static str {
+    match mode {
+        ResizeMode::Anchor => strings.keep_positions,
+        ResizeMode::Center => strings.keep_centered,
+        ResizeMode::Scale => strings.scale_drawing,
+    }
+}
+
+const RESIZE_MODES: [ResizeMode; 3] = [ResizeMode::Anchor, ResizeMode::Center, ResizeMode::Scale];
+
+/// the most decimals exported numbers can be written with,
+/// more than a `f64` holds for the sizes of a drawing.
+const MAX_SVG_PRECISION: u32 = 15;
+
+/// a positive length typed in `unit`, returned in pixels.
+///
+/// # Examples
+/// ```
+/// use program_core::Unit;
+/// use web::parse_size;
+///
+/// assert_eq!(Some(20.0), parse_size(" 20 ", Unit::Px));
+/// assert_eq!(Some(Unit::Cm.px_per_unit() * 2.0), parse_size("2", Unit::Cm));
+/// assert_eq!(None, parse_size("0", Unit::Px));
+/// assert_eq!(None, parse_size("wide", Unit::Px));
+///
+/// ```
+pub fn parse_size(value: &str, unit: Unit) -> Option<f64> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|size| size.is_finite() && *size > 0f64)
+        .map(|size| size * unit.px_per_unit())
+}
+
+#[derive(PartialEq, Properties)]
+pub struct SettingsDialogProps {
+    pub appstate: UseStateHandle<AppState>,
+    pub mode: UseStateHandle<Option<SettingsMode>>,
+}
+
+/// a dialog with the size, background, grid, unit and export precision of the drawing.
+/// when editing the current drawing every change applies right away,
+/// a new drawing is only made once it's confirmed.
+#[function_component(SettingsDialog)]
+pub fn settings_dialog(props: &SettingsDialogProps) -> Html {
+    let draft = use_state(DocumentSettings::default);
+    let resize_mode = use_state(ResizeMode::default);
+
+    let strings = props.appstate.strings();
+    let mode = match *props.mode {
+        Some(mode) => mode,
+        None => return html! {},
+    };
+    let settings = match mode {
+        SettingsMode::Edit => DocumentSettings::of(props.appstate.canvas()),
+        SettingsMode::NewDocument => (*draft).clone(),
+    };
+
+    // every field hands its change to this, which applies it right
+    // away or keeps it for the new drawing
+    let change = {
+        let (appstate, draft, resize_mode) = (props.appstate.clone(), draft.clone(), resize_mode.clone());
+
+        move |update: Box<dyn Fn(&mut DocumentSettings)>| match mode {
+            SettingsMode::Edit => {
+                let mut settings = DocumentSettings::of(appstate.canvas());
+                update(&mut settings);
+
+                let mut new_state = appstate.edited();
+                if new_state.apply_settings(&settings, *resize_mode) {
+                    appstate.set(new_state);
+                }
+            }
+            SettingsMode::NewDocument => {
+                let mut settings = (*draft).clone();
+                update(&mut settings);
+                draft.set(settings);
+            }
+        }
+    };
+
+    let unit = settings.unit;
+    let size_onchange = |set: fn(&mut DocumentSettings, f64), current: f64| {
+        let change = change.clone();
+
+        Callback::from(move |evt: Event| {
+            let input: HtmlInputElement = evt.target_unchecked_into();
+            match parse_size(&input.value(), unit) {
+                Some(size) => change(Box::new(move |settings| set(settings, size))),
+                None => input.set_value(&(current / unit.px_per_unit()).to_string()),
+            }
+        })
+    };
+    let width_onchange = size_onchange(|settings, width| settings.width = width, settings.width);
+    let height_onchange = size_onchange(|settings, height| settings.height = height, settings.height);
+
+    let resize_mode_onchange = {
+        let resize_mode = resize_mode.clone();
+
+        Callback::from(move |evt: Event| {
+            let select: HtmlSelectElement = evt.target_unchecked_into();
+            if let Some(&mode) = RESIZE_MODES.get(select.selected_index().max(0) as usize) {
+                resize_mode.set(mode);
+            }
+        })
+    };
+
+    let background_onclick = {
+        let (change, background) = (change.clone(), settings.background.clone());
+
+        Callback::from(move |_| {
+            let toggled = match background {
+                Some(_) => None,
+                None => Some(String::from(DEFAULT_BACKGROUND)),
+            };
+            change(Box::new(move |settings| settings.background = toggled.clone()));
+        })
+    };
+
+    let background_onchange = {
+        let change = change.clone();
+
+        Callback::from(move |evt: Event| {
+            let color = evt.target_unchecked_into::<HtmlInputElement>().value();
+            change(Box::new(move |settings| settings.background = Some(color.clone())));
+        })
+    };
+
+    let grid_onchange = {
+        let (change, current) = (change.clone(), settings.grid_spacing);
+
+        // left empty there's no grid
+        Callback::from(move |evt: Event| {
+            let input: HtmlInputElement = evt.target_unchecked_into();
+            if input.value().trim().is_empty() {
+                return change(Box::new(|settings| settings.grid_spacing = None));
+            }
+            match parse_size(&input.value(), Unit::Px) {
+                Some(spacing) => change(Box::new(move |settings| settings.grid_spacing = Some(spacing))),
+                None => input.set_value(&current.map(|spacing| spacing.to_string()).unwrap_or_default()),
+            }
+        })
+    };
+
+    let precision_onchange = {
+        let (change, current) = (change.clone(), settings.svg_precision);
+
+        // left empty the numbers are written in full
+        Callback::from(move |evt: Event| {
+            let input: HtmlInputElement = evt.target_unchecked_into();
+            if input.value().trim().is_empty() {
+                return change(Box::new(|settings| settings.svg_precision = None));
+            }
+            match input.value().trim().parse::<u32>() {
+                Ok(decimals) if decimals <= MAX_SVG_PRECISION => {
+                    change(Box::new(move |settings| settings.svg_precision = Some(decimals)))
+                }
+                _ => input.set_value(&current.map(|decimals| decimals.to_string()).unwrap_or_default()),
+            }
+        })
+    };
+
+    let unit_onchange = {
+        let change = change.clone();
+
+        Callback::from(move |evt: Event| {
+            let select: HtmlSelectElement = evt.target_unchecked_into();
+            if let Some(&unit) = Unit::ALL.get(select.selected_index().max(0) as usize) {
+                change(Box::new(move |settings| settings.unit = unit));
+            }
+        })
+    };
+
+    let close = props.mode.clone();
+    let close_onclick = Callback::from(move |_| close.set(None));
+
+    let create_onclick = {
+        let (appstate, draft, close) = (props.appstate.clone(), draft.clone(), props.mode.clone());
+
+        Callback::from(move |_| {
+            clear_saved_canvas();
+            let mut new_state = AppState::with_canvas(draft.new_canvas());
+            new_state.set_language(appstate.language());
+
+            appstate.set(new_state);
+            close.set(None);
+        })
+    };
+
+    let background_color = settings
+        .background
+        .clone()
+        .filter(|color| color.len() == 7 && color.starts_with('#'))
+        .unwrap_or_else(|| String::from(DEFAULT_BACKGROUND));
+
+    html! {
+        <div role="dialog" aria-label={strings.document_settings}
+            style="position: fixed; inset: 0; display: flex; align-items: center; justify-content: center; background: rgba(0, 0, 0, 0.3); z-index: 20;"
+        >
+            <div style="display: flex; flex-direction: column; gap: 0.5em; padding: 1em; background: white; border: 1px solid gray;">
+                <h2>{ if mode == SettingsMode::NewDocument { strings.new_drawing } else { strings.document_settings } }</h2>
+                <label>
+                    { fill(strings.width_in, &[&unit]) }{ " " }
+                    <input type="number" min="0" value={(settings.width / unit.px_per_unit()).to_string()} onchange={width_onchange} />
+                </label>
+                <label>
+                    { fill(strings.height_in, &[&unit]) }{ " " }
+                    <input type="number" min="0" value={(settings.height / unit.px_per_unit()).to_string()} onchange={height_onchange} />
+                </label>
+                if mode == SettingsMode::Edit {
+                    <label>
+                        { strings.when_resizing }{ " " }
+                        <select onchange={resize_mode_onchange}>
+                            {
+                                RESIZE_MODES.iter().map(|&mode| html! {
+                                    <option selected={mode == *resize_mode}>{ resize_mode_name(mode, strings) }</option>
+                                }).collect::<Html>()
+                            }
+                        </select>
+                    </label>
+                }
+                <label>
+                    <input type="checkbox" checked={settings.background.is_some()} onclick={background_onclick} />
+                    { strings.background }{ " " }
+                    <input type="color" value={background_color} onchange={background_onchange} disabled={settings.background.is_none()} />
+                </label>
+                <label>
+                    { strings.grid_spacing_px }{ " " }
+                    <input type="number" min="1" placeholder={strings.no_grid}
+                        value={settings.grid_spacing.map(|spacing| spacing.to_string()).unwrap_or_default()}
+                        onchange={grid_onchange}
+                    />
+                </label>
+                <label>
+                    { strings.svg_precision }{ " " }
+                    <input type="number" min="0" max={MAX_SVG_PRECISION.to_string()} step="1" placeholder={strings.full_precision}
+                        value={settings.svg_precision.map(|decimals| decimals.to_string()).unwrap_or_default()}
+                        onchange={precision_onchange}
+                    />
+                </label>
+                <label>
+                    { strings.unit }{ " " }
+                    <select onchange={unit_onchange}>
+                        {
+                            Unit::ALL.iter().map(|&option| html! {
+                                <option selected={option == unit}>{ option.symbol() }</option>
+                            }).collect::<Html>()
+                        }
+                    </select>
+                </label>
+                <div>
+                    if mode == SettingsMode::NewDocument {
+                        <button onclick={create_onclick}>{ strings.create }</button>
+                        <button onclick={close_onclick}>{ strings.cancel }</button>
+                    } else {
+                        <button onclick={close_onclick}>{ strings.close }</button>
+                    }
+                </div>
+            </div>
+        </div>
+    }
+}
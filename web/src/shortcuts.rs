@@ -0,0 +1,80 @@
+use program_core::Point;
+use super::app_state::Tool;
+
+/// how far the arrow keys move the selected drawable.
+pub const NUDGE_STEP: f64 = 1.0;
+/// how far the arrow keys move the selected drawable with shift held.
+pub const LARGE_NUDGE_STEP: f64 = 10.0;
+/// how far a duplicate is placed from the original.
+pub const DUPLICATE_OFFSET: f64 = 10.0;
+
+/// an action bound to a key.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Shortcut {
+    /// selects a drawing tool.
+    Tool(Tool),
+    /// goes back to selecting and dragging drawables.
+    SelectTool,
+    /// drops the selected tool and any point it was holding on to.
+    Cancel,
+    Delete,
+    Nudge(Point),
+    Duplicate,
+    /// saves the drawing right away instead of waiting for the autosave.
+    Save,
+}
+
+/// the shortcut bound to a key press, `key` being the value of
+/// `KeyboardEvent.key`. with ctrl or cmd held only duplicating and saving are bound,
+/// so the browser's own shortcuts keep working.
+///
+/// # Examples
+/// ```
+/// use program_core::Point;
+/// use web::{shortcut_for, Shortcut, Tool};
+///
+/// assert_eq!(Some(Shortcut::Tool(Tool::Line)), shortcut_for("l", false, false));
+/// assert_eq!(Some(Shortcut::Tool(Tool::Rect)), shortcut_for("R", true, false));
+/// assert_eq!(Some(Shortcut::Tool(Tool::Circle)), shortcut_for("c", false, false));
+/// assert_eq!(Some(Shortcut::Tool(Tool::CircleThreePoints)), shortcut_for("3", false, false));
+/// assert_eq!(Some(Shortcut::Tool(Tool::Text)), shortcut_for("t", false, false));
+/// assert_eq!(Some(Shortcut::SelectTool), shortcut_for("v", false, false));
+/// assert_eq!(Some(Shortcut::Cancel), shortcut_for("Escape", false, false));
+/// assert_eq!(Some(Shortcut::Delete), shortcut_for("Backspace", false, false));
+/// assert_eq!(Some(Shortcut::Nudge(Point::new(-1.0, 0.0))), shortcut_for("ArrowLeft", false, false));
+/// assert_eq!(Some(Shortcut::Nudge(Point::new(0.0, 10.0))), shortcut_for("ArrowDown", true, false));
+/// assert_eq!(Some(Shortcut::Duplicate), shortcut_for("d", false, true));
+/// assert_eq!(Some(Shortcut::Save), shortcut_for("s", false, true));
+///
+/// assert_eq!(None, shortcut_for("d", false, false));
+/// assert_eq!(None, shortcut_for("c", false, true));
+/// assert_eq!(None, shortcut_for("x", false, false));
+///
+/// ```
+pub fn shortcut_for(key: &str, shift: bool, ctrl: bool) -> Option<Shortcut> {
+    let step = if shift { LARGE_NUDGE_STEP } else { NUDGE_STEP };
+
+    if ctrl {
+        return match key {
+            "d" | "D" => Some(Shortcut::Duplicate),
+            "s" | "S" => Some(Shortcut::Save),
+            _ => None,
+        };
+    }
+
+    match key {
+        "l" | "L" => Some(Shortcut::Tool(Tool::Line)),
+        "r" | "R" => Some(Shortcut::Tool(Tool::Rect)),
+        "c" | "C" => Some(Shortcut::Tool(Tool::Circle)),
+        "3" => Some(Shortcut::Tool(Tool::CircleThreePoints)),
+        "t" | "T" => Some(Shortcut::Tool(Tool::Text)),
+        "v" | "V" => Some(Shortcut::SelectTool),
+        "Escape" => Some(Shortcut::Cancel),
+        "Delete" | "Backspace" => Some(Shortcut::Delete),
+        "ArrowLeft" => Some(Shortcut::Nudge(Point::new(-step, 0.0))),
+        "ArrowRight" => Some(Shortcut::Nudge(Point::new(step, 0.0))),
+        "ArrowUp" => Some(Shortcut::Nudge(Point::new(0.0, -step))),
+        "ArrowDown" => Some(Shortcut::Nudge(Point::new(0.0, step))),
+        _ => None,
+    }
+}
@@ -0,0 +1,261 @@
+use yew::prelude::*;
+use program_core::{drawables_from_json, drawables_to_json, Canvas, Drawable};
+use serde::{Deserialize, Serialize};
+use web_sys::HtmlInputElement;
+use super::app_state::{AppState, Tool};
+use super::autosave::local_storage;
+use super::i18n::fill;
+
+/// drawables saved to be placed again and again, like a door swing or a north arrow.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Stamp {
+    pub name: String,
+    pub drawables: Vec<Drawable>,
+    /// an SVG document of the drawables, shown on the stamp's button.
+    pub thumbnail: String,
+}
+
+impl Stamp {
+    /// a stamp of the selected drawables of `canvas`, `None` when nothing is selected.
+    pub fn from_selection(canvas: &Canvas, name: String) -> Option<Stamp> {
+        let thumbnail = canvas.selection_to_svg()?;
+
+        Some(Stamp { name, drawables: canvas.selected_drawables(), thumbnail })
+    }
+}
+
+/// a stamp as it's kept in local storage, its drawables in the format of
+/// `drawables_to_json` so they're checked like a saved drawing.
+#[derive(Serialize, Deserialize)]
+struct StoredStamp {
+    name: String,
+    drawables: String,
+    thumbnail: String,
+}
+
+/// # Examples
+/// ```
+/// use program_core::{Canvas, Point};
+/// use web::{stamps_from_json, stamps_to_json, Stamp};
+///
+/// // a door swing, the door, its swing and a label placed as one
+/// let mut canvas = Canvas::new(200.0, 200.0);
+/// canvas.add_line(&Point::new(0.0, 0.0), &Point::new(0.0, 40.0)).unwrap();
+/// canvas.add_circle(&Point::new(0.0, 0.0), 40.0).unwrap();
+/// canvas.add_text(String::from("door"), Point::new(5.0, 20.0)).unwrap();
+/// canvas.select_drawables_in_rect(&program_core::Aabb::new(&Point::new(-50.0, -50.0), &Point::new(50.0, 50.0)), false, false);
+///
+/// let door = Stamp::from_selection(&canvas, String::from("Door \"90\"")).unwrap();
+/// assert_eq!(3, door.drawables.len());
+///
+/// let loaded = stamps_from_json(&stamps_to_json(&[door.clone()]));
+/// assert_eq!(vec![door], loaded);
+///
+/// canvas.set_selected_drawable(None);
+/// assert_eq!(None, Stamp::from_selection(&canvas, String::from("nothing")));
+///
+/// ```
+pub fn stamps_to_json(stamps: &[Stamp]) -> String {
+    let stored: Vec<StoredStamp> = stamps
+        .iter()
+        .map(|stamp| StoredStamp {
+            name: stamp.name.clone(),
+            drawables: drawables_to_json(&stamp.drawables),
+            thumbnail: stamp.thumbnail.clone(),
+        })
+        .collect();
+
+    serde_json::to_string(&stored).expect("stamps only hold strings")
+}
+
+/// the stamps saved by `stamps_to_json`. stamps that can't be read are
+/// logged and left out, and nothing is loaded from text that isn't a list of stamps.
+///
+/// # Examples
+/// ```
+/// use web::stamps_from_json;
+///
+/// let json = r#"[
+///     {"name": "old", "drawables": "{\"version\": 99, \"drawables\": []}", "thumbnail": ""},
+///     {"name": "dot", "drawables": "{\"version\": 1, \"drawables\": [{\"kind\": \"point\", \"pos\": {\"x\": 1, \"y\": 2}}]}", "thumbnail": "<svg />"}
+/// ]"#;
+/// let stamps = stamps_from_json(json);
+/// assert_eq!(1, stamps.len());
+/// assert_eq!("dot", stamps[0].name);
+///
+/// assert!(stamps_from_json("not json").is_empty());
+///
+/// ```
+pub fn stamps_from_json(json: &str) -> Vec<Stamp> {
+    let stored: Vec<StoredStamp> = match serde_json::from_str(json) {
+        Ok(stored) => stored,
+        Err(error) => {
+            log::warn!("ignoring the saved stamps: {}", error);
+            return Vec::new();
+        }
+    };
+
+    let mut stamps = Vec::new();
+    for stamp in stored {
+        match drawables_from_json(&stamp.drawables) {
+            Ok(drawables) => stamps.push(Stamp { name: stamp.name, drawables, thumbnail: stamp.thumbnail }),
+            Err(error) => log::warn!("ignoring the stamp \"{}\": {}", stamp.name, error),
+        }
+    }
+
+    stamps
+}
+
+const STAMPS_KEY: &str = "alrassam.stamps";
+
+/// the stamps saved in local storage, none when there aren't any.
+pub fn load_stamps() -> Vec<Stamp> {
+    match local_storage().and_then(|storage| storage.get_item(STAMPS_KEY).ok().flatten()) {
+        Some(json) => stamps_from_json(&json),
+        None => Vec::new(),
+    }
+}
+
+/// writes the stamps to local storage, returning whether it could.
+pub fn save_stamps(stamps: &[Stamp]) -> bool {
+    let saved = local_storage().map(|storage| storage.set_item(STAMPS_KEY, &stamps_to_json(stamps)));
+
+    if !matches!(saved, Some(Ok(()))) {
+        log::warn!("the stamps couldn't be saved to local storage");
+        return false;
+    }
+
+    true
+}
+
+/// a change made to the saved stamps.
+type StampsChange = Box<dyn FnOnce(&mut Vec<Stamp>)>;
+
+#[derive(PartialEq, Properties)]
+pub struct StampsPanelProps {
+    pub appstate: UseStateHandle<AppState>,
+}
+
+/// saves the selection as a stamp and lists the saved ones. clicking one
+/// arms the stamp tool with it, and they can be renamed or deleted.
+#[function_component(StampsPanel)]
+pub fn stamps_panel(props: &StampsPanelProps) -> Html {
+    let stamps = use_state(load_stamps);
+    let name = use_state(String::new);
+    let renaming = use_state(|| None::<usize>);
+
+    let appstate = &props.appstate;
+    let strings = appstate.strings();
+
+    // every change to the stamps is saved right away
+    let update = {
+        let stamps = stamps.clone();
+        move |change: StampsChange| {
+            let mut changed = (*stamps).clone();
+            change(&mut changed);
+            save_stamps(&changed);
+            stamps.set(changed);
+        }
+    };
+
+    let name_oninput = {
+        let name = name.clone();
+        Callback::from(move |evt: InputEvent| name.set(evt.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+
+    let save_onclick = {
+        let (appstate, name, update) = (props.appstate.clone(), name.clone(), update.clone());
+        let count = stamps.len();
+        Callback::from(move |_| {
+            let stamp_name = match name.trim() {
+                "" => fill(appstate.strings().default_stamp_name, &[&(count + 1)]),
+                given => given.to_string(),
+            };
+            if let Some(stamp) = Stamp::from_selection(appstate.canvas(), stamp_name) {
+                update(Box::new(move |stamps| stamps.push(stamp)));
+                name.set(String::new());
+            }
+        })
+    };
+
+    let stamp_html = |(index, stamp): (usize, &Stamp)| {
+        let armed = appstate.tool() == Some(Tool::Stamp) && appstate.stamp() == Some(&stamp.drawables[..]);
+
+        let onclick = {
+            let (appstate, drawables) = (props.appstate.clone(), stamp.drawables.clone());
+            Callback::from(move |_| {
+                let mut new_state = appstate.edited();
+                new_state.arm_stamp(&drawables);
+                appstate.set(new_state);
+            })
+        };
+        let ondblclick = {
+            let renaming = renaming.clone();
+            Callback::from(move |_| renaming.set(Some(index)))
+        };
+        let delete_onclick = {
+            let update = update.clone();
+            Callback::from(move |_| update(Box::new(move |stamps| {
+                stamps.remove(index);
+            })))
+        };
+
+        let label = if *renaming == Some(index) {
+            let onchange = {
+                let (update, renaming) = (update.clone(), renaming.clone());
+                Callback::from(move |evt: Event| {
+                    let new_name = evt.target_unchecked_into::<HtmlInputElement>().value().trim().to_string();
+                    if !new_name.is_empty() {
+                        update(Box::new(move |stamps| stamps[index].name = new_name));
+                    }
+                    renaming.set(None);
+                })
+            };
+            let onkeydown = {
+                let renaming = renaming.clone();
+                Callback::from(move |evt: KeyboardEvent| {
+                    if evt.key() == "Escape" {
+                        renaming.set(None);
+                    }
+                })
+            };
+            // a name that was changed is saved by onchange first
+            let onblur = {
+                let renaming = renaming.clone();
+                Callback::from(move |_| renaming.set(None))
+            };
+
+            html! {
+                <input type="text" value={stamp.name.clone()} onchange={onchange} onkeydown={onkeydown} onblur={onblur} />
+            }
+        } else {
+            html! {
+                <button onclick={onclick} ondblclick={ondblclick} title={strings.stamp_hint} aria-pressed={armed.to_string()}
+                    style={if armed { "font-weight: bold;" } else { "" }}
+                >
+                    <img alt="" width="32" height="32"
+                        src={format!("data:image/svg+xml,{}", js_sys::encode_uri_component(&stamp.thumbnail))} />
+                    { stamp.name.clone() }
+                </button>
+            }
+        };
+
+        html! {
+            <li key={index.to_string()} style="display: flex; gap: 0.3em; align-items: center;">
+                { label }
+                <button onclick={delete_onclick} title={strings.delete}>{ "\u{d7}" }</button>
+            </li>
+        }
+    };
+
+    html! {
+        <div id="stamps">
+            <h3>{ strings.stamps }</h3>
+            <input type="text" placeholder={strings.stamp_name} value={(*name).clone()} oninput={name_oninput} />
+            <button onclick={save_onclick} disabled={appstate.canvas().selection().is_empty()}>{ strings.save_as_stamp }</button>
+            <ul style="list-style: none; margin: 0; padding: 0;">
+                { stamps.iter().enumerate().map(stamp_html).collect::<Html>() }
+            </ul>
+        </div>
+    }
+}
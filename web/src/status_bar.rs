@@ -0,0 +1,133 @@
+use yew::prelude::*;
+use program_core::{Arc, CanvasError, Props, SnapKind};
+use super::app_state::{AppState, Tool};
+use super::i18n::{fill, Strings};
+
+/// a short description of a drawable, its kind and key dimensions.
+///
+/// # Examples
+/// ```
+/// use program_core::{Angle, ArcProps, CircleProps, LineCap, LineJoin, LineProps, PivotSpec, Point, PointProps, Props, RectProps, StrokeAlign, StrokeStyle, TextProps};
+/// use web::{selection_summary, ENGLISH};
+///
+/// let point = Props::Point(PointProps { pos: Point::new(1.0, -2.25) });
+/// let line = Props::Line(LineProps {
+///     start: Point::new(0.0, 0.0), end: Point::new(30.0, 40.0), stroke_style: StrokeStyle::Solid, line_cap: LineCap::Butt, line_join: LineJoin::Miter,
+///     stroke: None,
+/// });
+/// let circle = Props::Circle(CircleProps { center: Point::new(0.0, 0.0), radius: 2.26, stroke_align: StrokeAlign::Center, stroke: None, fill: None, filled: true });
+/// let rect = Props::Rect(RectProps {
+///     corner: Point::new(0.0, 0.0), width: 3.0, height: 4.5, rotation: Angle::default(), rotation_pivot: PivotSpec::Center, stroke_align: StrokeAlign::Center,
+///     stroke_style: StrokeStyle::Solid, stroke: None, fill: None,
+/// });
+/// let text = Props::Text(TextProps { content: String::from("hello"), pos: Point::new(0.0, 0.0), font_size: 16.0, fill: None });
+/// let arc = Props::Arc(ArcProps {
+///     center: Point::new(0.0, 0.0), radius: 10.0, start_angle: Angle::degrees(300.0), end_angle: Angle::degrees(60.0), stroke_style: StrokeStyle::Solid,
+///     line_cap: LineCap::Butt, stroke: None,
+/// });
+///
+/// assert_eq!("Point, 1.0, -2.2", selection_summary(&point, &ENGLISH));
+/// assert_eq!("Line, len 50.0", selection_summary(&line, &ENGLISH));
+/// assert_eq!("Circle, r 2.3", selection_summary(&circle, &ENGLISH));
+/// assert_eq!("Rect, 3.0 \u{d7} 4.5", selection_summary(&rect, &ENGLISH));
+/// assert_eq!("Text, 5 chars", selection_summary(&text, &ENGLISH));
+/// assert_eq!("Arc, r 10.0, 120\u{b0}", selection_summary(&arc, &ENGLISH));
+///
+/// ```
+pub fn selection_summary(props: &Props, strings: &Strings) -> String {
+    let length = |length: f64| format!("{:.1}", length);
+
+    match props {
+        Props::Point(point) => fill(strings.point_summary, &[&length(point.pos.x), &length(point.pos.y)]),
+        Props::Line(line) => {
+            let len = ((line.end.x - line.start.x).powi(2) + (line.end.y - line.start.y).powi(2)).sqrt();
+            fill(strings.line_summary, &[&length(len)])
+        }
+        Props::Circle(circle) => fill(strings.circle_summary, &[&length(circle.radius)]),
+        Props::Rect(rect) => fill(strings.rect_summary, &[&length(rect.width), &length(rect.height)]),
+        Props::Text(text) => fill(strings.text_summary, &[&text.content.chars().count()]),
+        Props::Arc(arc) => {
+            let sweep = Arc::new(&arc.center, arc.radius, arc.start_angle, arc.end_angle).sweep();
+            fill(strings.arc_summary, &[&length(arc.radius), &format!("{:.0}", sweep.to_degrees())])
+        }
+    }
+}
+
+pub(crate) fn tool_name(tool: Option<Tool>, strings: &Strings) -> &'static str {
+    match tool {
+        Some(Tool::Line) => strings.tool_line,
+        Some(Tool::Circle) => strings.tool_circle,
+        Some(Tool::CircleThreePoints) => strings.tool_circle_three_points,
+        Some(Tool::Rect) => strings.tool_rect,
+        Some(Tool::Text) => strings.tool_text,
+        Some(Tool::Stamp) => strings.tool_stamp,
+        None => strings.tool_select,
+    }
+}
+
+pub(crate) fn snap_kind_name(kind: SnapKind, strings: &Strings) -> &'static str {
+    match kind {
+        SnapKind::Endpoint => strings.snap_endpoint,
+        SnapKind::Midpoint => strings.snap_midpoint,
+        SnapKind::Center => strings.snap_center,
+        SnapKind::Intersection => strings.snap_intersection,
+        SnapKind::Grid => strings.grid,
+        SnapKind::Guide => strings.snap_guide,
+    }
+}
+
+pub(crate) fn canvas_error_message(error: CanvasError, strings: &Strings) -> String {
+    match error {
+        CanvasError::NotFinite => strings.not_finite.to_string(),
+        CanvasError::NonPositiveRadius(radius) => fill(strings.non_positive_radius, &[&radius]),
+        CanvasError::ZeroLength => strings.zero_length_line.to_string(),
+        CanvasError::ZeroArea => strings.zero_area_rect.to_string(),
+        CanvasError::ZeroSweep => strings.zero_sweep_arc.to_string(),
+        CanvasError::CollinearPoints => strings.collinear_points.to_string(),
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct StatusBarProps {
+    pub appstate: UseStateHandle<AppState>,
+}
+
+/// shows where the cursor is and what it snapped to, the zoom, the selected
+/// tool, and the selection with its lengths in the unit of the drawing,
+/// or how far it's turned while it's being rotated. when a shape couldn't
+/// be drawn it says why.
+#[function_component(StatusBar)]
+pub fn status_bar(props: &StatusBarProps) -> Html {
+    let appstate = &props.appstate;
+    let strings = appstate.strings();
+    let unit = appstate.canvas().unit();
+    let cursor = match appstate.cursor() {
+        Some(cursor) => format!("{:.1}, {:.1} {}", cursor.x() / unit.px_per_unit(), cursor.y() / unit.px_per_unit(), unit),
+        None => String::from("\u{2013}"),
+    };
+    let selection = match appstate.canvas().selection().len() {
+        0 | 1 => appstate
+            .canvas()
+            .selected_props()
+            .map(|selected| selection_summary(&selected.props.scaled(1f64 / unit.px_per_unit()), strings)),
+        count => Some(fill(strings.shapes, &[&count])),
+    };
+
+    html! {
+        <div style="display: flex; gap: 2em; font-family: monospace;">
+            <span>{ cursor }</span>
+            if let Some(kind) = appstate.snap().and_then(|snap| snap.kind) {
+                <span>{ fill(strings.snapped_to, &[&snap_kind_name(kind, strings)]) }</span>
+            }
+            <span>{ format!("{:.0}%", appstate.canvas().viewport().zoom() * 100f64) }</span>
+            <span>{ tool_name(appstate.tool(), strings) }</span>
+            <span>{ selection.unwrap_or_default() }</span>
+            if let Some(angle) = appstate.rotation_angle() {
+                <span>{ format!("{:.1}\u{b0}", angle) }</span>
+            }
+            if let Some(error) = appstate.draw_error() {
+                <span role="alert" style="color: red;">{ canvas_error_message(error, strings) }</span>
+            }
+        </div>
+    }
+}
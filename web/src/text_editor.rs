@@ -0,0 +1,101 @@
+use yew::prelude::*;
+use program_core::{Aabb, Point};
+use web_sys::{Element, HtmlInputElement};
+use super::{AppState, pointer::screen_to_client};
+
+#[derive(PartialEq, Properties)]
+pub struct TextEditorProps {
+    pub appstate: UseStateHandle<AppState>,
+    /// the SVG element the text is typed over.
+    pub svg: NodeRef,
+}
+
+/// an input over the canvas where a text is typed in place.
+/// Enter puts the text on the canvas and Escape drops it.
+#[function_component(TextEditor)]
+pub fn text_editor(props: &TextEditorProps) -> Html {
+    let (edit, svg) = match (props.appstate.text_edit(), props.svg.cast::<Element>()) {
+        (Some(edit), Some(svg)) => (edit.clone(), svg),
+        _ => return html! {},
+    };
+    let canvas = props.appstate.canvas();
+
+    // the box inside the SVG's border, relative to the SVG itself
+    let min = Point::new(svg.client_left().into(), svg.client_top().into());
+    let target = Aabb::new(
+        &min,
+        &Point::new(min.x() + f64::from(svg.client_width()), min.y() + f64::from(svg.client_height())),
+    );
+    let scale = if canvas.width() > 0f64 { f64::from(svg.client_width()) / canvas.width() } else { 1f64 };
+
+    // texts sit on their baseline, so the input starts a line above it
+    let top_left = canvas.viewport().world_to_screen(&Point::new(edit.pos.x(), edit.pos.y() - edit.font_size));
+    let position = screen_to_client(&top_left, &target, canvas.width(), canvas.height());
+    let font_size = edit.font_size * canvas.viewport().zoom() * scale;
+    let style = format!(
+        "position: absolute; left: {}px; top: {}px; height: {}px; font-size: {}px; \
+         padding: 0; border: 1px dashed rgb(0, 120, 215); background: transparent; font-family: inherit;",
+        position.x(), position.y(), font_size * 1.25, font_size,
+    );
+
+    let appstate = props.appstate.clone();
+    let onkeydown = Callback::from(move |evt: KeyboardEvent| {
+        let mut new_state = appstate.edited();
+
+        match evt.key().as_str() {
+            "Enter" => {
+                let input: HtmlInputElement = evt.target_unchecked_into();
+                new_state.commit_text(input.value());
+            }
+            "Escape" => new_state.cancel_text(),
+            _ => return,
+        }
+
+        evt.prevent_default();
+        appstate.set(new_state);
+    });
+
+    html! {
+        <TextInput
+            key={format!("{}:{}:{:?}", edit.pos.x(), edit.pos.y(), edit.editing)}
+            value={edit.content}
+            style={style}
+            onkeydown={onkeydown}
+        />
+    }
+}
+
+#[derive(PartialEq, Properties)]
+struct TextInputProps {
+    value: String,
+    style: String,
+    onkeydown: Callback<KeyboardEvent>,
+}
+
+/// the input itself, focused with its text selected when it appears.
+#[function_component(TextInput)]
+fn text_input(props: &TextInputProps) -> Html {
+    let input = use_node_ref();
+
+    use_effect_with_deps(
+        |input: &NodeRef| {
+            if let Some(input) = input.cast::<HtmlInputElement>() {
+                let _ = input.focus();
+                input.select();
+            }
+
+            || ()
+        },
+        input.clone(),
+    );
+
+    html! {
+        <input
+            ref={input}
+            type="text"
+            value={props.value.clone()}
+            style={props.style.clone()}
+            onkeydown={props.onkeydown.clone()}
+        />
+    }
+}
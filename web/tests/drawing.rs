@@ -0,0 +1,149 @@
+//! the drawables' elements in a real document. runs in a browser, with
+//! `wasm-pack test --headless --firefox web`.
+#![cfg(target_arch = "wasm32")]
+
+use program_core::{Aabb, DrawableId, Point};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+use web::{AppState, Drawing, Modifiers, FULL_REDRAW_THRESHOLD};
+use web_sys::Element;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const SHAPES: usize = 500;
+
+/// a change made to the state between two renders.
+type Edit<'a> = Box<dyn Fn(&mut AppState) + 'a>;
+
+/// an empty group in the page, like the one the canvas component renders.
+fn group() -> Element {
+    let document = gloo_utils::document();
+    let svg = document.create_element_ns(Some("http://www.w3.org/2000/svg"), "svg").unwrap();
+    let group = document.create_element_ns(Some("http://www.w3.org/2000/svg"), "g").unwrap();
+    svg.append_child(&group).unwrap();
+    document.body().unwrap().append_child(&svg).unwrap();
+
+    group
+}
+
+/// a state with a grid of small circles, and their ids.
+fn circles() -> (AppState, Vec<DrawableId>) {
+    let mut state = AppState::new();
+    let ids = (0..SHAPES)
+        .map(|i| {
+            let center = Point::new((i % 25) as f64 * 30.0 + 15.0, (i / 25) as f64 * 30.0 + 15.0);
+            state.canvas_mut().add_circle(&center, 5.0).unwrap()
+        })
+        .collect();
+
+    (state, ids)
+}
+
+/// the `data-id` of every element in the group, in document order.
+fn drawn_ids(group: &Element) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut child = group.first_element_child();
+    while let Some(element) = child {
+        ids.push(element.get_attribute("data-id").unwrap());
+        child = element.next_element_sibling();
+    }
+
+    ids
+}
+
+/// the ids of the drawables that should be drawn, from the bottom up.
+fn stacked_ids(state: &AppState) -> Vec<String> {
+    let canvas = state.canvas();
+
+    canvas.ids().into_iter().filter(|&id| !canvas.is_hidden(id)).map(|id| id.to_string()).collect()
+}
+
+#[wasm_bindgen_test]
+fn dragging_a_shape_keeps_the_other_elements() {
+    let (mut state, ids) = circles();
+    let group = group();
+    let mut drawing = Drawing::new();
+    drawing.draw(&group, &state).unwrap();
+
+    let before: Vec<Element> = ids.iter().map(|&id| drawing.element(id).unwrap().clone()).collect();
+    assert_eq!(stacked_ids(&state), drawn_ids(&group));
+
+    // one render per pointer event, like the canvas component
+    let dragged = ids[123];
+    let center = Point::new(15.0 + 23.0 * 30.0, 15.0 + 4.0 * 30.0);
+    let mut steps = vec![Box::new(|state: &mut AppState| state.press(center.clone(), Modifiers::default())) as Edit];
+    for step in 1..=10 {
+        let to = Point::new(center.x + step as f64 * 7.0, center.y + step as f64 * 3.0);
+        steps.push(Box::new(move |state: &mut AppState| state.drag_to(to.clone(), Modifiers::default())));
+    }
+    steps.push(Box::new(|state: &mut AppState| state.release()));
+
+    for step in steps {
+        let mut next = state.edited();
+        step(&mut next);
+        drawing.draw(&group, &next).unwrap();
+        state = next;
+    }
+
+    assert_eq!(&[dragged], state.canvas().selection());
+    for (&id, element) in ids.iter().zip(&before).filter(|(&id, _)| id != dragged) {
+        assert!(drawing.element(id).unwrap().is_same_node(Some(element)), "the element of {} was replaced", id);
+        assert!(element.parent_node().is_some());
+    }
+
+    let moved = drawing.element(dragged).unwrap();
+    assert!(!moved.is_same_node(Some(&before[123])));
+    assert!(before[123].parent_node().is_none());
+    assert_ne!(before[123].get_attribute("cx"), moved.get_attribute("cx"));
+    assert_eq!(stacked_ids(&state), drawn_ids(&group));
+}
+
+#[wasm_bindgen_test]
+fn restacking_deleting_and_undoing_keep_the_order() {
+    let (mut state, ids) = circles();
+    let group = group();
+    let mut drawing = Drawing::new();
+    drawing.draw(&group, &state).unwrap();
+    let untouched = drawing.element(ids[0]).unwrap().clone();
+
+    let edits: Vec<Edit> = vec![
+        Box::new(|state| assert!(state.canvas_mut().bring_to_front(ids[10]))),
+        Box::new(|state| assert!(state.canvas_mut().send_to_back(ids[400]))),
+        Box::new(|state| assert!(state.canvas_mut().set_hidden(ids[20], true))),
+        Box::new(|state| {
+            state.canvas_mut().set_selected_drawable(Some(ids[30]));
+            assert!(state.delete_selected());
+        }),
+        Box::new(|state| assert!(state.canvas_mut().set_hidden(ids[20], false))),
+        // restoring marks every drawable dirty, so it draws everything again
+        Box::new(|state| assert!(state.undo())),
+    ];
+
+    for (i, edit) in edits.iter().enumerate() {
+        let mut next = state.edited();
+        edit(&mut next);
+        drawing.draw(&group, &next).unwrap();
+        state = next;
+
+        assert_eq!(stacked_ids(&state), drawn_ids(&group));
+        assert_eq!(i + 1 < edits.len(), drawing.element(ids[0]).unwrap().is_same_node(Some(&untouched)));
+    }
+}
+
+#[wasm_bindgen_test]
+fn many_changes_draw_everything_again() {
+    let (mut state, ids) = circles();
+    let group = group();
+    let mut drawing = Drawing::new();
+    drawing.draw(&group, &state).unwrap();
+    let first = drawing.element(ids[0]).unwrap().clone();
+
+    let mut next = state.edited();
+    next.canvas_mut().select_drawables_in_rect(&Aabb::new(&Point::new(0.0, 0.0), &Point::new(1000.0, 1000.0)), false, false);
+    next.canvas_mut().translate_selected_drawable(&Point::new(1.0, 0.0));
+    assert!(next.canvas().dirty_ids().len() > FULL_REDRAW_THRESHOLD);
+    drawing.draw(&group, &next).unwrap();
+    state = next;
+
+    assert!(!drawing.element(ids[0]).unwrap().is_same_node(Some(&first)));
+    assert_eq!(stacked_ids(&state), drawn_ids(&group));
+}